@@ -0,0 +1,70 @@
+//! 셸 출력 반영 벤치마크 - IO 스레드(alacritty EventLoop)가 계속 읽는 방식과
+//! `pump_output`으로 정해진 크기씩 나눠 읽는 방식이 같은 출력을 그리드에 반영하는 시간 비교
+//! `cargo bench --bench pump_output`
+
+use alacritty_terminal::event::Event as AlacTermEvent;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use futures::StreamExt;
+use rust_terminal::config::Config;
+use rust_terminal::env_policy::EnvPolicy;
+use rust_terminal::terminal::{Shell, TerminalBuilder};
+
+/// 셸이 출력하는 줄 수 (약 1.2MB)
+const LINES: usize = 200_000;
+
+/// 출력만 하고 끝나는 셸
+fn shell() -> Shell {
+    Shell::WithArguments {
+        program: "/bin/sh".to_string(),
+        args: vec!["-c".to_string(), format!("seq 1 {}", LINES)],
+    }
+}
+
+fn output_bytes() -> u64 {
+    (1..=LINES).map(|line| line.to_string().len() as u64 + 2).sum() // PTY는 줄 끝을 \r\n으로 바꿈
+}
+
+/// IO 스레드가 읽는 터미널 - 셸이 끝나고 남은 출력까지 반영했다는 `Exit` 이벤트까지
+fn continuous(config: &Config) {
+    let builder = TerminalBuilder::new(None, shell(), std::env::vars().collect(), &EnvPolicy::default(), 0, config).unwrap();
+    let (_terminal, mut events_rx) = builder.build();
+    futures::executor::block_on(async {
+        while let Some(event) = events_rx.next().await {
+            if let AlacTermEvent::Exit = event {
+                break;
+            }
+        }
+    });
+}
+
+/// 호출한 스레드가 `batch_size`씩 읽는 터미널 - 셸이 끝나고 남은 출력까지 반영했다는 `Exit` 이벤트까지
+fn batched(config: &Config, batch_size: usize) {
+    let builder = TerminalBuilder::pumped(None, shell(), std::env::vars().collect(), &EnvPolicy::default(), 0, config).unwrap();
+    let (mut terminal, mut events_rx) = builder.build();
+    loop {
+        if terminal.pump_output(batch_size).unwrap() > 0 {
+            continue;
+        }
+        if std::iter::from_fn(|| events_rx.try_next().ok().flatten()).any(|event| matches!(event, AlacTermEvent::Exit)) {
+            break;
+        }
+        std::thread::yield_now();
+    }
+}
+
+fn ingest(c: &mut Criterion) {
+    let config = Config::default();
+    let mut group = c.benchmark_group("ingest");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(output_bytes()));
+    group.bench_function("continuous", |b| b.iter(|| continuous(&config)));
+    for batch_size in [4 * 1024, 64 * 1024] {
+        group.bench_with_input(BenchmarkId::new("batched", batch_size), &batch_size, |b, &batch_size| {
+            b.iter(|| batched(&config, batch_size))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, ingest);
+criterion_main!(benches);
@@ -0,0 +1,127 @@
+//! 출력 활동 감시 모듈
+//! 세션마다 마지막 출력 시각과 이어진 출력이 시작된 시각을 기억해, 백그라운드 탭이 조용하다가 다시 출력하거나(활동)
+//! 오래 출력하다가 멈추면(침묵) 알림 - 다른 탭에서 긴 빌드를 돌려 놓고 끝나기를 기다릴 때 유용
+//!
+//! 출력 사이 간격이 설정한 침묵 시간보다 짧으면 하나의 이어진 출력으로 봄
+
+use std::time::{Duration, Instant};
+
+/// 알릴 변화
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityEvent {
+    Activity, // 침묵 시간 이상 조용하다가 다시 출력
+    Silence,  // 침묵 시간 이상 이어진 출력이 멈추고 침묵 시간이 지남
+}
+
+/// 데스크톱 알림을 보낼 변화 (탭 표시는 항상)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ActivityNotify {
+    pub activity: bool,
+    pub silence: bool,
+}
+
+impl ActivityNotify {
+    /// `activity`/`silence`를 공백이나 쉼표로 나열 (`off`면 알림 없음)
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut notify = Self::default();
+        for name in value.split(|c: char| c == ',' || c.is_whitespace()).filter(|name| !name.is_empty()) {
+            match name.to_ascii_lowercase().as_str() {
+                "activity" => notify.activity = true,
+                "silence" => notify.silence = true,
+                "off" | "none" => {}
+                _ => return None,
+            }
+        }
+        Some(notify)
+    }
+
+    pub fn includes(&self, event: ActivityEvent) -> bool {
+        match event {
+            ActivityEvent::Activity => self.activity,
+            ActivityEvent::Silence => self.silence,
+        }
+    }
+}
+
+/// 세션 하나의 출력 시각 기록
+#[derive(Debug, Default)]
+pub struct ActivityMonitor {
+    last_output: Option<Instant>,
+    burst_start: Option<Instant>, // 지금 이어지는 출력이 시작된 시각 (침묵을 알렸으면 None)
+}
+
+impl ActivityMonitor {
+    /// 출력 기록 - 마지막 출력 뒤 `silence` 이상 조용했으면 활동
+    /// (처음 출력은 셸 시작 프롬프트이므로 알리지 않음)
+    pub fn output(&mut self, now: Instant, silence: Duration) -> Option<ActivityEvent> {
+        let quiet = self.last_output.map(|last| now.duration_since(last) >= silence);
+        if quiet != Some(false) {
+            self.burst_start = Some(now);
+        }
+        self.last_output = Some(now);
+        (quiet == Some(true)).then_some(ActivityEvent::Activity)
+    }
+
+    /// 침묵 확인 - `silence` 이상 이어진 출력이 멈추고 `silence`가 지났으면 한 번만 침묵
+    pub fn check_silence(&mut self, now: Instant, silence: Duration) -> Option<ActivityEvent> {
+        let deadline = self.silence_deadline(silence)?;
+        if now < deadline {
+            return None;
+        }
+        self.burst_start = None;
+        Some(ActivityEvent::Silence)
+    }
+
+    /// 침묵을 알릴 시각 (오래 이어진 출력이 없으면 None)
+    pub fn silence_deadline(&self, silence: Duration) -> Option<Instant> {
+        let (start, last) = (self.burst_start?, self.last_output?);
+        (last.duration_since(start) >= silence).then(|| last + silence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SILENCE: Duration = Duration::from_secs(10);
+
+    fn secs(start: Instant, secs: u64) -> Instant {
+        start + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_output_after_silence_is_activity() {
+        let start = Instant::now();
+        let mut monitor = ActivityMonitor::default();
+
+        assert_eq!(monitor.output(start, SILENCE), None);
+        assert_eq!(monitor.output(secs(start, 5), SILENCE), None);
+        assert_eq!(monitor.output(secs(start, 15), SILENCE), Some(ActivityEvent::Activity));
+        // 짧은 출력 뒤 조용해져도 침묵은 아님
+        assert_eq!(monitor.check_silence(secs(start, 60), SILENCE), None);
+    }
+
+    #[test]
+    fn test_long_activity_then_silence() {
+        let start = Instant::now();
+        let mut monitor = ActivityMonitor::default();
+
+        for second in 0..=12 {
+            monitor.output(secs(start, second), SILENCE);
+        }
+        assert_eq!(monitor.silence_deadline(SILENCE), Some(secs(start, 22)));
+        assert_eq!(monitor.check_silence(secs(start, 21), SILENCE), None);
+        assert_eq!(monitor.check_silence(secs(start, 22), SILENCE), Some(ActivityEvent::Silence));
+        // 한 번만 알림
+        assert_eq!(monitor.check_silence(secs(start, 40), SILENCE), None);
+        assert_eq!(monitor.silence_deadline(SILENCE), None);
+    }
+
+    #[test]
+    fn test_parse_notify() {
+        assert_eq!(ActivityNotify::parse("activity, silence"), Some(ActivityNotify { activity: true, silence: true }));
+        assert_eq!(ActivityNotify::parse("off"), Some(ActivityNotify::default()));
+        assert_eq!(ActivityNotify::parse("bell"), None);
+        assert!(ActivityNotify { activity: false, silence: true }.includes(ActivityEvent::Silence));
+    }
+}
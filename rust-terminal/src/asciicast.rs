@@ -0,0 +1,275 @@
+//! asciicast v2 녹화/재생 모듈
+//! PTY 출력을 시각과 함께 asciinema 호환 `.cast` 파일(첫 줄 헤더 JSON, 이후 한 줄에 이벤트 하나)로 기록하고,
+//! 기록한 파일을 읽어 재생 위치(일시 정지, 되감기/빨리 감기, 재생 속도)를 관리
+//!
+//! 이벤트는 출력(`"o"`)과 크기 변경(`"r"`, `"<열>x<행>"`)만 사용하고, 나머지 종류는 읽을 때 무시
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// 재생 속도 범위 (배)
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 8.0;
+
+/// `.cast` 파일 첫 줄
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Header {
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// 기록된 이벤트 하나
+#[derive(Debug, Clone, PartialEq)]
+pub enum CastEvent {
+    Output(String),
+    Resize { cols: usize, rows: usize },
+}
+
+/// PTY 출력을 `.cast` 파일로 기록
+#[derive(Debug)]
+pub struct Recorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    started: Instant,
+    pending: Vec<u8>, // 읽기 경계에서 잘린 UTF-8 글자의 앞부분
+}
+
+impl Recorder {
+    /// `path`에 헤더를 쓰고 녹화 시작
+    pub fn start(path: &Path, width: usize, height: usize, title: Option<String>) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).ok().map(|elapsed| elapsed.as_secs());
+        let header = Header { version: 2, width, height, timestamp, title };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        Ok(Self { writer, path: path.to_path_buf(), started: Instant::now(), pending: Vec::new() })
+    }
+
+    /// 녹화 파일 경로
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 받은 출력 기록 (완성된 UTF-8 글자까지만 쓰고, 잘린 글자는 다음 출력과 합침)
+    pub fn output(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // 끝에서 잘린 글자만 남기고, 중간의 잘못된 바이트는 대체 문자로
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if valid == 0 {
+            return Ok(());
+        }
+        let rest = self.pending.split_off(valid);
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+        self.event("o", &text)
+    }
+
+    /// 터미널 크기 변경 기록
+    pub fn resize(&mut self, cols: usize, rows: usize) -> io::Result<()> {
+        self.event("r", &format!("{}x{}", cols, rows))
+    }
+
+    fn event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        let time = (self.started.elapsed().as_secs_f64() * 1_000_000.0).round() / 1_000_000.0;
+        serde_json::to_writer(&mut self.writer, &(time, code, data))?;
+        self.writer.write_all(b"\n")?;
+        // 앱이 비정상 종료되어도 녹화가 남도록 이벤트마다 내보냄
+        self.writer.flush()
+    }
+}
+
+/// 읽어 들인 녹화
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cast {
+    pub header: Header,
+    /// (시각(초), 이벤트) - 시각 순
+    pub events: Vec<(f64, CastEvent)>,
+}
+
+impl Cast {
+    /// 파일에서 읽기
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("녹화 파일 읽기 실패: {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("녹화 파일 오류: {}", path.display()))
+    }
+
+    /// asciicast v2 내용 파싱
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let (_, first) = lines.next().ok_or_else(|| anyhow!("빈 파일"))?;
+        let header: Header = serde_json::from_str(first).context("헤더를 읽을 수 없음")?;
+        if header.version != 2 {
+            bail!("지원하지 않는 asciicast 버전: {}", header.version);
+        }
+
+        let mut events = Vec::new();
+        for (index, line) in lines {
+            let (time, code, data): (f64, String, String) = serde_json::from_str(line)
+                .with_context(|| format!("{}번째 줄: 이벤트를 읽을 수 없음", index + 1))?;
+            let event = match code.as_str() {
+                "o" => CastEvent::Output(data),
+                "r" => {
+                    let size = data.split_once('x')
+                        .and_then(|(cols, rows)| Some((cols.trim().parse().ok()?, rows.trim().parse().ok()?)));
+                    let Some((cols, rows)) = size else {
+                        bail!("{}번째 줄: 잘못된 크기: {}", index + 1, data);
+                    };
+                    CastEvent::Resize { cols, rows }
+                }
+                _ => continue,
+            };
+            events.push((time, event));
+        }
+        // 시각이 뒤섞인 파일도 순서대로 재생
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { header, events })
+    }
+
+    /// 마지막 이벤트 시각 (초)
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |(time, _)| *time)
+    }
+}
+
+/// 재생 위치
+#[derive(Debug, Clone)]
+pub struct Playback {
+    position: f64,  // 녹화 기준 현재 시각 (초)
+    applied: usize, // 화면에 적용한 이벤트 수
+    speed: f64,
+    paused: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self { position: 0.0, applied: 0, speed: 1.0, paused: false }
+    }
+}
+
+impl Playback {
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// 재생 속도를 `factor`배로 (0.25배 ~ 8배)
+    pub fn scale_speed(&mut self, factor: f64) {
+        self.speed = (self.speed * factor).clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    /// 실제 시간 `elapsed`초만큼 진행하고 새로 적용할 이벤트 범위 반환 (일시 정지 중이면 빈 범위)
+    pub fn advance(&mut self, cast: &Cast, elapsed: f64) -> Range<usize> {
+        if self.paused {
+            return self.applied..self.applied;
+        }
+        let target = (self.position + elapsed * self.speed).min(cast.duration());
+        self.seek(cast, target).1
+    }
+
+    /// `target`초로 이동하고 (처음부터 다시 그려야 하는지, 적용할 이벤트 범위) 반환
+    /// 뒤로 이동하면 화면을 처음 상태로 되돌린 뒤 처음부터 다시 적용해야 함
+    pub fn seek(&mut self, cast: &Cast, target: f64) -> (bool, Range<usize>) {
+        let target = target.clamp(0.0, cast.duration());
+        let restart = target < self.position;
+        let start = if restart { 0 } else { self.applied };
+        let end = start + cast.events[start..].partition_point(|(time, _)| *time <= target);
+        self.position = target;
+        self.applied = end;
+        (restart, start..end)
+    }
+
+    /// 끝까지 재생했는지
+    pub fn is_finished(&self, cast: &Cast) -> bool {
+        self.applied == cast.events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_parse() {
+        let path = std::env::temp_dir().join(format!("rust-terminal-cast-test-{}.cast", std::process::id()));
+        let mut recorder = Recorder::start(&path, 80, 24, Some("demo".into())).unwrap();
+        recorder.output(b"hello \xed\x95").unwrap(); // '한'의 앞 두 바이트
+        recorder.output(b"\x9c\r\n").unwrap();
+        recorder.resize(100, 30).unwrap();
+        drop(recorder);
+
+        let cast = Cast::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((cast.header.width, cast.header.height), (80, 24));
+        assert_eq!(cast.header.title.as_deref(), Some("demo"));
+        let events: Vec<&CastEvent> = cast.events.iter().map(|(_, event)| event).collect();
+        assert_eq!(events, vec![
+            &CastEvent::Output("hello ".into()),
+            &CastEvent::Output("한\r\n".into()),
+            &CastEvent::Resize { cols: 100, rows: 30 },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Cast::parse("").is_err());
+        assert!(Cast::parse("{\"version\": 1, \"width\": 80, \"height\": 24}").is_err());
+        let error = Cast::parse("{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.5, \"o\"]").unwrap_err();
+        assert!(error.to_string().starts_with("2번째 줄"));
+        // 입력(`"i"`)과 표시(`"m"`) 이벤트는 무시
+        let cast = Cast::parse("{\"version\": 2, \"width\": 80, \"height\": 24}\n[0.1, \"i\", \"ls\"]\n[0.2, \"m\", \"\"]").unwrap();
+        assert!(cast.events.is_empty());
+    }
+
+    #[test]
+    fn test_playback_seek_and_speed() {
+        let cast = Cast {
+            header: Header { version: 2, width: 80, height: 24, timestamp: None, title: None },
+            events: (0..5).map(|i| (i as f64, CastEvent::Output(i.to_string()))).collect(),
+        };
+        let mut playback = Playback::default();
+        assert_eq!(playback.advance(&cast, 0.5), 0..1);
+        playback.scale_speed(2.0);
+        assert_eq!(playback.advance(&cast, 1.0), 1..3); // 2.5초
+        playback.toggle_pause();
+        assert_eq!(playback.advance(&cast, 10.0), 3..3);
+        playback.toggle_pause();
+
+        // 되감기는 처음부터 다시 적용
+        assert_eq!(playback.seek(&cast, 1.0), (true, 0..2));
+        assert_eq!(playback.seek(&cast, 100.0), (false, 2..5));
+        assert!(playback.is_finished(&cast));
+        assert_eq!(playback.position(), 4.0);
+
+        playback.scale_speed(100.0);
+        assert_eq!(playback.speed(), MAX_SPEED);
+    }
+}
@@ -0,0 +1,126 @@
+//! 벨 처리 모듈
+//! 프로그램이 BEL(`\a`)을 보내면 설정한 방식으로 알림 - 호스트 터미널로 BEL 전달(소리),
+//! 터미널 화면을 잠깐 반전(시각 벨), 창에 포커스가 없을 때 데스크톱 알림
+//!
+//! 벨을 연달아 보내는 프로그램 때문에 알림이 쌓이지 않도록 데스크톱 알림은 일정 간격에 한 번만 보냄
+
+use std::time::{Duration, Instant};
+
+/// 시각 벨로 화면을 반전하는 시간
+const FLASH_DURATION: Duration = Duration::from_millis(150);
+
+/// 데스크톱 알림 사이 최소 간격
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 벨에 대한 반응 (여러 개 함께 가능)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BellResponse {
+    pub sound: bool,  // 호스트 터미널로 BEL 전달
+    pub visual: bool, // 포커스 세션의 화면을 잠깐 반전
+    pub notify: bool, // 창에 포커스가 없으면 데스크톱 알림
+}
+
+impl Default for BellResponse {
+    fn default() -> Self {
+        Self { sound: true, visual: false, notify: false }
+    }
+}
+
+impl BellResponse {
+    /// `sound`/`visual`/`notify`를 공백이나 쉼표로 나열 (`off`면 아무것도 하지 않음)
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut response = Self { sound: false, visual: false, notify: false };
+        for name in value.split(|c: char| c == ',' || c.is_whitespace()).filter(|name| !name.is_empty()) {
+            match name.to_ascii_lowercase().as_str() {
+                "sound" | "audible" => response.sound = true,
+                "visual" | "flash" => response.visual = true,
+                "notify" | "notification" => response.notify = true,
+                "off" | "none" => {}
+                _ => return None,
+            }
+        }
+        Some(response)
+    }
+}
+
+/// 벨 한 번에 할 일
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BellActions {
+    pub sound: bool,
+    pub notify: bool,
+}
+
+/// 시각 벨과 알림 간격 상태
+#[derive(Debug, Default)]
+pub struct BellState {
+    flash_until: Option<Instant>,
+    last_notification: Option<Instant>,
+}
+
+impl BellState {
+    /// 벨 처리 - 포커스 세션(`focused_session`)의 벨이면 시각 벨 시작, 소리와 알림 여부 반환
+    pub fn ring(&mut self, response: &BellResponse, now: Instant, focused_session: bool, window_focused: bool) -> BellActions {
+        if response.visual && focused_session {
+            self.flash_until = Some(now + FLASH_DURATION);
+        }
+        let notify = response.notify
+            && !window_focused
+            && self.last_notification.is_none_or(|last| now.duration_since(last) >= NOTIFY_INTERVAL);
+        if notify {
+            self.last_notification = Some(now);
+        }
+        BellActions { sound: response.sound, notify }
+    }
+
+    /// 화면을 반전해 그려야 하는지
+    pub fn flashing(&self, now: Instant) -> bool {
+        self.flash_until.is_some_and(|until| now < until)
+    }
+
+    /// 시각 벨이 끝나 다시 그릴 때까지 남은 시간
+    pub fn flash_remaining(&self, now: Instant) -> Option<Duration> {
+        self.flash_until.filter(|&until| now < until).map(|until| until - now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(BellResponse::parse("visual, notify"), Some(BellResponse { sound: false, visual: true, notify: true }));
+        assert_eq!(BellResponse::parse("off"), Some(BellResponse { sound: false, visual: false, notify: false }));
+        assert_eq!(BellResponse::parse("sound beep"), None);
+    }
+
+    #[test]
+    fn test_visual_bell_only_for_focused_session() {
+        let response = BellResponse { sound: true, visual: true, notify: false };
+        let mut state = BellState::default();
+        let now = Instant::now();
+
+        assert_eq!(state.ring(&response, now, false, true), BellActions { sound: true, notify: false });
+        assert!(!state.flashing(now));
+
+        state.ring(&response, now, true, true);
+        assert!(state.flashing(now + Duration::from_millis(100)));
+        assert_eq!(state.flash_remaining(now + Duration::from_millis(100)), Some(Duration::from_millis(50)));
+        assert!(!state.flashing(now + FLASH_DURATION));
+        assert_eq!(state.flash_remaining(now + FLASH_DURATION), None);
+    }
+
+    #[test]
+    fn test_notification_when_unfocused_and_throttled() {
+        let response = BellResponse { sound: false, visual: false, notify: true };
+        let mut state = BellState::default();
+        let now = Instant::now();
+
+        // 창에 포커스가 있으면 알리지 않음
+        assert!(!state.ring(&response, now, true, true).notify);
+        assert!(state.ring(&response, now, true, false).notify);
+        // 연달아 울린 벨은 간격이 지날 때까지 알리지 않음
+        assert!(!state.ring(&response, now + Duration::from_secs(1), true, false).notify);
+        assert!(state.ring(&response, now + NOTIFY_INTERVAL, true, false).notify);
+    }
+}
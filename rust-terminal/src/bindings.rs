@@ -0,0 +1,513 @@
+//! 키 바인딩 모듈
+//! 기본 단축키와 설정 파일의 `RUST_TERMINAL_BIND=<키 조합> <동작> [인자]` 줄을 파싱하고,
+//! 키 이벤트에 해당하는 동작을 찾음 (사용자 바인딩을 기본 단축키보다 먼저 확인)
+
+use crate::export::ExportFormat;
+use crate::panes::PaneDirection;
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// 키에 연결할 수 있는 앱 동작
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Copy,
+    CopyAs(ExportFormat), // 선택 영역을 색과 글자 속성까지 복사
+    Paste,        // 설정에 따라 끝 줄 바꿈 처리
+    PasteAndRun,  // 붙여넣은 뒤 Enter 전송
+    PasteOnly,    // 끝 줄 바꿈을 제거하여 실행하지 않음
+    Search,
+    CommandPalette, // 동작을 이름으로 찾아 실행하는 팔레트 열기
+    ScrollPageUp,
+    ScrollPageDown,
+    ScrollTop,
+    ScrollBottom,
+    PrevPrompt, // 이전 명령의 프롬프트로 스크롤 (셸 통합 OSC 133 필요)
+    NextPrompt,
+    QuoteNext,
+    ToggleWhitespace, // 공백 표시 모드 토글
+    ToggleAutowrap,   // 자동 줄 바꿈(DECAWM) 토글
+    SetMark,    // 다음에 입력한 글자 이름으로 마크 설정
+    JumpToMark, // 다음에 입력한 글자 이름의 마크로 이동
+    ReloadConfig,
+    CloseSession, // 현재 세션 닫기 (마지막 세션이면 종료)
+    Quit,         // 확인 없이 앱 종료
+    Detach,       // 세션 데몬에서 분리하고 종료 (셸은 계속 실행)
+    ToggleCursorBlink,
+    ToggleStats,       // 성능 통계 오버레이 토글
+    ToggleOutputLog,   // 현재 세션의 PTY 출력 기록 토글
+    ToggleRecording,   // 현재 세션의 asciicast 녹화 토글
+    ToggleBroadcast,   // 입력 동기화 토글 (현재 탭의 모든 pane에 입력)
+    ExportScrollback(ExportFormat), // 현재 세션의 스크롤백 전체를 파일로 저장
+    CaptureSnapshot,   // 현재 화면을 스냅샷으로 저장
+    CycleSnapshotDiff, // 스냅샷 비교 (최신 -> 이전 -> 끄기 순환)
+    ClearScrollback,     // 스크롤백 지우기 (일정 시간 내 되돌리기 가능)
+    UndoClearScrollback,
+    NewTab,
+    NextTab,
+    PrevTab,
+    SplitRight,                // 좌우로 분할
+    SplitDown,                 // 위아래로 분할
+    FocusPane(PaneDirection),  // 방향으로 이웃 pane에 포커스
+    ResizePane(PaneDirection), // 포커스 pane 경계를 방향으로 이동
+    SetTheme(String),          // 색 테마 바꾸기 (내장 또는 사용자 테마 이름)
+    NewTabWithProfile(String), // 프로필의 셸/환경으로 새 탭 열기
+    ConnectSsh(String),        // SSH 호스트(`[ssh.이름]` 또는 `사용자@호스트:포트`)로 새 탭 열기
+    OpenSerial(String),        // 시리얼 장치로 새 탭 열기
+    Connect(String),           // telnet/TCP 대상으로 새 탭 열기
+    SmartActions,              // 화면의 스마트 선택 항목과 동작을 메뉴로 보여 주기
+    HintMode,                  // 화면의 항목에 라벨을 붙이고 입력한 라벨의 항목 열기/복사
+    CopyText(String),          // 주어진 글자를 클립보드에 복사
+    OpenUrl(String),           // URL을 기본 브라우저로 열기
+    RunCommand(String),        // 명령을 셸로 새 탭에서 실행
+    RunProgram(Vec<String>),   // 프로그램과 인자를 셸 없이 새 탭에서 실행 (스마트 선택 `run`)
+    SendString(Vec<u8>), // 임의의 바이트를 PTY로 전송
+    Unbind, // 기본 단축키 해제 (키를 그대로 셸에 전송)
+}
+
+/// 인자 없는 동작의 (이름, 동작, 설명) 목록
+/// 키 바인딩의 동작 이름과 명령 팔레트 목록이 함께 사용
+pub const ACTIONS: &[(&str, Action, &str)] = &[
+    ("copy", Action::Copy, "선택 영역 복사"),
+    ("copy-ansi", Action::CopyAs(ExportFormat::Ansi), "선택 영역을 ANSI 색 코드와 함께 복사"),
+    ("copy-html", Action::CopyAs(ExportFormat::Html), "선택 영역을 색과 함께 HTML로 복사"),
+    ("paste", Action::Paste, "붙여넣기"),
+    ("paste-and-run", Action::PasteAndRun, "붙여넣고 실행 (Enter 전송)"),
+    ("paste-only", Action::PasteOnly, "붙여넣기 (끝 줄 바꿈 제거)"),
+    ("search", Action::Search, "스크롤백 검색"),
+    ("command-palette", Action::CommandPalette, "명령 팔레트"),
+    ("scroll-page-up", Action::ScrollPageUp, "한 페이지 위로 스크롤"),
+    ("scroll-page-down", Action::ScrollPageDown, "한 페이지 아래로 스크롤"),
+    ("scroll-top", Action::ScrollTop, "스크롤백 맨 위로"),
+    ("scroll-bottom", Action::ScrollBottom, "맨 아래로"),
+    ("prev-prompt", Action::PrevPrompt, "이전 명령 프롬프트로 이동"),
+    ("next-prompt", Action::NextPrompt, "다음 명령 프롬프트로 이동"),
+    ("quote-next", Action::QuoteNext, "다음 키를 셸에 그대로 전송"),
+    ("toggle-whitespace", Action::ToggleWhitespace, "공백 표시 켜기/끄기"),
+    ("toggle-autowrap", Action::ToggleAutowrap, "자동 줄 바꿈 켜기/끄기"),
+    ("set-mark", Action::SetMark, "마크 설정"),
+    ("jump-to-mark", Action::JumpToMark, "마크로 이동"),
+    ("reload-config", Action::ReloadConfig, "설정 다시 읽기"),
+    ("close-session", Action::CloseSession, "현재 세션 닫기"),
+    ("quit", Action::Quit, "종료"),
+    ("detach", Action::Detach, "세션 분리 (셸은 데몬에서 계속 실행)"),
+    ("toggle-cursor-blink", Action::ToggleCursorBlink, "커서 깜빡임 켜기/끄기"),
+    ("toggle-stats", Action::ToggleStats, "성능 통계 표시 켜기/끄기"),
+    ("toggle-output-log", Action::ToggleOutputLog, "출력 기록 켜기/끄기"),
+    ("toggle-recording", Action::ToggleRecording, "asciicast 녹화 켜기/끄기"),
+    ("toggle-broadcast", Action::ToggleBroadcast, "입력 동기화 켜기/끄기 (탭의 모든 pane에 입력)"),
+    ("export-scrollback", Action::ExportScrollback(ExportFormat::Text), "스크롤백을 텍스트 파일로 저장"),
+    ("export-scrollback-ansi", Action::ExportScrollback(ExportFormat::Ansi), "스크롤백을 색과 함께 ANSI 텍스트 파일로 저장"),
+    ("export-scrollback-html", Action::ExportScrollback(ExportFormat::Html), "스크롤백을 색과 함께 HTML 파일로 저장"),
+    ("capture-snapshot", Action::CaptureSnapshot, "화면 스냅샷 저장"),
+    ("cycle-snapshot-diff", Action::CycleSnapshotDiff, "스냅샷과 비교 전환"),
+    ("clear-scrollback", Action::ClearScrollback, "스크롤백 지우기"),
+    ("undo-clear-scrollback", Action::UndoClearScrollback, "지운 스크롤백 되돌리기"),
+    ("new-tab", Action::NewTab, "새 탭"),
+    ("next-tab", Action::NextTab, "다음 탭"),
+    ("prev-tab", Action::PrevTab, "이전 탭"),
+    ("split-right", Action::SplitRight, "좌우로 분할"),
+    ("split-down", Action::SplitDown, "위아래로 분할"),
+    ("smart-actions", Action::SmartActions, "화면의 스마트 선택 항목 동작"),
+    ("hint-mode", Action::HintMode, "힌트 모드 (화면의 URL, 경로, 단어를 라벨로 골라 열기/복사)"),
+];
+
+impl Action {
+    /// 동작 이름과 인자에서 파싱 (`send-string`, `focus-pane`, `resize-pane`, `set-theme`, `new-tab-profile`, `ssh`, `serial`, `connect`,
+    /// `copy-text`, `open-url`, `run-command`만 인자 사용)
+    fn parse(name: &str, argument: &str) -> Result<Self> {
+        let lower = name.to_ascii_lowercase();
+        if let Some((_, action, _)) = ACTIONS.iter().find(|(action_name, _, _)| *action_name == lower) {
+            return Ok(action.clone());
+        }
+        let action = match lower.as_str() {
+            "none" | "unbind" => Self::Unbind,
+            "focus-pane" | "resize-pane" => {
+                let direction = PaneDirection::parse(argument)
+                    .ok_or_else(|| anyhow!("{}에는 방향(left/right/up/down)이 필요함: {:?}", name, argument))?;
+                if lower == "focus-pane" {
+                    Self::FocusPane(direction)
+                } else {
+                    Self::ResizePane(direction)
+                }
+            }
+            "set-theme" => {
+                if argument.is_empty() {
+                    bail!("set-theme에 테마 이름이 없음");
+                }
+                Self::SetTheme(argument.to_string())
+            }
+            "new-tab-profile" => {
+                if argument.is_empty() {
+                    bail!("new-tab-profile에 프로필 이름이 없음");
+                }
+                Self::NewTabWithProfile(argument.to_string())
+            }
+            "ssh" => {
+                if argument.is_empty() {
+                    bail!("ssh에 호스트가 없음");
+                }
+                Self::ConnectSsh(argument.to_string())
+            }
+            "serial" => {
+                if argument.is_empty() {
+                    bail!("serial에 장치 경로가 없음");
+                }
+                Self::OpenSerial(argument.to_string())
+            }
+            "connect" => {
+                if argument.is_empty() {
+                    bail!("connect에 접속 대상이 없음");
+                }
+                Self::Connect(argument.to_string())
+            }
+            "copy-text" | "open-url" | "run-command" => {
+                if argument.is_empty() {
+                    bail!("{}에 인자가 없음", lower);
+                }
+                match lower.as_str() {
+                    "copy-text" => Self::CopyText(argument.to_string()),
+                    "open-url" => Self::OpenUrl(argument.to_string()),
+                    _ => Self::RunCommand(argument.to_string()),
+                }
+            }
+            "send-string" => {
+                let bytes = unescape(argument)?;
+                if bytes.is_empty() {
+                    bail!("send-string에 보낼 문자열이 없음");
+                }
+                Self::SendString(bytes)
+            }
+            _ => bail!("알 수 없는 동작: {}", name),
+        };
+        Ok(action)
+    }
+}
+
+/// 키 조합과 동작 연결
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    pub modifiers: KeyModifiers,
+    pub action: Action,
+}
+
+impl KeyBinding {
+    /// `ctrl+shift+c copy`, `alt+l send-string ls -la\r` 형식 파싱
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (combo, rest) = spec.split_once(char::is_whitespace)
+            .ok_or_else(|| anyhow!("키 바인딩 형식이 아님 (`<키 조합> <동작>`): {}", spec))?;
+        let rest = rest.trim_start();
+        let (name, argument) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let (key, modifiers) = parse_key_combo(combo)?;
+
+        Ok(Self {
+            key,
+            modifiers,
+            action: Action::parse(name, argument.trim_start())?,
+        })
+    }
+
+    /// 키 이벤트가 이 바인딩의 키 조합인지 확인 (Ctrl/Shift/Alt가 정확히 같아야 함)
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let (code, modifiers) = normalize(key.code, key.modifiers);
+        code == self.key && modifiers == self.modifiers
+    }
+}
+
+/// 기본 단축키 (`<키 조합> <동작>`)
+const DEFAULT_BINDINGS: &[&str] = &[
+    "ctrl+shift+c copy",
+    "ctrl+shift+v paste",
+    "shift+insert paste",
+    "ctrl+shift+q quote-next",
+    "ctrl+shift+b toggle-cursor-blink",
+    "ctrl+shift+r reload-config",
+    "ctrl+shift+m set-mark",
+    "ctrl+shift+j jump-to-mark",
+    "ctrl+shift+w toggle-whitespace",
+    "ctrl+shift+a toggle-autowrap",
+    "f12 toggle-stats",
+    "ctrl+shift+l toggle-output-log",
+    "f9 toggle-recording",
+    "ctrl+shift+s capture-snapshot",
+    "ctrl+shift+h export-scrollback",
+    "ctrl+shift+d cycle-snapshot-diff",
+    "ctrl+shift+f search",
+    "ctrl+shift+x hint-mode",
+    "ctrl+shift+p command-palette",
+    "ctrl+shift+k clear-scrollback",
+    "ctrl+shift+z undo-clear-scrollback",
+    "ctrl+shift+e split-right",
+    "ctrl+shift+o split-down",
+    "ctrl+t new-tab",
+    "ctrl+w close-session",
+    "ctrl+z close-session",
+    "ctrl+q quit",
+    "ctrl+tab next-tab",
+    "ctrl+shift+tab prev-tab",
+    "alt+left focus-pane left",
+    "alt+right focus-pane right",
+    "alt+up focus-pane up",
+    "alt+down focus-pane down",
+    "alt+shift+left resize-pane left",
+    "alt+shift+right resize-pane right",
+    "alt+shift+up resize-pane up",
+    "alt+shift+down resize-pane down",
+    "pageup scroll-page-up",
+    "pagedown scroll-page-down",
+    "shift+pageup scroll-page-up",
+    "shift+pagedown scroll-page-down",
+    "ctrl+home scroll-top",
+    "ctrl+end scroll-bottom",
+    "ctrl+up prev-prompt",
+    "ctrl+down next-prompt",
+];
+
+/// 키 조합 → 동작 맵 (사용자 바인딩 다음에 기본 단축키 확인)
+#[derive(Debug, Clone)]
+pub struct Keybindings {
+    user: Vec<KeyBinding>,
+    defaults: Vec<KeyBinding>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let defaults = DEFAULT_BINDINGS.iter()
+            .filter_map(|spec| KeyBinding::parse(spec).ok())
+            .collect();
+        Self { user: Vec::new(), defaults }
+    }
+}
+
+impl Keybindings {
+    /// 사용자 바인딩 추가 (먼저 추가한 바인딩 우선)
+    pub fn push(&mut self, binding: KeyBinding) {
+        self.user.push(binding);
+    }
+
+    /// 설정에서 추가한 사용자 바인딩
+    #[cfg(test)]
+    pub fn user(&self) -> &[KeyBinding] {
+        &self.user
+    }
+
+    /// 키 이벤트에 연결된 동작 찾기
+    /// `none`으로 해제한 키는 기본 단축키가 있어도 None (셸에 그대로 전송)
+    pub fn find(&self, key: &KeyEvent) -> Option<&Action> {
+        find_action(&self.user, key)
+            .or_else(|| find_action(&self.defaults, key))
+            .filter(|action| **action != Action::Unbind)
+    }
+
+    /// 동작에 연결된 키 조합 표시 문자열 (`Ctrl+Shift+P` 등, 연결된 키가 없으면 None)
+    /// 사용자 바인딩이 다른 동작으로 덮어쓴 기본 단축키는 제외
+    pub fn key_for(&self, action: &Action) -> Option<String> {
+        self.user.iter().chain(&self.defaults)
+            .filter(|binding| binding.action == *action)
+            .find(|binding| self.find(&KeyEvent::new(binding.key, binding.modifiers)) == Some(action))
+            .map(|binding| format_key_combo(binding.key, binding.modifiers))
+    }
+}
+
+/// 키 조합을 사람이 읽는 형식으로 (`Ctrl+Shift+P`, `Alt+Left`, `F12`)
+pub fn format_key_combo(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let mut parts: Vec<String> = [(KeyModifiers::CONTROL, "Ctrl"), (KeyModifiers::ALT, "Alt"), (KeyModifiers::SHIFT, "Shift")]
+        .into_iter()
+        .filter(|(modifier, _)| modifiers.contains(*modifier))
+        .map(|(_, name)| name.to_string())
+        .collect();
+    parts.push(match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        code => format!("{:?}", code),
+    });
+    parts.join("+")
+}
+
+/// 키 이벤트에 연결된 동작 찾기 (먼저 정의된 바인딩 우선)
+pub fn find_action<'a>(bindings: &'a [KeyBinding], key: &KeyEvent) -> Option<&'a Action> {
+    bindings.iter().find(|binding| binding.matches(key)).map(|binding| &binding.action)
+}
+
+/// 비교용 키 정규화 - 문자는 소문자로, 대문자 입력은 Shift로, Shift+Tab(BackTab)은 Tab + Shift로 봄
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::SHIFT | KeyModifiers::ALT);
+    let code = match code {
+        KeyCode::Char(c) if c.is_ascii_uppercase() => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::Char(c.to_ascii_lowercase())
+        }
+        KeyCode::BackTab => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::Tab
+        }
+        code => code,
+    };
+    (code, modifiers)
+}
+
+/// `ctrl+shift+c`, `alt+enter`, `f5` 형식의 키 조합 파싱
+fn parse_key_combo(combo: &str) -> Result<(KeyCode, KeyModifiers)> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut parts: Vec<&str> = combo.split('+').collect();
+    let key = parts.pop().filter(|key| !key.is_empty())
+        .ok_or_else(|| anyhow!("키가 없음: {}", combo))?;
+
+    for part in parts {
+        modifiers |= match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "shift" => KeyModifiers::SHIFT,
+            "alt" => KeyModifiers::ALT,
+            _ => bail!("알 수 없는 수정 키: {}", part),
+        };
+    }
+
+    let lower = key.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "insert" => KeyCode::Insert,
+        "delete" => KeyCode::Delete,
+        _ => match (lower.strip_prefix('f').and_then(|n| n.parse().ok()), key.chars().count()) {
+            (Some(n @ 1..=24), _) => KeyCode::F(n),
+            (_, 1) => KeyCode::Char(key.chars().next().unwrap_or_default()),
+            _ => bail!("알 수 없는 키: {}", key),
+        },
+    };
+    Ok(normalize(code, modifiers))
+}
+
+/// `\r`, `\n`, `\t`, `\e`, `\\`, `\xNN` 이스케이프를 바이트로 변환
+fn unescape(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('r') => bytes.push(b'\r'),
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('e') => bytes.push(0x1b),
+            Some('\\') => bytes.push(b'\\'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&hex, 16)
+                    .map_err(|_| anyhow!("잘못된 \\x 이스케이프: \\x{}", hex))?;
+                bytes.push(byte);
+            }
+            other => bail!("알 수 없는 이스케이프: \\{}", other.map(String::from).unwrap_or_default()),
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bindings() {
+        let binding = KeyBinding::parse("Ctrl+Shift+C copy").unwrap();
+        assert_eq!(binding, KeyBinding {
+            key: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            action: Action::Copy,
+        });
+
+        let binding = KeyBinding::parse("alt+l  send-string ls -la\\r").unwrap();
+        assert_eq!(binding.action, Action::SendString(b"ls -la\r".to_vec()));
+        assert_eq!(KeyBinding::parse("f5 send-string \\e[15~\\x00").unwrap().action,
+            Action::SendString(b"\x1b[15~\x00".to_vec()));
+        assert_eq!(KeyBinding::parse("shift+pageup scroll-top").unwrap().key, KeyCode::PageUp);
+
+        assert_eq!(KeyBinding::parse("alt+h focus-pane left").unwrap().action, Action::FocusPane(PaneDirection::Left));
+        assert_eq!(KeyBinding::parse("alt+t set-theme dracula").unwrap().action, Action::SetTheme("dracula".into()));
+        assert!(KeyBinding::parse("alt+t set-theme").is_err());
+        assert_eq!(KeyBinding::parse("alt+w new-tab-profile work").unwrap().action, Action::NewTabWithProfile("work".into()));
+        assert!(KeyBinding::parse("alt+w new-tab-profile").is_err());
+        assert_eq!(KeyBinding::parse("alt+s ssh me@box:22").unwrap().action, Action::ConnectSsh("me@box:22".into()));
+        assert!(KeyBinding::parse("alt+s ssh").is_err());
+        assert_eq!(KeyBinding::parse("alt+u serial /dev/ttyUSB0").unwrap().action, Action::OpenSerial("/dev/ttyUSB0".into()));
+        assert_eq!(KeyBinding::parse("alt+b connect bbs.example.org:23").unwrap().action, Action::Connect("bbs.example.org:23".into()));
+        assert!(KeyBinding::parse("alt+b connect").is_err());
+        assert_eq!(KeyBinding::parse("alt+g run-command git log -1").unwrap().action, Action::RunCommand("git log -1".into()));
+        assert!(KeyBinding::parse("alt+o open-url").is_err());
+        assert!(KeyBinding::parse("alt+h resize-pane sideways").is_err());
+
+        assert!(KeyBinding::parse("ctrl+c").is_err());
+        assert!(KeyBinding::parse("hyper+c copy").is_err());
+        assert!(KeyBinding::parse("ctrl+c explode").is_err());
+        assert!(KeyBinding::parse("ctrl+x send-string").is_err());
+        assert!(KeyBinding::parse("ctrl+x send-string \\q").is_err());
+    }
+
+    #[test]
+    fn test_dispatch_first_matching_binding() {
+        let bindings = vec![
+            KeyBinding::parse("ctrl+shift+v paste").unwrap(),
+            KeyBinding::parse("ctrl+v send-string \\x16").unwrap(),
+            KeyBinding::parse("ctrl+shift+v search").unwrap(),
+        ];
+
+        // 대문자 입력은 Shift가 눌린 것으로 봄, 먼저 정의된 바인딩 우선
+        let key = KeyEvent::new(KeyCode::Char('V'), KeyModifiers::CONTROL);
+        assert_eq!(find_action(&bindings, &key), Some(&Action::Paste));
+
+        let key = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL);
+        assert_eq!(find_action(&bindings, &key), Some(&Action::SendString(vec![0x16])));
+
+        // 수정 키가 다르면 일치하지 않음
+        let key = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL | KeyModifiers::ALT);
+        assert_eq!(find_action(&bindings, &key), None);
+    }
+
+    #[test]
+    fn test_default_bindings_and_overrides() {
+        let ctrl = KeyModifiers::CONTROL;
+        let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        let mut keybindings = Keybindings::default();
+        assert_eq!(keybindings.defaults.len(), DEFAULT_BINDINGS.len());
+
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('Q'), ctrl_shift)), Some(&Action::QuoteNext));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('z'), ctrl)), Some(&Action::CloseSession));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::BackTab, ctrl_shift)), Some(&Action::PrevTab));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('V'), ctrl_shift)), Some(&Action::Paste));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Insert, KeyModifiers::SHIFT)), Some(&Action::Paste));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('H'), ctrl_shift)), Some(&Action::ExportScrollback(ExportFormat::Text)));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('c'), ctrl)), None);
+
+        // 사용자 바인딩이 기본 단축키보다 우선, `none`이면 해제
+        keybindings.push(KeyBinding::parse("ctrl+z none").unwrap());
+        keybindings.push(KeyBinding::parse("ctrl+shift+x close-session").unwrap());
+        keybindings.push(KeyBinding::parse("f12 search").unwrap());
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('z'), ctrl)), None);
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::Char('X'), ctrl_shift)), Some(&Action::CloseSession));
+        assert_eq!(keybindings.find(&KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE)), Some(&Action::Search));
+        assert_eq!(keybindings.user().len(), 3);
+
+        // 팔레트 표시용 키: 덮어쓴 기본 단축키는 제외
+        assert_eq!(keybindings.key_for(&Action::CommandPalette).as_deref(), Some("Ctrl+Shift+P"));
+        assert_eq!(keybindings.key_for(&Action::CloseSession).as_deref(), Some("Ctrl+Shift+X"));
+        assert_eq!(keybindings.key_for(&Action::ToggleStats), None);
+        assert_eq!(keybindings.key_for(&Action::FocusPane(PaneDirection::Left)).as_deref(), Some("Alt+Left"));
+    }
+}
@@ -0,0 +1,202 @@
+//! 명령줄 인자 모듈
+//! `rust-terminal [옵션] [-e 명령 [인자...]]` 형식을 직접 파싱하고, 설정 파일/환경 변수보다 우선하는 값으로 적용
+//!
+//! `-e` 뒤의 인자는 모두 실행할 명령과 그 인자로 보므로 `-e`는 마지막에 둠
+
+use crate::config::{Config, ExitBehavior};
+use crate::serial::{self, FlowControl, Parity};
+use crate::telnet::NetworkTarget;
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+
+/// `--help` 출력
+pub const USAGE: &str = "사용법: rust-terminal [옵션] [-e 명령 [인자...]]
+
+옵션:
+  -e, --command <명령> [인자...]   셸 대신 명령 실행 (뒤의 인자는 모두 명령에 전달)
+      --profile <이름>             config.toml의 [profiles.이름] 프로필로 시작
+      --serial <장치>              셸 대신 시리얼 장치에 연결 (예: /dev/ttyUSB0)
+      --baud <속도>                시리얼 속도 (기본값: 115200)
+      --parity <none|even|odd>     시리얼 패리티
+      --flow-control <none|hardware|software>  시리얼 흐름 제어
+      --connect <호스트:포트>      셸 대신 telnet 서버에 연결 (tcp://호스트:포트는 협상 없는 TCP)
+      --working-directory <경로>   첫 세션의 작업 디렉토리
+      --hold                       명령이 끝나도 종료 안내를 표시하며 창을 열어 둠
+  -T, --title <제목>               프로그램이 제목을 정하지 않았을 때의 창 제목
+      --restore                    마지막으로 저장된 탭/pane 복원
+      --attach                     세션 데몬에서 실행 중인 셸에 다시 붙기 (데몬 모드로 시작)
+      --daemon                     세션 데몬으로 실행 (보통 자동으로 실행됨)
+      --play <파일.cast>           셸 없이 asciicast 녹화 재생
+  -h, --help                       이 도움말 표시";
+
+/// 명령줄에서 지정한 값 (지정하지 않은 항목은 None/false)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliArgs {
+    pub command: Option<(String, Vec<String>)>, // 실행할 프로그램과 인자
+    pub profile: Option<String>,
+    pub serial: Option<PathBuf>, // 시리얼 장치
+    pub baud: Option<u32>,
+    pub parity: Option<Parity>,
+    pub flow_control: Option<FlowControl>,
+    pub connect: Option<NetworkTarget>, // telnet/TCP 접속 대상
+    pub working_directory: Option<PathBuf>,
+    pub hold: bool,
+    pub title: Option<String>,
+    pub restore: bool,
+    pub attach: bool, // 데몬 세션에 다시 붙기
+    pub daemon: bool, // TUI 대신 세션 데몬 실행
+    pub play: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl CliArgs {
+    /// 프로그램 이름을 뺀 인자 목록 파싱 (`--옵션 값`과 `--옵션=값` 모두 허용)
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = |option: &str| {
+                inline.clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{} 뒤에 값이 필요함", option))
+            };
+            match name.as_str() {
+                "-e" | "--command" => {
+                    let program = value(&name)?;
+                    cli.command = Some((program, args.by_ref().collect()));
+                }
+                "--profile" => cli.profile = Some(value(&name)?),
+                "--serial" => cli.serial = Some(PathBuf::from(value(&name)?)),
+                "--baud" => {
+                    let baud = value(&name)?;
+                    cli.baud = Some(baud.parse().map_err(|_| anyhow!("잘못된 속도: {}", baud))?);
+                }
+                "--parity" => {
+                    let parity = value(&name)?;
+                    cli.parity = Some(serial::parse_parity(&parity).ok_or_else(|| anyhow!("잘못된 패리티: {}", parity))?);
+                }
+                "--flow-control" => {
+                    let flow = value(&name)?;
+                    cli.flow_control = Some(serial::parse_flow_control(&flow).ok_or_else(|| anyhow!("잘못된 흐름 제어: {}", flow))?);
+                }
+                "--connect" => cli.connect = Some(NetworkTarget::parse(&value(&name)?)?),
+                "--working-directory" => cli.working_directory = Some(PathBuf::from(value(&name)?)),
+                "-T" | "--title" => cli.title = Some(value(&name)?),
+                "--play" => cli.play = Some(PathBuf::from(value(&name)?)),
+                "--hold" => cli.hold = true,
+                "--restore" => cli.restore = true,
+                "--attach" => cli.attach = true,
+                "--daemon" => cli.daemon = true,
+                "-h" | "--help" => cli.help = true,
+                _ => bail!("알 수 없는 인자: {}", arg),
+            }
+        }
+        if cli.serial.is_some() && cli.connect.is_some() {
+            bail!("--serial과 --connect는 함께 쓸 수 없음");
+        }
+        Ok(cli)
+    }
+
+    /// 한 번 쓰고 끝내는 실행인지 (`-e` 명령, 시리얼 장치, 연결, 녹화 재생)
+    /// 이런 실행은 저장된 레이아웃을 복원하지도, 종료할 때 덮어쓰지도 않음
+    pub fn is_one_off(&self) -> bool {
+        self.command.is_some() || self.serial.is_some() || self.connect.is_some() || self.play.is_some()
+    }
+
+    /// 설정에 명령줄 값 덮어쓰기 (설정을 다시 읽을 때도 다시 적용)
+    /// 프로필을 먼저 적용하므로 `-e` 등 다른 인자가 프로필보다 우선 (없는 프로필이면 오류)
+    pub fn apply(&self, config: &mut Config) -> Result<()> {
+        if let Some(profile) = &self.profile {
+            config.apply_profile(profile)?;
+        }
+        if let Some((program, args)) = &self.command {
+            config.shell.program = Some(program.clone());
+            config.shell.args = args.clone();
+        }
+        if let Some(directory) = &self.working_directory {
+            config.shell.working_directory = Some(directory.clone());
+        }
+        if self.hold {
+            config.session.on_exit = ExitBehavior::Hold;
+        }
+        if self.attach {
+            config.session.daemon = true;
+        }
+        if let Some(title) = &self.title {
+            config.title.fallback = title.clone();
+        }
+        if let Some(device) = &self.serial {
+            config.serial.device = device.clone();
+        }
+        config.serial.baud = self.baud.unwrap_or(config.serial.baud);
+        config.serial.parity = self.parity.unwrap_or(config.serial.parity);
+        config.serial.flow_control = self.flow_control.unwrap_or(config.serial.flow_control);
+        config.serial.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::Shell;
+
+    fn parse(args: &[&str]) -> Result<CliArgs> {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_command_and_options() {
+        let cli = parse(&["--hold", "--title=빌드", "--working-directory", "/tmp", "-e", "make", "-j", "4", "--hold"]).unwrap();
+        assert_eq!(cli.command, Some(("make".to_string(), vec!["-j".to_string(), "4".to_string(), "--hold".to_string()])));
+        assert_eq!(cli.working_directory, Some(PathBuf::from("/tmp")));
+        assert_eq!(cli.title.as_deref(), Some("빌드"));
+        assert!(cli.hold && !cli.restore);
+        assert!(cli.is_one_off());
+
+        assert_eq!(parse(&[]).unwrap(), CliArgs::default());
+        assert!(!parse(&["--restore"]).unwrap().is_one_off());
+        assert_eq!(parse(&["--play", "a.cast"]).unwrap().play, Some(PathBuf::from("a.cast")));
+        assert!(parse(&["-e"]).is_err());
+        assert!(parse(&["--title"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_apply_overrides_config() {
+        let mut config = Config::default();
+        parse(&["--hold", "-T", "work", "-e", "htop"]).unwrap().apply(&mut config).unwrap();
+        assert_eq!(config.shell.shell(), Shell::Program("htop".to_string()));
+        assert_eq!(config.session.on_exit, ExitBehavior::Hold);
+        assert_eq!(config.title.fallback, "work");
+
+        let mut config = Config::default();
+        parse(&["-e", "ssh", "host"]).unwrap().apply(&mut config).unwrap();
+        assert_eq!(config.shell.shell(), Shell::WithArguments { program: "ssh".into(), args: vec!["host".into()] });
+        assert_eq!(config.session.on_exit, ExitBehavior::default());
+
+        // 정의하지 않은 프로필은 오류
+        let cli = parse(&["--profile", "work"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+        assert!(cli.apply(&mut Config::default()).is_err());
+
+        let mut config = Config::default();
+        parse(&["--serial", "/dev/ttyUSB0", "--baud=9600", "--parity", "odd"]).unwrap().apply(&mut config).unwrap();
+        assert!(matches!(config.startup_shell(), Shell::Serial(settings) if settings.baud == 9600 && settings.parity == Parity::Odd));
+        assert!(parse(&["--parity", "mark"]).is_err());
+        assert!(parse(&["--baud", "fast"]).is_err());
+
+        let cli = parse(&["--connect", "tcp://console.lab:7001"]).unwrap();
+        assert_eq!(cli.connect.map(|target| target.label()).as_deref(), Some("tcp://console.lab:7001"));
+        assert!(parse(&["--connect", "tcp://console.lab"]).is_err());
+        assert!(parse(&["--serial", "/dev/ttyS0", "--connect", "bbs:23"]).is_err());
+
+        // 다시 붙으면 새 셸도 데몬에서 실행
+        let mut config = Config::default();
+        parse(&["--attach"]).unwrap().apply(&mut config).unwrap();
+        assert!(config.session.daemon);
+    }
+}
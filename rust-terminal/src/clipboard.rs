@@ -0,0 +1,206 @@
+//! 시스템 클립보드 모듈
+//! `arboard::Clipboard` 핸들을 재사용하되, X11/Wayland에서 다른 앱이 클립보드
+//! 소유권을 가져가 핸들이 무효화되면 핸들을 다시 만들어 한 번 재시도
+//! X11/Wayland에서는 선택 클립보드(PRIMARY)도 지원 (다른 플랫폼에서는 시스템 클립보드 사용)
+
+use crate::config::ClipboardTarget;
+use anyhow::{anyhow, Result};
+use log::debug;
+
+/// 캐시된 클립보드 핸들
+#[derive(Default)]
+pub struct SystemClipboard {
+    handle: Option<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    /// 텍스트 복사
+    pub fn set_text(&mut self, text: &str) -> Result<()> {
+        with_retry(&mut self.handle, arboard::Clipboard::new, |clipboard| clipboard.set_text(text))
+            .map_err(|e| anyhow!("클립보드 복사 실패: {}", e))
+    }
+
+    /// 텍스트 붙여넣기용으로 읽기
+    pub fn get_text(&mut self) -> Result<String> {
+        with_retry(&mut self.handle, arboard::Clipboard::new, |clipboard| clipboard.get_text())
+            .map_err(|e| anyhow!("클립보드 읽기 실패: {}", e))
+    }
+
+    /// 선택 클립보드(PRIMARY)에 텍스트 복사
+    pub fn set_primary(&mut self, text: &str) -> Result<()> {
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+        {
+            use arboard::{LinuxClipboardKind, SetExtLinux};
+            with_retry(&mut self.handle, arboard::Clipboard::new, |clipboard| {
+                clipboard.set().clipboard(LinuxClipboardKind::Primary).text(text)
+            })
+            .map_err(|e| anyhow!("선택 클립보드 복사 실패: {}", e))
+        }
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+        {
+            self.set_text(text)
+        }
+    }
+
+    /// 선택 클립보드(PRIMARY)에서 읽기
+    pub fn get_primary(&mut self) -> Result<String> {
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+        {
+            use arboard::{GetExtLinux, LinuxClipboardKind};
+            with_retry(&mut self.handle, arboard::Clipboard::new, |clipboard| {
+                clipboard.get().clipboard(LinuxClipboardKind::Primary).text()
+            })
+            .map_err(|e| anyhow!("선택 클립보드 읽기 실패: {}", e))
+        }
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+        {
+            self.get_text()
+        }
+    }
+
+    /// 설정한 클립보드에 텍스트 복사
+    pub fn set_text_to(&mut self, target: ClipboardTarget, text: &str) -> Result<()> {
+        match target {
+            ClipboardTarget::Clipboard => self.set_text(text),
+            ClipboardTarget::Primary => self.set_primary(text),
+            ClipboardTarget::Both => self.set_text(text).and(self.set_primary(text)),
+        }
+    }
+
+    /// 설정한 클립보드에 HTML 복사 (`text`는 HTML을 받지 않는 앱에 붙여넣을 일반 텍스트)
+    pub fn set_html_to(&mut self, target: ClipboardTarget, html: &str, text: &str) -> Result<()> {
+        match target {
+            ClipboardTarget::Clipboard => self.set_html(html, text),
+            ClipboardTarget::Primary => self.set_primary_html(html, text),
+            ClipboardTarget::Both => self.set_html(html, text).and(self.set_primary_html(html, text)),
+        }
+    }
+
+    fn set_html(&mut self, html: &str, text: &str) -> Result<()> {
+        with_retry(&mut self.handle, arboard::Clipboard::new, |clipboard| clipboard.set_html(html, Some(text)))
+            .map_err(|e| anyhow!("클립보드 복사 실패: {}", e))
+    }
+
+    fn set_primary_html(&mut self, html: &str, text: &str) -> Result<()> {
+        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+        {
+            use arboard::{LinuxClipboardKind, SetExtLinux};
+            with_retry(&mut self.handle, arboard::Clipboard::new, |clipboard| {
+                clipboard.set().clipboard(LinuxClipboardKind::Primary).html(html, Some(text))
+            })
+            .map_err(|e| anyhow!("선택 클립보드 복사 실패: {}", e))
+        }
+        #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+        {
+            self.set_html(html, text)
+        }
+    }
+}
+
+/// 핸들을 다시 만들면 해결될 수 있는 오류인지 확인
+/// (빈 클립보드나 지원하지 않는 클립보드는 재시도해도 같은 결과)
+fn is_retryable(error: &arboard::Error) -> bool {
+    !matches!(error, arboard::Error::ContentNotAvailable | arboard::Error::ClipboardNotSupported)
+}
+
+/// 캐시된 핸들로 작업을 수행하고, 재시도 가능한 오류면 핸들을 다시 만들어 한 번 더 시도
+fn with_retry<C, T>(
+    handle: &mut Option<C>,
+    create: impl Fn() -> Result<C, arboard::Error>,
+    mut op: impl FnMut(&mut C) -> Result<T, arboard::Error>,
+) -> Result<T, arboard::Error> {
+    let mut fresh = false;
+    if handle.is_none() {
+        *handle = Some(create()?);
+        fresh = true;
+    }
+
+    let error = match op(handle.as_mut().expect("clipboard handle")) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+
+    // 방금 만든 핸들이거나 재시도해도 소용없는 오류면 그대로 반환
+    if fresh || !is_retryable(&error) {
+        if is_retryable(&error) {
+            *handle = None;
+        }
+        return Err(error);
+    }
+
+    debug!("클립보드 작업 실패 ({}) - 핸들을 다시 만들어 재시도", error);
+    *handle = None;
+    let mut clipboard = create()?;
+    let result = op(&mut clipboard);
+    if result.is_ok() {
+        *handle = Some(clipboard);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// 생성 순번을 기억하는 가짜 클립보드
+    struct FakeClipboard {
+        generation: u32,
+    }
+
+    #[test]
+    fn test_stale_handle_is_recreated_once() {
+        let created = Cell::new(0);
+        let create = || {
+            created.set(created.get() + 1);
+            Ok(FakeClipboard { generation: created.get() })
+        };
+        let mut handle = None;
+
+        // 첫 핸들은 정상 동작
+        let result = with_retry(&mut handle, create, |c| Ok(c.generation));
+        assert_eq!(result.unwrap(), 1);
+
+        // 다른 앱이 소유권을 가져가 기존 핸들이 실패 -> 새 핸들로 한 번 재시도
+        let result = with_retry(&mut handle, create, |c| {
+            if c.generation == 1 { Err(arboard::Error::ClipboardOccupied) } else { Ok(c.generation) }
+        });
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(created.get(), 2);
+        assert_eq!(handle.as_ref().map(|c| c.generation), Some(2));
+    }
+
+    #[test]
+    fn test_persistent_failure_retries_only_once() {
+        let created = Cell::new(0);
+        let create = || {
+            created.set(created.get() + 1);
+            Ok(FakeClipboard { generation: created.get() })
+        };
+        let mut handle = Some(FakeClipboard { generation: 0 });
+        let attempts = Cell::new(0);
+
+        let result: Result<(), _> = with_retry(&mut handle, create, |_| {
+            attempts.set(attempts.get() + 1);
+            Err(arboard::Error::ClipboardOccupied)
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(created.get(), 1);
+    }
+
+    #[test]
+    fn test_empty_clipboard_is_not_retried() {
+        let created = Cell::new(0);
+        let create = || {
+            created.set(created.get() + 1);
+            Ok(FakeClipboard { generation: created.get() })
+        };
+        let mut handle = Some(FakeClipboard { generation: 0 });
+
+        let result: Result<String, _> = with_retry(&mut handle, create, |_| Err(arboard::Error::ContentNotAvailable));
+        assert!(matches!(result, Err(arboard::Error::ContentNotAvailable)));
+        assert_eq!(created.get(), 0);
+        assert!(handle.is_some());
+    }
+}
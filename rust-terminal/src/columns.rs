@@ -0,0 +1,131 @@
+//! 화면 칸(열) 계산 모듈
+//! 화면 줄 문자열(`get_visible_content`/`get_renderable_content`)의 글자 위치와 그리드 열 사이 변환
+//! 한글/CJK 같은 넓은 글자는 두 칸, 결합 문자처럼 폭이 0인 글자는 앞 글자와 한 묶음(grapheme)으로 같은 칸을 차지
+//!
+//! 마우스/선택/커서 좌표는 모두 그리드 열이므로, 줄 문자열을 자르거나 글자를 찾을 때는 이 모듈로 변환
+//! 줄 끝을 넘는 열은 한 칸을 한 글자로 셈
+
+use unicode_width::UnicodeWidthChar;
+
+/// 글자가 차지하는 칸 수 (폭 0 글자는 0, 폭을 알 수 없는 제어 문자는 한 칸)
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(1)
+}
+
+/// 줄 전체가 차지하는 칸 수
+pub fn line_width(line: &str) -> usize {
+    line.chars().map(char_width).sum()
+}
+
+/// `column`열을 덮는 글자 묶음의 첫 글자 위치 (넓은 글자의 오른쪽 칸도 그 글자)
+pub fn column_to_index(line: &str, column: usize) -> usize {
+    let mut start = 0;
+    let mut count = 0;
+    for (index, ch) in line.chars().enumerate() {
+        let width = char_width(ch);
+        if width > 0 && column < start + width {
+            return index;
+        }
+        start += width;
+        count = index + 1;
+    }
+    count + (column - start)
+}
+
+/// `index`번째 글자가 시작하는 열 (폭 0 글자는 앞 글자의 열)
+pub fn index_to_column(line: &str, index: usize) -> usize {
+    let mut column = 0;
+    let mut previous = 0;
+    let mut count = 0;
+    for (i, ch) in line.chars().enumerate() {
+        let width = char_width(ch);
+        if i == index {
+            return if width == 0 { previous } else { column };
+        }
+        if width > 0 {
+            previous = column;
+        }
+        column += width;
+        count = i + 1;
+    }
+    column + (index - count)
+}
+
+/// `index`번째 글자가 끝나는 열 (끝 포함, 넓은 글자는 오른쪽 칸)
+pub fn index_end_column(line: &str, index: usize) -> usize {
+    let width = line.chars().nth(index).map_or(1, char_width).max(1);
+    index_to_column(line, index) + width - 1
+}
+
+/// `start`열부터 `end`열까지(끝 포함) 걸친 글자 묶음
+/// 넓은 글자는 한 칸만 걸쳐도 포함하고, 묶음 뒤의 폭 0 글자도 함께 포함
+pub fn slice_columns(line: &str, start: usize, end: usize) -> &str {
+    let mut column = 0;
+    let mut from = None;
+    let mut to = line.len();
+    for (offset, ch) in line.char_indices() {
+        let width = char_width(ch);
+        if width == 0 {
+            continue; // 앞 글자 묶음에 포함
+        }
+        if from.is_none() && column + width > start {
+            from = Some(offset);
+        }
+        if column > end {
+            to = offset;
+            break;
+        }
+        column += width;
+    }
+    match from {
+        Some(from) if from < to => &line[from..to],
+        _ => "",
+    }
+}
+
+/// 줄의 글자마다 (시작 열, 글자) - 폭 0 글자는 앞 글자와 같은 열
+pub fn char_columns(line: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut column = 0;
+    let mut previous = 0;
+    line.chars().map(move |ch| {
+        let width = char_width(ch);
+        if width == 0 {
+            return (previous, ch);
+        }
+        previous = column;
+        column += width;
+        (previous, ch)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_and_combining_columns() {
+        // 'e' + 결합 악센트(폭 0), 한글 두 글자(각 두 칸)
+        let line = "ae\u{301}한글 x";
+        assert_eq!(line_width(line), 8);
+        assert_eq!(column_to_index(line, 1), 1);
+        assert_eq!(column_to_index(line, 2), 3); // '한' 왼쪽 칸
+        assert_eq!(column_to_index(line, 3), 3); // '한' 오른쪽 칸
+        assert_eq!(column_to_index(line, 4), 4);
+        assert_eq!(column_to_index(line, 7), 6);
+        assert_eq!(column_to_index(line, 10), 9); // 줄 끝 너머
+        assert_eq!(index_to_column(line, 2), 1); // 결합 문자는 앞 글자의 열
+        assert_eq!(index_to_column(line, 4), 4);
+        assert_eq!(index_end_column(line, 4), 5);
+        assert_eq!(index_to_column(line, 9), 10);
+        assert_eq!(char_columns(line).map(|(column, _)| column).collect::<Vec<_>>(), vec![0, 1, 1, 2, 4, 6, 7]);
+    }
+
+    #[test]
+    fn test_slice_columns() {
+        let line = "ae\u{301}한글 x";
+        assert_eq!(slice_columns(line, 1, 1), "e\u{301}");
+        assert_eq!(slice_columns(line, 3, 4), "한글"); // 양쪽 모두 한 칸만 걸침
+        assert_eq!(slice_columns(line, 0, usize::MAX), line);
+        assert_eq!(slice_columns(line, 20, 30), "");
+    }
+}
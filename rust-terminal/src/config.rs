@@ -0,0 +1,1770 @@
+//! 애플리케이션 설정 모듈
+//! 하드코딩된 값들을 한 곳에 모아 사용자 조정이 가능하도록 함
+//! 설정 파일은 `config.toml`(TOML)을 먼저 찾고, 없으면 예전 `config`(`KEY=value` 줄) 파일을 읽음
+
+use crate::activity::ActivityNotify;
+use crate::bell::BellResponse;
+use crate::bindings::{KeyBinding, Keybindings};
+use crate::env_policy::{self, EnvPolicy};
+use crate::export::ExportFormat;
+use crate::hints;
+use crate::notify::NotifyMethod;
+use crate::profile::{self, Profile};
+use crate::remote::{self, SshHost};
+use crate::serial::{self, SerialSettings};
+use crate::smart::{self, SmartRule};
+use crate::status::StatusLayout;
+use crate::terminal::{CellSize, Shell};
+use crate::theme::Theme;
+use crate::toml;
+use anyhow::{anyhow, bail, Context, Result};
+use crossterm::event::KeyModifiers;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+};
+use std::path::PathBuf;
+
+/// 전체 설정
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub mouse: MouseConfig,
+    pub cursor: CursorConfig,
+    pub selection: SelectionConfig,
+    pub scrollback: ScrollbackConfig,
+    pub search: SearchConfig,
+    pub banner: BannerConfig,
+    pub colors: ColorsConfig,
+    pub bindings: Keybindings,    // 기본 단축키 + 사용자 정의 키 바인딩 (사용자 바인딩 우선)
+    pub cell_size: CellSize,       // PTY에 보고하는 셀 픽셀 크기
+    pub paste: PasteConfig,
+    pub title: TitleConfig,
+    pub status_bar: StatusLayout, // 하단 상태 표시줄 구역 배치 (비어 있으면 표시하지 않음)
+    pub bell: BellResponse,       // 프로그램이 BEL을 보냈을 때 반응
+    pub notification: NotifyMethod, // 데스크톱 알림 보내는 방법
+    pub activity: ActivityConfig,
+    pub editor: String, // 줄 번호가 붙은 파일 경로를 열 편집기 명령 (비어 있으면 `$VISUAL`/`$EDITOR`)
+    pub hints: HintConfig,
+    pub width: WidthConfig,
+    pub session: SessionConfig,
+    pub shell: ShellConfig,
+    pub output_log: OutputLogConfig,
+    pub recording: RecordingConfig,
+    pub export: ExportConfig,
+    pub keyboard: KeyboardConfig,
+    pub render: RenderConfig,
+    pub profiles: Vec<Profile>, // config.toml의 `[profiles.이름]`
+    pub env: EnvPolicy,         // 셸에 넘길 환경 변수 정책
+    pub ssh_hosts: Vec<SshHost>, // config.toml의 `[ssh.이름]`
+    pub smart: Vec<SmartRule>,   // config.toml의 `[smart.이름]` 스마트 선택 규칙 (앞 규칙 우선)
+    pub serial: SerialSettings, // 시리얼 연결 설정 (장치를 지정하면 셸 대신 장치에 연결)
+}
+
+/// 설정 키 (설정 파일의 `KEY=value`와 같은 이름의 환경 변수로 지정)
+const SETTING_KEYS: &[&str] = &[
+    "RUST_TERMINAL_CURSOR",
+    "RUST_TERMINAL_CURSOR_BLINK",
+    "RUST_TERMINAL_CURSOR_SHAPE",
+    "RUST_TERMINAL_CURSOR_BLINK_INTERVAL",
+    "RUST_TERMINAL_COPY_LINE_ENDING",
+    "RUST_TERMINAL_COPY_ON_SELECT",
+    "RUST_TERMINAL_SELECTION_CLIPBOARD",
+    "RUST_TERMINAL_COPY_TRIM",
+    "RUST_TERMINAL_COPY_FORMAT",
+    "RUST_TERMINAL_WHEEL_SELECTION",
+    "RUST_TERMINAL_CLEAR_SELECTION",
+    "RUST_TERMINAL_URL_MODIFIER",
+    "RUST_TERMINAL_EDITOR",
+    "RUST_TERMINAL_HINT_ALPHABET",
+    "RUST_TERMINAL_UNDERLINE_LINKS",
+    "RUST_TERMINAL_THEME",
+    "RUST_TERMINAL_BACKGROUND",
+    "RUST_TERMINAL_FOREGROUND",
+    "RUST_TERMINAL_COLORTERM",
+    "RUST_TERMINAL_SCROLLBACK_LINES",
+    "RUST_TERMINAL_SCROLL_PAST_END",
+    "RUST_TERMINAL_ALT_SCREEN_SETTLE",
+    "RUST_TERMINAL_SCROLL_EDGES",
+    "RUST_TERMINAL_SCROLL_LINES",
+    "RUST_TERMINAL_SMOOTH_SCROLL",
+    "RUST_TERMINAL_BANNER",
+    "RUST_TERMINAL_BIND",
+    "RUST_TERMINAL_CELL_SIZE",
+    "RUST_TERMINAL_MAX_COLUMNS",
+    "RUST_TERMINAL_ALIGN",
+    "RUST_TERMINAL_PASTE_NEWLINE",
+    "RUST_TERMINAL_TITLE",
+    "RUST_TERMINAL_LOCK_TITLE",
+    "RUST_TERMINAL_STATUS_BAR",
+    "RUST_TERMINAL_BELL",
+    "RUST_TERMINAL_NOTIFY",
+    "RUST_TERMINAL_ACTIVITY_NOTIFY",
+    "RUST_TERMINAL_SILENCE_SECONDS",
+    "RUST_TERMINAL_CONFIRM_CLOSE_LAST",
+    "RUST_TERMINAL_ON_EXIT",
+    "RUST_TERMINAL_RESTORE_SESSION",
+    "RUST_TERMINAL_RESTORE_SCROLLBACK",
+    "RUST_TERMINAL_DAEMON",
+    "RUST_TERMINAL_SHELL",
+    "RUST_TERMINAL_SHELL_ARGS",
+    "RUST_TERMINAL_WORKING_DIRECTORY",
+    "RUST_TERMINAL_SERIAL_DEVICE",
+    "RUST_TERMINAL_SERIAL_BAUD",
+    "RUST_TERMINAL_SERIAL_DATA_BITS",
+    "RUST_TERMINAL_SERIAL_PARITY",
+    "RUST_TERMINAL_SERIAL_STOP_BITS",
+    "RUST_TERMINAL_SERIAL_FLOW_CONTROL",
+    "RUST_TERMINAL_ENV_INHERIT",
+    "RUST_TERMINAL_ENV_REMOVE",
+    "RUST_TERMINAL_ENV_SET",
+    "RUST_TERMINAL_OUTPUT_LOG",
+    "RUST_TERMINAL_OUTPUT_LOG_DIR",
+    "RUST_TERMINAL_OUTPUT_LOG_ROTATE_MB",
+    "RUST_TERMINAL_RECORDING_DIR",
+    "RUST_TERMINAL_EXPORT_DIR",
+    "RUST_TERMINAL_KITTY_KEYBOARD",
+    "RUST_TERMINAL_MAX_FPS",
+];
+
+/// config.toml의 `[테이블] 키`에 대응하는 설정 키
+/// (`shell.args` 배열, `[keybindings]`, `[env.set]`, `[themes.이름]`, `[profiles.이름]`, `[ssh.이름]`과 `[smart.이름]` 테이블은 따로 처리)
+/// `env.inherit`와 `env.remove`는 문자열 배열도 허용
+const TOML_KEYS: &[(&str, &str)] = &[
+    ("shell.program", "RUST_TERMINAL_SHELL"),
+    ("shell.working_directory", "RUST_TERMINAL_WORKING_DIRECTORY"),
+    ("serial.device", "RUST_TERMINAL_SERIAL_DEVICE"),
+    ("serial.baud", "RUST_TERMINAL_SERIAL_BAUD"),
+    ("serial.data_bits", "RUST_TERMINAL_SERIAL_DATA_BITS"),
+    ("serial.parity", "RUST_TERMINAL_SERIAL_PARITY"),
+    ("serial.stop_bits", "RUST_TERMINAL_SERIAL_STOP_BITS"),
+    ("serial.flow_control", "RUST_TERMINAL_SERIAL_FLOW_CONTROL"),
+    ("env.inherit", "RUST_TERMINAL_ENV_INHERIT"),
+    ("env.remove", "RUST_TERMINAL_ENV_REMOVE"),
+    ("cursor.shape", "RUST_TERMINAL_CURSOR_SHAPE"),
+    ("cursor.blink", "RUST_TERMINAL_CURSOR_BLINK"),
+    ("cursor.blink_interval_ms", "RUST_TERMINAL_CURSOR_BLINK_INTERVAL"),
+    ("cursor.render", "RUST_TERMINAL_CURSOR"),
+    ("colors.theme", "RUST_TERMINAL_THEME"),
+    ("colors.foreground", "RUST_TERMINAL_FOREGROUND"),
+    ("colors.background", "RUST_TERMINAL_BACKGROUND"),
+    ("colors.colorterm", "RUST_TERMINAL_COLORTERM"),
+    ("scrollback.lines", "RUST_TERMINAL_SCROLLBACK_LINES"),
+    ("scrollback.past_end", "RUST_TERMINAL_SCROLL_PAST_END"),
+    ("scrollback.edge_indicators", "RUST_TERMINAL_SCROLL_EDGES"),
+    ("scrollback.alt_screen_settle", "RUST_TERMINAL_ALT_SCREEN_SETTLE"),
+    ("scrollback.scroll_lines", "RUST_TERMINAL_SCROLL_LINES"),
+    ("scrollback.smooth", "RUST_TERMINAL_SMOOTH_SCROLL"),
+    ("selection.copy_line_ending", "RUST_TERMINAL_COPY_LINE_ENDING"),
+    ("selection.copy_on_select", "RUST_TERMINAL_COPY_ON_SELECT"),
+    ("selection.clipboard", "RUST_TERMINAL_SELECTION_CLIPBOARD"),
+    ("selection.trim_trailing_whitespace", "RUST_TERMINAL_COPY_TRIM"),
+    ("selection.copy_format", "RUST_TERMINAL_COPY_FORMAT"),
+    ("selection.wheel", "RUST_TERMINAL_WHEEL_SELECTION"),
+    ("selection.clear_on_output", "RUST_TERMINAL_CLEAR_SELECTION"),
+    ("mouse.url_modifier", "RUST_TERMINAL_URL_MODIFIER"),
+    ("editor.command", "RUST_TERMINAL_EDITOR"),
+    ("hints.alphabet", "RUST_TERMINAL_HINT_ALPHABET"),
+    ("mouse.underline_links", "RUST_TERMINAL_UNDERLINE_LINKS"),
+    ("paste.trailing_newline", "RUST_TERMINAL_PASTE_NEWLINE"),
+    ("window.title", "RUST_TERMINAL_TITLE"),
+    ("window.lock_title", "RUST_TERMINAL_LOCK_TITLE"),
+    ("window.status_bar", "RUST_TERMINAL_STATUS_BAR"),
+    ("bell.response", "RUST_TERMINAL_BELL"),
+    ("notification.method", "RUST_TERMINAL_NOTIFY"),
+    ("activity.notify", "RUST_TERMINAL_ACTIVITY_NOTIFY"),
+    ("activity.silence_seconds", "RUST_TERMINAL_SILENCE_SECONDS"),
+    ("window.banner", "RUST_TERMINAL_BANNER"),
+    ("window.cell_size", "RUST_TERMINAL_CELL_SIZE"),
+    ("window.max_columns", "RUST_TERMINAL_MAX_COLUMNS"),
+    ("window.align", "RUST_TERMINAL_ALIGN"),
+    ("session.confirm_close_last", "RUST_TERMINAL_CONFIRM_CLOSE_LAST"),
+    ("session.on_exit", "RUST_TERMINAL_ON_EXIT"),
+    ("session.restore", "RUST_TERMINAL_RESTORE_SESSION"),
+    ("session.restore_scrollback", "RUST_TERMINAL_RESTORE_SCROLLBACK"),
+    ("session.daemon", "RUST_TERMINAL_DAEMON"),
+    ("output_log.enabled", "RUST_TERMINAL_OUTPUT_LOG"),
+    ("output_log.directory", "RUST_TERMINAL_OUTPUT_LOG_DIR"),
+    ("output_log.rotate_mb", "RUST_TERMINAL_OUTPUT_LOG_ROTATE_MB"),
+    ("recording.directory", "RUST_TERMINAL_RECORDING_DIR"),
+    ("export.directory", "RUST_TERMINAL_EXPORT_DIR"),
+    ("keyboard.kitty", "RUST_TERMINAL_KITTY_KEYBOARD"),
+    ("render.max_fps", "RUST_TERMINAL_MAX_FPS"),
+];
+
+impl Config {
+    /// 설정 로드 (기본값 + 설정 파일 + 환경 변수 재정의)
+    /// 설정 파일에 오류가 있으면 경고 후 파일 없이 로드
+    pub fn load() -> Self {
+        Self::try_load().unwrap_or_else(|e| {
+            log::warn!("설정 파일 무시: {:#}", e);
+            Self::from_sources(None, env_setting).unwrap_or_default()
+        })
+    }
+
+    /// 설정 로드 - 설정 파일에 오류가 있으면 Err (런타임 다시 읽기용)
+    /// config.toml이 있으면 그것만 읽고, 없으면 예전 형식의 config 파일 사용
+    pub fn try_load() -> Result<Self> {
+        let read = |path: &PathBuf| {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("설정 파일 읽기 실패: {}", path.display()))
+        };
+        if let Some(path) = Self::toml_path().filter(|path| path.exists()) {
+            return Self::from_toml_sources(&read(&path)?, env_setting)
+                .with_context(|| format!("설정 파일 오류: {}", path.display()));
+        }
+        let text = match Self::default_path() {
+            Some(path) if path.exists() => Some(read(&path)?),
+            _ => None,
+        };
+        Self::from_sources(text.as_deref(), env_setting)
+    }
+
+    /// 예전 형식(`KEY=value` 줄) 설정 파일 경로
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rust-terminal").join("config"))
+    }
+
+    /// TOML 설정 파일 경로
+    pub fn toml_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rust-terminal").join("config.toml"))
+    }
+
+    /// config.toml 내용과 환경 변수로 설정 생성
+    pub fn from_toml_sources(text: &str, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut config = Self::default();
+        for entry in toml::parse(text)? {
+            config.set_toml(&entry)
+                .with_context(|| format!("{}번째 줄", entry.line))?;
+        }
+        config.apply_env(env);
+        config.colors.resolve_theme()?;
+        // 프로필의 테마 이름은 사용자 테마까지 모두 읽은 뒤 확인
+        for profile in &config.profiles {
+            if let Some(theme) = profile.theme.as_deref().filter(|theme| config.colors.find_theme(theme).is_none()) {
+                bail!("profiles.{}: 알 수 없는 테마: {}", profile.name, theme);
+            }
+        }
+        if let Some(rule) = config.smart.iter().find(|rule| rule.pattern.is_none()) {
+            bail!("smart.{}: pattern이 없음", rule.name);
+        }
+        Ok(config)
+    }
+
+    /// 이름으로 프로필 찾기
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// 이름으로 SSH 호스트 찾기 (없으면 `[사용자@]호스트[:포트]`로 보고 즉석 대상 생성)
+    pub fn ssh_host(&self, target: &str) -> Result<SshHost> {
+        match self.ssh_hosts.iter().find(|host| host.name == target) {
+            Some(host) => Ok(host.clone()),
+            None => SshHost::parse_target(target),
+        }
+    }
+
+    /// 첫 세션에 연결할 셸 (시리얼 장치를 지정했으면 장치)
+    pub fn startup_shell(&self) -> Shell {
+        if self.serial.device.as_os_str().is_empty() {
+            self.shell.shell()
+        } else {
+            Shell::Serial(self.serial.clone())
+        }
+    }
+
+    /// 정의된 SSH 호스트 이름 (파일에 나온 순서)
+    pub fn ssh_host_names(&self) -> Vec<String> {
+        self.ssh_hosts.iter().map(|host| host.name.clone()).collect()
+    }
+
+    /// 정의된 프로필 이름 (파일에 나온 순서)
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.iter().map(|profile| profile.name.clone()).collect()
+    }
+
+    /// 프로필의 셸, 환경 변수, 작업 디렉토리, 테마, 제목으로 기본 설정 덮어쓰기
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self.profile(name).cloned().ok_or_else(|| {
+            let names: Vec<&str> = self.profiles.iter().map(|profile| profile.name.as_str()).collect();
+            anyhow!("알 수 없는 프로필: {} (정의된 프로필: {})", name, names.join(", "))
+        })?;
+        if let Some(program) = profile.program {
+            self.shell.program = Some(program);
+            self.shell.args = profile.args;
+        }
+        for (key, value) in &profile.env {
+            self.env.set_var(key, value);
+        }
+        if let Some(directory) = profile.working_directory {
+            self.shell.working_directory = Some(directory);
+        }
+        if let Some(theme) = profile.theme {
+            self.colors.set_theme(&theme)?;
+        }
+        if let Some(title) = profile.title {
+            self.title.fallback = title;
+        }
+        Ok(())
+    }
+
+    /// config.toml의 `키 = 값` 하나 적용
+    fn set_toml(&mut self, entry: &toml::Entry) -> Result<()> {
+        let path = entry.path();
+        if entry.table == "keybindings" {
+            // `"ctrl+shift+v" = "paste"` → `ctrl+shift+v paste`
+            let action = entry.value.as_str()
+                .ok_or_else(|| anyhow!("키 바인딩 동작은 문자열이어야 함: {}", path))?;
+            self.bindings.push(KeyBinding::parse(&format!("{} {}", entry.key, action))?);
+            return Ok(());
+        }
+        if let Some(name) = entry.table.strip_prefix("themes.") {
+            // 같은 이름의 테마 값은 한 테마로 모음
+            let themes = &mut self.colors.user_themes;
+            let index = match themes.iter().position(|theme| theme.name == name) {
+                Some(index) => index,
+                None => {
+                    themes.push(Theme::named(name));
+                    themes.len() - 1
+                }
+            };
+            return themes[index].set(&entry.key, &entry.value);
+        }
+        if let Some(table) = entry.table.strip_prefix("profiles.") {
+            return profile::set_entry(&mut self.profiles, table, entry);
+        }
+        if let Some(name) = entry.table.strip_prefix("ssh.") {
+            return remote::set_entry(&mut self.ssh_hosts, name, entry);
+        }
+        if let Some(name) = entry.table.strip_prefix("smart.") {
+            return smart::set_entry(&mut self.smart, name, entry);
+        }
+        if entry.table == "env.set" {
+            let value = match &entry.value {
+                toml::Value::String(value) => value.clone(),
+                toml::Value::Integer(value) => value.to_string(),
+                toml::Value::Boolean(value) => value.to_string(),
+                toml::Value::Array(_) => bail!("{}에는 배열을 쓸 수 없음", path),
+            };
+            self.env.set_var(&entry.key, &value);
+            return Ok(());
+        }
+        if path == "shell.args" {
+            self.shell.args = string_array(&entry.value).ok_or_else(|| anyhow!("shell.args는 문자열 배열이어야 함"))?;
+            return Ok(());
+        }
+        if let ("env.inherit" | "env.remove", toml::Value::Array(_)) = (path.as_str(), &entry.value) {
+            let names = string_array(&entry.value).ok_or_else(|| anyhow!("{}는 문자열 배열이어야 함", path))?;
+            match path.as_str() {
+                "env.inherit" => self.env.inherit = Some(names),
+                _ => self.env.remove = names,
+            }
+            return Ok(());
+        }
+
+        let key = TOML_KEYS.iter()
+            .find(|(name, _)| *name == path)
+            .map(|(_, key)| *key)
+            .ok_or_else(|| anyhow!("알 수 없는 설정: {}", path))?;
+        let value = match &entry.value {
+            toml::Value::String(value) => value.clone(),
+            toml::Value::Integer(value) => value.to_string(),
+            toml::Value::Boolean(value) => value.to_string(),
+            toml::Value::Array(_) => bail!("{}에는 배열을 쓸 수 없음", path),
+        };
+        self.set(key, &value)
+    }
+
+    /// 설정 파일 내용과 환경 변수로 설정 생성
+    /// 설정 파일은 한 줄에 `KEY=value` (`#`으로 시작하면 주석), 환경 변수가 파일보다 우선
+    pub fn from_sources(file: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut config = Self::default();
+
+        for (idx, line) in file.unwrap_or_default().lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or_else(|| anyhow!("{}번째 줄: `KEY=value` 형식이 아님", idx + 1))?;
+            config.set(key.trim(), value)
+                .with_context(|| format!("{}번째 줄", idx + 1))?;
+        }
+
+        config.apply_env(env);
+        config.colors.resolve_theme()?;
+        Ok(config)
+    }
+
+    /// 환경 변수로 설정 재정의
+    /// 환경 변수 값이 잘못되어도 시작은 가능하도록 경고만 남김
+    fn apply_env(&mut self, env: impl Fn(&str) -> Option<String>) {
+        for key in SETTING_KEYS {
+            if let Some(value) = env(key) {
+                if let Err(e) = self.set(key, &value) {
+                    log::warn!("{:#}", e);
+                }
+            }
+        }
+    }
+
+    /// 설정 값 하나 적용
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let invalid = || anyhow!("알 수 없는 {} 값: {}", key, value);
+        match key {
+            "RUST_TERMINAL_CURSOR" => {
+                self.cursor.render_mode = CursorRenderMode::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_CURSOR_BLINK" => {
+                self.cursor.blink = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_CURSOR_SHAPE" => {
+                self.cursor.shape = CursorShape::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_CURSOR_BLINK_INTERVAL" => {
+                self.cursor.blink_interval_ms = value.trim().parse().ok()
+                    .filter(|&ms| ms > 0)
+                    .ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_COPY_LINE_ENDING" => {
+                self.selection.copy_line_ending = LineEnding::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_COPY_ON_SELECT" => {
+                self.selection.copy_on_select = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_SELECTION_CLIPBOARD" => {
+                self.selection.clipboard = ClipboardTarget::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_COPY_TRIM" => {
+                self.selection.trim_trailing_whitespace = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_COPY_FORMAT" => {
+                self.selection.copy_format = ExportFormat::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_WHEEL_SELECTION" => {
+                self.selection.on_wheel_scroll = WheelScrollSelection::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_CLEAR_SELECTION" => {
+                self.selection.clear_on_output = ClearSelectionOnOutput::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_URL_MODIFIER" => {
+                self.mouse.url_modifier = UrlOpenModifier::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_UNDERLINE_LINKS" => {
+                self.mouse.underline_links = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_KITTY_KEYBOARD" => {
+                self.keyboard.kitty = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_MAX_FPS" => {
+                self.render.max_fps = value.trim().parse().map_err(|_| invalid())?;
+            }
+            // 테마는 설정을 모두 읽은 뒤 사용자 테마까지 포함해서 찾음
+            "RUST_TERMINAL_THEME" => self.colors.theme_name = value.trim().to_string(),
+            "RUST_TERMINAL_BACKGROUND" => {
+                self.colors.background = Some(value.trim().parse::<Color>().map_err(|_| invalid())?);
+            }
+            "RUST_TERMINAL_FOREGROUND" => {
+                self.colors.foreground = Some(value.trim().parse::<Color>().map_err(|_| invalid())?);
+            }
+            // 빈 값이면 COLORTERM을 설정하지 않음
+            "RUST_TERMINAL_COLORTERM" => {
+                let value = value.trim();
+                self.colors.colorterm = (!value.is_empty()).then(|| value.to_string());
+            }
+            "RUST_TERMINAL_SCROLLBACK_LINES" => {
+                self.scrollback.history_lines = value.trim().parse().map_err(|_| invalid())?;
+            }
+            "RUST_TERMINAL_SCROLL_PAST_END" => {
+                self.scrollback.scroll_past_end_lines = value.trim().parse().map_err(|_| invalid())?;
+            }
+            "RUST_TERMINAL_SCROLL_EDGES" => {
+                self.scrollback.edge_indicators = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_ALT_SCREEN_SETTLE" => {
+                self.scrollback.alt_screen_settle = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_SCROLL_LINES" => {
+                self.scrollback.scroll_lines = value.trim().parse().ok().filter(|&lines| lines > 0).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_SMOOTH_SCROLL" => {
+                self.scrollback.smooth = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_CELL_SIZE" => {
+                self.cell_size = CellSize::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_MAX_COLUMNS" => {
+                self.width.max_columns = value.trim().parse().map_err(|_| invalid())?;
+            }
+            "RUST_TERMINAL_ALIGN" => {
+                self.width.align = ContentAlign::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_PASTE_NEWLINE" => {
+                self.paste.trailing_newline = PasteNewline::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_TITLE" => self.title.fallback = value.to_string(),
+            "RUST_TERMINAL_EDITOR" => self.editor = value.trim().to_string(),
+            "RUST_TERMINAL_HINT_ALPHABET" => {
+                self.hints.alphabet = hints::parse_alphabet(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_LOCK_TITLE" => {
+                self.title.lock = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_STATUS_BAR" => {
+                self.status_bar = StatusLayout::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_BELL" => {
+                self.bell = BellResponse::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_NOTIFY" => {
+                self.notification = NotifyMethod::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_ACTIVITY_NOTIFY" => {
+                self.activity.notify = ActivityNotify::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_SILENCE_SECONDS" => {
+                self.activity.silence_seconds = value.trim().parse().ok().filter(|&seconds| seconds > 0).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_ON_EXIT" => {
+                self.session.on_exit = ExitBehavior::parse(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_CONFIRM_CLOSE_LAST" => {
+                self.session.confirm_close_last = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_RESTORE_SESSION" => {
+                self.session.restore = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_RESTORE_SCROLLBACK" => {
+                self.session.restore_scrollback_lines = value.trim().parse().map_err(|_| invalid())?;
+            }
+            "RUST_TERMINAL_DAEMON" => {
+                self.session.daemon = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_BANNER" => self.banner.set_text(value),
+            // 비어 있으면 시스템 기본 셸
+            "RUST_TERMINAL_SHELL" => {
+                let value = value.trim();
+                self.shell.program = (!value.is_empty()).then(|| value.to_string());
+            }
+            "RUST_TERMINAL_SHELL_ARGS" => {
+                self.shell.args = value.split_whitespace().map(str::to_string).collect();
+            }
+            "RUST_TERMINAL_WORKING_DIRECTORY" => {
+                let value = value.trim();
+                self.shell.working_directory = (!value.is_empty()).then(|| expand_home(value));
+            }
+            "RUST_TERMINAL_SERIAL_DEVICE" => self.serial.device = expand_home(value.trim()),
+            "RUST_TERMINAL_SERIAL_BAUD" => {
+                self.serial.baud = value.trim().parse().map_err(|_| invalid())?;
+                self.serial.validate()?;
+            }
+            "RUST_TERMINAL_SERIAL_DATA_BITS" => {
+                self.serial.data_bits = value.trim().parse().map_err(|_| invalid())?;
+                self.serial.validate()?;
+            }
+            "RUST_TERMINAL_SERIAL_STOP_BITS" => {
+                self.serial.stop_bits = value.trim().parse().map_err(|_| invalid())?;
+                self.serial.validate()?;
+            }
+            "RUST_TERMINAL_SERIAL_PARITY" => self.serial.parity = serial::parse_parity(value).ok_or_else(invalid)?,
+            "RUST_TERMINAL_SERIAL_FLOW_CONTROL" => {
+                self.serial.flow_control = serial::parse_flow_control(value).ok_or_else(invalid)?;
+            }
+            // `all`, `none`, 또는 쉼표/공백으로 구분한 이름 목록
+            "RUST_TERMINAL_ENV_INHERIT" => self.env.set_inherit(value),
+            "RUST_TERMINAL_ENV_REMOVE" => self.env.remove = env_policy::parse_names(value),
+            // `KEY=value` - 설정 파일에 여러 줄로 지정 가능
+            "RUST_TERMINAL_ENV_SET" => {
+                let (name, value) = value.split_once('=').ok_or_else(invalid)?;
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(invalid());
+                }
+                self.env.set_var(name, value);
+            }
+            "RUST_TERMINAL_OUTPUT_LOG" => {
+                self.output_log.enabled = parse_flag(value).ok_or_else(invalid)?;
+            }
+            "RUST_TERMINAL_OUTPUT_LOG_DIR" => {
+                let value = value.trim();
+                self.output_log.directory = (!value.is_empty()).then(|| expand_home(value));
+            }
+            "RUST_TERMINAL_OUTPUT_LOG_ROTATE_MB" => {
+                self.output_log.rotate_mb = value.trim().parse().map_err(|_| invalid())?;
+            }
+            "RUST_TERMINAL_RECORDING_DIR" => {
+                let value = value.trim();
+                self.recording.directory = (!value.is_empty()).then(|| expand_home(value));
+            }
+            "RUST_TERMINAL_EXPORT_DIR" => {
+                let value = value.trim();
+                self.export.directory = (!value.is_empty()).then(|| expand_home(value));
+            }
+            // 여러 줄에 걸쳐 지정하면 모두 추가
+            "RUST_TERMINAL_BIND" => self.bindings.push(KeyBinding::parse(value)?),
+            _ => bail!("알 수 없는 설정: {}", key),
+        }
+        Ok(())
+    }
+}
+
+/// 환경 변수에서 설정 값 읽기
+fn env_setting(key: &str) -> Option<String> {
+    std::env::var(key).ok()
+}
+
+/// 마우스 관련 설정
+#[derive(Debug, Clone)]
+pub struct MouseConfig {
+    /// 더블/트리플 클릭으로 인정되는 클릭 간 최대 간격 (ms)
+    pub multi_click_interval_ms: u64,
+    /// 연속 클릭으로 인정되는 열(column) 이동 허용 범위
+    pub multi_click_col_tolerance: u16,
+    /// 연속 클릭으로 인정되는 행(row) 이동 허용 범위
+    pub multi_click_row_tolerance: u16,
+    /// 클릭으로 URL을 열 때 함께 눌러야 하는 수정 키 (수정 키 없는 클릭/드래그는 항상 텍스트 선택)
+    pub url_modifier: UrlOpenModifier,
+    /// 화면에 보이는 URL/파일 경로에 항상 밑줄 (끄면 수정 키를 누른 채 가리킨 링크에만)
+    pub underline_links: bool,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            multi_click_interval_ms: 400,
+            multi_click_col_tolerance: 1,
+            multi_click_row_tolerance: 0,
+            url_modifier: UrlOpenModifier::default(),
+            underline_links: true,
+        }
+    }
+}
+
+impl MouseConfig {
+    /// 멀티 클릭 간격을 Duration으로 반환
+    pub fn multi_click_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.multi_click_interval_ms)
+    }
+}
+
+/// URL 열기 수정 키
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UrlOpenModifier {
+    #[default]
+    Ctrl,
+    Alt,
+    Shift,
+    /// 클릭으로 URL을 열지 않음
+    Disabled,
+}
+
+impl UrlOpenModifier {
+    /// 문자열에서 파싱 (ctrl / alt / shift / none)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Some(Self::Ctrl),
+            "alt" => Some(Self::Alt),
+            "shift" => Some(Self::Shift),
+            "none" | "off" => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+
+    /// 마우스 이벤트의 수정 키에 URL 열기 수정 키가 포함되어 있는지 확인
+    pub fn is_held(self, modifiers: KeyModifiers) -> bool {
+        let required = match self {
+            Self::Ctrl => KeyModifiers::CONTROL,
+            Self::Alt => KeyModifiers::ALT,
+            Self::Shift => KeyModifiers::SHIFT,
+            Self::Disabled => return false,
+        };
+        modifiers.contains(required)
+    }
+}
+
+/// 커서 렌더링 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorRenderMode {
+    /// 환경을 보고 자동 결정 (TERM=dumb, CI 환경 등에서는 소프트웨어 커서)
+    #[default]
+    Auto,
+    /// 호스트 터미널의 커서 위치 지정 사용
+    Hardware,
+    /// 셀을 직접 그려서 커서 표시 (커서 위치 지정이 무시되는 환경용)
+    Software,
+}
+
+impl CursorRenderMode {
+    /// 문자열에서 렌더링 방식 파싱 (auto / hardware / software)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "hardware" => Some(Self::Hardware),
+            "software" => Some(Self::Software),
+            _ => None,
+        }
+    }
+}
+
+/// 커서 모양 (Zed 방식)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    #[default]
+    Block,
+    Underline,
+    Beam,
+    Hollow,
+}
+
+impl CursorShape {
+    /// 문자열에서 커서 모양 파싱 (block / underline / beam / hollow)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "block" => Some(Self::Block),
+            "underline" => Some(Self::Underline),
+            "beam" | "bar" => Some(Self::Beam),
+            "hollow" => Some(Self::Hollow),
+            _ => None,
+        }
+    }
+}
+
+/// 커서 관련 설정
+#[derive(Debug, Clone)]
+pub struct CursorConfig {
+    /// 커서 렌더링 방식.
+    /// 일부 CI pty나 제한된 에뮬레이터처럼 커서 위치 지정을 따르지 않는 환경에서
+    /// 커서가 보이지 않으면 `Software`로 설정
+    pub render_mode: CursorRenderMode,
+    /// 커서 깜빡임 여부. 화면 녹화/스크린샷처럼 프레임이 일정해야 할 때 끔
+    pub blink: bool,
+    /// 커서 모양
+    pub shape: CursorShape,
+    /// 소프트웨어 커서 깜빡임 간격 (ms)
+    pub blink_interval_ms: u64,
+}
+
+impl Default for CursorConfig {
+    fn default() -> Self {
+        Self {
+            render_mode: CursorRenderMode::default(),
+            blink: true,
+            shape: CursorShape::default(),
+            blink_interval_ms: 500,
+        }
+    }
+}
+
+impl CursorConfig {
+    /// 소프트웨어 커서를 사용해야 하는지 확인
+    pub fn use_software_cursor(&self) -> bool {
+        match self.render_mode {
+            CursorRenderMode::Hardware => false,
+            CursorRenderMode::Software => true,
+            CursorRenderMode::Auto => hardware_cursor_unreliable(),
+        }
+    }
+}
+
+/// on/off 형태의 설정값 파싱 (1/0, true/false, on/off, yes/no)
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" | "yes" => Some(true),
+        "0" | "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// TOML 문자열 배열 (배열이 아니거나 문자열이 아닌 항목이 있으면 None)
+fn string_array(value: &toml::Value) -> Option<Vec<String>> {
+    let toml::Value::Array(items) = value else {
+        return None;
+    };
+    items.iter().map(|item| item.as_str().map(str::to_string)).collect()
+}
+
+/// 호스트 터미널의 커서 위치 지정을 신뢰할 수 없는 환경인지 감지
+fn hardware_cursor_unreliable() -> bool {
+    let term = std::env::var("TERM").unwrap_or_default();
+    term.is_empty() || term == "dumb" || std::env::var_os("CI").is_some()
+}
+
+/// 새 출력이 도착했을 때 선택 영역 해제 정책
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClearSelectionOnOutput {
+    /// 해제하지 않음
+    Never,
+    /// 기본 화면에서 스크롤하지 않은(맨 아래) 상태일 때만 해제
+    #[default]
+    PrimaryScreenBottom,
+    /// 항상 해제
+    Always,
+}
+
+impl ClearSelectionOnOutput {
+    /// 문자열에서 정책 파싱 (never / bottom / always)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "never" => Some(Self::Never),
+            "bottom" => Some(Self::PrimaryScreenBottom),
+            "always" => Some(Self::Always),
+            _ => None,
+        }
+    }
+
+    /// 현재 화면 상태에서 선택 영역을 해제해야 하는지 확인
+    pub fn should_clear(&self, alternate_screen: bool, scrolled_back: bool) -> bool {
+        match self {
+            ClearSelectionOnOutput::Never => false,
+            ClearSelectionOnOutput::PrimaryScreenBottom => !alternate_screen && !scrolled_back,
+            ClearSelectionOnOutput::Always => true,
+        }
+    }
+}
+
+/// 여러 줄을 복사할 때 사용할 줄 바꿈 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n` (Windows 앱에 붙여넣기용)
+    Crlf,
+    /// 현재 플랫폼 기본값 (Windows는 CRLF, 그 외는 LF)
+    Native,
+}
+
+impl LineEnding {
+    /// 문자열에서 줄 바꿈 방식 파싱 (lf / crlf / native)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "lf" => Some(Self::Lf),
+            "crlf" => Some(Self::Crlf),
+            "native" => Some(Self::Native),
+            _ => None,
+        }
+    }
+
+    /// 줄 사이에 넣을 구분자
+    pub fn separator(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Native if cfg!(windows) => "\r\n",
+            LineEnding::Native => "\n",
+        }
+    }
+}
+
+/// 선택 영역이 있을 때 마우스 휠 스크롤 정책
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WheelScrollSelection {
+    /// 선택 영역을 버퍼 좌표에 고정하여 유지 (화면과 함께 스크롤)
+    #[default]
+    Anchor,
+    /// 선택 영역 해제
+    Clear,
+}
+
+impl WheelScrollSelection {
+    /// 문자열에서 정책 파싱 (anchor / clear)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "anchor" => Some(Self::Anchor),
+            "clear" => Some(Self::Clear),
+            _ => None,
+        }
+    }
+}
+
+/// 붙여넣은 텍스트 끝의 줄 바꿈 처리
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PasteNewline {
+    /// 클립보드 내용 그대로 (끝에 줄 바꿈이 있으면 그대로 전송)
+    #[default]
+    Preserve,
+    /// 붙여넣은 뒤 Enter를 보내 명령 실행 (이미 줄 바꿈으로 끝나면 한 번만)
+    Append,
+    /// 끝의 줄 바꿈을 제거하여 실행하지 않고 붙여넣기만 함
+    Strip,
+}
+
+impl PasteNewline {
+    /// 문자열에서 파싱 (preserve / append / strip)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "preserve" => Some(Self::Preserve),
+            "append" => Some(Self::Append),
+            "strip" => Some(Self::Strip),
+            _ => None,
+        }
+    }
+
+    /// 붙여넣을 텍스트와 붙여넣은 뒤 Enter를 보낼지 여부
+    /// Enter는 bracketed paste 표시 밖에서 보내야 셸이 명령을 실행하므로 텍스트와 분리
+    pub fn apply(self, text: &str) -> (&str, bool) {
+        let trimmed = text.trim_end_matches(['\r', '\n']);
+        match self {
+            Self::Preserve => (text, false),
+            Self::Append => (trimmed, true),
+            Self::Strip => (trimmed, false),
+        }
+    }
+}
+
+/// 창 제목 설정
+#[derive(Debug, Clone)]
+pub struct TitleConfig {
+    /// 프로그램이 제목을 설정하지 않았거나 빈 제목/초기화를 보냈을 때의 제목
+    pub fallback: String,
+    /// 프로그램이 OSC 0/2로 보낸 제목을 무시하고 창 제목과 탭 이름을 고정
+    pub lock: bool,
+}
+
+impl Default for TitleConfig {
+    fn default() -> Self {
+        Self {
+            fallback: crate::title::DEFAULT_TITLE.to_string(),
+            lock: false,
+        }
+    }
+}
+
+/// 너비를 제한했을 때 남는 영역 안에서 터미널 영역의 위치
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentAlign {
+    #[default]
+    Left,
+    Center,
+}
+
+impl ContentAlign {
+    /// 문자열에서 파싱 (left / center)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "left" => Some(Self::Left),
+            "center" => Some(Self::Center),
+            _ => None,
+        }
+    }
+}
+
+/// 터미널 영역 너비 설정
+#[derive(Debug, Clone, Default)]
+pub struct WidthConfig {
+    /// PTY에 알리는 최대 열 수 (0이면 제한 없음, 남는 호스트 너비는 여백)
+    pub max_columns: usize,
+    /// 너비를 제한했을 때 터미널 영역 위치
+    pub align: ContentAlign,
+}
+
+impl WidthConfig {
+    /// 사용 가능한 영역을 최대 열 수에 맞춰 줄임
+    /// `chrome`은 열 외에 영역이 차지하는 칸 수 (테두리, 스크롤바)
+    pub fn cap(&self, area: Rect, chrome: u16) -> Rect {
+        let max_width = u16::try_from(self.max_columns).unwrap_or(u16::MAX).saturating_add(chrome);
+        if self.max_columns == 0 || area.width <= max_width {
+            return area;
+        }
+        let x = match self.align {
+            ContentAlign::Left => area.x,
+            ContentAlign::Center => area.x + (area.width - max_width) / 2,
+        };
+        Rect { x, width: max_width, ..area }
+    }
+}
+
+/// 세션(탭) 설정
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+    /// 마지막 세션을 닫을 때(앱 종료) 한 번 더 눌러야 닫히게 함
+    pub confirm_close_last: bool,
+    /// 셸이 종료되었을 때 pane 처리
+    pub on_exit: ExitBehavior,
+    /// 시작할 때 `--restore` 없이도 마지막으로 저장된 탭/pane 복원
+    pub restore: bool,
+    /// 종료할 때 pane마다 저장하는 스크롤백 줄 수 (0이면 저장하지 않음)
+    pub restore_scrollback_lines: usize,
+    /// 셸을 백그라운드 세션 데몬에서 실행 (분리했다가 `--attach`로 다시 붙을 수 있음)
+    pub daemon: bool,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            confirm_close_last: true,
+            on_exit: ExitBehavior::default(),
+            restore: false,
+            restore_scrollback_lines: 1000,
+            daemon: false,
+        }
+    }
+}
+
+/// 셸 종료 시 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExitBehavior {
+    /// 항상 pane 닫기
+    Close,
+    /// 종료 코드가 0이면 닫고, 실패하면 종료 코드를 표시하며 열어 둠
+    #[default]
+    CloseOnSuccess,
+    /// 종료 코드를 표시하며 열어 두고 키를 누르면 닫기
+    Hold,
+    /// 같은 셸을 같은 디렉토리에서 다시 실행
+    Respawn,
+}
+
+impl ExitBehavior {
+    /// 문자열에서 파싱 (close / close-on-success / hold / respawn)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "close" => Some(Self::Close),
+            "close-on-success" => Some(Self::CloseOnSuccess),
+            "hold" => Some(Self::Hold),
+            "respawn" => Some(Self::Respawn),
+            _ => None,
+        }
+    }
+
+    /// 종료 코드에 따라 실제로 할 동작 (`CloseOnSuccess`는 `Close` 또는 `Hold`)
+    /// 종료 코드를 모르면(시그널로 종료 등) 실패로 봄
+    pub fn for_code(self, code: Option<i32>) -> Self {
+        match self {
+            Self::CloseOnSuccess if code == Some(0) => Self::Close,
+            Self::CloseOnSuccess => Self::Hold,
+            other => other,
+        }
+    }
+}
+
+/// 새 세션에서 실행할 셸 설정
+#[derive(Debug, Clone, Default)]
+pub struct ShellConfig {
+    /// 셸 프로그램 (None이면 시스템 기본 셸)
+    pub program: Option<String>,
+    /// 셸 인자
+    pub args: Vec<String>,
+    /// 첫 세션의 작업 디렉토리 (None이면 앱을 실행한 디렉토리)
+    pub working_directory: Option<PathBuf>,
+}
+
+/// PTY 출력 기록(typescript) 설정
+#[derive(Debug, Clone)]
+pub struct OutputLogConfig {
+    /// 새 세션마다 시작할 때부터 기록 (꺼져 있어도 단축키로 세션별로 켤 수 있음)
+    pub enabled: bool,
+    /// 기록 파일을 만들 디렉토리 (None이면 데이터 디렉토리의 `rust-terminal/logs`)
+    pub directory: Option<PathBuf>,
+    /// 파일 하나가 이 크기(MB)를 넘으면 새 파일로 교체 (0이면 교체하지 않음)
+    pub rotate_mb: u64,
+}
+
+impl Default for OutputLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+            rotate_mb: 10,
+        }
+    }
+}
+
+impl OutputLogConfig {
+    /// 기록 파일 디렉토리 (설정이 없으면 기본 위치, 기본 위치도 알 수 없으면 None)
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.clone()
+            .or_else(|| dirs::data_dir().map(|dir| dir.join("rust-terminal").join("logs")))
+    }
+
+    /// 파일 하나의 최대 바이트 수 (0이면 교체하지 않음)
+    pub fn max_bytes(&self) -> u64 {
+        self.rotate_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+/// asciicast 녹화 설정
+#[derive(Debug, Clone, Default)]
+pub struct RecordingConfig {
+    /// 녹화 파일(`.cast`)을 만들 디렉토리 (None이면 데이터 디렉토리의 `rust-terminal/recordings`)
+    pub directory: Option<PathBuf>,
+}
+
+impl RecordingConfig {
+    /// 녹화 파일 디렉토리 (설정이 없으면 기본 위치, 기본 위치도 알 수 없으면 None)
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.clone()
+            .or_else(|| dirs::data_dir().map(|dir| dir.join("rust-terminal").join("recordings")))
+    }
+}
+
+/// 스크롤백 내보내기 설정
+#[derive(Debug, Clone, Default)]
+pub struct ExportConfig {
+    /// 내보낸 파일을 만들 디렉토리 (None이면 데이터 디렉토리의 `rust-terminal/exports`)
+    pub directory: Option<PathBuf>,
+}
+
+impl ExportConfig {
+    /// 내보내기 디렉토리 (설정이 없으면 기본 위치, 기본 위치도 알 수 없으면 None)
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.clone()
+            .or_else(|| dirs::data_dir().map(|dir| dir.join("rust-terminal").join("exports")))
+    }
+}
+
+impl ShellConfig {
+    /// 터미널 생성에 사용할 셸
+    pub fn shell(&self) -> Shell {
+        match &self.program {
+            None => Shell::System,
+            Some(program) if self.args.is_empty() => Shell::Program(program.clone()),
+            Some(program) => Shell::WithArguments { program: program.clone(), args: self.args.clone() },
+        }
+    }
+}
+
+/// `~`로 시작하는 경로를 홈 디렉토리 기준으로 펼침
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// 키보드 입력 설정
+#[derive(Debug, Clone, Default)]
+pub struct KeyboardConfig {
+    /// 프로그램이 kitty 키보드 프로토콜(`CSI > 플래그 u`)을 요청하면 그 방식으로 키 보고
+    pub kitty: bool,
+}
+
+/// 화면 그리기 설정
+#[derive(Debug, Clone)]
+pub struct RenderConfig {
+    /// 출력이 쏟아질 때 초당 최대 다시 그리기 횟수 (0이면 제한 없음, 키/마우스 입력에 대한 다시 그리기는 제한하지 않음)
+    pub max_fps: u32,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self { max_fps: 60 }
+    }
+}
+
+impl RenderConfig {
+    /// 출력으로 인한 다시 그리기 사이 최소 간격 (제한이 없으면 None)
+    pub fn frame_interval(&self) -> Option<std::time::Duration> {
+        (self.max_fps > 0).then(|| std::time::Duration::from_secs(1) / self.max_fps)
+    }
+}
+
+/// 백그라운드 탭 활동/침묵 알림 설정
+#[derive(Debug, Clone)]
+pub struct ActivityConfig {
+    /// 데스크톱 알림을 보낼 변화 (탭 표시는 항상)
+    pub notify: ActivityNotify,
+    /// 이만큼 출력이 없으면 조용하다고 봄 (초)
+    pub silence_seconds: u64,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        Self { notify: ActivityNotify::default(), silence_seconds: 10 }
+    }
+}
+
+impl ActivityConfig {
+    pub fn silence(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.silence_seconds)
+    }
+}
+
+/// 힌트 모드 설정
+#[derive(Debug, Clone)]
+pub struct HintConfig {
+    /// 라벨에 쓸 글자 (앞 글자부터 사용)
+    pub alphabet: String,
+}
+
+impl Default for HintConfig {
+    fn default() -> Self {
+        Self { alphabet: hints::DEFAULT_ALPHABET.to_string() }
+    }
+}
+
+/// 붙여넣기 설정
+#[derive(Debug, Clone, Default)]
+pub struct PasteConfig {
+    /// `paste` 동작과 가운데 버튼 붙여넣기의 끝 줄 바꿈 처리
+    pub trailing_newline: PasteNewline,
+}
+
+/// 텍스트 선택 관련 설정
+#[derive(Debug, Clone)]
+pub struct SelectionConfig {
+    /// PTY 출력으로 내용이 바뀌면 (드래그 중이 아닌) 선택 영역 해제.
+    /// 선택된 셀의 내용이 바뀌어 잘못된 텍스트를 복사하는 것을 방지
+    pub clear_on_output: ClearSelectionOnOutput,
+    /// 여러 줄 선택을 복사할 때의 줄 바꿈 방식
+    pub copy_line_ending: LineEnding,
+    /// 선택 영역이 있을 때 마우스 휠 스크롤 정책
+    pub on_wheel_scroll: WheelScrollSelection,
+    /// 마우스로 선택을 마치면 바로 복사 (끄면 복사 단축키로만 복사)
+    pub copy_on_select: bool,
+    /// 선택하자마자 복사할 때 쓰는 클립보드 (가운데 버튼 붙여넣기도 같은 클립보드에서 읽음)
+    pub clipboard: ClipboardTarget,
+    /// 복사할 때 줄마다 끝의 공백 제거
+    pub trim_trailing_whitespace: bool,
+    /// 복사 단축키로 복사하는 형식 (HTML은 일반 텍스트와 함께, 선택하자마자 복사는 항상 일반 텍스트)
+    pub copy_format: ExportFormat,
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        Self {
+            clear_on_output: ClearSelectionOnOutput::default(),
+            copy_line_ending: LineEnding::default(),
+            on_wheel_scroll: WheelScrollSelection::default(),
+            copy_on_select: true,
+            clipboard: ClipboardTarget::default(),
+            trim_trailing_whitespace: false,
+            copy_format: ExportFormat::Text,
+        }
+    }
+}
+
+/// 선택 영역을 복사할 클립보드
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClipboardTarget {
+    /// 시스템 클립보드 (Ctrl+C/Ctrl+V로 쓰는 클립보드)
+    #[default]
+    Clipboard,
+    /// 선택 클립보드 (X11/Wayland PRIMARY, 다른 플랫폼에서는 시스템 클립보드)
+    Primary,
+    /// 둘 다
+    Both,
+}
+
+impl ClipboardTarget {
+    /// 문자열에서 파싱 (clipboard / primary / both)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "clipboard" => Some(Self::Clipboard),
+            "primary" => Some(Self::Primary),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+}
+
+/// 스크롤백 관련 설정
+#[derive(Debug, Clone)]
+pub struct ScrollbackConfig {
+    /// 화면 위로 넘어간 출력을 보관하는 최대 줄 수 (0이면 스크롤백 없음)
+    pub history_lines: usize,
+    /// 스크롤백 지우기(Ctrl+Shift+K) 후 되돌리기(Ctrl+Shift+Z)가 가능한 시간 (초)
+    pub clear_undo_timeout_secs: u64,
+    /// 되돌리기용으로 저장하는 최대 줄 수 (메모리 사용량 제한, 0이면 저장하지 않음)
+    pub clear_undo_max_lines: usize,
+    /// 마지막 줄 아래로 더 스크롤할 수 있는 빈 줄 수 (0이면 마지막 줄에서 멈춤)
+    pub scroll_past_end_lines: usize,
+    /// 대체 화면 전환 직후 출력이 잠잠해질 때까지 다시 그리기를 미룸 (전환 중 깜빡임 방지)
+    pub alt_screen_settle: bool,
+    /// 화면 위/아래에 더 볼 내용이 있을 때 가장자리 표시
+    pub edge_indicators: bool,
+    /// 가장자리 표시(테두리의 ▲/▼) 스타일
+    pub edge_indicator_style: Style,
+    /// 마우스 휠 한 칸에 스크롤하는 줄 수 (1 이상)
+    pub scroll_lines: usize,
+    /// 휠 스크롤을 여러 프레임에 나눠 감속하며 적용 (연달아 굴리면 관성으로 빨라짐)
+    pub smooth: bool,
+}
+
+impl Default for ScrollbackConfig {
+    fn default() -> Self {
+        Self {
+            history_lines: 10_000,
+            clear_undo_timeout_secs: 5,
+            clear_undo_max_lines: 10_000,
+            scroll_past_end_lines: 0,
+            alt_screen_settle: true,
+            edge_indicators: true,
+            edge_indicator_style: Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            scroll_lines: 3,
+            smooth: false,
+        }
+    }
+}
+
+/// 검색 하이라이트 설정
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// 일치 항목 스타일
+    pub match_style: Style,
+    /// 현재 초점이 맞춰진 일치 항목 스타일 (다른 일치 항목과 구분되어야 함)
+    pub current_match_style: Style,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            match_style: Style::default().fg(Color::Black).bg(Color::Yellow),
+            current_match_style: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+/// 색상 설정
+#[derive(Debug, Clone)]
+pub struct ColorsConfig {
+    /// 터미널 영역 기본 배경색 (빈 줄과 짧은 줄의 오른쪽 빈 칸 포함, None이면 테마 배경색)
+    pub background: Option<Color>,
+    /// 프로그램이 색을 지정하지 않은 글자의 전경색 (None이면 테마 글자색)
+    pub foreground: Option<Color>,
+    /// 셸에 `COLORTERM`으로 알리는 색상 지원 (None이면 설정하지 않음)
+    pub colorterm: Option<String>,
+    /// 사용할 테마 이름 (내장 테마 또는 사용자 테마)
+    pub theme_name: String,
+    /// config.toml의 `[themes.이름]`으로 정의한 테마
+    pub user_themes: Vec<Theme>,
+    /// 적용된 테마 (설정을 모두 읽은 뒤 `theme_name`으로 결정)
+    pub theme: Theme,
+}
+
+impl Default for ColorsConfig {
+    fn default() -> Self {
+        Self {
+            background: None,
+            foreground: None,
+            colorterm: Some("truecolor".to_string()),
+            theme_name: "default".to_string(),
+            user_themes: Vec::new(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl ColorsConfig {
+    /// 터미널 영역 배경색 (직접 지정한 색이 테마보다 우선)
+    pub fn background(&self) -> Color {
+        self.background.unwrap_or(self.theme.background)
+    }
+
+    /// 기본 글자색 (직접 지정한 색이 테마보다 우선)
+    pub fn foreground(&self) -> Color {
+        self.foreground.unwrap_or(self.theme.foreground)
+    }
+
+    /// 고를 수 있는 테마 이름 (내장 테마 다음에 사용자 테마, 중복 없음)
+    pub fn theme_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = crate::theme::BUILTIN_THEMES.iter().map(|name| name.to_string()).collect();
+        for theme in &self.user_themes {
+            if !names.contains(&theme.name) {
+                names.push(theme.name.clone());
+            }
+        }
+        names
+    }
+
+    /// 실행 중에 테마 바꾸기 (알 수 없는 이름이면 지금 테마 유지)
+    pub fn set_theme(&mut self, name: &str) -> Result<()> {
+        let previous = std::mem::replace(&mut self.theme_name, name.to_string());
+        self.resolve_theme().inspect_err(|_| self.theme_name = previous)
+    }
+
+    /// 이름으로 테마 찾기 (같은 이름이면 사용자 테마가 내장 테마보다 우선)
+    pub fn find_theme(&self, name: &str) -> Option<Theme> {
+        self.user_themes.iter()
+            .find(|theme| theme.name == name)
+            .cloned()
+            .or_else(|| Theme::builtin(name))
+    }
+
+    /// `theme_name`에 해당하는 테마 적용
+    fn resolve_theme(&mut self) -> Result<()> {
+        self.theme = self.find_theme(&self.theme_name)
+            .ok_or_else(|| anyhow!("알 수 없는 테마: {} (내장 테마: {})",
+                self.theme_name, crate::theme::BUILTIN_THEMES.join(", ")))?;
+        Ok(())
+    }
+}
+
+/// 시작 배너 설정 (예: "PRODUCTION" 같은 세션 표시)
+/// 터미널 영역 위에 스크롤되지 않는 한 줄로 표시
+#[derive(Debug, Clone)]
+pub struct BannerConfig {
+    /// 배너 문구 (None이면 배너 줄을 만들지 않음)
+    pub text: Option<String>,
+    /// 배너 스타일
+    pub style: Style,
+}
+
+impl Default for BannerConfig {
+    fn default() -> Self {
+        Self {
+            text: None,
+            style: Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+impl BannerConfig {
+    /// 배너 문구 설정 (제어 문자 제거, 빈 문구면 배너 끄기)
+    pub fn set_text(&mut self, text: &str) {
+        let text = crate::title::sanitize_title(text);
+        self.text = (!text.is_empty()).then_some(text);
+    }
+
+    /// 배너가 차지하는 줄 수
+    pub fn height(&self) -> u16 {
+        u16::from(self.text.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_selection_on_output_policy() {
+        let policy = ClearSelectionOnOutput::default();
+        assert!(policy.should_clear(false, false));
+        assert!(!policy.should_clear(true, false));
+        assert!(!policy.should_clear(false, true));
+
+        assert!(ClearSelectionOnOutput::Always.should_clear(true, true));
+        assert!(!ClearSelectionOnOutput::Never.should_clear(false, false));
+
+        assert_eq!(ClearSelectionOnOutput::parse(" Always "), Some(ClearSelectionOnOutput::Always));
+        assert_eq!(ClearSelectionOnOutput::parse("bottom"), Some(ClearSelectionOnOutput::PrimaryScreenBottom));
+        assert_eq!(ClearSelectionOnOutput::parse("sometimes"), None);
+    }
+
+    #[test]
+    fn test_parse_flag() {
+        assert_eq!(parse_flag("OFF"), Some(false));
+        assert_eq!(parse_flag(" 1 "), Some(true));
+        assert_eq!(parse_flag("maybe"), None);
+    }
+
+    #[test]
+    fn test_banner_text() {
+        let mut banner = BannerConfig::default();
+        assert_eq!(banner.height(), 0);
+
+        banner.set_text(" PRODUCTION\x1b[31m ");
+        assert_eq!(banner.text.as_deref(), Some("PRODUCTION[31m"));
+        assert_eq!(banner.height(), 1);
+
+        // 빈 문구는 배너를 끔
+        banner.set_text("  ");
+        assert_eq!(banner.height(), 0);
+    }
+
+    #[test]
+    fn test_line_ending_parse() {
+        assert_eq!(LineEnding::parse("CRLF"), Some(LineEnding::Crlf));
+        assert_eq!(LineEnding::parse(" native "), Some(LineEnding::Native));
+        assert_eq!(LineEnding::parse("cr"), None);
+    }
+
+    #[test]
+    fn test_config_file_and_env_override() {
+        let file = "# 테마\nRUST_TERMINAL_BACKGROUND = #282c34\n\nRUST_TERMINAL_SCROLL_PAST_END=2\n";
+        let config = Config::from_sources(Some(file), |_| None).unwrap();
+        assert_eq!(config.colors.background(), Color::Rgb(0x28, 0x2c, 0x34));
+        assert_eq!(config.scrollback.scroll_past_end_lines, 2);
+        assert_eq!(config.scrollback.history_lines, 10_000);
+
+        // 환경 변수가 파일보다 우선, 잘못된 환경 변수 값은 무시
+        let env = |key: &str| match key {
+            "RUST_TERMINAL_BACKGROUND" => Some("blue".to_string()),
+            "RUST_TERMINAL_SCROLL_PAST_END" => Some("many".to_string()),
+            "RUST_TERMINAL_SCROLLBACK_LINES" => Some("500".to_string()),
+            _ => None,
+        };
+        let config = Config::from_sources(Some(file), env).unwrap();
+        assert_eq!(config.colors.background(), Color::Blue);
+        assert_eq!(config.scrollback.scroll_past_end_lines, 2);
+        assert_eq!(config.scrollback.history_lines, 500);
+
+        // 셸 환경 변수 정책 (ENV_SET은 여러 줄)
+        let file = "RUST_TERMINAL_ENV_INHERIT=none\nRUST_TERMINAL_ENV_REMOVE=A, B\nRUST_TERMINAL_ENV_SET=X=1=2\nRUST_TERMINAL_ENV_SET=Y=\n";
+        let config = Config::from_sources(Some(file), |_| None).unwrap();
+        assert_eq!(config.env.inherit, Some(Vec::new()));
+        assert_eq!(config.env.remove, vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(config.env.set, vec![("X".to_string(), "1=2".to_string()), ("Y".to_string(), String::new())]);
+        assert!(Config::from_sources(Some("RUST_TERMINAL_ENV_SET=novalue"), |_| None).is_err());
+
+        // 시리얼 장치를 지정하면 첫 세션은 장치에 연결
+        let config = Config::from_sources(Some("RUST_TERMINAL_SERIAL_DEVICE=/dev/ttyS0\nRUST_TERMINAL_SERIAL_STOP_BITS=2"), |_| None).unwrap();
+        assert!(matches!(config.startup_shell(), Shell::Serial(settings) if settings.stop_bits == 2 && settings.baud == 115_200));
+        assert!(Config::from_sources(Some("RUST_TERMINAL_SERIAL_DATA_BITS=9"), |_| None).is_err());
+    }
+
+    #[test]
+    fn test_toml_config_file() {
+        let file = r##"
+[shell]
+program = "/bin/zsh"
+args = ["-l"]
+working_directory = "/tmp"
+
+[cursor]
+shape = "beam"
+blink_interval_ms = 300
+
+[colors]
+foreground = "#abb2bf"
+
+[scrollback]
+lines = 2_000
+scroll_lines = 5
+smooth = true
+
+[selection]
+copy_on_select = false
+clipboard = "primary"
+copy_format = "html"
+clear_on_output = "never"
+
+[session]
+restore = true
+restore_scrollback = 0
+daemon = true
+
+[output_log]
+enabled = true
+directory = "/tmp/logs"
+rotate_mb = 1
+
+[recording]
+directory = "~/casts"
+
+[export]
+directory = "~/exports"
+
+[keyboard]
+kitty = true
+
+[render]
+max_fps = 30
+
+[window]
+status_bar = "mode git | scroll"
+lock_title = true
+
+[bell]
+response = "visual notify"
+
+[notification]
+method = "command"
+
+[editor]
+command = "code --goto {file}:{line}:{column}"
+
+[hints]
+alphabet = "jkl"
+
+[activity]
+notify = "silence"
+silence_seconds = 30
+
+[profiles.work]
+program = "/bin/bash"
+theme = "dracula"
+title = "work"
+
+[profiles.work.env]
+AWS_PROFILE = "work"
+
+[ssh.prod]
+host = "prod.example.com"
+port = 2222
+
+[smart.sha]
+pattern = '\b[0-9a-f]{7,40}\b'
+actions = ["copy", "run git show {0}"]
+
+[serial]
+baud = 9600
+parity = "even"
+flow_control = "hardware"
+
+[env]
+inherit = ["PATH", "HOME"]
+remove = ["SSH_*"]
+
+[env.set]
+EDITOR = "vim"
+
+[keybindings]
+"ctrl+shift+v" = "paste"
+"##;
+        let config = Config::from_toml_sources(file, |_| None).unwrap();
+        assert_eq!(config.shell.shell(), Shell::WithArguments { program: "/bin/zsh".into(), args: vec!["-l".into()] });
+        assert_eq!(config.shell.working_directory, Some(PathBuf::from("/tmp")));
+        assert_eq!(config.cursor.shape, CursorShape::Beam);
+        assert_eq!(config.cursor.blink_interval_ms, 300);
+        assert_eq!(config.colors.foreground(), Color::Rgb(0xab, 0xb2, 0xbf));
+        assert_eq!(config.scrollback.history_lines, 2_000);
+        assert_eq!(config.scrollback.scroll_lines, 5);
+        assert!(config.scrollback.smooth);
+        assert_eq!(config.bindings.user()[0].action, crate::bindings::Action::Paste);
+        assert!(config.session.restore);
+        assert!(!config.selection.copy_on_select);
+        assert_eq!(config.selection.copy_format, ExportFormat::Html);
+        assert_eq!(config.selection.clipboard, ClipboardTarget::Primary);
+        assert_eq!(config.selection.clear_on_output, ClearSelectionOnOutput::Never);
+        assert_eq!(config.session.restore_scrollback_lines, 0);
+        assert!(config.session.daemon);
+        assert!(config.output_log.enabled);
+        assert_eq!(config.output_log.directory(), Some(PathBuf::from("/tmp/logs")));
+        assert_eq!(config.output_log.max_bytes(), 1024 * 1024);
+        assert_eq!(config.recording.directory(), Some(expand_home("~/casts")));
+        assert_eq!(config.export.directory(), Some(expand_home("~/exports")));
+        assert!(config.keyboard.kitty);
+        assert_eq!(config.render.frame_interval(), Some(std::time::Duration::from_secs(1) / 30));
+        assert_eq!(RenderConfig { max_fps: 0 }.frame_interval(), None);
+        assert_eq!(config.status_bar, StatusLayout::parse("mode git | scroll").unwrap());
+        assert!(config.title.lock);
+        assert_eq!(config.bell, BellResponse { sound: false, visual: true, notify: true });
+        assert_eq!(config.notification, NotifyMethod::Command);
+        assert_eq!(config.activity.notify, ActivityNotify { activity: false, silence: true });
+        assert_eq!(config.activity.silence(), std::time::Duration::from_secs(30));
+        assert_eq!(config.editor, "code --goto {file}:{line}:{column}");
+        assert_eq!(config.hints.alphabet, "jkl");
+        assert!(Config::from_toml_sources("[hints]\nalphabet = \"aa\"", |_| None).is_err());
+        assert_eq!(config.env.inherit, Some(vec!["PATH".to_string(), "HOME".to_string()]));
+        assert_eq!(config.env.remove, vec!["SSH_*".to_string()]);
+        assert_eq!(config.ssh_host("prod").unwrap().label(), "prod.example.com:2222");
+        assert_eq!(config.ssh_host("me@other").unwrap().destination(), "me@other");
+        assert_eq!(config.ssh_host_names(), vec!["prod".to_string()]);
+        assert_eq!(config.smart[0].actions().len(), 2);
+        assert_eq!(smart::matches(&config.smart, "HEAD is now at 1a2b3c4d fix")[0].captures[0], "1a2b3c4d");
+        assert!(Config::from_toml_sources("[smart.x]\nactions = \"copy\"", |_| None).is_err());
+        assert_eq!((config.serial.baud, config.serial.parity, config.serial.flow_control), (9600, serial::Parity::Even, serial::FlowControl::Hardware));
+        assert_eq!(config.startup_shell(), config.shell.shell()); // 장치를 지정하지 않으면 셸
+
+        // 프로필을 고르면 셸, 환경 변수, 테마, 제목을 덮어씀 (인자를 지정하지 않았으면 인자 없이)
+        let mut profiled = config.clone();
+        profiled.apply_profile("work").unwrap();
+        assert_eq!(profiled.shell.shell(), Shell::Program("/bin/bash".into()));
+        assert_eq!(profiled.env.set, vec![("EDITOR".to_string(), "vim".to_string()), ("AWS_PROFILE".to_string(), "work".to_string())]);
+        assert_eq!(profiled.shell.working_directory, Some(PathBuf::from("/tmp")));
+        assert_eq!((profiled.colors.theme_name.as_str(), profiled.title.fallback.as_str()), ("dracula", "work"));
+        assert!(profiled.apply_profile("home").is_err());
+        assert!(Config::from_toml_sources("[profiles.x]\ntheme = \"nope\"", |_| None).is_err());
+
+        // 환경 변수가 파일보다 우선
+        let env = |key: &str| (key == "RUST_TERMINAL_SHELL").then(|| "fish".to_string());
+        let config = Config::from_toml_sources(file, env).unwrap();
+        assert_eq!(config.shell.program.as_deref(), Some("fish"));
+
+        // 알 수 없는 키와 잘못된 값은 줄 번호와 함께 오류
+        let error = |text: &str| format!("{:#}", Config::from_toml_sources(text, |_| None).unwrap_err());
+        assert!(error("[cursor]\nsize = 3").starts_with("2번째 줄: 알 수 없는 설정: cursor.size"));
+        assert!(error("[cursor]\n\nshape = \"star\"").starts_with("3번째 줄"));
+        assert!(error("[shell]\nargs = \"-l\"").contains("문자열 배열"));
+        assert!(error("[keybindings]\n\"ctrl+x\" = 1").contains("문자열"));
+    }
+
+    #[test]
+    fn test_themes() {
+        // 내장 테마의 글자/배경색, 직접 지정한 색이 테마보다 우선
+        let config = Config::from_toml_sources("[colors]\ntheme = \"gruvbox\"\nbackground = \"black\"", |_| None).unwrap();
+        assert_eq!(config.colors.theme.name, "gruvbox");
+        assert_eq!(config.colors.foreground(), Color::Rgb(0xeb, 0xdb, 0xb2));
+        assert_eq!(config.colors.background(), Color::Black);
+
+        // 사용자 테마는 사용하는 줄보다 뒤에 정의해도 됨
+        let file = "[colors]\ntheme = \"mine\"\n\n[themes.mine]\nbackground = \"#101010\"\ncursor = \"red\"\n";
+        let config = Config::from_toml_sources(file, |_| None).unwrap();
+        assert_eq!(config.colors.background(), Color::Rgb(0x10, 0x10, 0x10));
+        assert_eq!(config.colors.theme.cursor, Color::Red);
+
+        // 실행 중 테마 변경 (팔레트의 set-theme), 없는 테마면 그대로
+        let mut colors = config.colors.clone();
+        assert_eq!(colors.theme_names(), vec!["default", "solarized", "dracula", "gruvbox", "mine"]);
+        colors.set_theme("solarized").unwrap();
+        assert_eq!(colors.theme.name, "solarized");
+        assert!(colors.set_theme("nord").is_err());
+        assert_eq!((colors.theme_name.as_str(), colors.theme.name.as_str()), ("solarized", "solarized"));
+
+        // 환경 변수로 테마 변경, 없는 테마는 오류
+        let env = |key: &str| (key == "RUST_TERMINAL_THEME").then(|| "dracula".to_string());
+        assert_eq!(Config::from_toml_sources(file, env).unwrap().colors.theme.name, "dracula");
+        let error = Config::from_sources(Some("RUST_TERMINAL_THEME=nord"), |_| None).unwrap_err().to_string();
+        assert!(error.contains("알 수 없는 테마: nord"), "{}", error);
+        assert!(Config::from_toml_sources("[themes.x]\nsize = 1", |_| None).is_err());
+    }
+
+    #[test]
+    fn test_config_file_bindings() {
+        let file = "RUST_TERMINAL_BIND=ctrl+shift+v paste\nRUST_TERMINAL_BIND=alt+g send-string git status\\r";
+        let config = Config::from_sources(Some(file), |_| None).unwrap();
+        assert_eq!(config.bindings.user().len(), 2);
+        assert_eq!(config.bindings.user()[1].action, crate::bindings::Action::SendString(b"git status\r".to_vec()));
+
+        assert!(Config::from_sources(Some("RUST_TERMINAL_BIND=ctrl+v"), |_| None).is_err());
+    }
+
+    #[test]
+    fn test_exit_behavior() {
+        assert_eq!(ExitBehavior::parse(" Close-On-Success "), Some(ExitBehavior::CloseOnSuccess));
+        assert_eq!(ExitBehavior::parse("restart"), None);
+
+        let default = ExitBehavior::default();
+        assert_eq!(default.for_code(Some(0)), ExitBehavior::Close);
+        assert_eq!(default.for_code(Some(2)), ExitBehavior::Hold);
+        assert_eq!(default.for_code(None), ExitBehavior::Hold);
+        assert_eq!(ExitBehavior::Respawn.for_code(Some(0)), ExitBehavior::Respawn);
+        assert_eq!(ExitBehavior::Close.for_code(Some(1)), ExitBehavior::Close);
+    }
+
+    #[test]
+    fn test_paste_trailing_newline() {
+        assert_eq!(PasteNewline::Preserve.apply("ls\n"), ("ls\n", false));
+        assert_eq!(PasteNewline::Preserve.apply("ls"), ("ls", false));
+
+        assert_eq!(PasteNewline::Append.apply("ls"), ("ls", true));
+        assert_eq!(PasteNewline::Append.apply("ls\r\n"), ("ls", true));
+
+        assert_eq!(PasteNewline::Strip.apply("a\nb\n\n"), ("a\nb", false));
+        assert_eq!(PasteNewline::parse(" Append "), Some(PasteNewline::Append));
+    }
+
+    #[test]
+    fn test_width_cap() {
+        let area = Rect::new(1, 2, 200, 40);
+        let mut width = WidthConfig::default();
+        assert_eq!(width.cap(area, 3), area);
+
+        width.max_columns = 120;
+        assert_eq!(width.cap(area, 3), Rect::new(1, 2, 123, 40));
+        width.align = ContentAlign::Center;
+        assert_eq!(width.cap(area, 3), Rect::new(39, 2, 123, 40));
+
+        // 영역이 더 좁으면 그대로
+        assert_eq!(width.cap(Rect::new(0, 0, 100, 10), 3), Rect::new(0, 0, 100, 10));
+    }
+
+    #[test]
+    fn test_config_file_errors() {
+        let error = Config::from_sources(Some("RUST_TERMINAL_CURSOR=auto\nRUST_TERMINAL_BACKGROUND=nocolor"), |_| None)
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("2번째 줄"));
+
+        assert!(Config::from_sources(Some("UNKNOWN=1"), |_| None).is_err());
+        assert!(Config::from_sources(Some("RUST_TERMINAL_SCROLL_LINES=0"), |_| None).is_err());
+        assert!(Config::from_sources(Some("no separator"), |_| None).is_err());
+    }
+}
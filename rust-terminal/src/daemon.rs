@@ -0,0 +1,726 @@
+//! 세션 데몬 모듈
+//! `session.daemon`을 켜면 셸(PTY)을 백그라운드 데몬 프로세스에서 실행하고, TUI는 Unix 소켓으로 붙어서 사용
+//! 클라이언트를 분리(`Ctrl+B D`)하거나 호스트 터미널이 닫혀도 셸은 계속 실행되고, `--attach`로 다시 붙음
+//!
+//! 데몬은 첫 클라이언트가 필요할 때 `rust-terminal --daemon`으로 띄우고, 마지막 세션이 끝나면 스스로 종료
+//! 세션마다 최근 출력을 보관했다가 다시 붙을 때 보내고, 셸에 SIGWINCH를 보내 화면을 다시 그리게 함
+//!
+//! 프로토콜: `[종류 1바이트][길이 4바이트][내용]` 프레임
+//! - 클라이언트 -> 데몬: 첫 프레임으로 OPEN(새 세션)/ATTACH(다시 붙기)/KILL/LIST, 이후 INPUT/RESIZE
+//! - 데몬 -> 클라이언트: OPENED(세션 id) 또는 ERROR, 이후 OUTPUT/EXITED
+
+use crate::transport::StreamProtocol;
+use alacritty_terminal::event::WindowSize;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// 다시 붙을 때 보내려고 세션마다 보관하는 최근 출력 크기
+const HISTORY_BYTES: usize = 1 << 20;
+
+/// 데몬을 띄운 뒤 연결을 기다리는 최대 시간
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 첫 응답(OPENED/ERROR)을 기다리는 최대 시간
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 클라이언트가 출력을 받지 않을 때 기다리는 최대 시간 (넘으면 분리된 것으로 봄)
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 셸 종료와 PTY 출력을 확인하는 간격
+const POLL_INTERVAL_MS: i32 = 200;
+
+/// 프레임 내용의 최대 크기 (잘못된 상대가 큰 메모리를 잡지 못하게, INPUT/OUTPUT 포함)
+const MAX_FRAME: usize = 1 << 20;
+
+/// 한 OUTPUT 프레임에 담는 최대 크기
+const OUTPUT_CHUNK: usize = 64 * 1024;
+
+// 클라이언트 -> 데몬
+const OPEN: u8 = 1;
+const ATTACH: u8 = 2;
+const INPUT: u8 = 3;
+const RESIZE: u8 = 4;
+const KILL: u8 = 5;
+const LIST: u8 = 6;
+// 데몬 -> 클라이언트
+const OPENED: u8 = 16;
+const OUTPUT: u8 = 17;
+const EXITED: u8 = 18;
+const ERROR: u8 = 19;
+const SESSIONS: u8 = 20;
+
+/// 새 세션 요청 (셸 환경은 클라이언트가 정책을 적용해 만든 전체 환경)
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenRequest {
+    program: String,
+    args: Vec<String>,
+    working_directory: Option<PathBuf>,
+    env: HashMap<String, String>,
+    columns: u16,
+    lines: u16,
+}
+
+/// 프레임 하나 만들기
+fn encode_frame(out: &mut Vec<u8>, kind: u8, payload: &[u8]) {
+    out.push(kind);
+    out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    out.extend_from_slice(payload);
+}
+
+fn write_frame(writer: &mut impl Write, kind: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(payload.len() + 5);
+    encode_frame(&mut frame, kind, payload);
+    writer.write_all(&frame)
+}
+
+/// 프레임 하나 읽기 (딱 한 프레임만 읽으므로 뒤따르는 출력은 소켓에 남음)
+fn read_frame(reader: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut header = [0u8; 5];
+    reader.read_exact(&mut header)?;
+    let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+    if len > MAX_FRAME {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too large"));
+    }
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    Ok((header[0], payload))
+}
+
+fn session_id(payload: &[u8]) -> Option<u64> {
+    Some(u64::from_be_bytes(payload.try_into().ok()?))
+}
+
+/// 데몬 소켓 경로 (`$XDG_RUNTIME_DIR/rust-terminal/daemon.sock`, 없으면 임시 디렉토리의 사용자별 디렉토리)
+pub fn socket_path() -> PathBuf {
+    let dir = match dirs::runtime_dir() {
+        Some(dir) => dir.join("rust-terminal"),
+        // SAFETY: getuid는 항상 성공
+        None => std::env::temp_dir().join(format!("rust-terminal-{}", unsafe { libc::getuid() })),
+    };
+    dir.join("daemon.sock")
+}
+
+/// 나만 쓰는 디렉토리 준비 - 없으면 0700으로 만들고, 다른 사용자 것이거나 다른 사용자가 접근할 수 있으면 에러
+/// (다른 사용자가 미리 만들어 둔 디렉토리에 소켓을 두면 그 사용자가 소켓을 바꿔치기할 수 있음)
+pub fn private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    std::fs::DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    // 심볼릭 링크는 따라가지 않음 (링크 자체의 주인과 종류로 판단)
+    let metadata = std::fs::symlink_metadata(dir)?;
+    // SAFETY: getuid는 항상 성공
+    let uid = unsafe { libc::getuid() };
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o777 != 0o700 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("not a private directory (owner {}, mode {:o})", metadata.uid(), metadata.mode() & 0o777),
+        ));
+    }
+    Ok(())
+}
+
+/// 데몬에 연결 (`start`면 데몬이 없을 때 띄움)
+fn connect(start: bool) -> Result<UnixStream> {
+    let path = socket_path();
+    let dir = path.parent().expect("socket path has a parent");
+    // 다른 사용자가 만든 디렉토리의 소켓이면 그 사용자의 데몬일 수 있으므로 연결하지 않음
+    private_dir(dir).with_context(|| format!("데몬 디렉토리를 쓸 수 없음: {}", dir.display()))?;
+    if let Ok(stream) = UnixStream::connect(&path) {
+        return Ok(stream);
+    }
+    if !start {
+        bail!("세션 데몬이 실행 중이 아님");
+    }
+    spawn_daemon()?;
+    let deadline = std::time::Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        match UnixStream::connect(&path) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if std::time::Instant::now() >= deadline => {
+                bail!("세션 데몬에 연결할 수 없음 ({}): {}", path.display(), e)
+            }
+            Err(_) => std::thread::sleep(Duration::from_millis(20)),
+        }
+    }
+}
+
+/// 이 실행 파일을 `--daemon`으로 터미널과 분리해서 실행
+fn spawn_daemon() -> Result<()> {
+    use std::os::unix::process::CommandExt;
+
+    let exe = std::env::current_exe().context("실행 파일 경로를 알 수 없음")?;
+    let mut command = std::process::Command::new(exe);
+    command.arg("--daemon")
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    // SAFETY: fork 뒤 exec 전에 async-signal-safe인 setsid만 호출
+    unsafe {
+        command.pre_exec(|| {
+            // 호스트 터미널이 닫혀도 SIGHUP을 받지 않도록 새 세션으로
+            if libc::setsid() == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    command.spawn().context("세션 데몬 실행 실패")?;
+    log::info!("session daemon started");
+    Ok(())
+}
+
+/// 첫 응답 기다리기 - OPENED면 세션 id
+fn handshake(stream: &mut UnixStream) -> Result<u64> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let (kind, payload) = read_frame(stream).context("세션 데몬 응답 없음")?;
+    stream.set_read_timeout(None)?;
+    match kind {
+        OPENED => session_id(&payload).ok_or_else(|| anyhow!("잘못된 데몬 응답")),
+        ERROR => bail!("{}", String::from_utf8_lossy(&payload)),
+        _ => bail!("잘못된 데몬 응답: {}", kind),
+    }
+}
+
+/// IO 스레드에 넘길 non-blocking 소켓
+fn into_file(stream: UnixStream) -> Result<File> {
+    stream.set_nonblocking(true)?;
+    Ok(File::from(std::os::fd::OwnedFd::from(stream)))
+}
+
+/// 데몬에서 새 세션 실행 - 세션 id와 연결된 소켓 반환
+pub fn open(
+    program: &str,
+    args: &[String],
+    working_directory: Option<&Path>,
+    env: HashMap<String, String>,
+    window_size: WindowSize,
+) -> Result<(u64, File)> {
+    let request = OpenRequest {
+        program: program.to_string(),
+        args: args.to_vec(),
+        working_directory: working_directory.map(Path::to_path_buf),
+        env,
+        columns: window_size.num_cols,
+        lines: window_size.num_lines,
+    };
+    let mut stream = connect(true)?;
+    write_frame(&mut stream, OPEN, &serde_json::to_vec(&request)?)?;
+    let session = handshake(&mut stream)?;
+    log::info!("daemon session {} opened: {}", session, program);
+    Ok((session, into_file(stream)?))
+}
+
+/// 데몬의 세션에 다시 붙기 - 보관한 최근 출력이 먼저 옴
+pub fn attach(session: u64) -> Result<File> {
+    let mut stream = connect(false)?;
+    write_frame(&mut stream, ATTACH, &session.to_be_bytes())?;
+    handshake(&mut stream)?;
+    log::info!("daemon session {} attached", session);
+    into_file(stream)
+}
+
+/// 세션 끝내기 (셸에 SIGHUP)
+pub fn kill(session: u64) -> Result<()> {
+    let mut stream = connect(false)?;
+    write_frame(&mut stream, KILL, &session.to_be_bytes())?;
+    Ok(())
+}
+
+/// 붙어 있는 클라이언트가 없는 세션 id (데몬이 없으면 빈 목록)
+pub fn detached_sessions() -> Vec<u64> {
+    let list = || -> Result<Vec<u64>> {
+        let mut stream = connect(false)?;
+        write_frame(&mut stream, LIST, &[])?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        match read_frame(&mut stream)? {
+            (SESSIONS, payload) => Ok(serde_json::from_slice(&payload)?),
+            (kind, _) => bail!("잘못된 데몬 응답: {}", kind),
+        }
+    };
+    list().unwrap_or_default()
+}
+
+/// 클라이언트 쪽 프로토콜 - OUTPUT 내용만 터미널로 넘기고, 입력과 창 크기는 프레임으로 보냄
+/// OUTPUT 내용은 받는 대로 넘기므로 프레임이 읽기 경계에서 잘려도 출력이 늦어지지 않음
+#[derive(Debug, Default)]
+pub struct ClientCodec {
+    header: Vec<u8>,      // 읽는 중인 프레임 머리 (5바이트)
+    frame: Option<(u8, usize)>, // 읽는 중인 프레임의 종류와 남은 길이
+    control: Vec<u8>,     // OUTPUT이 아닌 프레임 내용
+    exit_code: Option<i32>,
+}
+
+impl ClientCodec {
+    fn finish_frame(&mut self, kind: u8) {
+        if kind == EXITED {
+            if let Ok(code) = <[u8; 4]>::try_from(self.control.as_slice()) {
+                self.exit_code = Some(i32::from_be_bytes(code));
+            }
+        }
+        self.control.clear();
+        self.frame = None;
+    }
+}
+
+impl StreamProtocol for ClientCodec {
+    fn receive(&mut self, buf: &mut [u8], _reply: &mut Vec<u8>) -> usize {
+        let mut len = 0;
+        for index in 0..buf.len() {
+            let byte = buf[index];
+            match &mut self.frame {
+                None => {
+                    self.header.push(byte);
+                    if self.header.len() == 5 {
+                        let kind = self.header[0];
+                        let size = u32::from_be_bytes([self.header[1], self.header[2], self.header[3], self.header[4]]) as usize;
+                        self.header.clear();
+                        self.frame = Some((kind, size));
+                        if size == 0 {
+                            self.finish_frame(kind);
+                        }
+                    }
+                }
+                Some((kind, remaining)) => {
+                    let kind = *kind;
+                    *remaining -= 1;
+                    let done = *remaining == 0;
+                    if kind == OUTPUT {
+                        buf[len] = byte;
+                        len += 1;
+                    } else if self.control.len() < MAX_FRAME {
+                        self.control.push(byte);
+                    }
+                    if done {
+                        self.finish_frame(kind);
+                    }
+                }
+            }
+        }
+        len
+    }
+
+    fn encode(&self, data: &[u8], out: &mut Vec<u8>) {
+        // 큰 붙여넣기는 데몬이 받는 최대 크기 안으로 나눠 보냄
+        for chunk in data.chunks(MAX_FRAME) {
+            encode_frame(out, INPUT, chunk);
+        }
+    }
+
+    fn resize(&mut self, columns: u16, lines: u16, reply: &mut Vec<u8>) {
+        let mut payload = [0u8; 4];
+        payload[..2].copy_from_slice(&columns.to_be_bytes());
+        payload[2..].copy_from_slice(&lines.to_be_bytes());
+        encode_frame(reply, RESIZE, &payload);
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+}
+
+/// 잠금 (다른 클라이언트 처리 스레드가 잠근 채 패닉했어도 계속 씀 - 데몬과 분리된 세션이 같이 죽지 않게)
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 데몬의 세션 하나
+struct DaemonSession {
+    id: u64,
+    master: File, // PTY master (입력 쓰기와 창 크기 변경)
+    pid: u32,
+    attachment: Mutex<Attachment>,
+}
+
+/// 세션의 출력 보관과 붙어 있는 클라이언트
+#[derive(Default)]
+struct Attachment {
+    history: VecDeque<u8>,
+    client: Option<(u64, UnixStream)>, // (연결 번호, 출력을 쓸 소켓)
+    redraw: bool,                      // 다시 붙은 뒤 첫 창 크기에서 SIGWINCH를 보냄
+}
+
+impl DaemonSession {
+    /// PTY 출력 보관 후 붙어 있는 클라이언트에 전달
+    fn output(&self, data: &[u8]) {
+        let mut attachment = lock(&self.attachment);
+        attachment.history.extend(data);
+        let excess = attachment.history.len().saturating_sub(HISTORY_BYTES);
+        attachment.history.drain(..excess);
+        if let Some((_, client)) = &mut attachment.client {
+            if let Err(e) = write_frame(client, OUTPUT, data) {
+                log::info!("daemon session {} client detached: {}", self.id, e);
+                attachment.client = None;
+            }
+        }
+    }
+
+    /// 클라이언트 붙이기 - 이미 붙은 클라이언트가 있으면 떼어 냄
+    fn attach(&self, connection: u64, mut client: UnixStream) -> io::Result<()> {
+        let mut attachment = lock(&self.attachment);
+        if let Some((_, previous)) = attachment.client.take() {
+            let _ = previous.shutdown(std::net::Shutdown::Both);
+        }
+        client.set_write_timeout(Some(CLIENT_WRITE_TIMEOUT))?;
+        write_frame(&mut client, OPENED, &self.id.to_be_bytes())?;
+        let (front, back) = attachment.history.as_slices();
+        for chunk in front.chunks(OUTPUT_CHUNK).chain(back.chunks(OUTPUT_CHUNK)) {
+            write_frame(&mut client, OUTPUT, chunk)?;
+        }
+        attachment.client = Some((connection, client));
+        attachment.redraw = !attachment.history.is_empty();
+        Ok(())
+    }
+
+    /// 연결이 끊긴 클라이언트 떼기 (그 사이 다른 클라이언트가 붙었으면 그대로)
+    fn detach(&self, connection: u64) {
+        let mut attachment = lock(&self.attachment);
+        if attachment.client.as_ref().is_some_and(|(id, _)| *id == connection) {
+            attachment.client = None;
+            log::info!("daemon session {} detached", self.id);
+        }
+    }
+
+    fn input(&self, data: &[u8]) -> io::Result<()> {
+        (&self.master).write_all(data)
+    }
+
+    /// PTY 크기 변경 (다시 붙은 뒤 처음이면 크기가 같아도 프로그램이 다시 그리도록 SIGWINCH)
+    fn resize(&self, columns: u16, lines: u16) {
+        use std::os::unix::io::AsRawFd;
+
+        let size = libc::winsize { ws_row: lines, ws_col: columns, ws_xpixel: 0, ws_ypixel: 0 };
+        let fd = self.master.as_raw_fd();
+        // SAFETY: fd는 이 세션이 가진 PTY master, size는 지역 변수
+        unsafe {
+            libc::ioctl(fd, libc::TIOCSWINSZ, &size);
+            if std::mem::take(&mut lock(&self.attachment).redraw) {
+                let group = libc::tcgetpgrp(fd);
+                if group > 0 {
+                    libc::kill(-group, libc::SIGWINCH);
+                }
+            }
+        }
+    }
+
+    /// 셸 종료를 클라이언트에 알리고 연결 닫기
+    fn exited(&self, code: i32) {
+        let mut attachment = lock(&self.attachment);
+        if let Some((_, mut client)) = attachment.client.take() {
+            let _ = write_frame(&mut client, EXITED, &code.to_be_bytes());
+            let _ = client.shutdown(std::net::Shutdown::Both);
+        }
+    }
+}
+
+/// 데몬 상태
+struct Daemon {
+    sessions: Mutex<HashMap<u64, Arc<DaemonSession>>>,
+    next_session: AtomicU64,
+    next_connection: AtomicU64,
+    socket: PathBuf,
+}
+
+/// 데몬 실행 (`rust-terminal --daemon`) - 마지막 세션이 끝나면 반환하지 않고 프로세스 종료
+pub fn run() -> Result<()> {
+    let socket = socket_path();
+    let dir = socket.parent().expect("socket path has a parent");
+    // 다른 사용자가 세션에 붙지 못하게 나만 쓰는 디렉토리에만 소켓을 둠
+    private_dir(dir).with_context(|| format!("데몬 디렉토리를 쓸 수 없음: {}", dir.display()))?;
+    // 이미 실행 중인 데몬이 있으면 그대로 두고, 남은 소켓 파일만 지움
+    if UnixStream::connect(&socket).is_ok() {
+        bail!("세션 데몬이 이미 실행 중: {}", socket.display());
+    }
+    let _ = std::fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket).with_context(|| format!("데몬 소켓 생성 실패: {}", socket.display()))?;
+    log::info!("session daemon listening on {}", socket.display());
+
+    // 데몬을 다시 띄워도 예전 레이아웃의 id가 새 세션을 가리키지 않도록 시작 시각에서 시작
+    let start = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+    let daemon = Arc::new(Daemon {
+        sessions: Mutex::new(HashMap::new()),
+        next_session: AtomicU64::new(start.as_millis() as u64 * 1000),
+        next_connection: AtomicU64::new(1),
+        socket,
+    });
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let daemon = daemon.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = daemon.serve(stream) {
+                        log::warn!("daemon connection: {:#}", e);
+                    }
+                });
+            }
+            Err(e) => log::warn!("daemon accept: {}", e),
+        }
+    }
+    Ok(())
+}
+
+impl Daemon {
+    /// 연결 하나 처리 - 첫 프레임에 따라 새 세션/다시 붙기 뒤 입력을 PTY로 전달
+    fn serve(self: Arc<Self>, mut stream: UnixStream) -> Result<()> {
+        let connection = self.next_connection.fetch_add(1, Ordering::Relaxed);
+        let (kind, payload) = read_frame(&mut stream)?;
+        let session = match kind {
+            OPEN => {
+                let request: OpenRequest = serde_json::from_slice(&payload)?;
+                match self.open(request) {
+                    Ok(session) => session,
+                    Err(e) => return Ok(write_frame(&mut stream, ERROR, format!("{:#}", e).as_bytes())?),
+                }
+            }
+            ATTACH => {
+                let session = session_id(&payload).and_then(|id| lock(&self.sessions).get(&id).cloned());
+                match session {
+                    Some(session) => session,
+                    None => return Ok(write_frame(&mut stream, ERROR, "세션이 없음".as_bytes())?),
+                }
+            }
+            KILL => {
+                if let Some(session) = session_id(&payload).and_then(|id| lock(&self.sessions).get(&id).cloned()) {
+                    log::info!("daemon session {} killed", session.id);
+                    // SAFETY: 세션 셸의 pid (끝나면 세션 스레드가 정리)
+                    unsafe { libc::kill(session.pid as i32, libc::SIGHUP) };
+                }
+                return Ok(());
+            }
+            LIST => {
+                let detached: Vec<u64> = lock(&self.sessions).values()
+                    .filter(|session| lock(&session.attachment).client.is_none())
+                    .map(|session| session.id)
+                    .collect();
+                return Ok(write_frame(&mut stream, SESSIONS, &serde_json::to_vec(&detached)?)?);
+            }
+            _ => bail!("unexpected first frame {}", kind),
+        };
+        session.attach(connection, stream.try_clone()?)?;
+
+        loop {
+            match read_frame(&mut stream) {
+                Ok((INPUT, data)) => {
+                    if let Err(e) = session.input(&data) {
+                        log::warn!("daemon session {} input: {}", session.id, e);
+                    }
+                }
+                Ok((RESIZE, size)) if size.len() == 4 => {
+                    session.resize(u16::from_be_bytes([size[0], size[1]]), u16::from_be_bytes([size[2], size[3]]));
+                }
+                Ok((kind, _)) => log::warn!("daemon session {}: unexpected frame {}", session.id, kind),
+                // 클라이언트가 분리되거나 종료됨 - 셸은 계속 실행
+                Err(_) => break,
+            }
+        }
+        session.detach(connection);
+        Ok(())
+    }
+
+    /// 새 PTY에서 셸 실행 후 출력을 읽는 스레드 시작
+    fn open(self: &Arc<Self>, request: OpenRequest) -> Result<Arc<DaemonSession>> {
+        let (master, child) = spawn_pty(&request)?;
+        let id = self.next_session.fetch_add(1, Ordering::Relaxed);
+        let session = Arc::new(DaemonSession {
+            id,
+            master: master.try_clone()?,
+            pid: child.id(),
+            attachment: Mutex::new(Attachment::default()),
+        });
+        lock(&self.sessions).insert(id, session.clone());
+        log::info!("daemon session {} started: {} (pid {})", id, request.program, child.id());
+
+        let daemon = self.clone();
+        let reader = session.clone();
+        std::thread::spawn(move || {
+            let code = pump_output(&reader, master, child);
+            reader.exited(code);
+            daemon.remove(reader.id, code);
+        });
+        Ok(session)
+    }
+
+    /// 끝난 세션 제거 - 남은 세션이 없으면 데몬 종료
+    fn remove(&self, id: u64, code: i32) {
+        let mut sessions = lock(&self.sessions);
+        sessions.remove(&id);
+        log::info!("daemon session {} exited with {} ({} left)", id, code, sessions.len());
+        if sessions.is_empty() {
+            let _ = std::fs::remove_file(&self.socket);
+            log::info!("session daemon exiting");
+            std::process::exit(0);
+        }
+    }
+}
+
+/// PTY를 만들고 셸 실행 - 환경은 요청의 것만 사용
+fn spawn_pty(request: &OpenRequest) -> Result<(File, std::process::Child)> {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::os::unix::process::CommandExt;
+
+    let size = libc::winsize { ws_row: request.lines.max(1), ws_col: request.columns.max(1), ws_xpixel: 0, ws_ypixel: 0 };
+    let (mut master, mut slave) = (0, 0);
+    // SAFETY: 출력 인자는 지역 변수, 성공하면 두 fd를 OwnedFd로 넘겨받음
+    let (master, slave) = unsafe {
+        if libc::openpty(&mut master, &mut slave, std::ptr::null_mut(), std::ptr::null(), &size) != 0 {
+            return Err(io::Error::last_os_error()).context("PTY 생성 실패");
+        }
+        (OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave))
+    };
+    let slave_fd = slave.as_raw_fd();
+    let mut command = std::process::Command::new(&request.program);
+    command.args(&request.args)
+        .env_clear()
+        .envs(&request.env)
+        .stdin(slave.try_clone()?)
+        .stdout(slave.try_clone()?)
+        .stderr(slave);
+    if let Some(dir) = request.working_directory.as_ref().filter(|dir| dir.is_dir()) {
+        command.current_dir(dir);
+    }
+    // SAFETY: fork 뒤 exec 전에 async-signal-safe인 호출만 사용
+    unsafe {
+        command.pre_exec(move || {
+            // 새 세션의 제어 터미널로 PTY를 잡아야 셸의 작업 제어가 동작
+            if libc::setsid() == -1 || libc::ioctl(slave_fd, libc::TIOCSCTTY, 0) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let child = command.spawn().with_context(|| format!("셸 실행 실패: {}", request.program))?;
+    Ok((File::from(master), child))
+}
+
+/// 셸이 끝날 때까지 PTY 출력을 읽어 세션에 전달 - 종료 코드 반환
+fn pump_output(session: &DaemonSession, mut master: File, mut child: std::process::Child) -> i32 {
+    use std::os::unix::io::AsRawFd;
+
+    let mut buf = [0u8; 8192];
+    loop {
+        let mut poll = libc::pollfd { fd: master.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        // SAFETY: 지역 pollfd 하나
+        let ready = unsafe { libc::poll(&mut poll, 1, POLL_INTERVAL_MS) };
+        if ready > 0 && poll.revents & libc::POLLIN != 0 {
+            match master.read(&mut buf) {
+                Ok(read) if read > 0 => session.output(&buf[..read]),
+                // 셸 쪽이 모두 닫힘 (EIO) - 종료를 기다림
+                _ => std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS as u64)),
+            }
+        } else if ready > 0 {
+            std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS as u64));
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => return status.code().unwrap_or(1),
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!("daemon session {} wait: {}", session.id, e);
+                return 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_codec_frames() {
+        let mut stream = Vec::new();
+        encode_frame(&mut stream, OUTPUT, b"$ ls\r\n");
+        encode_frame(&mut stream, OUTPUT, b"");
+        encode_frame(&mut stream, OUTPUT, b"a b");
+        encode_frame(&mut stream, EXITED, &3i32.to_be_bytes());
+
+        // 프레임이 읽기 경계에서 잘려도 출력은 받는 대로 넘김
+        let mut codec = ClientCodec::default();
+        let mut output = Vec::new();
+        for chunk in stream.chunks(4) {
+            let mut buf = chunk.to_vec();
+            let len = codec.receive(&mut buf, &mut Vec::new());
+            output.extend_from_slice(&buf[..len]);
+        }
+        assert_eq!(output, b"$ ls\r\na b");
+        assert_eq!(codec.exit_code(), Some(3));
+
+        let mut out = Vec::new();
+        codec.encode(b"q", &mut out);
+        codec.resize(80, 24, &mut out);
+        assert_eq!(out, [INPUT, 0, 0, 0, 1, b'q', RESIZE, 0, 0, 0, 4, 0, 80, 0, 24]);
+        assert_eq!(read_frame(&mut &out[..]).unwrap(), (INPUT, b"q".to_vec()));
+
+        // 너무 큰 프레임은 INPUT이라도 내용을 읽기 전에 거절, 큰 입력은 나눠 보냄
+        let huge = [INPUT, 0xff, 0xff, 0xff, 0xff];
+        assert_eq!(read_frame(&mut &huge[..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let mut out = Vec::new();
+        codec.encode(&vec![b'x'; MAX_FRAME + 1], &mut out);
+        let mut reader = &out[..];
+        assert_eq!(read_frame(&mut reader).unwrap().1.len(), MAX_FRAME);
+        assert_eq!(read_frame(&mut reader).unwrap(), (INPUT, b"x".to_vec()));
+    }
+
+    #[test]
+    fn test_private_dir_rejects_shared_directory() {
+        use std::os::unix::fs::PermissionsExt;
+        let base = std::env::temp_dir().join(format!("rust-terminal-private-test-{}", std::process::id()));
+        let dir = base.join("daemon");
+        private_dir(&dir).unwrap();
+        assert_eq!(std::fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+        private_dir(&dir).unwrap();
+
+        // 다른 사용자가 들어올 수 있는 디렉토리나 링크는 쓰지 않음
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(private_dir(&dir).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+        let link = base.join("link");
+        std::os::unix::fs::symlink(&dir, &link).unwrap();
+        assert!(private_dir(&link).is_err());
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_session_keeps_history_for_reattach() {
+        let (master, _slave) = UnixStream::pair().unwrap();
+        let session = DaemonSession {
+            id: 7,
+            master: File::from(std::os::fd::OwnedFd::from(master)),
+            pid: 0,
+            attachment: Mutex::new(Attachment::default()),
+        };
+        // 붙은 클라이언트가 없는 동안의 출력도 보관
+        session.output(b"hello ");
+        session.output(b"world");
+
+        let (client, mut peer) = UnixStream::pair().unwrap();
+        session.attach(1, client).unwrap();
+        assert_eq!(read_frame(&mut peer).unwrap(), (OPENED, 7u64.to_be_bytes().to_vec()));
+        assert_eq!(read_frame(&mut peer).unwrap(), (OUTPUT, b"hello world".to_vec()));
+
+        // 다른 연결이 끊겨도 지금 붙은 클라이언트는 그대로
+        session.detach(2);
+        session.output(b"!");
+        assert_eq!(read_frame(&mut peer).unwrap(), (OUTPUT, b"!".to_vec()));
+        session.exited(0);
+        assert_eq!(read_frame(&mut peer).unwrap(), (EXITED, 0i32.to_be_bytes().to_vec()));
+        assert!(session.attachment.lock().unwrap().client.is_none());
+
+        // 다른 처리 스레드가 잠근 채 패닉해도 세션은 계속 씀
+        let session = Arc::new(session);
+        let poisoner = session.clone();
+        let _ = std::thread::spawn(move || {
+            let _attachment = poisoner.attachment.lock().unwrap();
+            panic!("client handler");
+        }).join();
+        assert!(session.attachment.is_poisoned());
+        session.output(b"after");
+        let history: Vec<u8> = lock(&session.attachment).history.iter().copied().collect();
+        assert!(history.ends_with(b"after"));
+    }
+}
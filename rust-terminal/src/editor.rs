@@ -0,0 +1,160 @@
+//! 편집기 열기 모듈
+//! 출력에 나온 `src/main.rs:120:5` 같은 파일 위치를 설정한 편집기로 그 줄(과 열)에서 열기
+//!
+//! 편집기 명령은 세 가지 형식:
+//! - `{file}`/`{line}`/`{column}` 자리 표시자를 쓴 명령 (`code --goto {file}:{line}:{column}`)
+//! - 자리 표시자를 쓴 URI (`vscode://file{file}:{line}:{column}`) - 시스템 기본 프로그램으로 엶
+//! - 편집기 이름만 (`nvim`, `code -n`) - 알려진 편집기의 줄 이동 인자를 붙임
+//!
+//! 터미널 안에서 도는 편집기(vim, nano 등)는 새 탭에서 실행하고, 창을 띄우는 편집기(code, subl 등)는 따로 실행
+
+use std::path::Path;
+
+/// 창을 띄우는 편집기 (새 탭 대신 따로 실행)
+const GUI_EDITORS: &[&str] = &[
+    "code", "code-insiders", "codium", "cursor", "windsurf", "subl", "sublime_text", "zed", "gvim", "gedit", "kate",
+    "idea", "clion", "pycharm", "goland", "webstorm", "rustrover",
+];
+
+/// 편집기 실행 방법
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Launch {
+    Tab { program: String, args: Vec<String> },      // 새 탭에서 실행
+    Detached { program: String, args: Vec<String> }, // 터미널과 상관없이 실행
+    Uri(String),                                     // 시스템 기본 프로그램으로 열기
+}
+
+/// `경로:줄:열`/`경로:줄`을 경로, 줄, 열로 나눔 (끝의 `:`는 무시)
+pub fn split_location(text: &str) -> (&str, Option<u32>, Option<u32>) {
+    let text = text.trim_end_matches(':');
+    let number = |part: &str| part.parse::<u32>().ok().filter(|_| part.bytes().all(|b| b.is_ascii_digit()));
+    let Some((rest, last)) = text.rsplit_once(':') else {
+        return (text, None, None);
+    };
+    let Some(last) = number(last) else {
+        return (text, None, None);
+    };
+    match rest.rsplit_once(':') {
+        Some((file, line)) if number(line).is_some() => (file, number(line), Some(last)),
+        _ => (rest, Some(last), None),
+    }
+}
+
+/// 설정한 편집기 명령(`command`, 비어 있으면 `$VISUAL`/`$EDITOR`, 둘 다 없으면 `vi`)으로 파일 위치를 여는 방법
+pub fn launch(command: &str, file: &Path, line: Option<u32>, column: Option<u32>) -> Launch {
+    let command = match command.trim() {
+        "" => std::env::var("VISUAL")
+            .or_else(|_| std::env::var("EDITOR"))
+            .ok()
+            .filter(|editor| !editor.trim().is_empty())
+            .unwrap_or_else(|| "vi".to_string()),
+        command => command.to_string(),
+    };
+    let file = file.to_string_lossy();
+    let substitute = |word: &str| {
+        word.replace("{file}", &file)
+            .replace("{line}", &line.unwrap_or(1).to_string())
+            .replace("{column}", &column.unwrap_or(1).to_string())
+    };
+    if command.contains("://") {
+        return Launch::Uri(substitute(command.trim()));
+    }
+
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or("vi").to_string();
+    let mut args: Vec<String> = words.map(str::to_string).collect();
+    if command.contains("{file}") {
+        args = args.iter().map(|arg| substitute(arg)).collect();
+    } else {
+        args.extend(goto_args(editor_name(&program), &file, line, column));
+    }
+
+    if GUI_EDITORS.contains(&editor_name(&program)) {
+        Launch::Detached { program, args }
+    } else {
+        Launch::Tab { program, args }
+    }
+}
+
+/// 경로와 확장자를 뗀 편집기 이름 (`/usr/bin/nvim` -> `nvim`, `Code.exe` -> `code`)
+fn editor_name(program: &str) -> &str {
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    GUI_EDITORS.iter().copied().find(|editor| editor.eq_ignore_ascii_case(name)).unwrap_or(name)
+}
+
+/// 알려진 편집기별로 파일을 그 줄(과 열)에서 여는 인자 (모르는 편집기는 vi처럼 `+줄`)
+fn goto_args(editor: &str, file: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    let Some(line) = line else {
+        return vec![file.to_string()];
+    };
+    let column = column.unwrap_or(1);
+    match editor {
+        "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => {
+            vec!["--goto".to_string(), format!("{}:{}:{}", file, line, column)]
+        }
+        "subl" | "sublime_text" | "zed" | "hx" | "helix" => vec![format!("{}:{}:{}", file, line, column)],
+        "idea" | "clion" | "pycharm" | "goland" | "webstorm" | "rustrover" => vec![
+            "--line".to_string(), line.to_string(), "--column".to_string(), column.to_string(), file.to_string(),
+        ],
+        "emacs" | "emacsclient" | "kak" | "micro" => vec![format!("+{}:{}", line, column), file.to_string()],
+        "nano" => vec![format!("+{},{}", line, column), file.to_string()],
+        _ => vec![format!("+{}", line), file.to_string()],
+    }
+}
+
+/// 터미널과 상관없이 편집기 실행 (셸을 거치지 않고 인자로 전달)
+pub fn spawn_detached(program: &str, args: &[String]) -> std::io::Result<()> {
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    // 편집기가 끝나면 거둬서 좀비 프로세스로 남지 않게
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_location() {
+        assert_eq!(split_location("src/main.rs:120:5"), ("src/main.rs", Some(120), Some(5)));
+        assert_eq!(split_location("src/main.rs:120:"), ("src/main.rs", Some(120), None));
+        assert_eq!(split_location("/etc/hosts"), ("/etc/hosts", None, None));
+        assert_eq!(split_location("notes:v2"), ("notes:v2", None, None));
+    }
+
+    #[test]
+    fn test_launch_forms() {
+        let file = Path::new("/work/src/main.rs");
+        assert_eq!(launch("nvim", file, Some(120), Some(5)), Launch::Tab {
+            program: "nvim".to_string(),
+            args: strings(&["+120", "/work/src/main.rs"]),
+        });
+        assert_eq!(launch("/usr/bin/code -r", file, Some(120), Some(5)), Launch::Detached {
+            program: "/usr/bin/code".to_string(),
+            args: strings(&["-r", "--goto", "/work/src/main.rs:120:5"]),
+        });
+        assert_eq!(launch("hx", file, Some(7), None), Launch::Tab {
+            program: "hx".to_string(),
+            args: strings(&["/work/src/main.rs:7:1"]),
+        });
+        // 자리 표시자를 쓰면 그대로 채움
+        assert_eq!(launch("emacsclient -n +{line}:{column} {file}", file, Some(3), Some(9)), Launch::Tab {
+            program: "emacsclient".to_string(),
+            args: strings(&["-n", "+3:9", "/work/src/main.rs"]),
+        });
+        assert_eq!(
+            launch("vscode://file{file}:{line}:{column}", file, Some(120), None),
+            Launch::Uri("vscode://file/work/src/main.rs:120:1".to_string()),
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! 셸 환경 변수 정책 모듈
+//! 호스트 환경에서 물려받을 변수, 지울 변수, 새로 넣을 변수를 정해 셸에 넘길 환경을 만듦
+//!
+//! 적용 순서: 물려받기(`inherit`) -> 터미널 변수(`TERM`, `TERM_PROGRAM` 등) -> 지우기(`remove`) -> 넣기(`set`)
+//! 변수 이름 끝의 `*`는 접두사 일치 (`KITTY_*`)
+
+use std::collections::HashMap;
+
+/// 기본으로 지우는 변수 - 호스트 터미널을 가리키는 값이라 이 터미널 안에서는 틀린 정보
+/// (`inherit` 목록에 이름을 직접 적으면 지우지 않음)
+pub const HOST_TERMINAL_VARS: &[&str] = &[
+    "ZED_TERM",
+    "TERM_SESSION_ID",
+    "ITERM_*",
+    "KITTY_*",
+    "WEZTERM_*",
+    "ALACRITTY_*",
+    "KONSOLE_*",
+    "GNOME_TERMINAL_*",
+    "VTE_VERSION",
+    "WT_SESSION",
+    "WT_PROFILE_ID",
+    "TMUX",
+    "TMUX_PANE",
+    "LINES",
+    "COLUMNS",
+];
+
+/// 셸 환경 변수 정책
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvPolicy {
+    /// 물려받을 변수 (None이면 모두, 빈 목록이면 하나도 물려받지 않음)
+    pub inherit: Option<Vec<String>>,
+    /// 지울 변수 (기본 목록 `HOST_TERMINAL_VARS`에 더해짐)
+    pub remove: Vec<String>,
+    /// 넣을 변수 (파일에 나온 순서, 같은 이름이면 나중 값)
+    pub set: Vec<(String, String)>,
+}
+
+/// 이름이 패턴과 일치하는지 (끝의 `*`는 접두사 일치)
+fn matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+/// 쉼표나 공백으로 구분한 변수 이름 목록
+pub fn parse_names(value: &str) -> Vec<String> {
+    value.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl EnvPolicy {
+    /// 물려받을 변수 설정 (`all`, `none`, 또는 이름 목록)
+    pub fn set_inherit(&mut self, value: &str) {
+        self.inherit = match value.trim() {
+            "all" => None,
+            "none" => Some(Vec::new()),
+            names => Some(parse_names(names)),
+        };
+    }
+
+    /// 넣을 변수 하나 추가 (같은 이름이 있으면 바꿈)
+    pub fn set_var(&mut self, key: &str, value: &str) {
+        self.set.retain(|(name, _)| name != key);
+        self.set.push((key.to_string(), value.to_string()));
+    }
+
+    /// 넣을 변수를 더한 정책 (세션 프로필의 환경 변수)
+    pub fn with_vars(&self, vars: &[(String, String)]) -> Self {
+        let mut policy = self.clone();
+        for (key, value) in vars {
+            policy.set_var(key, value);
+        }
+        policy
+    }
+
+    /// 호스트 환경 중 물려받을 변수
+    pub fn inherited(&self, parent: HashMap<String, String>) -> HashMap<String, String> {
+        match &self.inherit {
+            None => parent,
+            Some(names) => parent.into_iter()
+                .filter(|(name, _)| names.iter().any(|pattern| matches(pattern, name)))
+                .collect(),
+        }
+    }
+
+    /// 지우기와 넣기 적용 (터미널 변수를 넣은 뒤에 호출해야 `set`으로 `TERM` 등을 바꿀 수 있음)
+    pub fn apply(&self, env: &mut HashMap<String, String>) {
+        let kept = |name: &str| self.inherit.as_ref().is_some_and(|names| names.iter().any(|kept| kept == name));
+        env.retain(|name, _| {
+            let host_var = HOST_TERMINAL_VARS.iter().any(|pattern| matches(pattern, name)) && !kept(name);
+            !host_var && !self.remove.iter().any(|pattern| matches(pattern, name))
+        });
+        for (key, value) in &self.set {
+            env.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parent() -> HashMap<String, String> {
+        [("PATH", "/bin"), ("HOME", "/home/u"), ("KITTY_WINDOW_ID", "3"), ("SECRET_TOKEN", "x"), ("TMUX", "/tmp/t")]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn keys(env: &HashMap<String, String>) -> Vec<&str> {
+        let mut keys: Vec<&str> = env.keys().map(String::as_str).collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_default_policy_drops_host_terminal_vars() {
+        let policy = EnvPolicy::default();
+        let mut env = policy.inherited(parent());
+        policy.apply(&mut env);
+        assert_eq!(keys(&env), vec!["HOME", "PATH", "SECRET_TOKEN"]);
+    }
+
+    #[test]
+    fn test_inherit_remove_and_set() {
+        let mut policy = EnvPolicy { remove: parse_names("SECRET_*, HOME"), ..EnvPolicy::default() };
+        policy.set_var("EDITOR", "vi");
+        policy.set_var("EDITOR", "nvim");
+        let policy = policy.with_vars(&[("AWS_PROFILE".to_string(), "work".to_string())]);
+        let mut env = policy.inherited(parent());
+        policy.apply(&mut env);
+        assert_eq!(keys(&env), vec!["AWS_PROFILE", "EDITOR", "PATH"]);
+        assert_eq!(env["EDITOR"], "nvim");
+
+        // 목록에 적은 변수만 물려받고, 직접 적은 호스트 터미널 변수는 지우지 않음
+        let mut policy = EnvPolicy::default();
+        policy.set_inherit("PATH TMUX");
+        let mut env = policy.inherited(parent());
+        policy.apply(&mut env);
+        assert_eq!(keys(&env), vec!["PATH", "TMUX"]);
+
+        policy.set_inherit("none");
+        assert!(policy.inherited(parent()).is_empty());
+        policy.set_inherit("all");
+        assert_eq!(policy.inherit, None);
+    }
+}
@@ -0,0 +1,91 @@
+//! alacritty 터미널 이벤트 처리 모듈
+//! PTY 출력에서 생긴 이벤트를 앱이 수행할 동작으로 변환
+//! 변환을 앱 상태와 분리하여, 실제 프로세스 없이 이벤트를 주입해 각 처리를 테스트할 수 있게 함
+
+use crate::config::Config;
+use crate::title;
+use alacritty_terminal::event::Event as AlacTermEvent;
+
+/// 터미널 이벤트에 대해 앱이 수행할 동작
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TermEffect {
+    /// 새 출력이 그리드에 반영됨 - 입력을 기다리지 않고 바로 다시 그리기
+    Redraw,
+    /// 프로그램이 OSC 0/2로 바꾼 제목 (제어 문자 제거, 빈 제목과 초기화는 None)
+    /// 세션의 탭 이름이 되고, 포커스 세션이면 호스트 터미널 창 제목도 바꿈
+    SetTitle(Option<String>),
+    /// 호스트 터미널로 벨 전달
+    Bell,
+    /// 프로그램의 질의(커서 위치 보고 등)에 대한 응답을 PTY로 전송
+    WriteToPty(String),
+    /// 프로그램이 OSC 52로 요청한 클립보드 저장
+    StoreClipboard(String),
+    /// 셸 프로세스의 종료 코드 (종료 이벤트보다 먼저 옴)
+    ChildExited(i32),
+    /// 셸이 종료됨 - 설정에 따라 pane 닫기/열어 두기/다시 실행
+    Exit,
+}
+
+/// 이벤트에 해당하는 동작 (앱에서 처리하지 않는 이벤트는 None)
+/// 클립보드 읽기 요청(OSC 52 조회)은 다른 앱의 클립보드 내용이 프로그램으로 새어 나가지 않도록 무시
+pub fn effect_for(event: AlacTermEvent, config: &Config) -> Option<TermEffect> {
+    match event {
+        // alacritty가 종결자(BEL/ST)를 제거한 본문을 보내지만, 신뢰할 수 없는 출력이므로 제어 문자 제거 후 전달
+        AlacTermEvent::Wakeup => Some(TermEffect::Redraw),
+        // 제목을 고정했으면 무시
+        AlacTermEvent::Title(_) | AlacTermEvent::ResetTitle if config.title.lock => None,
+        AlacTermEvent::Title(text) => Some(TermEffect::SetTitle(title::program_title(&text))),
+        AlacTermEvent::ResetTitle => Some(TermEffect::SetTitle(None)),
+        AlacTermEvent::Bell => Some(TermEffect::Bell),
+        AlacTermEvent::PtyWrite(text) => Some(TermEffect::WriteToPty(text)),
+        AlacTermEvent::ClipboardStore(_, text) => Some(TermEffect::StoreClipboard(text)),
+        AlacTermEvent::ChildExit(code) => Some(TermEffect::ChildExited(code)),
+        AlacTermEvent::Exit => Some(TermEffect::Exit),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::Terminal;
+
+    /// 출력을 그리드에 반영하고 발생한 이벤트의 동작 목록 반환
+    fn effects_for_output(bytes: &[u8]) -> Vec<TermEffect> {
+        let config = Config::default();
+        let mut terminal = Terminal::for_test(5, 20);
+        terminal.feed(bytes);
+        terminal.take_events().into_iter().filter_map(|event| effect_for(event, &config)).collect()
+    }
+
+    #[test]
+    fn test_title_and_bell_effects() {
+        assert_eq!(effects_for_output(b"\x1b]0;vim\x07"), vec![TermEffect::SetTitle(Some("vim".to_string()))]);
+        assert_eq!(effects_for_output(b"\x1b]2;\x07"), vec![TermEffect::SetTitle(None)]);
+        assert_eq!(effects_for_output(b"\x07"), vec![TermEffect::Bell]);
+        assert_eq!(effect_for(AlacTermEvent::ResetTitle, &Config::default()), Some(TermEffect::SetTitle(None)));
+
+        // 제목을 고정하면 프로그램이 보낸 제목은 무시
+        let config = Config::from_sources(Some("RUST_TERMINAL_LOCK_TITLE=on"), |_| None).unwrap();
+        assert_eq!(effect_for(AlacTermEvent::Title("vim".to_string()), &config), None);
+        assert_eq!(effect_for(AlacTermEvent::ResetTitle, &config), None);
+    }
+
+    #[test]
+    fn test_device_status_report_is_written_back() {
+        // 커서 위치 보고(DSR 6) 응답은 PTY로 전송
+        assert_eq!(effects_for_output(b"ab\x1b[6n"), vec![TermEffect::WriteToPty("\x1b[1;3R".to_string())]);
+    }
+
+    #[test]
+    fn test_clipboard_store_and_exit_effects() {
+        // OSC 52 저장 요청 (base64 "hi")
+        assert_eq!(effects_for_output(b"\x1b]52;c;aGk=\x07"), vec![TermEffect::StoreClipboard("hi".to_string())]);
+
+        let config = Config::default();
+        assert_eq!(effect_for(AlacTermEvent::Exit, &config), Some(TermEffect::Exit));
+        assert_eq!(effect_for(AlacTermEvent::ChildExit(1), &config), Some(TermEffect::ChildExited(1)));
+        assert_eq!(effect_for(AlacTermEvent::Wakeup, &config), Some(TermEffect::Redraw));
+        assert_eq!(effect_for(AlacTermEvent::MouseCursorDirty, &config), None);
+    }
+}
@@ -0,0 +1,439 @@
+//! 스크롤백 내보내기 모듈
+//! 포커스 터미널의 히스토리와 화면 전체를 파일로 저장 - 일반 텍스트, ANSI 이스케이프가 든 텍스트(`less -R`, `cat`으로 색 그대로 보기),
+//! 또는 색을 인라인 스타일로 넣은 HTML(브라우저로 보거나 문서에 붙이기)
+//!
+//! 자동 줄 바꿈된 줄은 한 줄로 이어 붙이고, 줄 끝의 빈칸과 마지막의 빈 줄은 저장하지 않음
+//!
+//! 선택 영역을 색과 함께 복사할 때도 같은 변환을 사용 (HTML은 문서 대신 `<pre>` 조각)
+
+use crate::output_log;
+use crate::render::{ansi_color, named_index};
+use crate::selection::SelectionRange;
+use crate::terminal::RenderableCell;
+use crate::theme::Theme;
+use alacritty_terminal::term::cell::Flags;
+use alacritty_terminal::vte::ansi::Color as AnsiColor;
+use ratatui::style::Color;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// 내보내기 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Text,
+    Ansi, // 색과 글자 속성을 SGR 시퀀스로
+    Html, // 색과 글자 속성을 인라인 스타일로
+}
+
+impl ExportFormat {
+    /// `text`/`ansi`/`html`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" | "plain" => Some(ExportFormat::Text),
+            "ansi" => Some(ExportFormat::Ansi),
+            "html" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+
+    /// 파일 확장자
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Ansi => "ansi",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// 글자 모양이 같은 셀을 묶는 기준 (색과 보이는 속성만)
+const STYLE_FLAGS: Flags = Flags::BOLD.union(Flags::DIM).union(Flags::ITALIC).union(Flags::ALL_UNDERLINES)
+    .union(Flags::INVERSE).union(Flags::HIDDEN).union(Flags::STRIKEOUT);
+
+/// 글자 모양 (전경색, 배경색, 속성)
+type Style = (AnsiColor, AnsiColor, Flags);
+
+fn style(cell: &RenderableCell) -> Style {
+    (cell.fg, cell.bg, cell.flags & STYLE_FLAGS)
+}
+
+/// 기본 배경의 빈칸 (줄 끝에서 잘라 냄)
+fn is_blank(cell: &RenderableCell) -> bool {
+    matches!(cell.c, ' ' | '\0')
+        && cell.bg == AnsiColor::Named(alacritty_terminal::vte::ansi::NamedColor::Background)
+        && !cell.flags.intersects(Flags::INVERSE | Flags::ALL_UNDERLINES | Flags::STRIKEOUT)
+}
+
+/// 터미널 줄(셀, 자동 줄 바꿈 여부)을 논리 줄로 - 이어진 줄을 합치고 넓은 글자의 빈 셀과 줄 끝 빈칸 제거
+fn logical_lines(rows: &[(Vec<RenderableCell>, bool)]) -> Vec<Vec<RenderableCell>> {
+    let mut lines = Vec::new();
+    let mut current: Vec<RenderableCell> = Vec::new();
+    for (cells, wrapped) in rows {
+        current.extend(cells.iter()
+            .filter(|cell| !cell.flags.intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER))
+            .cloned());
+        if !wrapped {
+            while current.last().is_some_and(is_blank) {
+                current.pop();
+            }
+            lines.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    while lines.last().is_some_and(Vec::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// 스크롤백을 형식에 맞춰 문자열로 (`title`은 HTML 문서 제목)
+pub fn render(rows: &[(Vec<RenderableCell>, bool)], format: ExportFormat, theme: &Theme, title: &str) -> String {
+    let lines = logical_lines(rows);
+    let mut out = String::new();
+    match format {
+        ExportFormat::Text => {
+            for line in &lines {
+                out.extend(line.iter().map(|cell| if cell.c == '\0' { ' ' } else { cell.c }));
+                out.push('\n');
+            }
+        }
+        ExportFormat::Ansi => {
+            for line in &lines {
+                write_ansi_line(&mut out, line);
+                out.push('\n');
+            }
+        }
+        ExportFormat::Html => write_html(&mut out, &lines, theme, title),
+    }
+    out
+}
+
+/// 같은 모양끼리 묶은 (모양, 글자들)
+fn runs(line: &[RenderableCell]) -> Vec<(Style, String)> {
+    let mut runs: Vec<(Style, String)> = Vec::new();
+    for cell in line {
+        let c = if cell.c == '\0' { ' ' } else { cell.c };
+        match runs.last_mut() {
+            Some((style_, text)) if *style_ == style(cell) => text.push(c),
+            _ => runs.push((style(cell), c.to_string())),
+        }
+    }
+    runs
+}
+
+/// 한 줄을 SGR 시퀀스와 함께 (모양이 바뀔 때만 시퀀스를 넣고 줄 끝에서 초기화)
+fn write_ansi_line(out: &mut String, line: &[RenderableCell]) {
+    let plain: Style = (
+        AnsiColor::Named(alacritty_terminal::vte::ansi::NamedColor::Foreground),
+        AnsiColor::Named(alacritty_terminal::vte::ansi::NamedColor::Background),
+        Flags::empty(),
+    );
+    let mut current = plain;
+    for (style, text) in runs(line) {
+        if style != current {
+            out.push_str(&sgr(style));
+            current = style;
+        }
+        out.push_str(&text);
+    }
+    if current != plain {
+        out.push_str("\x1b[0m");
+    }
+}
+
+/// 모양 하나의 SGR 시퀀스 (항상 초기화부터)
+fn sgr((fg, bg, flags): Style) -> String {
+    let mut params = vec!["0".to_string()];
+    for (flag, param) in [
+        (Flags::BOLD, "1"), (Flags::DIM, "2"), (Flags::ITALIC, "3"), (Flags::ALL_UNDERLINES, "4"),
+        (Flags::INVERSE, "7"), (Flags::HIDDEN, "8"), (Flags::STRIKEOUT, "9"),
+    ] {
+        if flags.intersects(flag) {
+            params.push(param.to_string());
+        }
+    }
+    params.extend(color_param(fg, 30, 90, 38));
+    params.extend(color_param(bg, 40, 100, 48));
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// 색 하나의 SGR 인자 (기본 16색은 `base`/`bright` 번호, 나머지는 256색/24비트 확장)
+fn color_param(color: AnsiColor, base: u8, bright: u8, extended: u8) -> Option<String> {
+    match color {
+        AnsiColor::Named(named) => named_index(named).map(|index| match index {
+            0..=7 => (base + index).to_string(),
+            _ => (bright + index - 8).to_string(),
+        }),
+        AnsiColor::Indexed(index) => Some(format!("{};5;{}", extended, index)),
+        AnsiColor::Spec(rgb) => Some(format!("{};2;{};{};{}", extended, rgb.r, rgb.g, rgb.b)),
+    }
+}
+
+/// 색 팔레트의 실제 RGB 값 (이름 있는 색은 xterm 기본값, 기본 색(Reset)이면 None)
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00), (0xcd, 0x00, 0x00), (0x00, 0xcd, 0x00), (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee), (0xcd, 0x00, 0xcd), (0x00, 0xcd, 0xcd), (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f), (0xff, 0x00, 0x00), (0x00, 0xff, 0x00), (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff), (0xff, 0x00, 0xff), (0x00, 0xff, 0xff), (0xff, 0xff, 0xff),
+    ];
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(index) => index,
+    };
+    Some(match index {
+        0..=15 => BASIC[index as usize],
+        // 6x6x6 색 큐브
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        // 회색조
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape_html(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// 테마 색으로 칠한 HTML 문서
+fn write_html(out: &mut String, lines: &[Vec<RenderableCell>], theme: &Theme, title: &str) {
+    let background = rgb(theme.background).unwrap_or(DEFAULT_BACKGROUND);
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>");
+    escape_html(out, title);
+    out.push_str("</title>\n</head>\n");
+    out.push_str(&format!("<body style=\"margin:0;background:{}\">\n", hex(background)));
+    write_pre(out, lines, theme);
+    out.push_str("\n</body>\n</html>\n");
+}
+
+/// 테마의 기본 색을 RGB로 알 수 없을 때 쓰는 색
+const DEFAULT_FOREGROUND: (u8, u8, u8) = (0xe5, 0xe5, 0xe5);
+const DEFAULT_BACKGROUND: (u8, u8, u8) = (0x00, 0x00, 0x00);
+
+/// 줄들을 `<pre>` 하나로 (기본 글자색/배경색은 `<pre>`에, 나머지는 글자 묶음마다 인라인 스타일)
+fn write_pre(out: &mut String, lines: &[Vec<RenderableCell>], theme: &Theme) {
+    let foreground = rgb(theme.foreground).unwrap_or(DEFAULT_FOREGROUND);
+    let background = rgb(theme.background).unwrap_or(DEFAULT_BACKGROUND);
+    out.push_str(&format!(
+        "<pre style=\"margin:0;padding:8px;font-family:monospace;color:{};background:{}\">",
+        hex(foreground), hex(background),
+    ));
+    for line in lines {
+        for ((fg, bg, flags), text) in runs(line) {
+            let mut fg = ansi_color(fg, theme).and_then(rgb);
+            let mut bg = ansi_color(bg, theme).and_then(rgb);
+            if flags.contains(Flags::INVERSE) {
+                (fg, bg) = (Some(bg.unwrap_or(background)), Some(fg.unwrap_or(foreground)));
+            }
+            let mut css = Vec::new();
+            if flags.contains(Flags::HIDDEN) {
+                css.push("color:transparent".to_string());
+            } else if let Some(fg) = fg {
+                css.push(format!("color:{}", hex(fg)));
+            }
+            if let Some(bg) = bg {
+                css.push(format!("background:{}", hex(bg)));
+            }
+            if flags.contains(Flags::BOLD) {
+                css.push("font-weight:bold".to_string());
+            }
+            if flags.contains(Flags::DIM) {
+                css.push("opacity:0.7".to_string());
+            }
+            if flags.contains(Flags::ITALIC) {
+                css.push("font-style:italic".to_string());
+            }
+            let decorations: Vec<&str> = [(Flags::ALL_UNDERLINES, "underline"), (Flags::STRIKEOUT, "line-through")]
+                .into_iter()
+                .filter(|(flag, _)| flags.intersects(*flag))
+                .map(|(_, decoration)| decoration)
+                .collect();
+            if !decorations.is_empty() {
+                css.push(format!("text-decoration:{}", decorations.join(" ")));
+            }
+            if css.is_empty() {
+                escape_html(out, &text);
+            } else {
+                out.push_str(&format!("<span style=\"{}\">", css.join(";")));
+                escape_html(out, &text);
+                out.push_str("</span>");
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>");
+}
+
+/// 선택 범위(전체 버퍼 기준 행, 화면 열, 끝 포함)의 셀만 잘라 낸 줄들 - 사각형 선택(`block`)이면 줄마다 같은 열 범위
+/// 일반 텍스트 복사처럼 줄마다 나누며, 넓은 글자는 한 칸만 걸쳐도 포함
+pub fn selection_rows(rows: &[(Vec<RenderableCell>, bool)], range: SelectionRange, block: bool) -> Vec<(Vec<RenderableCell>, bool)> {
+    let (start_row, start_col, end_row, end_col) = range;
+    (start_row..=end_row)
+        .filter_map(|row| rows.get(row as usize).map(|(cells, _)| (row, cells)))
+        .map(|(row, cells)| {
+            let mut start = if block || row == start_row { start_col as usize } else { 0 };
+            let end = if block || row == end_row { end_col as usize } else { usize::MAX };
+            if start > 0 && cells.get(start).is_some_and(|cell| cell.flags.contains(Flags::WIDE_CHAR_SPACER)) {
+                start -= 1;
+            }
+            let cells = cells.iter().take(end.saturating_add(1)).skip(start).cloned().collect();
+            (cells, false)
+        })
+        .collect()
+}
+
+/// 선택 영역을 클립보드용으로 - ANSI는 줄을 `separator`로 나눈 텍스트, HTML은 `<pre>` 조각
+/// (일반 텍스트 복사는 `selection::selected_text`)
+pub fn render_selection(rows: &[(Vec<RenderableCell>, bool)], format: ExportFormat, theme: &Theme, separator: &str) -> String {
+    let lines = logical_lines(rows);
+    let mut out = String::new();
+    if format == ExportFormat::Html {
+        write_pre(&mut out, &lines, theme);
+        return out;
+    }
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            out.push_str(separator);
+        }
+        write_ansi_line(&mut out, line);
+    }
+    out
+}
+
+/// 스크롤백을 `directory`의 `scrollback-<UTC 시각>.<확장자>` 파일로 저장하고 경로 반환
+pub fn save(directory: &Path, rows: &[(Vec<RenderableCell>, bool)], format: ExportFormat, theme: &Theme, title: &str) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(directory)?;
+    let (mut file, path) = output_log::open_new(directory, "scrollback", format.extension())?;
+    file.write_all(render(rows, format, theme, title).as_bytes())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alacritty_terminal::vte::ansi::{NamedColor, Rgb};
+
+    fn cells(text: &str, fg: AnsiColor, flags: Flags) -> Vec<RenderableCell> {
+        text.chars().map(|c| RenderableCell {
+            c,
+            fg,
+            bg: AnsiColor::Named(NamedColor::Background),
+            flags,
+            hyperlink: None,
+        }).collect()
+    }
+
+    fn plain(text: &str) -> Vec<RenderableCell> {
+        cells(text, AnsiColor::Named(NamedColor::Foreground), Flags::empty())
+    }
+
+    #[test]
+    fn test_text_joins_wrapped_rows() {
+        let rows = vec![
+            (plain("$ echo long"), true),
+            (plain("er line    "), false),
+            (plain("a<b>&      "), false),
+            (plain("           "), false),
+            (plain("           "), false),
+        ];
+        let theme = Theme::default();
+        assert_eq!(render(&rows, ExportFormat::Text, &theme, "t"), "$ echo longer line\na<b>&\n");
+        // 색이 없으면 ANSI 형식도 시퀀스 없이 같은 내용
+        assert_eq!(render(&rows, ExportFormat::Ansi, &theme, "t"), "$ echo longer line\na<b>&\n");
+    }
+
+    #[test]
+    fn test_ansi_and_html_colors() {
+        let mut line = plain("ok ");
+        line.extend(cells("ERR", AnsiColor::Named(NamedColor::Red), Flags::BOLD));
+        line.extend(cells("!", AnsiColor::Spec(Rgb { r: 1, g: 2, b: 3 }), Flags::empty()));
+        line.extend(cells("<x>", AnsiColor::Indexed(196), Flags::UNDERLINE));
+        let rows = vec![(line, false)];
+        let theme = Theme::default();
+
+        assert_eq!(
+            render(&rows, ExportFormat::Ansi, &theme, "t"),
+            "ok \x1b[0;1;31mERR\x1b[0;38;2;1;2;3m!\x1b[0;4;38;5;196m<x>\x1b[0m\n",
+        );
+
+        let html = render(&rows, ExportFormat::Html, &theme, "build & test");
+        assert!(html.contains("<title>build &amp; test</title>"));
+        assert!(html.contains(">ok <span style=\"color:#cd0000;font-weight:bold\">ERR</span>"));
+        assert!(html.contains("<span style=\"color:#010203\">!</span>"));
+        assert!(html.contains("<span style=\"color:#ff0000;text-decoration:underline\">&lt;x&gt;</span>\n</pre>"));
+        assert_eq!(rgb(Color::Indexed(244)), Some((0x80, 0x80, 0x80)));
+        assert_eq!(rgb(Color::Indexed(17)), Some((0, 0, 0x5f)));
+    }
+
+    #[test]
+    fn test_selection_formats() {
+        let mut first = plain("$ ls ");
+        first.extend(cells("src", AnsiColor::Named(NamedColor::Blue), Flags::empty()));
+        // 넓은 글자 (두 번째 칸은 빈 셀)
+        let mut second = plain("a");
+        second.extend(cells("한", AnsiColor::Named(NamedColor::Green), Flags::WIDE_CHAR));
+        second.extend(cells(" ", AnsiColor::Named(NamedColor::Green), Flags::WIDE_CHAR_SPACER));
+        second.extend(plain("b   "));
+        let rows = vec![(first, false), (second, false)];
+        let theme = Theme::default();
+
+        // 넓은 글자의 두 번째 칸에서 시작해도 글자 전체를 포함
+        let selected = selection_rows(&rows, (0, 5, 1, 2), false);
+        assert_eq!(render_selection(&selected, ExportFormat::Ansi, &theme, "\r\n"), "\x1b[0;34msrc\x1b[0m\r\na\x1b[0;32m한\x1b[0m");
+        let selected = selection_rows(&rows, (0, 2, 1, 2), true);
+        assert_eq!(render_selection(&selected, ExportFormat::Ansi, &theme, "\n"), "l\n\x1b[0;32m한\x1b[0m");
+
+        let html = render_selection(&selection_rows(&rows, (0, 0, 0, 7), false), ExportFormat::Html, &theme, "\n");
+        assert!(html.starts_with("<pre style=\""));
+        assert!(html.ends_with(">$ ls <span style=\"color:#0000ee\">src</span>\n</pre>"));
+        assert_eq!(ExportFormat::parse("HTML"), Some(ExportFormat::Html));
+        assert_eq!(ExportFormat::parse("rtf"), None);
+    }
+
+    #[test]
+    fn test_save_creates_file() {
+        let dir = std::env::temp_dir().join(format!("rust-terminal-export-{}", std::process::id()));
+        let path = save(&dir, &[(plain("hello"), false)], ExportFormat::Text, &Theme::default(), "t").unwrap();
+        assert!(path.file_name().unwrap().to_string_lossy().starts_with("scrollback-"));
+        assert_eq!(path.extension().unwrap(), "txt");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
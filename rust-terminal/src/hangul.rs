@@ -1,16 +1,10 @@
-/// 한글 자모 조합 모듈
-/// 개별 자모(ㄱ, ㅏ, ㄴ 등)를 받아서 완성된 한글 음절(간 등)로 조합
+//! 한글 자모 조합 모듈
+//! 개별 자모(ㄱ, ㅏ, ㄴ 등)를 받아서 완성된 한글 음절(간 등)로 조합
 
 use std::char;
 
-/// 한글 자모 범위
-const JAMO_L_BASE: u32 = 0x1100; // 초성 시작
-const JAMO_V_BASE: u32 = 0x1161; // 중성 시작
-const JAMO_T_BASE: u32 = 0x11A7; // 종성 시작
-
 /// 완성된 한글 음절 범위
 const SYLLABLE_BASE: u32 = 0xAC00; // 가
-const L_COUNT: u32 = 19;  // 초성 개수
 const V_COUNT: u32 = 21;  // 중성 개수
 const T_COUNT: u32 = 28;  // 종성 개수 (빈 종성 포함)
 const N_COUNT: u32 = V_COUNT * T_COUNT; // 588
@@ -99,47 +93,93 @@ impl HangulComposer {
         }
     }
 
+    /// 현재 조합 중인 글자 (초성만 있으면 초성 자모 그대로)
+    pub fn pending_char(&self) -> Option<char> {
+        self.get_current_syllable().or_else(|| match (self.leading, self.vowel) {
+            (Some(l), None) => LEADING_CONSONANTS.iter()
+                .find(|(_, idx)| *idx == l)
+                .map(|(c, _)| *c),
+            _ => None,
+        })
+    }
+
     /// 자모 입력 처리
     pub fn input_jamo(&mut self, ch: char) -> CompositionResult {
-        // 초성 처리
+        // 자음 처리
         if let Some(l_idx) = self.is_leading_consonant(ch) {
-            if self.leading.is_none() {
-                // 첫 초성
-                self.leading = Some(l_idx);
-                return CompositionResult::Composing;
-            } else if self.vowel.is_none() {
-                // 초성만 있는 상태에서 새 초성 -> 기존 초성 출력 후 새 초성 시작
-                let prev = self.get_current_syllable();
-                self.clear();
-                self.leading = Some(l_idx);
-                return CompositionResult::CompletedWithNew(prev, None);
-            } else {
-                // 초성+중성 있는 상태에서 새 초성 -> 기존 음절 완성 후 새 초성 시작
-                let completed = self.get_current_syllable();
-                self.clear();
-                self.leading = Some(l_idx);
-                return CompositionResult::CompletedWithNew(completed, None);
+            match (self.leading, self.vowel, self.trailing) {
+                (None, _, _) => {
+                    // 첫 초성
+                    self.leading = Some(l_idx);
+                    return CompositionResult::Composing;
+                }
+                (Some(_), None, _) => {
+                    // 초성만 있는 상태에서 새 초성 -> 기존 초성 출력 후 새 초성 시작
+                    let prev = self.pending_char();
+                    self.clear();
+                    self.leading = Some(l_idx);
+                    return CompositionResult::CompletedWithNew(prev, None);
+                }
+                (Some(_), Some(_), None) => {
+                    // 초성+중성 다음 자음 -> 종성 (ㄸ, ㅃ, ㅉ는 종성이 될 수 없음)
+                    if let Some(t_idx) = self.is_trailing_consonant(ch) {
+                        self.trailing = Some(t_idx);
+                        return CompositionResult::Composing;
+                    }
+                    let completed = self.get_current_syllable();
+                    self.clear();
+                    self.leading = Some(l_idx);
+                    return CompositionResult::CompletedWithNew(completed, None);
+                }
+                (Some(_), Some(_), Some(_)) => {
+                    // 종성까지 있으면 기존 음절 완성 후 새 초성 시작
+                    let completed = self.get_current_syllable();
+                    self.clear();
+                    self.leading = Some(l_idx);
+                    return CompositionResult::CompletedWithNew(completed, None);
+                }
             }
         }
 
         // 중성 처리
         if let Some(v_idx) = self.is_vowel(ch) {
-            if self.leading.is_some() && self.vowel.is_none() {
-                // 초성 다음 중성
-                self.vowel = Some(v_idx);
-                return CompositionResult::Composing;
-            } else if self.leading.is_some() && self.vowel.is_some() {
-                // 이미 중성이 있으면 현재 음절 완성 후 새로 시작 (일단 단순 처리)
-                let completed = self.get_current_syllable();
-                self.clear();
-                return CompositionResult::CompletedWithNew(completed, Some(ch));
-            } else {
-                // 초성 없이 중성만 -> 그냥 출력
-                return CompositionResult::DirectOutput(ch);
+            match (self.leading, self.vowel, self.trailing) {
+                (Some(_), None, _) => {
+                    // 초성 다음 중성
+                    self.vowel = Some(v_idx);
+                    return CompositionResult::Composing;
+                }
+                (Some(_), Some(_), Some(t_idx)) => {
+                    // 종성 다음 중성 -> 종성을 다음 음절의 초성으로 이동 (안 + ㅏ -> 아 + 나)
+                    let next_leading = self.trailing_char(t_idx)
+                        .and_then(|t_char| self.is_leading_consonant(t_char));
+                    if let Some(l_idx) = next_leading {
+                        self.trailing = None;
+                        let completed = self.get_current_syllable();
+                        self.clear();
+                        self.leading = Some(l_idx);
+                        self.vowel = Some(v_idx);
+                        return CompositionResult::CompletedWithNew(completed, None);
+                    }
+                    // 겹받침 등 초성이 될 수 없는 종성은 음절 완성 후 중성 그대로 출력
+                    let completed = self.get_current_syllable();
+                    self.clear();
+                    return CompositionResult::CompletedWithNew(completed, Some(ch));
+                }
+                (Some(_), Some(_), None) => {
+                    // 이미 중성이 있으면 현재 음절 완성 후 중성 그대로 출력
+                    let completed = self.get_current_syllable();
+                    self.clear();
+                    return CompositionResult::CompletedWithNew(completed, Some(ch));
+                }
+                (None, _, _) => {
+                    // 초성 없이 중성만 -> 그냥 출력
+                    return CompositionResult::DirectOutput(ch);
+                }
             }
         }
 
-        // 종성 처리
+        // 종성 전용 자음 처리 (ㄳ, ㄵ 등 겹받침)
         if let Some(t_idx) = self.is_trailing_consonant(ch) {
             if self.leading.is_some() && self.vowel.is_some() && self.trailing.is_none() {
                 // 초성+중성 다음 종성
@@ -155,6 +195,14 @@ impl HangulComposer {
         CompositionResult::DirectOutput(ch)
     }
 
+    /// 종성 인덱스에 해당하는 자모
+    fn trailing_char(&self, t_idx: u32) -> Option<char> {
+        TRAILING_CONSONANTS.iter()
+            .skip(1) // 빈 종성 제외
+            .find(|(_, idx)| *idx == t_idx)
+            .map(|(c, _)| *c)
+    }
+
     /// 현재 조합 상태 클리어
     pub fn clear(&mut self) {
         self.leading = None;
@@ -167,9 +215,18 @@ impl HangulComposer {
         self.leading.is_some() || self.vowel.is_some() || self.trailing.is_some()
     }
 
+    /// 마지막으로 입력한 자모 지우기 (종성 → 중성 → 초성 순)
+    /// 조합 중이 아니었으면 false
+    pub fn backspace(&mut self) -> bool {
+        if self.trailing.take().is_some() || self.vowel.take().is_some() {
+            return true;
+        }
+        self.leading.take().is_some()
+    }
+
     /// 강제로 현재 음절 완성
     pub fn flush(&mut self) -> Option<char> {
-        let result = self.get_current_syllable();
+        let result = self.pending_char();
         self.clear();
         result
     }
@@ -213,4 +270,35 @@ mod tests {
         let result = composer.get_current_syllable();
         assert_eq!(result, Some('안'));
     }
+
+    #[test]
+    fn test_trailing_consonant_moves_to_next_syllable() {
+        let mut composer = HangulComposer::new();
+        composer.input_jamo('ㅇ');
+        composer.input_jamo('ㅏ');
+        composer.input_jamo('ㄴ');
+
+        // "안" + ㅏ -> "아" 완성, "나" 조합 중
+        match composer.input_jamo('ㅏ') {
+            CompositionResult::CompletedWithNew(Some('아'), None) => (),
+            other => panic!("Expected 아 completed, got {:?}", other),
+        }
+        assert_eq!(composer.get_current_syllable(), Some('나'));
+    }
+
+    #[test]
+    fn test_backspace_removes_last_jamo() {
+        let mut composer = HangulComposer::new();
+        for jamo in ['ㅎ', 'ㅏ', 'ㄴ'] {
+            composer.input_jamo(jamo);
+        }
+
+        assert!(composer.backspace());
+        assert_eq!(composer.pending_char(), Some('하'));
+        assert!(composer.backspace());
+        assert_eq!(composer.pending_char(), Some('ㅎ'));
+        assert!(composer.backspace());
+        assert!(!composer.is_composing());
+        assert!(!composer.backspace());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,291 @@
+//! 힌트 모드 모듈
+//! 화면에 보이는 URL, 파일 경로, 스마트 선택 항목, 커밋 해시와 단어 앞에 짧은 라벨을 덮어 그리고,
+//! 라벨을 입력하면 그 항목을 열거나 복사 (마우스 없이 화면의 글자를 복사)
+//!
+//! 라벨은 모두 같은 길이라 앞부분이 겹치지 않음 - 항목이 라벨 글자 수보다 많으면 두 글자 이상
+//! 라벨을 소문자로 입력하면 기본 동작(URL/경로는 열기, 스마트 선택은 규칙의 첫 동작, 나머지는 복사),
+//! 대문자(`Shift`)로 입력하면 무엇이든 복사
+
+use crate::columns;
+use crate::mouse;
+use crate::smart::{self, SmartRule};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    Frame,
+};
+
+/// 기본 라벨 글자 (홈 행 먼저)
+pub const DEFAULT_ALPHABET: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// 단어 힌트로 쓸 최소 글자 수
+const MIN_WORD_LEN: usize = 3;
+
+/// 단어 양끝에서 떼어 낼 문장 부호
+const WORD_TRIM: &[char] = &['.', ',', ';', ':', '!', '?', '(', ')', '[', ']', '{', '}', '<', '>', '"', '\'', '`'];
+
+/// 라벨 글자 검사 - 서로 다른 소문자 영문/숫자 두 개 이상 (대문자는 복사에 씀)
+pub fn parse_alphabet(value: &str) -> Option<String> {
+    let alphabet = value.trim();
+    let valid = alphabet.chars().count() >= 2
+        && alphabet.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && alphabet.chars().enumerate().all(|(idx, c)| !alphabet.chars().take(idx).any(|other| other == c));
+    valid.then(|| alphabet.to_string())
+}
+
+/// 힌트를 골랐을 때 할 일
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HintTarget {
+    Url(String),
+    Path(String), // 줄에 나온 그대로의 경로 (줄 번호 포함, 존재 여부는 여는 쪽에서 확인)
+    Smart { rule: usize, captures: Vec<String> },
+    Text, // 복사만
+}
+
+/// 화면의 항목 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint {
+    pub label: String,
+    pub row: usize,    // 보이는 줄 번호
+    pub column: usize, // 시작 화면 열
+    pub text: String,  // 복사할 글자
+    pub target: HintTarget,
+}
+
+/// 키 입력 처리 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HintOutcome {
+    Continue,
+    Close,
+    Open(Hint), // 기본 동작
+    Copy(Hint),
+}
+
+/// 줄에서 찾은 항목 (글자 위치 `start..end`)
+struct Found {
+    start: usize,
+    end: usize,
+    text: String,
+    target: HintTarget,
+}
+
+/// 줄의 항목 - 링크, 스마트 선택, 단어 순으로 찾고 앞에서 찾은 항목과 겹치면 제외
+fn find_in_line(line: &str, rules: &[SmartRule]) -> Vec<Found> {
+    let chars: Vec<char> = line.chars().collect();
+    let text = |start: usize, end: usize| chars[start..end].iter().collect::<String>();
+    let mut found: Vec<Found> = mouse::links(line).into_iter()
+        .map(|span| Found {
+            start: span.start,
+            end: span.end,
+            text: text(span.start, span.end),
+            target: if span.is_path() { HintTarget::Path(span.url) } else { HintTarget::Url(span.url) },
+        })
+        .collect();
+    for found_match in smart::matches(rules, line) {
+        if !found.iter().any(|other| found_match.start < other.end && other.start < found_match.end) {
+            found.push(Found {
+                start: found_match.start,
+                end: found_match.end,
+                text: found_match.captures[0].clone(),
+                target: HintTarget::Smart { rule: found_match.rule, captures: found_match.captures },
+            });
+        }
+    }
+
+    // 공백으로 나눈 단어 (끝의 문장 부호를 떼고, 글자나 숫자가 있는 것만 - 커밋 해시도 여기에 포함)
+    let mut word_start = 0;
+    for idx in 0..=chars.len() {
+        if chars.get(idx).is_some_and(|c| !c.is_whitespace()) {
+            continue;
+        }
+        let mut start = word_start;
+        let mut end = idx;
+        word_start = idx + 1;
+        while start < end && WORD_TRIM.contains(&chars[start]) {
+            start += 1;
+        }
+        while end > start && WORD_TRIM.contains(&chars[end - 1]) {
+            end -= 1;
+        }
+        if end - start < MIN_WORD_LEN
+            || !chars[start..end].iter().any(|c| c.is_alphanumeric())
+            || found.iter().any(|other| start < other.end && other.start < end)
+        {
+            continue;
+        }
+        found.push(Found { start, end, text: text(start, end), target: HintTarget::Text });
+    }
+    found.sort_by_key(|found| found.start);
+    found
+}
+
+/// `count`개의 같은 길이 라벨 (`alphabet` 순서대로)
+fn labels(count: usize, alphabet: &[char]) -> Vec<String> {
+    let base = alphabet.len().max(2);
+    let mut length = 1;
+    while base.pow(length) < count {
+        length += 1;
+    }
+    (0..count)
+        .map(|mut index| {
+            let mut label = vec![alphabet[0]; length as usize];
+            for slot in label.iter_mut().rev() {
+                *slot = alphabet[index % base];
+                index /= base;
+            }
+            label.into_iter().collect()
+        })
+        .collect()
+}
+
+/// 열려 있는 힌트 모드
+#[derive(Debug, Clone)]
+pub struct HintMode {
+    hints: Vec<Hint>,
+    typed: String, // 지금까지 입력한 라벨 글자 (소문자)
+}
+
+impl HintMode {
+    /// 보이는 줄(`lines`)의 항목에 라벨 붙이기 (아래쪽 항목부터 앞 라벨)
+    pub fn new(lines: &[String], rules: &[SmartRule], alphabet: &str) -> Self {
+        let mut hints: Vec<Hint> = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            for found in find_in_line(line, rules) {
+                hints.push(Hint {
+                    label: String::new(),
+                    row,
+                    column: columns::index_to_column(line, found.start),
+                    text: found.text,
+                    target: found.target,
+                });
+            }
+        }
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let labels = labels(hints.len(), &alphabet);
+        for (hint, label) in hints.iter_mut().rev().zip(labels) {
+            hint.label = label;
+        }
+        Self { hints, typed: String::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hints.is_empty()
+    }
+
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    /// 입력한 글자로 시작하는 라벨의 항목
+    pub fn visible(&self) -> impl Iterator<Item = &Hint> {
+        self.hints.iter().filter(|hint| hint.label.starts_with(&self.typed))
+    }
+
+    /// 키 입력 처리 (라벨 글자: 고르기, 대문자: 복사, `Backspace`: 한 글자 지우기, `Esc`: 닫기)
+    /// 어떤 라벨에도 맞지 않는 글자는 무시
+    pub fn handle_key(&mut self, key: KeyEvent) -> HintOutcome {
+        let c = match key.code {
+            KeyCode::Esc => return HintOutcome::Close,
+            KeyCode::Backspace => {
+                self.typed.pop();
+                return HintOutcome::Continue;
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => c,
+            _ => return HintOutcome::Continue,
+        };
+        let copy = c.is_uppercase();
+        self.typed.extend(c.to_lowercase());
+        if self.visible().next().is_none() {
+            self.typed.pop();
+            return HintOutcome::Continue;
+        }
+        match self.hints.iter().find(|hint| hint.label == self.typed) {
+            Some(hint) if copy => HintOutcome::Copy(hint.clone()),
+            Some(hint) => HintOutcome::Open(hint.clone()),
+            None => HintOutcome::Continue,
+        }
+    }
+
+    /// 터미널 내용 영역(`area`, 테두리 안쪽)을 흐리게 하고 항목 앞에 라벨 덮어 그리기
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let buffer = f.buffer_mut();
+        buffer.set_style(area, Style::default().add_modifier(Modifier::DIM));
+        let label_style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let typed_style = Style::default().fg(Color::DarkGray).bg(Color::Yellow);
+        for hint in self.visible() {
+            if hint.row >= area.height as usize {
+                continue;
+            }
+            for (offset, ch) in hint.label.chars().enumerate() {
+                let column = hint.column + offset;
+                if column >= area.width as usize {
+                    break;
+                }
+                let style = if offset < self.typed.len() { typed_style } else { label_style };
+                if let Some(cell) = buffer.cell_mut((area.x + column as u16, area.y + hint.row as u16)) {
+                    cell.set_char(ch).set_style(style);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_labels_are_prefix_free() {
+        let alphabet: Vec<char> = "abc".chars().collect();
+        assert_eq!(labels(3, &alphabet), vec!["a", "b", "c"]);
+        assert_eq!(labels(4, &alphabet), vec!["aa", "ab", "ac", "ba"]);
+        assert_eq!(labels(10, &alphabet).len(), 10);
+        assert!(labels(10, &alphabet).iter().all(|label| label.len() == 3));
+        assert_eq!(parse_alphabet("asdf"), Some("asdf".to_string()));
+        assert_eq!(parse_alphabet("aa"), None);
+        assert_eq!(parse_alphabet("ASD"), None);
+    }
+
+    #[test]
+    fn test_find_items() {
+        let mut rule = SmartRule::named("jira");
+        rule.set("pattern", &crate::toml::Value::String(r"[A-Z]+-[0-9]+".into())).unwrap();
+        let found = find_in_line("(see https://x.io/a) PROJ-7: 1a2b3c4 한글단어, ok ──", &[rule]);
+        let items: Vec<(&str, &HintTarget)> = found.iter().map(|found| (found.text.as_str(), &found.target)).collect();
+        assert_eq!(items, vec![
+            ("see", &HintTarget::Text),
+            ("https://x.io/a", &HintTarget::Url("https://x.io/a".into())),
+            ("PROJ-7", &HintTarget::Smart { rule: 0, captures: vec!["PROJ-7".into()] }),
+            ("1a2b3c4", &HintTarget::Text),
+            ("한글단어", &HintTarget::Text),
+        ]);
+    }
+
+    #[test]
+    fn test_type_label_to_open_or_copy() {
+        let lines = vec!["open ./src/main.rs".to_string(), "tail word".to_string()];
+        let mut mode = HintMode::new(&lines, &[], "ab");
+        // 항목 4개 - 두 글자 라벨, 아래쪽 항목부터
+        let labels: Vec<(&str, &str, usize)> = mode.visible().map(|hint| (hint.label.as_str(), hint.text.as_str(), hint.column)).collect();
+        assert_eq!(labels, vec![("bb", "open", 0), ("ba", "./src/main.rs", 5), ("ab", "tail", 0), ("aa", "word", 5)]);
+
+        assert_eq!(mode.handle_key(key('x')), HintOutcome::Continue);
+        assert_eq!(mode.typed(), "");
+        assert_eq!(mode.handle_key(key('b')), HintOutcome::Continue);
+        assert_eq!(mode.visible().count(), 2);
+        let HintOutcome::Open(hint) = mode.handle_key(key('a')) else { panic!("라벨을 고르지 못함") };
+        assert_eq!(hint.target, HintTarget::Path("./src/main.rs".into()));
+
+        let mut mode = HintMode::new(&lines, &[], "ab");
+        mode.handle_key(key('a'));
+        mode.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        mode.handle_key(key('a'));
+        assert!(matches!(mode.handle_key(key('A')), HintOutcome::Copy(hint) if hint.text == "word"));
+        assert_eq!(mode.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)), HintOutcome::Close);
+    }
+}
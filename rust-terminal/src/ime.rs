@@ -0,0 +1,121 @@
+//! 입력기(IME) 처리 모듈
+//! crossterm은 IME 조합(preedit) 이벤트를 제공하지 않으므로 호스트 터미널의 OS IME는
+//! 조합이 끝난 글자만 전달함. 완성형 글자(한글 음절, 한자, 가나)가 들어오면 OS IME가
+//! 동작 중인 것으로 보고 그대로 전달하고, 낱자모가 들어오면 내장 HangulComposer로 조합
+
+use crate::hangul::{CompositionResult, HangulComposer};
+
+/// 입력기 상태
+#[derive(Debug, Default)]
+pub struct InputMethod {
+    system_ime_active: bool, // OS IME가 완성된 글자를 보내고 있는지
+    composer: HangulComposer,
+}
+
+impl InputMethod {
+    /// 문자 입력 처리 - PTY로 보낼 확정된 텍스트 반환 (조합 중이면 빈 문자열)
+    pub fn input_char(&mut self, c: char) -> String {
+        let mut committed = String::new();
+
+        if is_ime_committed_char(c) {
+            // OS IME가 조합을 마친 글자 -> 이후 낱자모도 OS IME 결과로 취급
+            self.system_ime_active = true;
+        }
+
+        if self.system_ime_active || !is_compatibility_jamo(c) {
+            committed.extend(self.composer.flush());
+            committed.push(c);
+            return committed;
+        }
+
+        match self.composer.input_jamo(c) {
+            CompositionResult::Composing => {}
+            CompositionResult::DirectOutput(ch) => {
+                committed.extend(self.composer.flush());
+                committed.push(ch);
+            }
+            CompositionResult::CompletedWithNew(completed, next) => {
+                committed.extend(completed);
+                committed.extend(next);
+            }
+        }
+        committed
+    }
+
+    /// 조합 중인 글자를 확정하여 반환
+    pub fn flush(&mut self) -> Option<char> {
+        self.composer.flush()
+    }
+
+    /// 조합 중인 글자에서 마지막 자모 지우기 (조합 중이 아니면 false - Backspace를 셸로 전송)
+    pub fn backspace(&mut self) -> bool {
+        self.composer.backspace()
+    }
+
+    /// 화면에 표시할 조합 중인 텍스트 (preedit)
+    pub fn preedit(&self) -> Option<String> {
+        if !self.composer.is_composing() {
+            return None;
+        }
+        self.composer.pending_char().map(String::from)
+    }
+}
+
+/// 한글 호환 자모 (ㄱ~ㅣ) 인지 확인
+fn is_compatibility_jamo(c: char) -> bool {
+    ('\u{3131}'..='\u{3163}').contains(&c)
+}
+
+/// OS IME가 조합을 마친 뒤에만 들어오는 글자인지 확인 (한글 음절, 한자, 가나)
+fn is_ime_committed_char(c: char) -> bool {
+    matches!(c,
+        '\u{AC00}'..='\u{D7A3}'   // 한글 음절
+        | '\u{4E00}'..='\u{9FFF}' // CJK 통합 한자
+        | '\u{3040}'..='\u{30FF}' // 히라가나, 가타카나
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_chars(ime: &mut InputMethod, input: &str) -> String {
+        input.chars().map(|c| ime.input_char(c)).collect()
+    }
+
+    #[test]
+    fn test_jamo_composed_locally() {
+        let mut ime = InputMethod::default();
+        assert_eq!(type_chars(&mut ime, "ㅎㅏㄴ"), "");
+        assert_eq!(ime.preedit(), Some("한".to_string()));
+        assert_eq!(ime.input_char(' '), "한 ");
+        assert_eq!(ime.preedit(), None);
+    }
+
+    #[test]
+    fn test_system_ime_passthrough() {
+        let mut ime = InputMethod::default();
+        // OS IME가 완성형 글자를 보내면 그대로 전달
+        assert_eq!(ime.input_char('한'), "한");
+        // 이후 낱자모도 조합하지 않고 그대로 전달
+        assert_eq!(ime.input_char('ㅋ'), "ㅋ");
+        assert_eq!(ime.preedit(), None);
+    }
+
+    #[test]
+    fn test_lone_leading_consonant_is_flushed() {
+        let mut ime = InputMethod::default();
+        assert_eq!(ime.input_char('ㄱ'), "");
+        assert_eq!(ime.flush(), Some('ㄱ'));
+    }
+
+    #[test]
+    fn test_backspace_edits_preedit() {
+        let mut ime = InputMethod::default();
+        assert_eq!(type_chars(&mut ime, "ㄱㅏㅁ"), "");
+        assert!(ime.backspace());
+        assert_eq!(ime.preedit(), Some("가".to_string()));
+        assert_eq!(type_chars(&mut ime, "ㅇ "), "강 ");
+        assert!(!ime.backspace());
+    }
+}
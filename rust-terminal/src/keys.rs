@@ -0,0 +1,479 @@
+//! 키 입력 변환 모듈
+//! crossterm 키 이벤트를 셸(PTY)로 보낼 바이트 시퀀스로 변환 (xterm 방식)
+//!
+//! - 방향키/Home/End/F1~F4: 수정 키가 있으면 `CSI 1;<수정> <글자>` (예: Ctrl+→ = `\e[1;5C`)
+//! - Insert/Delete/PageUp/PageDown/F5~F12: `CSI <번호>;<수정> ~`
+//! - Ctrl+글자는 제어 문자, Alt는 ESC 접두
+//! - 기존 인코딩으로 구분할 수 없는 조합(Ctrl+Enter, Shift+Enter, Ctrl+1 등)은 modifyOtherKeys 형식 `CSI 27;<수정>;<코드> ~`
+//!
+//! 수정 값은 1 + Shift(1) + Alt(2) + Ctrl(4)
+//!
+//! 프로그램이 kitty 키보드 프로토콜(`CSI > 플래그 u`)을 켜면 요청한 플래그에 맞춰 `CSI <코드>;<수정> u` 형식으로 보냄
+//! 애플리케이션 커서 키 모드(DECCKM, `CSI ? 1 h`)면 수정 키 없는 방향키/Home/End를 SS3(`\eOA`)로,
+//! 애플리케이션 키패드 모드(DECKPAM, `ESC =`)면 숫자 키패드를 SS3 시퀀스(`\eOp` ~ `\eOy` 등)로 보냄
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+
+/// kitty 키보드 프로토콜 단계별 개선 플래그
+pub const KITTY_DISAMBIGUATE: u8 = 0b1;      // Esc, 수정 키 조합을 구분되는 시퀀스로
+pub const KITTY_EVENT_TYPES: u8 = 0b10;      // 반복/뗌 이벤트도 보고
+pub const KITTY_ALTERNATE_KEYS: u8 = 0b100;  // Shift로 바뀐 글자도 함께 보고
+pub const KITTY_ALL_KEYS: u8 = 0b1000;       // 글자, Enter, Tab, Backspace도 모두 시퀀스로
+pub const KITTY_ASSOCIATED_TEXT: u8 = 0b10000; // 키가 입력하는 텍스트도 함께 보고
+
+/// 프로그램이 요청한 키 입력 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyboardMode {
+    /// kitty 키보드 프로토콜 플래그 (0이면 xterm 방식)
+    pub kitty_flags: u8,
+    /// 애플리케이션 커서 키 모드 (DECCKM)
+    pub app_cursor: bool,
+    /// 애플리케이션 키패드 모드 (DECKPAM)
+    pub app_keypad: bool,
+}
+
+impl KeyboardMode {
+    /// 키를 뗀 이벤트도 보내야 하는지
+    pub fn reports_release(&self) -> bool {
+        self.kitty_flags & KITTY_EVENT_TYPES != 0
+    }
+
+    /// 수정 키 없는 글자도 시퀀스로 보내야 하는지 (입력기를 거치지 않음)
+    pub fn reports_all_keys(&self) -> bool {
+        self.kitty_flags & KITTY_ALL_KEYS != 0
+    }
+}
+
+/// 현재 키 입력 방식으로 키 이벤트를 PTY로 보낼 바이트로 변환 (보낼 것이 없으면 None)
+pub fn encode_key(key: &KeyEvent, mode: KeyboardMode) -> Option<Vec<u8>> {
+    if mode.kitty_flags != 0 {
+        return kitty_key(key, mode.kitty_flags);
+    }
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    // 키패드 키는 호스트 터미널이 구분해서 보낼 때만 알 수 있음 (kitty 프로토콜을 지원하는 호스트 등)
+    if mode.app_keypad && key.state.contains(KeyEventState::KEYPAD) && key.modifiers.is_empty() {
+        if let Some(byte) = keypad_byte(key.code) {
+            return Some(vec![0x1b, b'O', byte]);
+        }
+    }
+    if mode.app_cursor && key.modifiers.is_empty() {
+        let letter = match key.code {
+            KeyCode::Up => Some(b'A'),
+            KeyCode::Down => Some(b'B'),
+            KeyCode::Right => Some(b'C'),
+            KeyCode::Left => Some(b'D'),
+            KeyCode::Home => Some(b'H'),
+            KeyCode::End => Some(b'F'),
+            _ => None,
+        };
+        if let Some(letter) = letter {
+            return Some(vec![0x1b, b'O', letter]);
+        }
+    }
+    key_to_bytes(key)
+}
+
+/// 애플리케이션 키패드 모드의 키패드 키 SS3 글자 (VT100)
+fn keypad_byte(code: KeyCode) -> Option<u8> {
+    Some(match code {
+        KeyCode::Char(c @ '0'..='9') => b'p' + (c as u8 - b'0'),
+        KeyCode::Char('.') => b'n',
+        KeyCode::Char('+') => b'k',
+        KeyCode::Char('-') => b'm',
+        KeyCode::Char('*') => b'j',
+        KeyCode::Char('/') => b'o',
+        KeyCode::Char('=') => b'X',
+        KeyCode::Enter => b'M',
+        _ => return None,
+    })
+}
+
+/// 키 이벤트를 PTY로 보낼 바이트로 변환 (보낼 것이 없는 키는 None)
+pub fn key_to_bytes(key: &KeyEvent) -> Option<Vec<u8>> {
+    let modifiers = key.modifiers;
+    let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+    let alt = modifiers.contains(KeyModifiers::ALT);
+    let param = modifier_param(modifiers);
+
+    let bytes = match key.code {
+        KeyCode::Char(c) => return char_bytes(c, modifiers),
+        KeyCode::Enter if ctrl || modifiers.contains(KeyModifiers::SHIFT) => other_key(param?, 13),
+        KeyCode::Enter => alt_prefixed(alt, b"\r"),
+        KeyCode::Tab if ctrl => other_key(param?, 9),
+        KeyCode::Tab => alt_prefixed(alt, b"\t"),
+        KeyCode::BackTab if ctrl => other_key(param?, 9),
+        KeyCode::BackTab => alt_prefixed(alt, b"\x1b[Z"),
+        // Ctrl+Backspace는 BS, 그 외는 DEL (bash/zsh에서 단어 지우기와 글자 지우기 구분)
+        KeyCode::Backspace => alt_prefixed(alt, if ctrl { b"\x08" } else { b"\x7f" }),
+        KeyCode::Esc if ctrl || modifiers.contains(KeyModifiers::SHIFT) => other_key(param?, 27),
+        KeyCode::Esc => alt_prefixed(alt, b"\x1b"),
+        KeyCode::Up => cursor_key(b'A', param),
+        KeyCode::Down => cursor_key(b'B', param),
+        KeyCode::Right => cursor_key(b'C', param),
+        KeyCode::Left => cursor_key(b'D', param),
+        KeyCode::Home => cursor_key(b'H', param),
+        KeyCode::End => cursor_key(b'F', param),
+        KeyCode::Insert => tilde_key(2, param),
+        KeyCode::Delete => tilde_key(3, param),
+        KeyCode::PageUp => tilde_key(5, param),
+        KeyCode::PageDown => tilde_key(6, param),
+        KeyCode::F(n) => function_key(n, param)?,
+        _ => return None,
+    };
+    Some(bytes)
+}
+
+/// 글자 키 (Ctrl: 제어 문자, Alt: ESC 접두, 제어 문자가 없는 Ctrl 조합은 modifyOtherKeys)
+fn char_bytes(c: char, modifiers: KeyModifiers) -> Option<Vec<u8>> {
+    let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+    let alt = modifiers.contains(KeyModifiers::ALT);
+    let mut buffer = [0; 4];
+    let text = c.encode_utf8(&mut buffer).as_bytes();
+    // Windows의 AltGr는 Ctrl+Alt로 들어오므로 AltGr로 입력하는 글자(`@`, `{`, `€` 등)는 그대로
+    if !ctrl || (alt && !c.is_ascii_alphanumeric() && c != ' ') {
+        return Some(alt_prefixed(alt && !ctrl, text));
+    }
+    if let Some(byte) = control_byte(c) {
+        return Some(alt_prefixed(alt, &[byte]));
+    }
+    c.is_ascii_graphic().then(|| other_key(modifier_param(modifiers).unwrap_or(1), c as u32))
+}
+
+/// xterm 수정 값 (수정 키가 없으면 None)
+fn modifier_param(modifiers: KeyModifiers) -> Option<u8> {
+    let mut param = 0;
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        param |= 1;
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        param |= 2;
+    }
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        param |= 4;
+    }
+    (param != 0).then_some(param + 1)
+}
+
+/// Alt를 누르고 있으면 ESC를 앞에 붙임
+fn alt_prefixed(alt: bool, bytes: &[u8]) -> Vec<u8> {
+    let mut sequence = Vec::with_capacity(bytes.len() + 1);
+    if alt {
+        sequence.push(0x1b);
+    }
+    sequence.extend_from_slice(bytes);
+    sequence
+}
+
+/// 방향키/Home/End: `CSI <글자>`, 수정 키가 있으면 `CSI 1;<수정> <글자>`
+fn cursor_key(letter: u8, param: Option<u8>) -> Vec<u8> {
+    match param {
+        Some(param) => format!("\x1b[1;{}{}", param, letter as char).into_bytes(),
+        None => vec![0x1b, b'[', letter],
+    }
+}
+
+/// 편집/기능 키: `CSI <번호> ~`, 수정 키가 있으면 `CSI <번호>;<수정> ~`
+fn tilde_key(number: u8, param: Option<u8>) -> Vec<u8> {
+    match param {
+        Some(param) => format!("\x1b[{};{}~", number, param).into_bytes(),
+        None => format!("\x1b[{}~", number).into_bytes(),
+    }
+}
+
+/// modifyOtherKeys 형식 `CSI 27;<수정>;<코드> ~`
+fn other_key(param: u8, code: u32) -> Vec<u8> {
+    format!("\x1b[27;{};{}~", param, code).into_bytes()
+}
+
+/// kitty 키보드 프로토콜 인코딩
+/// 기존 방식과 겹치지 않는 키(수정 키 없는 글자, Enter/Tab/Backspace)는 `KITTY_ALL_KEYS`가 없으면 기존 방식 그대로
+fn kitty_key(key: &KeyEvent, flags: u8) -> Option<Vec<u8>> {
+    let event_type = match key.kind {
+        KeyEventKind::Press => 1,
+        KeyEventKind::Repeat => 2,
+        KeyEventKind::Release => 3,
+    };
+    if event_type != 1 && flags & KITTY_EVENT_TYPES == 0 {
+        return None;
+    }
+    let mut modifiers = kitty_modifiers(key.modifiers);
+    let ctrl_or_alt = modifiers & 0b110 != 0;
+
+    if flags & KITTY_ALL_KEYS == 0 {
+        // 텍스트를 입력하는 키와 수정 키 없는 Enter/Tab/Backspace는 기존 방식 (뗌은 보고하지 않음)
+        let legacy = match key.code {
+            KeyCode::Char(_) => !ctrl_or_alt && modifiers & !1 == 0,
+            KeyCode::Enter | KeyCode::Tab | KeyCode::Backspace => modifiers == 0,
+            _ => false,
+        };
+        if legacy {
+            return if event_type == 3 { None } else { key_to_bytes(key) };
+        }
+    }
+
+    // (번호, 끝 글자) - 방향키 등은 `CSI 1;<수정> <글자>`, 편집 키는 `CSI <번호>;<수정> ~`
+    let (number, terminator) = match key.code {
+        KeyCode::Char(c) => (c.to_lowercase().next().unwrap_or(c) as u32, 'u'),
+        KeyCode::Enter => (13, 'u'),
+        KeyCode::Tab => (9, 'u'),
+        KeyCode::BackTab => {
+            modifiers |= 1;
+            (9, 'u')
+        }
+        KeyCode::Backspace => (127, 'u'),
+        KeyCode::Esc => (27, 'u'),
+        KeyCode::Up => (1, 'A'),
+        KeyCode::Down => (1, 'B'),
+        KeyCode::Right => (1, 'C'),
+        KeyCode::Left => (1, 'D'),
+        KeyCode::Home => (1, 'H'),
+        KeyCode::End => (1, 'F'),
+        KeyCode::Insert => (2, '~'),
+        KeyCode::Delete => (3, '~'),
+        KeyCode::PageUp => (5, '~'),
+        KeyCode::PageDown => (6, '~'),
+        KeyCode::F(1) => (1, 'P'),
+        KeyCode::F(2) => (1, 'Q'),
+        KeyCode::F(3) => (13, '~'),
+        KeyCode::F(4) => (1, 'S'),
+        KeyCode::F(n @ 5..=12) => (u32::from(function_key_number(n)), '~'),
+        _ => return None,
+    };
+
+    let mut sequence = String::from("\x1b[");
+    let mut code = number.to_string();
+    if let KeyCode::Char(c) = key.code {
+        if flags & KITTY_ALTERNATE_KEYS != 0 && c as u32 != number {
+            code = format!("{}:{}", number, c as u32); // 기본 키:Shift로 바뀐 키
+        }
+    }
+    let mut fields = vec![code];
+    if modifiers != 0 || event_type != 1 {
+        let mut field = (modifiers + 1).to_string();
+        if event_type != 1 {
+            field = format!("{}:{}", field, event_type);
+        }
+        fields.push(field);
+    }
+    if let KeyCode::Char(c) = key.code {
+        if flags & KITTY_ASSOCIATED_TEXT != 0 && flags & KITTY_ALL_KEYS != 0 && !ctrl_or_alt && event_type != 3 {
+            if fields.len() == 1 {
+                fields.push("1".to_string());
+            }
+            fields.push((c as u32).to_string());
+        }
+    }
+    // 방향키 등은 수정 값이 없으면 `CSI <글자>` (번호 1 생략)
+    if fields.len() == 1 && terminator != 'u' && terminator != '~' {
+        fields.clear();
+    }
+    sequence.push_str(&fields.join(";"));
+    sequence.push(terminator);
+    Some(sequence.into_bytes())
+}
+
+/// kitty 수정 키 비트 (Shift 1, Alt 2, Ctrl 4, Super 8, Hyper 16, Meta 32)
+fn kitty_modifiers(modifiers: KeyModifiers) -> u8 {
+    [
+        (KeyModifiers::SHIFT, 1),
+        (KeyModifiers::ALT, 2),
+        (KeyModifiers::CONTROL, 4),
+        (KeyModifiers::SUPER, 8),
+        (KeyModifiers::HYPER, 16),
+        (KeyModifiers::META, 32),
+    ]
+    .into_iter()
+    .filter(|(modifier, _)| modifiers.contains(*modifier))
+    .map(|(_, bit)| bit)
+    .sum()
+}
+
+/// Ctrl+문자 조합의 제어 문자 (Ctrl+A = 0x01 ... Ctrl+Z = 0x1a)
+/// 제어 문자가 없는 조합은 None
+pub fn control_byte(c: char) -> Option<u8> {
+    match c {
+        c if c.is_ascii_alphabetic() => Some(c.to_ascii_uppercase() as u8 & 0x1f),
+        ' ' | '@' | '2' => Some(0x00), // NUL (emacs/readline set-mark)
+        '[' | '3' => Some(0x1b),       // ESC
+        '\\' | '4' => Some(0x1c),      // FS
+        ']' | '5' => Some(0x1d),       // GS
+        '^' | '6' => Some(0x1e),       // RS
+        '/' | '_' | '7' => Some(0x1f), // US
+        '?' | '8' => Some(0x7f),       // DEL
+        _ => None,
+    }
+}
+
+/// 기능 키(F1~F12) 시퀀스 (xterm)
+/// F1~F4는 수정 키가 없으면 SS3(`\eOP`), 있으면 `CSI 1;<수정> P`
+fn function_key(n: u8, param: Option<u8>) -> Option<Vec<u8>> {
+    let number = match n {
+        1..=4 => {
+            let letter = b"PQRS"[n as usize - 1];
+            return Some(match param {
+                Some(_) => cursor_key(letter, param),
+                None => vec![0x1b, b'O', letter],
+            });
+        }
+        5..=12 => function_key_number(n),
+        _ => return None,
+    };
+    Some(tilde_key(number, param))
+}
+
+/// F5~F12의 `CSI <번호> ~` 번호
+fn function_key_number(n: u8) -> u8 {
+    match n {
+        5 => 15,
+        6..=10 => n + 11,
+        _ => n + 12,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_quoted_app_keys_are_sent_verbatim() {
+        let ctrl = KeyModifiers::CONTROL;
+        let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+
+        // 앱이 가로채는 키(종료, 검색, 스크롤, 통계)도 접두 키 다음에는 셸이 받는 바이트로 변환
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('z'), ctrl)), Some(vec![0x1a]));
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('F'), ctrl_shift)), Some(vec![0x06]));
+        assert_eq!(key_to_bytes(&key(KeyCode::PageUp, KeyModifiers::NONE)), Some(b"\x1b[5~".to_vec()));
+        assert_eq!(key_to_bytes(&key(KeyCode::F(12), KeyModifiers::NONE)), Some(b"\x1b[24~".to_vec()));
+    }
+
+    #[test]
+    fn test_special_control_combos() {
+        let ctrl = |c| key_to_bytes(&key(KeyCode::Char(c), KeyModifiers::CONTROL));
+        assert_eq!(ctrl(' '), Some(vec![0x00]));
+        assert_eq!(ctrl('['), Some(vec![0x1b]));
+        assert_eq!(ctrl('\\'), Some(vec![0x1c]));
+        assert_eq!(ctrl(']'), Some(vec![0x1d]));
+        assert_eq!(ctrl('/'), Some(vec![0x1f]));
+        assert_eq!(ctrl('c'), Some(vec![0x03]));
+        assert_eq!(ctrl('한'), None);
+    }
+
+    #[test]
+    fn test_modified_special_keys() {
+        let ctrl = KeyModifiers::CONTROL;
+        let shift = KeyModifiers::SHIFT;
+        let alt = KeyModifiers::ALT;
+        let bytes = |code, modifiers| key_to_bytes(&key(code, modifiers)).unwrap();
+
+        assert_eq!(bytes(KeyCode::Right, ctrl), b"\x1b[1;5C");
+        assert_eq!(bytes(KeyCode::Up, shift | alt), b"\x1b[1;4A");
+        assert_eq!(bytes(KeyCode::Home, shift), b"\x1b[1;2H");
+        assert_eq!(bytes(KeyCode::Delete, ctrl), b"\x1b[3;5~");
+        assert_eq!(bytes(KeyCode::PageDown, shift), b"\x1b[6;2~");
+        assert_eq!(bytes(KeyCode::F(1), KeyModifiers::NONE), b"\x1bOP");
+        assert_eq!(bytes(KeyCode::F(4), ctrl), b"\x1b[1;5S");
+        assert_eq!(bytes(KeyCode::F(5), KeyModifiers::NONE), b"\x1b[15~");
+        assert_eq!(bytes(KeyCode::F(10), shift), b"\x1b[21;2~");
+        assert_eq!(bytes(KeyCode::F(11), KeyModifiers::NONE), b"\x1b[23~");
+        assert_eq!(key_to_bytes(&key(KeyCode::F(13), KeyModifiers::NONE)), None);
+
+        // Enter/Tab/Esc 조합은 modifyOtherKeys, Alt는 ESC 접두
+        assert_eq!(bytes(KeyCode::Enter, shift), b"\x1b[27;2;13~");
+        assert_eq!(bytes(KeyCode::Enter, ctrl), b"\x1b[27;5;13~");
+        assert_eq!(bytes(KeyCode::Enter, alt), b"\x1b\r");
+        assert_eq!(bytes(KeyCode::Tab, ctrl), b"\x1b[27;5;9~");
+        assert_eq!(bytes(KeyCode::BackTab, shift), b"\x1b[Z");
+        assert_eq!(bytes(KeyCode::Backspace, ctrl), b"\x08");
+        assert_eq!(bytes(KeyCode::Backspace, alt), b"\x1b\x7f");
+    }
+
+    #[test]
+    fn test_modified_char_keys() {
+        let bytes = |c, modifiers| key_to_bytes(&key(KeyCode::Char(c), modifiers));
+        let ctrl_alt = KeyModifiers::CONTROL | KeyModifiers::ALT;
+
+        assert_eq!(bytes('b', KeyModifiers::ALT), Some(b"\x1bb".to_vec()));
+        assert_eq!(bytes('한', KeyModifiers::ALT), Some("\x1b한".as_bytes().to_vec()));
+        assert_eq!(bytes('x', ctrl_alt), Some(vec![0x1b, 0x18]));
+        // 제어 문자가 없는 Ctrl 조합
+        assert_eq!(bytes('1', KeyModifiers::CONTROL), Some(b"\x1b[27;5;49~".to_vec()));
+        assert_eq!(bytes('.', KeyModifiers::CONTROL), Some(b"\x1b[27;5;46~".to_vec()));
+        // AltGr(Ctrl+Alt)로 입력한 글자는 그대로
+        assert_eq!(bytes('@', ctrl_alt), Some(b"@".to_vec()));
+        assert_eq!(bytes('€', ctrl_alt), Some("€".as_bytes().to_vec()));
+    }
+
+    #[test]
+    fn test_kitty_keyboard_encoding() {
+        let disambiguate = KeyboardMode { kitty_flags: KITTY_DISAMBIGUATE, ..KeyboardMode::default() };
+        let encode = |code, modifiers, mode| encode_key(&key(code, modifiers), mode);
+        let ctrl = KeyModifiers::CONTROL;
+
+        // 텍스트와 수정 키 없는 Enter는 기존 방식, Esc와 Ctrl/Alt 조합은 CSI u
+        assert_eq!(encode(KeyCode::Char('a'), KeyModifiers::NONE, disambiguate), Some(b"a".to_vec()));
+        assert_eq!(encode(KeyCode::Char('A'), KeyModifiers::SHIFT, disambiguate), Some(b"A".to_vec()));
+        assert_eq!(encode(KeyCode::Enter, KeyModifiers::NONE, disambiguate), Some(b"\r".to_vec()));
+        assert_eq!(encode(KeyCode::Esc, KeyModifiers::NONE, disambiguate), Some(b"\x1b[27u".to_vec()));
+        assert_eq!(encode(KeyCode::Char('c'), ctrl, disambiguate), Some(b"\x1b[99;5u".to_vec()));
+        assert_eq!(encode(KeyCode::Char('I'), ctrl | KeyModifiers::SHIFT, disambiguate), Some(b"\x1b[105;6u".to_vec()));
+        assert_eq!(encode(KeyCode::Enter, KeyModifiers::SHIFT, disambiguate), Some(b"\x1b[13;2u".to_vec()));
+        assert_eq!(encode(KeyCode::BackTab, KeyModifiers::SHIFT, disambiguate), Some(b"\x1b[9;2u".to_vec()));
+        assert_eq!(encode(KeyCode::Up, KeyModifiers::NONE, disambiguate), Some(b"\x1b[A".to_vec()));
+        assert_eq!(encode(KeyCode::Left, ctrl, disambiguate), Some(b"\x1b[1;5D".to_vec()));
+        assert_eq!(encode(KeyCode::F(3), KeyModifiers::NONE, disambiguate), Some(b"\x1b[13~".to_vec()));
+        assert_eq!(encode(KeyCode::Delete, KeyModifiers::ALT, disambiguate), Some(b"\x1b[3;3~".to_vec()));
+
+        // 모든 키 + 이벤트 종류 + 바뀐 키 + 텍스트
+        let all = KeyboardMode {
+            kitty_flags: KITTY_DISAMBIGUATE | KITTY_EVENT_TYPES | KITTY_ALTERNATE_KEYS | KITTY_ALL_KEYS | KITTY_ASSOCIATED_TEXT,
+            ..KeyboardMode::default()
+        };
+        assert_eq!(encode(KeyCode::Char('a'), KeyModifiers::NONE, all), Some(b"\x1b[97;1;97u".to_vec()));
+        assert_eq!(encode(KeyCode::Char('A'), KeyModifiers::SHIFT, all), Some(b"\x1b[97:65;2;65u".to_vec()));
+        assert_eq!(encode(KeyCode::Enter, KeyModifiers::NONE, all), Some(b"\x1b[13u".to_vec()));
+        let mut release = key(KeyCode::Char('a'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(encode_key(&release, all), Some(b"\x1b[97;1:3u".to_vec()));
+        release.code = KeyCode::Up;
+        assert_eq!(encode_key(&release, all), Some(b"\x1b[1;1:3A".to_vec()));
+
+        // 이벤트 종류를 요청하지 않았으면 뗌은 보내지 않음
+        assert_eq!(encode_key(&release, disambiguate), None);
+        assert_eq!(encode_key(&release, KeyboardMode::default()), None);
+    }
+
+    #[test]
+    fn test_application_cursor_and_keypad_modes() {
+        let app = KeyboardMode { app_cursor: true, app_keypad: true, ..KeyboardMode::default() };
+        let encode = |code, modifiers, mode| encode_key(&key(code, modifiers), mode);
+
+        // 수정 키 없는 방향키/Home/End만 SS3, 수정 키 조합은 그대로 CSI
+        assert_eq!(encode(KeyCode::Up, KeyModifiers::NONE, app), Some(b"\x1bOA".to_vec()));
+        assert_eq!(encode(KeyCode::End, KeyModifiers::NONE, app), Some(b"\x1bOF".to_vec()));
+        assert_eq!(encode(KeyCode::Up, KeyModifiers::CONTROL, app), Some(b"\x1b[1;5A".to_vec()));
+        assert_eq!(encode(KeyCode::Up, KeyModifiers::NONE, KeyboardMode::default()), Some(b"\x1b[A".to_vec()));
+        assert_eq!(encode(KeyCode::PageUp, KeyModifiers::NONE, app), Some(b"\x1b[5~".to_vec()));
+
+        // 키패드 키는 호스트가 구분해서 보낸 경우만
+        let keypad = |code| KeyEvent::new_with_kind_and_state(code, KeyModifiers::NONE, KeyEventKind::Press, KeyEventState::KEYPAD);
+        assert_eq!(encode_key(&keypad(KeyCode::Char('7')), app), Some(b"\x1bOw".to_vec()));
+        assert_eq!(encode_key(&keypad(KeyCode::Enter), app), Some(b"\x1bOM".to_vec()));
+        assert_eq!(encode_key(&keypad(KeyCode::Char('-')), app), Some(b"\x1bOm".to_vec()));
+        assert_eq!(encode_key(&keypad(KeyCode::Char('7')), KeyboardMode::default()), Some(b"7".to_vec()));
+        assert_eq!(encode(KeyCode::Char('7'), KeyModifiers::NONE, app), Some(b"7".to_vec()));
+    }
+
+    #[test]
+    fn test_plain_keys() {
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('한'), KeyModifiers::NONE)), Some("한".as_bytes().to_vec()));
+        assert_eq!(key_to_bytes(&key(KeyCode::Enter, KeyModifiers::NONE)), Some(b"\r".to_vec()));
+        assert_eq!(key_to_bytes(&key(KeyCode::Left, KeyModifiers::NONE)), Some(b"\x1b[D".to_vec()));
+        assert_eq!(key_to_bytes(&key(KeyCode::CapsLock, KeyModifiers::NONE)), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! 창 레이아웃 저장/복원 모듈
+//! 탭과 분할 pane 구성, 각 pane의 작업 디렉토리와 셸을 파일로 저장하고
+//! 다음 실행 시 `--restore`(또는 `session.restore` 설정)로 복원
+//! 스크롤백 내용도 pane마다 텍스트로 저장하여 복원한 터미널에 다시 표시
+
+use crate::terminal::Shell;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 레이아웃 파일 형식 버전
+const LAYOUT_VERSION: u32 = 1;
+
+/// 저장된 전체 레이아웃 (탭 목록)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedLayout {
+    pub version: u32,
+    pub tabs: Vec<LayoutNode>,
+}
+
+/// 레이아웃 트리 노드
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LayoutNode {
+    /// 단일 터미널 pane
+    Pane(PaneLayout),
+    /// 분할 영역 (자식 노드들을 방향에 따라 나란히 배치)
+    Split {
+        direction: SplitDirection,
+        children: Vec<LayoutNode>,
+    },
+}
+
+/// 분할 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitDirection {
+    Horizontal, // 좌우로 나란히
+    Vertical,   // 위아래로 나란히
+}
+
+/// pane 하나의 복원 정보
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneLayout {
+    pub working_directory: PathBuf,
+    pub shell: Shell,
+    /// 저장할 때 보이던 스크롤백과 화면 내용 (오래된 줄부터, 예전 파일에는 없음)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scrollback: Vec<String>,
+}
+
+impl SavedLayout {
+    pub fn new(tabs: Vec<LayoutNode>) -> Self {
+        Self {
+            version: LAYOUT_VERSION,
+            tabs,
+        }
+    }
+
+    /// 기본 레이아웃 파일 경로 (~/.config/rust-terminal/layout.json)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rust-terminal").join("layout.json"))
+    }
+
+    /// 파일에 저장
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("레이아웃 디렉토리 생성 실패: {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("레이아웃 저장 실패: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// 파일에서 로드
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("레이아웃 읽기 실패: {}", path.display()))?;
+        let layout: Self = serde_json::from_str(&json)
+            .with_context(|| format!("레이아웃 파싱 실패: {}", path.display()))?;
+        Ok(layout)
+    }
+}
+
+impl LayoutNode {
+    /// 트리의 모든 pane을 왼쪽/위쪽부터 순서대로 반환
+    pub fn panes(&self) -> Vec<&PaneLayout> {
+        match self {
+            LayoutNode::Pane(pane) => vec![pane],
+            LayoutNode::Split { children, .. } => {
+                children.iter().flat_map(|child| child.panes()).collect()
+            }
+        }
+    }
+}
+
+impl PaneLayout {
+    /// 복원 시 사용할 작업 디렉토리 (저장된 경로가 없어졌으면 홈 디렉토리)
+    pub fn resolved_working_directory(&self) -> Option<PathBuf> {
+        if self.working_directory.is_dir() {
+            Some(self.working_directory.clone())
+        } else {
+            log::warn!("저장된 작업 디렉토리가 없음: {} - 홈 디렉토리 사용",
+                self.working_directory.display());
+            dirs::home_dir()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_round_trip() {
+        let layout = SavedLayout::new(vec![LayoutNode::Split {
+            direction: SplitDirection::Vertical,
+            children: vec![
+                LayoutNode::Pane(PaneLayout {
+                    working_directory: PathBuf::from("/tmp"),
+                    shell: Shell::System,
+                    scrollback: vec!["$ ls".to_string(), "Cargo.toml  src".to_string()],
+                }),
+                LayoutNode::Pane(PaneLayout {
+                    working_directory: PathBuf::from("/"),
+                    shell: Shell::Program("bash".to_string()),
+                    scrollback: Vec::new(),
+                }),
+            ],
+        }]);
+
+        let path = std::env::temp_dir().join(format!("rust-terminal-layout-{}.json", std::process::id()));
+        layout.save(&path).unwrap();
+        let loaded = SavedLayout::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, layout);
+        assert_eq!(loaded.tabs[0].panes().len(), 2);
+    }
+
+    #[test]
+    fn test_layout_without_scrollback_loads() {
+        let json = r#"{"version":1,"tabs":[{"Pane":{"working_directory":"/tmp","shell":"System"}}]}"#;
+        let layout: SavedLayout = serde_json::from_str(json).unwrap();
+        assert!(layout.tabs[0].panes()[0].scrollback.is_empty());
+    }
+
+    #[test]
+    fn test_missing_working_directory_falls_back_to_home() {
+        let pane = PaneLayout {
+            working_directory: PathBuf::from("/nonexistent/rust-terminal/dir"),
+            shell: Shell::System,
+            scrollback: Vec::new(),
+        };
+        assert_eq!(pane.resolved_working_directory(), dirs::home_dir());
+    }
+}
@@ -0,0 +1,52 @@
+//! rust-terminal 라이브러리 - 실행 파일(`main.rs`)의 TUI가 쓰는 터미널 코어와 보조 모듈
+//! 다른 프로그램에 터미널을 넣을 때(`terminal::TerminalBuilder::pumped`)와 벤치마크(`benches/`)에서도 사용
+
+pub mod activity;
+pub mod asciicast;
+pub mod bell;
+pub mod bindings;
+pub mod cli;
+pub mod clipboard;
+pub mod columns;
+pub mod config;
+#[cfg(unix)]
+pub mod daemon;
+pub mod editor;
+pub mod env_policy;
+pub mod events;
+pub mod export;
+pub mod hangul;
+pub mod hints;
+pub mod ime;
+pub mod keys;
+pub mod layout;
+pub mod marks;
+pub mod mouse;
+pub mod notify;
+pub mod osc;
+pub mod output_log;
+pub mod quit;
+pub mod remote;
+pub mod render;
+pub mod scroll;
+pub mod search;
+pub mod selection;
+pub mod serial;
+pub mod smart;
+pub mod snapshot;
+pub mod split;
+pub mod palette;
+pub mod panes;
+pub mod playback;
+pub mod profile;
+pub mod prompts;
+pub mod stats;
+pub mod status;
+pub mod tabs;
+pub mod telnet;
+pub mod terminal;
+pub mod theme;
+pub mod title;
+pub mod toml;
+pub mod transport;
+pub mod wakeup;
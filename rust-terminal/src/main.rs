@@ -1,51 +1,104 @@
-
-mod terminal;
-// mod hangul; // 현재 사용하지 않음
+use rust_terminal::{
+    activity,
+    asciicast,
+    bell,
+    bindings,
+    cli,
+    clipboard,
+    columns,
+    config,
+    editor,
+    events,
+    export,
+    hints,
+    ime,
+    keys,
+    layout,
+    marks,
+    mouse,
+    notify,
+    output_log,
+    quit,
+    remote,
+    render,
+    scroll,
+    search,
+    selection,
+    serial,
+    smart,
+    snapshot,
+    palette,
+    panes,
+    playback,
+    profile,
+    prompts,
+    stats,
+    status,
+    tabs,
+    telnet,
+    terminal,
+    title,
+    wakeup,
+};
+#[cfg(unix)]
+use rust_terminal::daemon;
 
 use anyhow::Result;
-use log::{info, debug, error};
+use log::{info, debug, error, warn};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind, MouseButton},
+    event::{
+        DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, KeyboardEnhancementFlags, MouseEvent, MouseEventKind, MouseButton, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    cursor::SetCursorStyle,
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::Rect,
     style::{Color, Style, Modifier},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Terminal as RatatuiTerminal,
 };
-use std::{collections::HashMap, io, sync::Arc, sync::atomic::{AtomicBool, Ordering}};
-use terminal::{Shell, Terminal, TerminalBuilder};
-// 한글 처리 모듈은 현재 사용하지 않음
-// use hangul::HangulComposer;
-
-/// 텍스트 선택 영역
-#[derive(Debug, Clone, Default)]
-struct TextSelection {
-    start_row: u16,
-    start_col: u16,
-    end_row: u16,
-    end_col: u16,
-    is_active: bool,
-}
-
-/// 커서 모양 정의 (Zed 방식)
-#[derive(Debug, Clone, Copy)]
-enum CursorShape {
-    Block,
-    Underline,
-    Beam,
-    Hollow,
-}
+use alacritty_terminal::event::Event as AlacTermEvent;
+use futures::channel::mpsc::UnboundedReceiver;
+use std::{collections::{HashMap, HashSet}, io, path::PathBuf, sync::Arc, sync::atomic::{AtomicBool, Ordering}};
+use activity::{ActivityEvent, ActivityMonitor};
+use bell::BellState;
+use bindings::Action;
+use clipboard::SystemClipboard;
+use cli::CliArgs;
+use config::{ClipboardTarget, Config, CursorShape, ExitBehavior, PasteNewline};
+use events::TermEffect;
+use export::ExportFormat;
+use hints::{HintMode, HintOutcome, HintTarget};
+use layout::{PaneLayout, SavedLayout, SplitDirection};
+use marks::{MarkCommand, Marks};
+use mouse::{ClickAction, ClickTracker, UrlSpan};
+use ime::InputMethod;
+use quit::{CloseOutcome, QuitConfirm};
+use render::ScreenSwitchSettle;
+use scroll::SmoothScroll;
+use search::SearchState;
+use selection::{SelectionMode, SelectionUnit, TextSelection};
+use snapshot::FrameSnapshot;
+use palette::{CommandPalette, PaletteEntry, PaletteOutcome};
+use panes::{PaneDirection, PaneId, Panes};
+use profile::Profile;
+use prompts::CommandBlock;
+use stats::RenderStats;
+use tabs::Tabs;
+use terminal::{ScrollPosition, ScrollState, ScrollbackSnapshot, Shell, Terminal, TerminalBuilder};
+
+/// 이보다 빨리 종료된 셸은 다시 실행하지 않음 (실행 직후 실패하는 셸의 무한 재실행 방지)
+const RESPAWN_MIN_UPTIME: std::time::Duration = std::time::Duration::from_secs(1);
 
 /// 커서 상태 정보
 #[derive(Debug, Clone)]
 struct CursorState {
     position: (u16, u16), // (col, row)
-    shape: CursorShape,
     visible: bool,
     blink_state: bool,
     last_blink: std::time::Instant,
@@ -56,7 +109,6 @@ impl Default for CursorState {
     fn default() -> Self {
         Self {
             position: (0, 0),
-            shape: CursorShape::Block,
             visible: true,
             blink_state: true,
             last_blink: std::time::Instant::now(),
@@ -65,33 +117,478 @@ impl Default for CursorState {
     }
 }
 
+/// 탭 하나의 터미널 세션 (PTY와 세션별 보기 상태)
+struct Session {
+    terminal: Terminal,
+    terminal_events: UnboundedReceiver<AlacTermEvent>, // alacritty 이벤트 수신
+    text_selection: TextSelection,
+    scroll_offset: u16,  // 스크롤 오프셋 (위로 스크롤된 줄 수)
+    total_lines: usize,  // 전체 터미널 출력 라인 수
+    auto_scroll_enabled: bool, // 커서 자동 추적 활성화
+    last_manual_scroll: std::time::Instant, // 마지막 수동 스크롤 시간
+    smooth_scroll: SmoothScroll,   // 아직 적용하지 않은 휠 스크롤 (부드러운 스크롤)
+    seen_content_version: u64, // 마지막으로 확인한 터미널 내용 버전
+    cleared_scrollback: Option<(ScrollbackSnapshot, std::time::Instant)>, // 지운 스크롤백과 되돌리기 기한
+    search: SearchState,           // 스크롤백 검색 결과
+    marks: Marks,                  // 글자별 사용자 마크
+    command_block: Option<CommandBlock>, // 프롬프트 이동으로 고른 명령 (출력 범위 강조)
+    screen_switch: Option<ScreenSwitchSettle>, // 대체 화면 전환 후 다시 그리기 보류 상태
+    exited: bool,                  // 셸이 종료됨 (백그라운드 탭 정리용)
+    held: bool,                    // 셸이 종료된 뒤 종료 안내를 표시하며 열어 둠 (키를 누르면 닫기)
+    exit_code: Option<i32>,        // 셸 프로세스의 종료 코드
+    started: std::time::Instant,   // 셸을 시작한 시각 (바로 종료되는 셸의 무한 재실행 방지)
+    reconnect_at: Option<std::time::Instant>, // 연결이 끊긴 SSH 세션을 다시 연결할 시각
+    line_cache: render::LineCache, // 지난 프레임에 만든 보이는 줄 (바뀐 줄만 다시 만듦)
+    profile: Option<Profile>,      // 팔레트에서 고른 프로필 (이 세션에서 나눈 pane/탭도 물려받음)
+    status: status::StatusCache,   // 상태 표시줄의 프로세스 이름과 git 브랜치
+    bell: bool,                    // 벨이 울린 뒤 아직 확인하지 않음 (포커스 세션은 키 입력, 나머지는 포커스를 받으면 해제)
+    activity: bool,                // 백그라운드 탭에서 조용하다가 다시 출력함
+    silence: bool,                 // 백그라운드 탭에서 오래 출력하다가 멈춤
+    monitor: ActivityMonitor,      // 활동/침묵 판단용 출력 시각
+    title: Option<String>,         // 프로그램이 OSC 0/2로 설정한 제목 (탭 이름, 포커스 세션이면 창 제목)
+}
+
+impl Session {
+    /// 새로 생성한 터미널로 세션 구성
+    fn new((mut terminal, terminal_events): (Terminal, UnboundedReceiver<AlacTermEvent>), config: &Config) -> Self {
+        terminal.set_scroll_past_end(config.scrollback.scroll_past_end_lines);
+        if config.output_log.enabled {
+            match config.output_log.directory() {
+                Some(directory) => match terminal.start_output_log(&directory, config.output_log.max_bytes()) {
+                    Ok(path) => info!("출력 기록 시작: {}", path.display()),
+                    Err(e) => warn!("출력 기록 시작 실패 ({}): {}", directory.display(), e),
+                },
+                None => warn!("출력 기록 디렉토리를 알 수 없음"),
+            }
+        }
+        Self {
+            terminal,
+            terminal_events,
+            text_selection: TextSelection::default(),
+            scroll_offset: 0,
+            total_lines: 0,
+            auto_scroll_enabled: true, // 기본적으로 자동 추적 활성화
+            last_manual_scroll: std::time::Instant::now(),
+            smooth_scroll: SmoothScroll::default(),
+            seen_content_version: 0,
+            cleared_scrollback: None,
+            search: SearchState::default(),
+            marks: Marks::default(),
+            command_block: None,
+            screen_switch: None,
+            exited: false,
+            held: false,
+            exit_code: None,
+            started: std::time::Instant::now(),
+            reconnect_at: None,
+            line_cache: render::LineCache::default(),
+            profile: None,
+            status: status::StatusCache::default(),
+            bell: false,
+            activity: false,
+            silence: false,
+            monitor: ActivityMonitor::default(),
+            title: None,
+        }
+    }
+
+    /// 셸 종료 처리 - 설정과 종료 코드에 따라 닫을 표시(`exited`), 종료 안내 표시(`held`), 또는 같은 셸 다시 실행
+    /// 실제로 한 동작을 반환 (다시 실행에 실패하거나 시작하자마자 종료된 셸은 다시 실행하지 않고 열어 둠)
+    fn on_shell_exit(&mut self, config: &Config) -> ExitBehavior {
+        // 연결이 끊긴 SSH 세션은 종료 설정과 관계없이 잠시 뒤 다시 연결
+        if let Shell::Ssh(host) = self.terminal.shell().base() {
+            if host.reconnect && self.exit_code == Some(remote::CONNECTION_LOST_CODE) {
+                info!("SSH 연결 끊김, {:?} 뒤 다시 연결: {}", remote::RECONNECT_DELAY, host.label());
+                self.held = true;
+                self.reconnect_at = Some(std::time::Instant::now() + remote::RECONNECT_DELAY);
+                return ExitBehavior::Hold;
+            }
+        }
+        let mut behavior = config.session.on_exit.for_code(self.exit_code);
+        if behavior == ExitBehavior::Respawn && self.started.elapsed() < RESPAWN_MIN_UPTIME {
+            warn!("셸이 시작하자마자 종료되어 다시 실행하지 않음");
+            behavior = ExitBehavior::Hold;
+        }
+        if behavior == ExitBehavior::Respawn {
+            if let Err(e) = self.respawn(config) {
+                warn!("셸 다시 실행 실패: {}", e);
+                behavior = ExitBehavior::Hold;
+            }
+        }
+        match behavior {
+            ExitBehavior::Hold => self.held = true,
+            ExitBehavior::Respawn => {}
+            _ => self.exited = true,
+        }
+        behavior
+    }
+
+    /// 같은 셸을 셸의 마지막 작업 디렉토리에서 다시 실행 (스크롤백, 마크 등 세션 상태는 새로 시작)
+    /// 끝난 데몬 세션에는 다시 붙지 않고 새로 실행
+    fn respawn(&mut self, config: &Config) -> Result<()> {
+        let working_directory = self.terminal.new_session_directory();
+        let shell = self.terminal.shell().base().clone();
+        info!("셸 다시 실행: {:?}", working_directory);
+        let profile = self.profile.take();
+        *self = Session::new(App::spawn_terminal(working_directory, shell, profile.as_ref(), config)?, config);
+        self.profile = profile;
+        Ok(())
+    }
+
+    /// 다시 연결할 시각이 지났으면 SSH 다시 실행 (다시 연결했으면 true)
+    fn reconnect_if_due(&mut self, config: &Config) -> bool {
+        if self.reconnect_at.is_none_or(|at| at > std::time::Instant::now()) {
+            return false;
+        }
+        self.reconnect_at = None;
+        match self.respawn(config) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("SSH 다시 연결 실패: {}", e);
+                false
+            }
+        }
+    }
+
+    /// 셸이 종료되어 열어 둔 pane의 안내 문구 (다시 연결을 기다리면 남은 시간)
+    fn exit_notice(&self) -> String {
+        match self.reconnect_at {
+            Some(at) => remote::reconnect_notice(at.saturating_duration_since(std::time::Instant::now())),
+            None => render::exit_notice(self.exit_code),
+        }
+    }
+
+    /// 탭 표시 이름 (프로필 제목, SSH 대상, 없으면 셸이 보고한 현재 디렉토리 이름이나 시작 디렉토리)
+    fn tab_title(&self) -> String {
+        if let Some(title) = self.profile.as_ref().and_then(|profile| profile.title.clone()) {
+            return title;
+        }
+        if let Some(title) = &self.title {
+            return title.clone();
+        }
+        match self.terminal.shell().base() {
+            Shell::Ssh(host) => return host.label(),
+            Shell::Serial(settings) => return settings.label(),
+            Shell::Network(target) => return target.label(),
+            _ => {}
+        }
+        self.terminal.working_directory()
+            .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "shell".to_string())
+    }
+
+    /// 데몬에서 실행 중인 셸 끝내기 (분리가 아니라 닫은 세션)
+    fn kill_daemon_session(&self) {
+        #[cfg(unix)]
+        if let Shell::Attached { session, .. } = self.terminal.shell() {
+            if let Err(e) = daemon::kill(*session) {
+                warn!("데몬 세션 {} 종료 실패: {:#}", session, e);
+            }
+        }
+    }
+
+    /// 포커스 없는 세션의 쌓인 이벤트 처리 (셸이 종료되면 `exited` 표시)
+    /// 벨, 조용하다가 다시 시작한 출력, 오래 이어지다 멈춘 출력을 알릴 일로 반환
+    fn drain_background_events(&mut self, config: &Config, clipboard: &mut SystemClipboard) -> Vec<SessionAlert> {
+        let now = std::time::Instant::now();
+        let mut alerts = Vec::new();
+        while let Ok(event) = self.terminal_events.try_recv() {
+            match events::effect_for(event, config) {
+                Some(TermEffect::WriteToPty(text)) => {
+                    let _ = self.terminal.input(text.as_bytes());
+                }
+                Some(TermEffect::StoreClipboard(text)) => {
+                    if let Err(e) = clipboard.set_text(&text) {
+                        warn!("{}", e);
+                    }
+                }
+                Some(TermEffect::ChildExited(code)) => self.exit_code = Some(code),
+                Some(TermEffect::Exit) => {
+                    info!("백그라운드 셸 종료: {} (코드 {:?})", self.tab_title(), self.exit_code);
+                    self.on_shell_exit(config);
+                }
+                Some(TermEffect::Redraw) => {
+                    alerts.extend(self.monitor.output(now, config.activity.silence()).map(SessionAlert::Activity));
+                }
+                Some(TermEffect::Bell) => {
+                    self.bell = true;
+                    alerts.push(SessionAlert::Bell);
+                }
+                Some(TermEffect::SetTitle(title)) => self.title = title,
+                None => {}
+            }
+        }
+        alerts.extend(self.monitor.check_silence(now, config.activity.silence()).map(SessionAlert::Activity));
+        alerts
+    }
+
+    /// 셸이 종료된 pane 정리 - 포커스 세션이 종료됐으면 다른 pane으로 포커스 이동
+    /// 탭의 모든 pane이 종료되면 포커스 세션의 `exited`가 남음
+    fn reap_exited(focused: Option<&mut Session>, panes: &mut Panes<Session>) {
+        let exited: Vec<PaneId> = panes.others_mut()
+            .filter(|(_, session)| session.exited)
+            .map(|(id, _)| id)
+            .collect();
+        for id in exited {
+            panes.remove(id);
+        }
+        if let Some(focused) = focused.filter(|session| session.exited) {
+            panes.close(focused);
+        }
+    }
+
+    /// 저장용 pane 정보 (스크롤백은 마지막 `scrollback_lines`줄까지)
+    fn pane_layout(&self, scrollback_lines: usize) -> PaneLayout {
+        // 셸이 OSC 7로 보고한 현재 디렉토리 우선, 없으면 시작 디렉토리
+        let working_directory = self.terminal.new_session_directory()
+            .unwrap_or_default();
+
+        PaneLayout {
+            working_directory,
+            shell: self.terminal.shell().clone(),
+            scrollback: self.terminal.scrollback_lines(scrollback_lines),
+        }
+    }
+}
+
+/// 백그라운드 탭 (포커스 pane 세션과 pane 배치)
+struct Tab {
+    session: Session,
+    panes: Panes<Session>,
+}
+
+/// 포커스 없는 세션에서 생겨 App이 알려야 할 일
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionAlert {
+    Bell,
+    Activity(ActivityEvent),
+}
+
+impl Tab {
+    /// App이 보유한 활성 탭 상태와 맞바꿈 (탭 전환용)
+    fn swap_with(&mut self, session: &mut Session, panes: &mut Panes<Session>) {
+        std::mem::swap(&mut self.session, session);
+        std::mem::swap(&mut self.panes, panes);
+    }
+
+    /// 탭의 모든 세션 (포커스 pane 포함)
+    fn sessions(&self) -> impl Iterator<Item = &Session> {
+        std::iter::once(&self.session).chain(self.panes.others().map(|(_, session)| session))
+    }
+
+    fn sessions_mut(&mut self) -> impl Iterator<Item = &mut Session> {
+        std::iter::once(&mut self.session).chain(self.panes.others_mut().map(|(_, session)| session))
+    }
+
+    /// 탭 바에서 이름 앞에 붙일 알림 표시 (벨, 활동, 침묵 순으로 하나)
+    fn badge(&self) -> &'static str {
+        if self.sessions().any(|session| session.bell) {
+            "🔔 "
+        } else if self.sessions().any(|session| session.activity) {
+            "● "
+        } else if self.sessions().any(|session| session.silence) {
+            "○ "
+        } else {
+            ""
+        }
+    }
+}
+
 /// 메인 애플리케이션 구조체
 struct App {
-    terminal: Terminal,
+    session: Session,        // 활성 탭의 포커스 pane 세션
+    panes: Panes<Session>,   // 활성 탭의 pane 배치와 나머지 pane 세션
+    tabs: Tabs<Tab>,         // 백그라운드 탭
+    window_area: Rect,   // 마지막으로 확인한 창 전체 영역
+    pane_areas: Vec<(PaneId, Rect)>, // 마지막으로 그린 pane별 영역 (포커스 이동/마우스 판정용)
     should_quit: bool,
-    text_selection: TextSelection,
+    redraw_requested: bool, // 마지막 그리기 이후 입력, 새 출력 또는 타이머로 화면이 바뀌었을 수 있음
+    input_since_draw: bool, // 마지막 그리기 이후 키/마우스 입력이 있음 (초당 그리기 제한 없이 바로 그림)
+    last_draw: std::time::Instant, // 마지막으로 화면을 그린 시각 (출력으로 인한 그리기 횟수 제한용)
     is_dragging: bool,
     shutdown_signal: Arc<AtomicBool>,
     cursor_state: CursorState,
     terminal_area: Rect, // 실제 터미널 컨텐츠 영역
-    scroll_offset: u16,  // 스크롤 오프셋 (위로 스크롤된 줄 수)
-    total_lines: usize,  // 전체 터미널 출력 라인 수
-    quit_confirm_count: u8, // Ctrl+Z 종료 확인 카운터
-    auto_scroll_enabled: bool, // 커서 자동 추적 활성화
-    last_manual_scroll: std::time::Instant, // 마지막 수동 스크롤 시간
+    scrollbar_area: Rect, // 포커스 pane의 스크롤바 영역 (클릭/드래그 판정용)
+    scrollbar_drag: Option<i32>, // 스크롤바 썸을 끄는 중이면 썸 맨 위에서 잡은 위치까지의 칸 수
+    divider_drag: Option<panes::DividerDrag>, // 마우스로 끌고 있는 pane 구분선
+    quit_confirm: QuitConfirm, // 마지막 세션 닫기(Ctrl+W/Ctrl+Z) 확인 상태
+    config: Config, // 애플리케이션 설정
+    click_tracker: ClickTracker, // 더블/트리플 클릭 판정
+    software_cursor: bool, // 소프트웨어 커서 사용 여부
+    input_method: InputMethod, // 한글 조합 / OS IME 처리
+    clipboard: SystemClipboard,    // 재사용하는 시스템 클립보드 핸들
+    stats: Option<RenderStats>,    // 성능 통계 오버레이 (None이면 측정하지 않음)
+    snapshots: Vec<FrameSnapshot>, // 저장된 화면 스냅샷 (오래된 것부터)
+    diff_snapshot: Option<usize>,  // 현재 화면과 비교 중인 스냅샷 인덱스
+    changed_cells: HashSet<(usize, usize)>, // 스냅샷과 달라진 셀 (전체 버퍼 기준 행, 열)
+    search_prompt: Option<String>, // 입력 중인 검색어 (Some이면 검색 모드)
+    palette: Option<CommandPalette>, // 열려 있는 명령 팔레트
+    hints: Option<HintMode>,         // 열려 있는 힌트 모드
+    search_browsing: bool,         // 검색어를 Enter로 확정하여 n/N으로 일치 항목 이동 중
+    quote_next: bool, // true이면 다음 키를 앱 단축키 없이 그대로 셸에 전송
+    detach_prefix: bool, // 데몬 모드에서 Ctrl+B를 눌러 다음 키(D: 분리)를 기다리는 중
+    detaching: bool,     // 분리하고 종료 중 (데몬 세션을 끝내지 않음)
+    config_error: Option<String>, // 설정 다시 읽기 실패 메시지 (기존 설정 유지 중)
+    pending_mark: Option<MarkCommand>, // 마크 이름(글자) 입력 대기 중인 명령
+    show_whitespace: bool,         // 공백/탭/줄 바꿈 위치 표시
+    hovered_url: Option<(u16, UrlSpan)>, // URL 열기 수정 키를 누른 채 가리킨 URL (전체 버퍼 행, 위치)
+    url_clicked: bool,             // 누른 버튼으로 URL을 열었음 (버튼을 뗄 때 커서 이동 생략)
+    cli: CliArgs,                  // 명령줄 값 (설정을 다시 읽어도 계속 우선)
+    bell: BellState,               // 시각 벨 반전 시각과 마지막 벨 알림 시각
+    window_focused: bool,          // 호스트 창에 포커스가 있음 (없을 때만 벨을 데스크톱 알림으로)
 }
 
 impl App {
     /// 새 애플리케이션 인스턴스 생성 (Zed 방식 사용)
-    fn new(shutdown_signal: Arc<AtomicBool>) -> Result<Self> {
-        // Zed 문서에 따른 터미널 생성
-        let working_directory = Some(std::env::current_dir()?); // 현재 실행 디렉토리 사용
-        let shell = Shell::System; // 시스템 기본 셸 사용
+    fn new(config: Config, cli: CliArgs, shutdown_signal: Arc<AtomicBool>, restore_layout: Option<SavedLayout>) -> Result<Self> {
+        // 저장된 레이아웃이 있으면 탭마다 pane 트리대로 터미널 복원
+        let mut spawn = |pane: &PaneLayout| -> Result<Session> {
+            info!("레이아웃 복원: {:?} ({}줄)", pane.working_directory, pane.scrollback.len());
+            let mut spawned = Self::spawn_terminal(pane.resolved_working_directory(), pane.shell.clone(), None, &config)?;
+            // 데몬 세션에 다시 붙었으면 데몬이 보관한 출력을 보내므로 저장된 스크롤백은 건너뜀
+            if !matches!(pane.shell, Shell::Attached { .. }) || spawned.0.shell() != &pane.shell {
+                spawned.0.replay_lines(&pane.scrollback);
+            }
+            Ok(Session::new(spawned, &config))
+        };
+        let mut restored = Vec::new();
+        for node in restore_layout.iter().flat_map(|layout| layout.tabs.iter()) {
+            if node.panes().is_empty() {
+                continue;
+            }
+            let (session, panes) = Panes::from_layout(node, &mut spawn)?;
+            restored.push(Tab { session, panes });
+        }
+        // --attach: 레이아웃에 없는 분리된 데몬 세션은 탭으로 추가 (레이아웃의 세션은 위에서 이미 붙음)
+        #[cfg(unix)]
+        if cli.attach {
+            for id in daemon::detached_sessions() {
+                let shell = Shell::Attached { session: id, shell: Box::new(config.shell.shell()) };
+                let spawned = Self::spawn_terminal(None, shell, None, &config)?;
+                restored.push(Tab { session: Session::new(spawned, &config), panes: Panes::default() });
+            }
+        }
+        let mut restored = restored.into_iter();
+        let (session, panes) = match restored.next() {
+            Some(tab) => (tab.session, tab.panes),
+            // 설정의 작업 디렉토리(없으면 현재 실행 디렉토리)와 셸 사용
+            None => {
+                let working_directory = match &config.shell.working_directory {
+                    Some(dir) => dir.clone(),
+                    None => std::env::current_dir()?,
+                };
+                let shell = cli.connect.clone().map_or_else(|| config.startup_shell(), Shell::Network);
+                let spawned = Self::spawn_terminal(Some(working_directory), shell, None, &config)?;
+                (Session::new(spawned, &config), Panes::default())
+            }
+        };
+        let mut tabs = Tabs::default();
+        restored.for_each(|tab| tabs.push_back(tab));
+
+        let software_cursor = Self::software_cursor_for(&config);
+
+        Ok(Self {
+            session,
+            panes,
+            tabs,
+            window_area: Rect::default(),
+            pane_areas: Vec::new(),
+            should_quit: false,
+            redraw_requested: true,
+            input_since_draw: false,
+            last_draw: std::time::Instant::now(),
+            is_dragging: false,
+            shutdown_signal,
+            cursor_state: CursorState::default(),
+            terminal_area: Rect::default(),
+            scrollbar_area: Rect::default(),
+            scrollbar_drag: None,
+            divider_drag: None,
+            quit_confirm: QuitConfirm::default(),
+            config,
+            click_tracker: ClickTracker::default(),
+            software_cursor,
+            input_method: InputMethod::default(),
+            clipboard: SystemClipboard::default(),
+            stats: None,
+            snapshots: Vec::new(),
+            diff_snapshot: None,
+            changed_cells: HashSet::new(),
+            search_prompt: None,
+            palette: None,
+            hints: None,
+            search_browsing: false,
+            quote_next: false,
+            detach_prefix: false,
+            detaching: false,
+            config_error: None,
+            pending_mark: None,
+            show_whitespace: false,
+            hovered_url: None,
+            url_clicked: false,
+            cli,
+            bell: BellState::default(),
+            window_focused: true,
+        })
+    }
+
+    /// 설정에 따라 소프트웨어 커서 사용 여부 결정
+    fn software_cursor_for(config: &Config) -> bool {
+        let software_cursor = config.cursor.use_software_cursor();
+        info!("커서 렌더링: {}", if software_cursor { "software" } else { "hardware" });
+        software_cursor
+    }
+
+    /// 설정 파일을 다시 읽어 적용 (색상, 스크롤, 커서 설정 등)
+    /// 설정 파일에 오류가 있으면 기존 설정을 유지하고 오류를 표시
+    fn reload_config(&mut self) {
+        match Config::try_load() {
+            Ok(mut config) => match self.cli.apply(&mut config) {
+                Ok(()) => self.apply_config(config),
+                Err(e) => {
+                    warn!("설정 다시 읽기 실패: {:#}", e);
+                    self.config_error = Some(format!("{:#}", e));
+                }
+            },
+            Err(e) => {
+                warn!("설정 다시 읽기 실패: {:#}", e);
+                self.config_error = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    /// 새 설정으로 교체 - 다음 프레임부터 새 설정으로 그려짐
+    fn apply_config(&mut self, config: Config) {
+        for session in self.all_sessions_mut() {
+            session.terminal.set_history_lines(config.scrollback.history_lines);
+            session.terminal.set_kitty_keyboard(config.keyboard.kitty);
+            session.terminal.set_scroll_past_end(config.scrollback.scroll_past_end_lines);
+            session.line_cache.invalidate(); // 색 설정이나 스마트 선택 규칙이 바뀌었을 수 있음
+            if let Err(e) = session.terminal.set_cell_size(config.cell_size) {
+                warn!("셀 크기 변경 실패: {}", e);
+            }
+            // 제목을 고정하면 프로그램이 이미 바꾼 제목도 버림
+            if config.title.lock {
+                session.title = None;
+            }
+        }
+        self.software_cursor = Self::software_cursor_for(&config);
+        self.config = config;
+        self.config_error = None;
+        self.update_host_title();
+        self.apply_hardware_cursor_blink();
+        self.apply_session_theme();
+        info!("설정 적용");
+    }
+
+    /// 새 터미널 생성 (프로필이 있으면 프로필의 환경 변수 추가)
+    fn spawn_terminal(working_directory: Option<PathBuf>, shell: Shell, profile: Option<&Profile>, config: &Config) -> Result<(Terminal, UnboundedReceiver<AlacTermEvent>)> {
         let mut env = HashMap::new();
 
         // PowerShell 프롬프트 축약을 위한 환경변수 설정
-        if let Ok(current_dir) = std::env::current_dir() {
-            let abbreviated_path = Self::abbreviate_path(&current_dir);
+        if let Some(current_dir) = working_directory.as_ref() {
+            let abbreviated_path = Self::abbreviate_path(current_dir);
             // PowerShell 함수로 프롬프트 축약 설정
             let ps_function = format!(
                 "function prompt {{ 'PS {}> ' }}",
@@ -101,177 +598,643 @@ impl App {
             env.insert("POWERSHELL_PROMPT_OVERRIDE".to_string(), ps_function);
         }
 
-        // 기본 환경 변수들 추가
+        // 호스트 환경 변수들 (설정의 환경 변수 정책으로 거름)
         for (key, value) in std::env::vars() {
             env.insert(key, value);
         }
 
+        // 세션 프로필의 환경 변수는 설정의 정책에 더함 (시작할 때 고른 프로필은 이미 설정에 들어 있음)
+        let env_policy = match profile {
+            Some(profile) => config.env.with_vars(&profile.env),
+            None => config.env.clone(),
+        };
+
         let window_id = 1; // 임의의 윈도우 ID
 
-        let builder = TerminalBuilder::new(working_directory, shell, env, window_id)?;
-        let (terminal, _events_rx) = builder.build();
+        let builder = TerminalBuilder::new(working_directory, shell, env, &env_policy, window_id, config)?;
+        Ok(builder.build())
+    }
 
-        Ok(Self {
-            terminal,
-            should_quit: false,
-            text_selection: TextSelection::default(),
-            is_dragging: false,
-            shutdown_signal,
-            cursor_state: CursorState::default(),
-            terminal_area: Rect::default(),
-            scroll_offset: 0,
-            total_lines: 0,
-            quit_confirm_count: 0,
-            auto_scroll_enabled: true, // 기본적으로 자동 추적 활성화
-            last_manual_scroll: std::time::Instant::now(),
-        })
+    /// 현재 세션의 작업 디렉토리(OSC 7)와 셸로 새 세션 생성 (새 탭 동작용)
+    fn spawn_session_in_active_directory(&self) -> Result<(Terminal, UnboundedReceiver<AlacTermEvent>)> {
+        let working_directory = self.session.terminal.new_session_directory();
+        debug!("새 세션 작업 디렉토리: {:?}", working_directory);
+        // 시리얼 장치는 한 세션만 열 수 있고 네트워크 콘솔도 대개 그러므로 새 세션은 설정의 셸
+        let shell = match self.session.terminal.shell().base() {
+            Shell::Serial(_) | Shell::Network(_) => self.config.shell.shell(),
+            shell => shell.clone(),
+        };
+        Self::spawn_terminal(working_directory, shell, self.session.profile.as_ref(), &self.config)
+    }
+
+    /// 현재 레이아웃 (저장용) - 탭마다 pane 트리
+    fn current_layout(&self) -> SavedLayout {
+        let (left, right) = self.tabs.background();
+        let scrollback_lines = self.config.session.restore_scrollback_lines;
+        let pane_layout = |session: &Session| session.pane_layout(scrollback_lines);
+        let tab_layout = |tab: &Tab| tab.panes.to_layout(&tab.session, pane_layout);
+        SavedLayout::new(left.iter().map(tab_layout)
+            .chain(std::iter::once(self.panes.to_layout(&self.session, pane_layout)))
+            .chain(right.iter().map(tab_layout))
+            .collect())
+    }
+
+    /// 모든 탭, 모든 pane의 세션
+    fn all_sessions_mut(&mut self) -> impl Iterator<Item = &mut Session> {
+        std::iter::once(&mut self.session)
+            .chain(self.panes.others_mut().map(|(_, session)| session))
+            .chain(self.tabs.background_mut().flat_map(|tab| {
+                std::iter::once(&mut tab.session).chain(tab.panes.others_mut().map(|(_, session)| session))
+            }))
+    }
+
+    /// 탭 바에 표시할 탭 이름 (화면 순서, 백그라운드 탭은 알림 표시를 앞에)
+    fn tab_titles(&self) -> Vec<String> {
+        let (left, right) = self.tabs.background();
+        let background = |tab: &Tab| format!("{}{}", tab.badge(), title::tab_label(&tab.session.tab_title()));
+        left.iter().map(background)
+            .chain(std::iter::once(title::tab_label(&self.session.tab_title())))
+            .chain(right.iter().map(background))
+            .collect()
+    }
+
+    /// 설정한 편집기로 파일을 그 줄(과 열)에서 열기 - 터미널 편집기는 현재 디렉토리의 새 탭에서 실행
+    fn open_in_editor(&mut self, file: &std::path::Path, line: Option<u32>, column: Option<u32>) {
+        let result = match editor::launch(&self.config.editor, file, line, column) {
+            editor::Launch::Uri(uri) => mouse::open_url(&uri),
+            editor::Launch::Detached { program, args } => editor::spawn_detached(&program, &args),
+            editor::Launch::Tab { program, args } => {
+                let working_directory = self.session.terminal.new_session_directory();
+                match Self::spawn_terminal(working_directory, Shell::WithArguments { program, args }, None, &self.config) {
+                    Ok(spawned) => {
+                        self.open_tab_with_session(Session::new(spawned, &self.config));
+                        Ok(())
+                    }
+                    Err(e) => Err(io::Error::other(e.to_string())),
+                }
+            }
+        };
+        match result {
+            Ok(()) => info!("편집기로 열기: {} ({:?}:{:?})", file.display(), line, column),
+            Err(e) => warn!("편집기 열기 실패: {}", e),
+        }
+    }
+
+    /// 줄에 나온 파일 경로 열기 - 상대 경로는 셸의 현재 디렉토리 기준, 줄 번호가 붙었으면 편집기로 그 줄에서
+    /// (없는 파일이면 열지 않고 false)
+    fn open_path(&mut self, path: &str) -> bool {
+        let (path, line, column) = editor::split_location(path);
+        let base = self.session.terminal.new_session_directory();
+        let file = mouse::resolve_path(path, base.as_deref());
+        if !file.exists() {
+            return false;
+        }
+        if line.is_some() {
+            self.open_in_editor(&file, line, column);
+        } else if let Err(e) = mouse::open_url(&file.to_string_lossy()) {
+            warn!("링크 열기 실패: {}", e);
+        }
+        true
+    }
+
+    /// 힌트 모드에서 고른 항목의 기본 동작 (URL/경로는 열기, 스마트 선택은 규칙의 첫 동작, 나머지와 없는 경로는 복사)
+    fn open_hint(&mut self, hint: hints::Hint) -> Result<()> {
+        let action = match hint.target {
+            HintTarget::Url(url) => Action::OpenUrl(url),
+            HintTarget::Path(path) if self.open_path(&path) => return Ok(()),
+            HintTarget::Smart { rule, captures } => match self.config.smart.get(rule) {
+                Some(rule) => rule.actions()[0].action(&captures),
+                None => Action::CopyText(hint.text),
+            },
+            HintTarget::Path(_) | HintTarget::Text => Action::CopyText(hint.text),
+        };
+        self.run_action(action)
+    }
+
+    /// 명령을 실행하는 새 탭을 열고 활성화 (작업 디렉토리는 현재 세션의 디렉토리, `label`은 로그용)
+    fn run_command_tab(&mut self, shell: Shell, label: &str) {
+        info!("명령 실행: {}", label);
+        let working_directory = self.session.terminal.new_session_directory();
+        match Self::spawn_terminal(working_directory, shell, None, &self.config) {
+            Ok(spawned) => self.open_tab_with_session(Session::new(spawned, &self.config)),
+            Err(e) => error!("명령 탭 생성 실패 ({}): {}", label, e),
+        }
+    }
+
+    /// 스마트 선택 일치 항목의 동작을 메뉴로 열기 (같은 동작은 한 번만, 항목이 없으면 열지 않음)
+    fn open_smart_menu(&mut self, found: &[smart::SmartMatch]) {
+        let mut seen = HashSet::new();
+        let entries: Vec<PaletteEntry> = found.iter()
+            .flat_map(|found| {
+                let rule = &self.config.smart[found.rule];
+                rule.actions().iter().map(move |action| PaletteEntry {
+                    title: action.title(&found.captures),
+                    name: rule.name.clone(),
+                    key: None,
+                    action: action.action(&found.captures),
+                })
+            })
+            .filter(|entry| seen.insert(entry.title.clone()))
+            .collect();
+        if entries.is_empty() {
+            debug!("스마트 선택 항목 없음");
+            return;
+        }
+        self.palette = Some(CommandPalette::menu("스마트 선택", entries));
+    }
+
+    /// 현재 세션의 작업 디렉토리로 새 탭을 열고 활성화
+    fn open_tab(&mut self) {
+        match self.spawn_session_in_active_directory() {
+            Ok(spawned) => {
+                let mut session = Session::new(spawned, &self.config);
+                session.profile = self.session.profile.clone();
+                self.open_tab_with_session(session);
+            }
+            Err(e) => error!("새 탭 생성 실패: {}", e),
+        }
+    }
+
+    /// 프로필의 셸, 환경 변수, 작업 디렉토리로 새 탭을 열고 활성화
+    /// 프로필에서 지정하지 않은 셸은 설정의 셸, 작업 디렉토리는 현재 세션의 디렉토리를 따름
+    fn open_profile_tab(&mut self, name: &str) {
+        let Some(profile) = self.config.profile(name).cloned() else {
+            warn!("알 수 없는 프로필: {}", name);
+            self.config_error = Some(format!("알 수 없는 프로필: {}", name));
+            return;
+        };
+        let working_directory = profile.working_directory.clone()
+            .or_else(|| self.session.terminal.new_session_directory());
+        let shell = profile.shell().unwrap_or_else(|| self.config.shell.shell());
+        match Self::spawn_terminal(working_directory, shell, Some(&profile), &self.config) {
+            Ok(spawned) => {
+                let mut session = Session::new(spawned, &self.config);
+                session.profile = Some(profile);
+                self.open_tab_with_session(session);
+            }
+            Err(e) => error!("프로필 {} 탭 생성 실패: {}", name, e),
+        }
+    }
+
+    /// SSH 호스트(`[ssh.이름]` 또는 `사용자@호스트:포트`)로 새 탭을 열고 활성화
+    fn open_ssh_tab(&mut self, target: &str) {
+        let host = match self.config.ssh_host(target) {
+            Ok(host) => host,
+            Err(e) => {
+                warn!("{:#}", e);
+                self.config_error = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        info!("SSH 연결: {}", host.label());
+        let working_directory = self.session.terminal.new_session_directory();
+        match Self::spawn_terminal(working_directory, Shell::Ssh(host), None, &self.config) {
+            Ok(spawned) => self.open_tab_with_session(Session::new(spawned, &self.config)),
+            Err(e) => error!("SSH 탭 생성 실패 ({}): {}", target, e),
+        }
+    }
+
+    /// 시리얼 장치로 새 탭을 열고 활성화 (속도 등은 설정의 `[serial]` 값)
+    fn open_serial_tab(&mut self, device: &str) {
+        let settings = serial::SerialSettings { device: config::expand_home(device), ..self.config.serial.clone() };
+        info!("시리얼 연결: {}", settings.label());
+        match Self::spawn_terminal(None, Shell::Serial(settings), None, &self.config) {
+            Ok(spawned) => self.open_tab_with_session(Session::new(spawned, &self.config)),
+            Err(e) => {
+                error!("시리얼 탭 생성 실패 ({}): {:#}", device, e);
+                self.config_error = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    /// telnet/TCP 대상(`호스트:포트`, `tcp://호스트:포트`)으로 새 탭을 열고 활성화
+    fn open_network_tab(&mut self, target: &str) {
+        let target = match telnet::NetworkTarget::parse(target) {
+            Ok(target) => target,
+            Err(e) => {
+                warn!("{:#}", e);
+                self.config_error = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        info!("네트워크 연결: {}", target.label());
+        match Self::spawn_terminal(None, Shell::Network(target), None, &self.config) {
+            Ok(spawned) => self.open_tab_with_session(Session::new(spawned, &self.config)),
+            Err(e) => {
+                error!("네트워크 탭 생성 실패: {:#}", e);
+                self.config_error = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    /// 만든 세션으로 새 탭을 열고 활성화
+    fn open_tab_with_session(&mut self, session: Session) {
+        let tab = Tab { session, panes: Panes::default() };
+        self.tabs.open(tab, |tab| tab.swap_with(&mut self.session, &mut self.panes));
+        self.reset_view_for_session_switch();
+        self.resize_sessions_to_window();
+        info!("새 탭: {}/{}", self.tabs.active_index() + 1, self.tabs.len());
+    }
+
+    /// 활성 탭 닫기 (마지막 탭이면 앱 종료)
+    fn close_active_tab(&mut self) {
+        match self.tabs.close(|tab| tab.swap_with(&mut self.session, &mut self.panes)) {
+            Some(mut closed) => {
+                debug!("탭 닫기: {}", closed.session.tab_title());
+                closed.session.kill_daemon_session();
+                closed.panes.others_mut().for_each(|(_, session)| session.kill_daemon_session());
+                self.reset_view_for_session_switch();
+            }
+            None => {
+                info!("마지막 탭 닫기 - 앱 종료");
+                self.should_quit = true;
+            }
+        }
+    }
+
+    /// 다음(`forward`) 또는 이전 탭으로 전환
+    fn cycle_tab(&mut self, forward: bool) {
+        if self.tabs.len() < 2 {
+            return;
+        }
+        self.commit_preedit();
+        let activate = |tab: &mut Tab| tab.swap_with(&mut self.session, &mut self.panes);
+        if forward {
+            self.tabs.next(activate);
+        } else {
+            self.tabs.prev(activate);
+        }
+        self.reset_view_for_session_switch();
+        debug!("탭 전환: {}/{}", self.tabs.active_index() + 1, self.tabs.len());
+    }
+
+    /// 포커스 pane을 나눠 현재 작업 디렉토리로 새 pane 열기
+    fn split_pane(&mut self, direction: SplitDirection) {
+        match self.spawn_session_in_active_directory() {
+            Ok(spawned) => {
+                self.commit_preedit();
+                let mut session = Session::new(spawned, &self.config);
+                session.profile = self.session.profile.clone();
+                let id = self.panes.split(direction, &mut self.session, session);
+                self.reset_view_for_session_switch();
+                self.resize_sessions_to_window();
+                info!("pane 분할 ({:?}): pane {} / {}개", direction, id, self.panes.len());
+            }
+            Err(e) => error!("pane 생성 실패: {}", e),
+        }
+    }
+
+    /// 포커스 pane 닫기 (탭의 마지막 pane이면 탭 닫기)
+    fn close_focused_pane(&mut self) {
+        match self.panes.close(&mut self.session) {
+            Some(closed) => {
+                debug!("pane 닫기: {}", closed.tab_title());
+                closed.kill_daemon_session();
+                self.reset_view_for_session_switch();
+            }
+            None => self.close_active_tab(),
+        }
+    }
+
+    /// 지정한 pane으로 포커스 이동
+    fn focus_pane(&mut self, id: PaneId) {
+        self.commit_preedit();
+        if self.panes.focus(id, &mut self.session) {
+            self.reset_view_for_session_switch();
+            debug!("pane 포커스: {}", id);
+        }
+    }
+
+    /// 방향으로 이웃 pane에 포커스 이동
+    fn focus_pane_toward(&mut self, direction: PaneDirection) {
+        if let Some(id) = self.panes.neighbor(&self.pane_areas, direction) {
+            self.focus_pane(id);
+        }
+    }
+
+    /// 탭/pane이 바뀌면 이전 세션 화면에 묶인 앱 상태 해제
+    fn reset_view_for_session_switch(&mut self) {
+        self.is_dragging = false;
+        self.hovered_url = None;
+        self.search_prompt = None;
+        self.pending_mark = None;
+        self.hints = None;
+        self.diff_snapshot = None;
+        self.changed_cells.clear();
+        self.quit_confirm = QuitConfirm::default();
+        // 보게 된 탭의 벨/활동/침묵 표시 해제
+        for session in std::iter::once(&mut self.session).chain(self.panes.others_mut().map(|(_, session)| session)) {
+            session.bell = false;
+            session.activity = false;
+            session.silence = false;
+        }
+        self.update_host_title();
+        self.apply_session_theme();
+    }
+
+    /// 호스트 터미널 창 제목을 포커스 세션의 제목으로 (제목이 없으면 설정한 기본 제목)
+    fn update_host_title(&self) {
+        let title = title::host_title(self.session.title.as_deref().unwrap_or(""), &self.config.title.fallback);
+        let _ = execute!(io::stdout(), SetTitle(title));
+    }
+
+    /// 포커스 세션의 프로필 테마로 색 바꾸기 (프로필 테마가 없으면 설정의 테마)
+    /// 테마는 화면 전체에 하나이므로 포커스 없는 pane도 포커스 세션의 테마로 그림
+    fn apply_session_theme(&mut self) {
+        let name = self.session.profile.as_ref()
+            .and_then(|profile| profile.theme.as_deref())
+            .unwrap_or(&self.config.colors.theme_name);
+        let Some(theme) = self.config.colors.find_theme(name) else {
+            return;
+        };
+        if theme != self.config.colors.theme {
+            debug!("세션 테마: {}", theme.name);
+            self.config.colors.theme = theme;
+            for session in self.all_sessions_mut() {
+                session.line_cache.invalidate();
+            }
+        }
+    }
+
+    /// 창 크기에 맞춰 모든 탭, 모든 pane의 터미널 크기 조정 (PTY에 새 창 크기 전달)
+    /// 백그라운드 탭도 바로 맞춰 두어야 전환 전에도 프로그램이 올바른 크기로 그림
+    fn resize_sessions_to_window(&mut self) {
+        if self.window_area.is_empty() {
+            return;
+        }
+        let main_area = render::screen_layout(self.window_area, self.config.banner.height(), self.config.status_bar.is_enabled(), &self.config.width).main;
+        Self::resize_tab(&mut self.session, &mut self.panes, main_area);
+        for tab in self.tabs.background_mut() {
+            Self::resize_tab(&mut tab.session, &mut tab.panes, main_area);
+        }
+    }
+
+    /// 탭 하나의 pane 배치대로 터미널 크기 조정 (포커스 pane은 스크롤바 칸 제외)
+    fn resize_tab(session: &mut Session, panes: &mut Panes<Session>, main_area: Rect) {
+        let focused = panes.focused();
+        for (id, area) in panes.layout(main_area) {
+            let (terminal, bounds) = match panes.others_mut().find(|(other, _)| *other == id) {
+                Some((_, other)) => (&mut other.terminal, render::pane_terminal_bounds(area, false)),
+                None if id == focused => (&mut session.terminal, render::pane_terminal_bounds(area, true)),
+                None => continue,
+            };
+            match terminal.resize(bounds) {
+                Ok(true) => debug!("터미널 크기 변경: {}x{}", bounds.num_cols, bounds.num_lines),
+                Ok(false) => {}
+                Err(e) => warn!("터미널 크기 변경 실패: {}", e),
+            }
+        }
     }
 
     /// 메인 실행 루프
     fn run<B: ratatui::backend::Backend>(&mut self, ratatui_terminal: &mut RatatuiTerminal<B>) -> Result<()> {
+        self.apply_hardware_cursor_blink();
+
+        // 첫 프롬프트가 기본 크기(80x24)로 그려지지 않도록 시작하자마자 창 크기에 맞춤
+        let size = ratatui_terminal.size()?;
+        self.window_area = Rect::new(0, 0, size.width, size.height);
+        self.resize_sessions_to_window();
+        self.update_host_title();
+
+        // 입력과 터미널 출력 알림을 한 채널에서 기다림 (고정 간격 폴링 없음)
+        let loop_events = wakeup::start();
 
         loop {
-            // 화면 그리기
-            ratatui_terminal.draw(|f| {
-                // 전체 영역을 상단 정보 패널과 메인 영역으로 분할
-                let top_chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints([Constraint::Length(1), Constraint::Min(10)].as_ref()) // 정보 패널 1줄 + 터미널 영역
-                    .split(f.area());
-
-                let info_panel_area = top_chunks[0];
-                let main_area = top_chunks[1];
-
-                // 메인 영역을 터미널과 스크롤바로 분할
-                let main_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Min(10), Constraint::Length(1)].as_ref()) // 터미널 영역 + 스크롤바 1칸
-                    .split(main_area);
-
-                // 실제 터미널 컨텐츠 영역 저장 (스크롤바 제외)
-                self.terminal_area = main_chunks[0];
-                let scrollbar_area = main_chunks[1];
-
-                // 터미널 커서 위치 가져오기 및 상태 업데이트
-                let (cursor_col, cursor_row, cursor_char) = self.terminal.get_renderable_cursor();
-                self.cursor_state.position = (cursor_col, cursor_row);
-                self.cursor_state.character = cursor_char;
-
-                // 터미널 내용을 줄별로 가져오기 (선택 영역 하이라이트 포함)
-                let all_lines = match self.terminal.get_renderable_content() {
-                    Ok(content_lines) => {
-                        // 전체 라인 수 업데이트
-                        self.total_lines = content_lines.len();
-
-                        // 전체 라인들을 스크롤 오프셋과 함께 렌더링
-                        let all_lines_with_selection = content_lines.into_iter()
-                            .enumerate()
-                            .map(|(row_idx, line)| self.render_line_with_selection(line, row_idx as u16))
-                            .collect::<Vec<_>>();
-                        all_lines_with_selection
-                    },
-                    Err(_) => vec![Line::from(Span::raw("터미널 내용 로딩 중..."))]
-                };
+            // 터미널 이벤트 처리 (alacritty events)
+            self.process_terminal_events();
+
+            self.advance_smooth_scroll();
+
+            // 대체 화면 전환 중에는 중간 프레임을 그리지 않음 (입력/이벤트 처리는 계속)
+            let defer_draw = self.defer_draw_for_screen_switch();
+
+            // 입력, 새 출력, 타이머 중 하나라도 있었을 때만 다시 그림
+            // 출력만 쏟아지는 동안에는 설정한 초당 횟수까지만 그리고, 그 사이 출력은 다음 프레임에 한꺼번에 반영
+            if !defer_draw && self.redraw_requested && self.next_frame_delay().is_none() {
+                self.redraw_requested = false;
+                self.input_since_draw = false;
+                self.last_draw = std::time::Instant::now();
+
+                // 통계 오버레이가 켜져 있을 때만 측정
+                let frame_start = self.stats.is_some().then(std::time::Instant::now);
+
+                // 화면 그리기
+                ratatui_terminal.draw(|f| {
+                    // 전체 영역을 배너, 상단 정보 패널, 탭 바, 메인 영역으로 분할
+                    // (배너 줄은 terminal_area 밖이므로 커서/마우스 좌표 계산에 포함되지 않음)
+                    // 최대 열 수를 설정했으면 terminal_area가 줄어든 영역 기준이므로 마우스/커서 좌표 계산도 함께 이동
+                    self.window_area = f.area();
+                    let screen = render::screen_layout(self.window_area, self.config.banner.height(), self.config.status_bar.is_enabled(), &self.config.width);
+                    let (banner_area, info_panel_area, tab_bar_area, main_area) =
+                        (screen.banner, screen.info_panel, screen.tab_bar, screen.main);
+
+                    if let Some(text) = &self.config.banner.text {
+                        let banner = Paragraph::new(text.as_str())
+                            .style(self.config.banner.style)
+                            .alignment(ratatui::layout::Alignment::Center);
+                        f.render_widget(banner, banner_area);
+                    }
 
-                // 스크롤 오프셋을 적용하여 보여줄 라인들만 선택
-                let visible_height = self.terminal_area.height.saturating_sub(2) as usize;
-                let start_idx = self.scroll_offset as usize;
-                let end_idx = (start_idx + visible_height).min(all_lines.len());
+                    // 메인 영역을 pane별로 나누고, 포커스 없는 pane은 내용만 간단히 그림
+                    self.pane_areas = self.panes.layout(main_area);
+                    let focused_pane = self.panes.focused();
+                    let broadcast = self.panes.broadcast() && self.panes.len() > 1;
+                    let mut focused_area = main_area;
+                    for &(id, area) in &self.pane_areas {
+                        match self.panes.others_mut().find(|(other, _)| *other == id) {
+                            Some((_, session)) => Self::render_unfocused_pane(f, session, area, broadcast, &self.config),
+                            None if id == focused_pane => focused_area = area,
+                            None => {}
+                        }
+                    }
 
-                let lines = if start_idx < all_lines.len() {
-                    all_lines[start_idx..end_idx].to_vec()
-                } else {
-                    vec![]
-                };
+                    // 포커스 pane 영역을 터미널과 스크롤바로 분할
+                    // 실제 터미널 컨텐츠 영역 저장 (스크롤바 제외)
+                    let (terminal_area, scrollbar_area) = render::split_scrollbar(focused_area);
+                    self.terminal_area = terminal_area;
+                    self.scrollbar_area = scrollbar_area;
+
+                    // 터미널 크기를 테두리 안쪽 영역에 맞춤 (크기가 같으면 아무것도 하지 않음)
+                    let inner_bounds = render::pane_terminal_bounds(focused_area, true);
+                    match self.session.terminal.resize(inner_bounds) {
+                        Ok(true) => debug!("터미널 크기 변경: {}x{}", inner_bounds.num_cols, inner_bounds.num_lines),
+                        Ok(false) => {}
+                        Err(e) => warn!("터미널 크기 변경 실패: {}", e),
+                    }
 
-                // 선택 영역 상태 표시 추가
-                let selection_info = if self.text_selection.is_active {
-                    format!(" [선택: {}]", if self.is_dragging { "진행중" } else { "완료" })
-                } else {
-                    String::new()
-                };
+                    // 터미널 커서 위치 가져오기 및 상태 업데이트
+                    let (cursor_col, cursor_row, cursor_char) = self.session.terminal.get_renderable_cursor();
+                    self.cursor_state.position = (cursor_col, cursor_row);
+                    self.cursor_state.character = cursor_char;
+
+                    // 스크롤 상태는 Terminal(그리드 display offset)이 기준
+                    let scroll_state = self.sync_scroll_offset();
+                    let visible_height = self.terminal_area.height.saturating_sub(2) as usize;
+
+                    // 상태 표시줄 내용 (모드 안내, 프로세스, 디렉토리, git 브랜치, 스크롤 위치, 알림 표시)
+                    let status_values = self.status_values(&scroll_state, broadcast);
+
+                    // 현재 보기 위치의 화면 내용만 가져와서 렌더링 (선택 영역 하이라이트 포함)
+                    let first_row = scroll_state.first_visible_row();
+                    self.update_snapshot_diff(first_row);
+                    let skip = (self.session.scroll_offset as usize).saturating_sub(first_row);
+                    // 지난 프레임 뒤 바뀐 줄만 그리드에서 다시 읽어 만들고, 나머지는 캐시한 줄을 씀
+                    self.session.line_cache.refresh(&self.session.terminal, &self.config.colors, &self.config.smart);
+                    let lines = self.session.line_cache.rows()
+                        .iter()
+                        .enumerate()
+                        .skip(skip)
+                        .take(visible_height)
+                        .map(|(idx, row)| {
+                            let line = self.render_line_with_selection(row, (first_row + idx) as u16);
+                            // 프롬프트 이동으로 고른 명령의 출력은 배경색으로 강조
+                            let in_block = self.session.command_block
+                                .and_then(|block| block.output_rows())
+                                .is_some_and(|rows| rows.contains(&(first_row + idx)));
+                            if in_block { line.patch_style(render::command_block_style()) } else { line }
+                        })
+                        .collect::<Vec<_>>();
+
+                    // 현재 작업 디렉토리 정보 - 셸이 OSC 7로 보고한 경로 우선
+                    let current_dir = self.session.terminal.new_session_directory()
+                        .map(|path| path.to_string_lossy().to_string())
+                        .unwrap_or_default();
+
+                    // 정보 패널을 한 줄로 컴팩트하게 렌더링
+                    let info_text = format!("📁 {}", current_dir);
+                    let info_panel = Paragraph::new(info_text)
+                        .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+                        .alignment(ratatui::layout::Alignment::Center);
+
+                    f.render_widget(info_panel, info_panel_area);
+
+                    // 탭 바
+                    let tab_titles = self.tab_titles();
+                    f.render_widget(Paragraph::new(render::tab_bar_line(&tab_titles, self.tabs.active_index())), tab_bar_area);
+
+                    // 하단 상태 표시줄
+                    if self.config.status_bar.is_enabled() {
+                        let status_line = status::status_line(&self.config.status_bar, &status_values, screen.status_bar.width);
+                        f.render_widget(Paragraph::new(status_line).style(status::bar_style()), screen.status_bar);
+                    }
 
-                // 스크롤 위치 정보 (항상 표시)
-                let scroll_info = {
-                    let scroll_percentage = if self.total_lines > visible_height && self.total_lines > 0 {
-                        (self.scroll_offset as f32 / (self.total_lines.saturating_sub(visible_height)) as f32 * 100.0) as u16
+                    // 테두리 제목은 탭 이름 (상태 표시줄을 껐으면 모드 안내와 알림 표시도 제목에)
+                    let title = if self.config.status_bar.is_enabled() {
+                        format!(" {} ", self.session.tab_title())
                     } else {
-                        0
+                        format!(" {}{} ", self.session.tab_title(), status_values.title_suffix())
                     };
-                    format!(" [라인:{} 표시:{} 오프셋:{} ({}%)]",
-                        self.total_lines, visible_height, self.scroll_offset, scroll_percentage)
-                };
+                    let paragraph = Paragraph::new(lines)
+                        .block(Block::default()
+                            .title(title)
+                            .borders(Borders::ALL))
+                            .style(Style::default().bg(self.config.colors.background()));
+
+                    // 빈 줄/빈 칸까지 설정한 배경색으로 채운 뒤 내용 그리기
+                    render::fill_background(f, self.terminal_area, self.config.colors.background());
+                    f.render_widget(paragraph, self.terminal_area);
+                    if self.session.held {
+                        let inner = Block::default().borders(Borders::ALL).inner(self.terminal_area);
+                        render::draw_exit_notice(f, inner, &self.session.exit_notice());
+                    }
 
-                // 커서 디버그 정보
-                let cursor_debug = format!(" [커서:{}x{} 절대:{} 상대:{}]",
-                    cursor_col, cursor_row,
-                    if self.total_lines > visible_height { (self.total_lines - visible_height) as u16 + cursor_row } else { cursor_row },
-                    if self.total_lines > visible_height {
-                        let abs_row = (self.total_lines - visible_height) as u16 + cursor_row;
-                        if abs_row >= self.scroll_offset { abs_row - self.scroll_offset } else { 0 }
-                    } else { cursor_row }
-                );
+                    // 공백 표시 모드: 자동 줄 바꿈된 줄의 오른쪽 테두리에 마커
+                    if self.show_whitespace {
+                        let wraps: Vec<bool> = self.session.terminal.get_visible_wraps().into_iter().skip(skip).collect();
+                        let inner = Block::default().borders(Borders::ALL).inner(self.terminal_area);
+                        render::mark_wrapped_rows(f, inner, &wraps);
+                    }
 
-                // 종료 상태 메시지
-                let quit_status = if self.quit_confirm_count > 0 {
-                    " [Ctrl+Z로 다시 누르면 종료됩니다]"
-                } else {
-                    ""
-                };
+                    // 화면 위/아래에 더 볼 내용이 있으면 가장자리 표시
+                    if self.config.scrollback.edge_indicators {
+                        render::draw_scroll_edges(f, self.terminal_area,
+                            scroll_state.has_content_above(), scroll_state.has_content_below(),
+                            self.config.scrollback.edge_indicator_style);
+                    }
 
-                // 현재 작업 디렉토리 정보 (축약된 형태)
-                let (current_dir_short, current_dir_full) = std::env::current_dir()
-                    .map(|path| {
-                        let short = format!(" [{}]", Self::abbreviate_path(&path));
-                        let full = format!(" [{}]", path.to_string_lossy());
-                        (short, full)
-                    })
-                    .unwrap_or_else(|_| (String::new(), String::new()));
+                    // 시각 벨: 포커스 pane의 내용을 잠깐 반전
+                    if self.bell.flashing(std::time::Instant::now()) {
+                        let inner = Block::default().borders(Borders::ALL).inner(self.terminal_area);
+                        render::flash_visual_bell(f, inner);
+                    }
 
-                // 정보 패널을 한 줄로 컴팩트하게 렌더링
-                let info_text = format!("📁 {}", &current_dir_full[2..current_dir_full.len()-1]);
-                let info_panel = Paragraph::new(info_text)
-                    .style(Style::default().bg(Color::DarkGray).fg(Color::White))
-                    .alignment(ratatui::layout::Alignment::Center);
+                    // 스크롤바 렌더링
+                    self.render_scrollbar(f, scrollbar_area);
 
-                f.render_widget(info_panel, info_panel_area);
+                    // 커서 표시 (하드웨어 커서를 신뢰할 수 없으면 직접 그림)
+                    if self.software_cursor {
+                        self.update_cursor_state();
+                        self.render_software_cursor(f);
+                    } else {
+                        self.set_terminal_cursor_position(f);
+                    }
 
-                let paragraph = Paragraph::new(lines)
-                    .block(Block::default()
-                        .title(format!("Rust Terminal{}{}{}{}{} - 마우스휠/PageUp/Down: 스크롤, Ctrl+Z: 종료",
-                            current_dir_short, selection_info, scroll_info, cursor_debug, quit_status))
-                        .borders(Borders::ALL))
-                        .style(Style::default().bg(Color::Black));
+                    // 조합 중인 글자(preedit)를 커서 위치에 표시
+                    self.render_preedit(f);
 
-                f.render_widget(paragraph, main_chunks[0]);
+                    // 성능 통계 오버레이
+                    self.render_stats_overlay(f);
 
-                // 스크롤바 렌더링
-                self.render_scrollbar(f, scrollbar_area);
+                    // 힌트 모드 라벨 (포커스 pane의 내용 위)
+                    if let Some(hints) = &self.hints {
+                        let inner = Block::default().borders(Borders::ALL).inner(self.terminal_area);
+                        hints.draw(f, inner);
+                    }
 
-                // 실제 터미널 커서 위치로 이동
-                self.set_terminal_cursor_position(f);
-            })?;
+                    // 명령 팔레트는 다른 모든 것 위에
+                    if let Some(palette) = &self.palette {
+                        palette.draw(f, self.window_area);
+                    }
+                })?;
 
-            // 터미널 이벤트 처리 (alacritty events)
-            // 향후 터미널 출력 변경사항을 처리할 수 있음
+                if let (Some(frame_start), Some(render_stats)) = (frame_start, self.stats.as_mut()) {
+                    let render_time = frame_start.elapsed();
+                    render_stats.record_frame(std::time::Instant::now(), render_time, stats::take_lock_wait(), stats::pty_bytes());
+                }
+            }
 
-            // 키보드 및 마우스 이벤트 처리
-            if event::poll(std::time::Duration::from_millis(50))? {
-                match event::read()? {
-                    Event::Key(key) if key.kind == KeyEventKind::Press => {
-                        self.handle_key_event(key)?;
-                    }
-                    Event::Mouse(mouse) => {
-                        self.handle_mouse_event(mouse)?;
+            // 키보드 및 마우스 입력이나 터미널 출력 알림이 올 때까지, 또는 다음 타이머 시각까지 잠듦
+            match loop_events.wait(self.next_wakeup(defer_draw)) {
+                Some(wakeup::LoopEvent::Input(event)) => {
+                    self.redraw_requested = true;
+                    self.input_since_draw = true;
+                    match event {
+                        // 뗌 이벤트는 kitty 키보드 프로토콜로 요청한 프로그램에만 전달
+                        Event::Key(key) if key.kind != KeyEventKind::Release || self.session.terminal.keyboard_mode().reports_release() => {
+                            self.handle_key_event(key)?;
+                        }
+                        Event::Mouse(mouse) => {
+                            self.handle_mouse_event(mouse)?;
+                        }
+                        Event::Resize(width, height) => {
+                            // 다음 그리기를 기다리지 않고 모든 탭의 PTY에 새 크기 알림
+                            self.window_area = Rect::new(0, 0, width, height);
+                            self.resize_sessions_to_window();
+                        }
+                        Event::FocusGained => self.window_focused = true,
+                        Event::FocusLost => {
+                            // 다른 창으로 전환하면 조합 중인 글자 확정
+                            self.commit_preedit();
+                            self.window_focused = false;
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
+                // 어느 세션에서든 출력/이벤트가 생김 - 다음 반복 처음에 쌓인 이벤트를 처리하고 다시 그림
+                Some(wakeup::LoopEvent::Terminal) => self.redraw_requested = true,
+                // 타이머 시각 (커서 깜빡임, 부드러운 스크롤, 화면 전환 대기, 남은 시간 표시 등)
+                None => self.redraw_requested = true,
             }
 
+            // 새 출력으로 내용이 바뀌었으면 오래된 선택 영역 해제
+            self.clear_stale_selection();
+
             // 자동 스크롤 상태 업데이트 (3초 후 추적 재활성화만)
             self.update_auto_scroll();
 
+            // 기한이 지난 스크롤백 되돌리기 정보 해제
+            self.expire_scrollback_undo();
+
             // 종료 신호 확인
             if self.should_quit || self.shutdown_signal.load(Ordering::Relaxed) {
                 break;
@@ -281,182 +1244,739 @@ impl App {
         Ok(())
     }
 
+    /// 출력으로 인한 다시 그리기를 미뤄야 하면 남은 시간 (입력이 있었거나 초당 그리기 제한이 없으면 None)
+    fn next_frame_delay(&self) -> Option<std::time::Duration> {
+        if self.input_since_draw {
+            return None;
+        }
+        let interval = self.config.render.frame_interval()?;
+        Some(interval.saturating_sub(self.last_draw.elapsed())).filter(|delay| !delay.is_zero())
+    }
+
+    /// 입력이 없을 때 다음으로 깨어날 때까지 기다릴 시간
+    /// 그리기를 미루는 중이면 전환이 끝났는지 곧 다시 확인, 부드러운 스크롤 중이면 다음 프레임 간격,
+    /// 직접 그리는 커서가 깜빡이면 다음 깜빡임까지, 초당 그리기 제한으로 미룬 그리기가 있으면 다음 프레임까지,
+    /// 시각 벨로 반전 중이면 되돌릴 때까지, 백그라운드 탭의 출력이 멈췄는지 확인할 때까지
+    fn next_wakeup(&self, defer_draw: bool) -> std::time::Duration {
+        let blink = (self.software_cursor && self.config.cursor.blink).then(|| {
+            let interval = std::time::Duration::from_millis(self.config.cursor.blink_interval_ms + 1);
+            interval.saturating_sub(self.cursor_state.last_blink.elapsed())
+        });
+        wakeup::wait_timeout([
+            defer_draw.then(|| std::time::Duration::from_millis(5)),
+            self.session.smooth_scroll.is_active().then_some(scroll::FRAME_INTERVAL),
+            blink,
+            self.redraw_requested.then(|| self.next_frame_delay()).flatten(),
+            self.session.reconnect_at.map(|at| at.saturating_duration_since(std::time::Instant::now())),
+            self.bell.flash_remaining(std::time::Instant::now()),
+            self.next_silence_check(),
+        ])
+    }
+
+    /// 백그라운드 탭의 다음 침묵 확인까지 남은 시간
+    fn next_silence_check(&self) -> Option<std::time::Duration> {
+        let silence = self.config.activity.silence();
+        let (left, right) = self.tabs.background();
+        left.iter().chain(right).flat_map(Tab::sessions)
+            .filter_map(|session| session.monitor.silence_deadline(silence))
+            .min()
+            .map(|deadline| deadline.saturating_duration_since(std::time::Instant::now()))
+    }
+
+    /// 쌓인 alacritty 이벤트 처리
+    fn process_terminal_events(&mut self) {
+        while let Ok(event) = self.session.terminal_events.try_recv() {
+            self.handle_term_event(event);
+        }
+        self.process_background_events();
+        self.reconnect_due_sessions();
+    }
+
+    /// 다시 연결할 시각이 된 SSH 세션 다시 연결 (백그라운드 탭/pane 포함)
+    fn reconnect_due_sessions(&mut self) {
+        let config = &self.config;
+        let focused = self.session.reconnect_if_due(config);
+        let mut reconnected = focused;
+        for (_, session) in self.panes.others_mut() {
+            reconnected |= session.reconnect_if_due(config);
+        }
+        for tab in self.tabs.background_mut() {
+            reconnected |= tab.session.reconnect_if_due(config);
+            for (_, session) in tab.panes.others_mut() {
+                reconnected |= session.reconnect_if_due(config);
+            }
+        }
+        if focused {
+            self.reset_view_for_session_switch();
+        }
+        if reconnected {
+            self.resize_sessions_to_window();
+        }
+    }
+
+    /// 포커스 없는 세션(다른 pane, 백그라운드 탭)의 이벤트 처리
+    /// 제목 변경은 포커스 세션만 반영하고, 셸이 종료된 pane/탭은 닫음
+    fn process_background_events(&mut self) {
+        let (config, clipboard) = (&self.config, &mut self.clipboard);
+        let mut alerts = Vec::new(); // (탭 이름, 알릴 일)
+        // 활성 탭의 다른 pane은 화면에 보이므로 벨만 알림
+        for (_, session) in self.panes.others_mut() {
+            for alert in session.drain_background_events(config, clipboard) {
+                if alert == SessionAlert::Bell {
+                    alerts.push((session.tab_title(), alert));
+                }
+            }
+        }
+        for tab in self.tabs.background_mut() {
+            for session in tab.sessions_mut() {
+                for alert in session.drain_background_events(config, clipboard) {
+                    match alert {
+                        SessionAlert::Activity(ActivityEvent::Activity) => (session.activity, session.silence) = (true, false),
+                        SessionAlert::Activity(ActivityEvent::Silence) => (session.activity, session.silence) = (false, true),
+                        SessionAlert::Bell => {}
+                    }
+                    alerts.push((session.tab_title(), alert));
+                }
+            }
+        }
+        for (source, alert) in alerts {
+            match alert {
+                SessionAlert::Bell => self.ring_bell(false, &source),
+                SessionAlert::Activity(event) => self.notify_activity(event, &source),
+            }
+        }
+
+        // 활성 탭의 포커스 pane 종료는 handle_term_event에서 처리
+        Session::reap_exited(None, &mut self.panes);
+        for tab in self.tabs.background_mut() {
+            Session::reap_exited(Some(&mut tab.session), &mut tab.panes);
+        }
+        self.tabs.retain_background(|tab| !tab.session.exited);
+    }
+
+    /// 설정한 방식으로 벨 알림 - 호스트 터미널로 BEL 전달, 포커스 세션(`focused_session`)이면 시각 벨,
+    /// 창에 포커스가 없으면 `source` 탭 이름으로 데스크톱 알림
+    fn ring_bell(&mut self, focused_session: bool, source: &str) {
+        let actions = self.bell.ring(&self.config.bell, std::time::Instant::now(), focused_session, self.window_focused);
+        if actions.sound {
+            let _ = io::Write::write_all(&mut io::stdout(), b"\x07");
+            let _ = io::Write::flush(&mut io::stdout());
+        }
+        if actions.notify {
+            notify::send(self.config.notification, "rust-terminal", &format!("{}: 벨", source));
+        }
+        self.redraw_requested = true;
+    }
+
+    /// 백그라운드 탭의 활동/침묵을 설정에 따라 데스크톱 알림
+    fn notify_activity(&self, event: ActivityEvent, source: &str) {
+        if !self.config.activity.notify.includes(event) {
+            return;
+        }
+        let body = match event {
+            ActivityEvent::Activity => format!("{}: 출력 시작", source),
+            ActivityEvent::Silence => format!("{}: 출력 멈춤", source),
+        };
+        notify::send(self.config.notification, "rust-terminal", &body);
+    }
+
+    /// alacritty 이벤트 하나 처리 (이벤트별 동작 결정은 events 모듈)
+    fn handle_term_event(&mut self, event: AlacTermEvent) {
+        let Some(effect) = events::effect_for(event, &self.config) else {
+            return;
+        };
+        match effect {
+            TermEffect::Redraw => {
+                // 나중에 백그라운드 탭이 되어도 이어진 출력을 알 수 있게 기록
+                self.session.monitor.output(std::time::Instant::now(), self.config.activity.silence());
+                self.redraw_requested = true;
+            }
+            TermEffect::SetTitle(title) => {
+                debug!("Title changed: {:?}", title);
+                self.session.title = title;
+                self.update_host_title();
+            }
+            TermEffect::Bell => {
+                self.session.bell = true;
+                let source = self.session.tab_title();
+                self.ring_bell(true, &source);
+            }
+            TermEffect::WriteToPty(text) => {
+                let _ = self.session.terminal.input(text.as_bytes());
+            }
+            TermEffect::StoreClipboard(text) => {
+                debug!("OSC 52 clipboard store: {} bytes", text.len());
+                if let Err(e) = self.clipboard.set_text(&text) {
+                    warn!("{}", e);
+                }
+            }
+            TermEffect::ChildExited(code) => {
+                debug!("Shell exit code: {}", code);
+                self.session.exit_code = Some(code);
+            }
+            TermEffect::Exit => {
+                info!("셸 종료 (코드 {:?})", self.session.exit_code);
+                match self.session.on_shell_exit(&self.config) {
+                    // 탭의 마지막 pane이면 탭을 닫고, 마지막 탭이면 앱 종료
+                    ExitBehavior::Close | ExitBehavior::CloseOnSuccess => self.close_focused_pane(),
+                    ExitBehavior::Respawn => self.reset_view_for_session_switch(),
+                    ExitBehavior::Hold => {}
+                }
+            }
+        }
+    }
+
     /// 키 이벤트 처리
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
-        match key.code {
-            KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                // Ctrl+Z 안전 종료 - 첫 번째 누름 시 경고, 두 번째 누름 시 종료
-                if self.quit_confirm_count == 0 {
-                    self.quit_confirm_count = 1;
-                    debug!("First Ctrl+Z pressed - showing quit confirmation");
-                } else {
-                    self.should_quit = true;
-                    debug!("Second Ctrl+Z pressed - exiting application");
+        // 뗀 키는 단축키/입력기를 거치지 않고 프로그램에만 보고
+        if key.kind == KeyEventKind::Release {
+            self.send_input(|terminal| {
+                if let Some(bytes) = keys::encode_key(&key, terminal.keyboard_mode()) {
+                    let _ = terminal.input(&bytes);
+                }
+            });
+            return Ok(());
+        }
+
+        self.session.bell = false;
+
+        // 셸이 종료되어 열어 둔 pane은 아무 키나 누르면 닫기
+        if self.session.held {
+            self.close_focused_pane();
+            return Ok(());
+        }
+
+        // 조합 중에 Backspace를 누르면 셸로 보내지 않고 마지막 자모만 지움
+        if key.code == KeyCode::Backspace && key.modifiers.is_empty() && self.input_method.backspace() {
+            return Ok(());
+        }
+
+        // 문자 이외의 키를 누르면 조합 중인 글자를 먼저 확정
+        if !matches!(key.code, KeyCode::Char(_)) {
+            self.commit_preedit();
+        }
+
+        // 접두 키 다음 키는 모든 앱 단축키(종료, 검색, 스크롤 등)를 건너뛰고 그대로 전송
+        if self.quote_next {
+            self.quote_next = false;
+            self.commit_preedit();
+            debug!("Quoted key: {:?}", key);
+            self.send_key(&key);
+            return Ok(());
+        }
+
+        // 마크 명령 다음 글자는 마크 이름 (다른 키는 취소)
+        if let Some(command) = self.pending_mark.take() {
+            if let KeyCode::Char(name) = key.code {
+                self.run_mark_command(command, name);
+            }
+            return Ok(());
+        }
+
+        // 힌트 모드면 키 입력을 라벨 고르기에 사용
+        if let Some(hints) = self.hints.as_mut() {
+            match hints.handle_key(key) {
+                HintOutcome::Continue => {}
+                HintOutcome::Close => self.hints = None,
+                HintOutcome::Open(hint) => {
+                    self.hints = None;
+                    self.open_hint(hint)?;
+                }
+                HintOutcome::Copy(hint) => {
+                    self.hints = None;
+                    self.run_action(Action::CopyText(hint.text))?;
                 }
             }
+            return Ok(());
+        }
+
+        // 명령 팔레트가 열려 있으면 키 입력을 팔레트에 사용
+        if let Some(palette) = self.palette.as_mut() {
+            match palette.handle_key(key) {
+                PaletteOutcome::Continue => {}
+                PaletteOutcome::Close => self.palette = None,
+                PaletteOutcome::Run(action) => {
+                    self.palette = None;
+                    if self.action_available(&action) {
+                        debug!("Command palette: {:?}", action);
+                        return self.run_action(action);
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        // 검색 모드에서는 키 입력을 검색어 편집/일치 항목 이동에 사용
+        if self.search_prompt.is_some() {
+            self.handle_search_key(key);
+            return Ok(());
+        }
+
+        // 데몬 모드의 Ctrl+B 접두 키 - D는 분리, Ctrl+B는 Ctrl+B 전송, 다른 키는 Ctrl+B와 함께 그대로 처리
+        let ctrl_b = key.code == KeyCode::Char('b') && key.modifiers == KeyModifiers::CONTROL;
+        if std::mem::take(&mut self.detach_prefix) {
+            if matches!(key.code, KeyCode::Char('d' | 'D')) && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                return self.run_action(Action::Detach);
+            }
+            self.send_key(&KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL));
+            if ctrl_b {
+                return Ok(());
+            }
+        } else if ctrl_b && self.config.session.daemon {
+            self.detach_prefix = true;
+            return Ok(());
+        }
+
+        // 키 바인딩(사용자 정의 → 기본 단축키) 확인
+        if let Some(action) = self.config.bindings.find(&key).cloned() {
+            if self.action_available(&action) {
+                debug!("Key binding: {:?} -> {:?}", key, action);
+                return self.run_action(action);
+            }
+        }
+
+        match key.code {
+            KeyCode::Char(_) if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                || self.session.terminal.keyboard_mode().reports_all_keys() => {
+                // Ctrl/Alt+문자 - 제어 문자(Ctrl+Space = NUL 등), ESC 접두(Alt+b) 또는 modifyOtherKeys 시퀀스 전송
+                // kitty 프로토콜로 모든 키 보고를 요청했으면 글자도 시퀀스로 전송
+                self.commit_preedit();
+                self.send_key(&key);
+            }
             KeyCode::Char(c) => {
                 self.handle_char_input(c)?;
             }
-            KeyCode::Enter => {
-                let _ = self.terminal.input(b"\r");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            // 단축키가 아닌 키(해제한 기본 단축키 포함)는 셸로 전송
+            _ => self.send_key(&key),
+        }
+        Ok(())
+    }
+
+    /// 지금 실행할 수 있는 동작인지 확인 (아니면 키를 셸로 전송)
+    /// pane 포커스/크기 조절은 분할했을 때만 Alt+방향키를 가로챔
+    fn action_available(&self, action: &Action) -> bool {
+        match action {
+            Action::FocusPane(_) | Action::ResizePane(_) => self.panes.len() > 1,
+            Action::Detach => self.config.session.daemon,
+            Action::SmartActions => !self.config.smart.is_empty(),
+            _ => true,
+        }
+    }
+
+    /// 키 바인딩 동작 실행
+    fn run_action(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::Copy => self.copy_selected_text(ClipboardTarget::Clipboard, self.config.selection.copy_format)?,
+            Action::CopyAs(format) => self.copy_selected_text(ClipboardTarget::Clipboard, format)?,
+            Action::Paste => self.paste_clipboard(self.config.paste.trailing_newline),
+            Action::PasteAndRun => self.paste_clipboard(PasteNewline::Append),
+            Action::PasteOnly => self.paste_clipboard(PasteNewline::Strip),
+            Action::Search => {
+                self.search_prompt = Some(self.session.search.query().to_string());
+                self.search_browsing = false;
             }
-            KeyCode::Backspace => {
-                let _ = self.terminal.input(b"\x7f");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::CommandPalette => {
+                self.palette = Some(CommandPalette::new(&self.config.bindings, &self.config.colors.theme_names(), &self.config.profile_names(), &self.config.ssh_host_names()));
             }
-            KeyCode::Tab => {
-                let _ = self.terminal.input(b"\t");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::SetTheme(name) => {
+                if let Err(e) = self.config.colors.set_theme(&name) {
+                    warn!("테마 변경 실패: {:#}", e);
+                    self.config_error = Some(format!("{:#}", e));
+                }
+                for session in self.all_sessions_mut() {
+                    session.line_cache.invalidate();
+                }
             }
-            KeyCode::Esc => {
-                let _ = self.terminal.input(b"\x1b");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::NewTabWithProfile(name) => self.open_profile_tab(&name),
+            Action::ConnectSsh(target) => self.open_ssh_tab(&target),
+            Action::OpenSerial(device) => self.open_serial_tab(&device),
+            Action::Connect(target) => self.open_network_tab(&target),
+            Action::SmartActions => {
+                let found: Vec<smart::SmartMatch> = self.session.terminal.get_visible_content().iter()
+                    .flat_map(|line| smart::matches(&self.config.smart, line))
+                    .collect();
+                self.open_smart_menu(&found);
             }
-            KeyCode::Up => {
-                let _ = self.terminal.input(b"\x1b[A");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::HintMode => {
+                let hints = HintMode::new(&self.session.terminal.get_visible_content(), &self.config.smart, &self.config.hints.alphabet);
+                if hints.is_empty() {
+                    debug!("힌트 모드: 화면에 항목 없음");
+                } else {
+                    self.hints = Some(hints);
+                }
             }
-            KeyCode::Down => {
-                let _ = self.terminal.input(b"\x1b[B");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::CopyText(text) => {
+                if let Err(e) = self.clipboard.set_text_to(ClipboardTarget::Clipboard, &text) {
+                    warn!("{}", e);
+                }
             }
-            KeyCode::Right => {
-                let _ = self.terminal.input(b"\x1b[C");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::OpenUrl(url) => {
+                if let Err(e) = mouse::open_url(&url) {
+                    warn!("링크 열기 실패: {}", e);
+                }
             }
-            KeyCode::Left => {
-                let _ = self.terminal.input(b"\x1b[D");
-                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-                self.auto_scroll_enabled = true;
-                self.auto_scroll_to_cursor();
+            Action::RunCommand(command) => self.run_command_tab(Shell::command_line(&command), &command),
+            Action::RunProgram(command) => {
+                if let Some((program, args)) = command.split_first() {
+                    let shell = Shell::WithArguments { program: program.clone(), args: args.to_vec() };
+                    self.run_command_tab(shell, &command.join(" "));
+                }
             }
-            KeyCode::PageUp => {
-                // Page Up - 한 페이지 위로 스크롤
+            Action::ScrollPageUp | Action::ScrollPageDown => {
                 // 수동 스크롤 감지 - 자동 추적 임시 비활성화
-                self.auto_scroll_enabled = false;
-                self.last_manual_scroll = std::time::Instant::now();
-
-                let page_size = self.terminal_area.height.saturating_sub(2) as u16;
-                self.scroll_offset = self.scroll_offset.saturating_sub(page_size);
-                debug!("Page up to offset: {}", self.scroll_offset);
+                self.session.auto_scroll_enabled = false;
+                self.session.last_manual_scroll = std::time::Instant::now();
+
+                let page_size = self.terminal_area.height.saturating_sub(2) as i32;
+                let lines = if action == Action::ScrollPageUp { page_size } else { -page_size };
+                self.session.smooth_scroll.stop();
+                self.session.terminal.scroll_by(lines);
+                self.sync_scroll_offset();
+                debug!("Page scroll to offset: {}", self.session.scroll_offset);
             }
-            KeyCode::PageDown => {
-                // Page Down - 한 페이지 아래로 스크롤
+            Action::ScrollTop | Action::ScrollBottom => {
                 // 수동 스크롤 감지 - 자동 추적 임시 비활성화
-                self.auto_scroll_enabled = false;
-                self.last_manual_scroll = std::time::Instant::now();
-
-                let page_size = self.terminal_area.height.saturating_sub(2) as u16;
-                let visible_lines = self.terminal_area.height.saturating_sub(2) as usize;
-                if self.total_lines > visible_lines {
-                    let max_scroll = self.total_lines.saturating_sub(visible_lines) as u16;
-                    self.scroll_offset = (self.scroll_offset + page_size).min(max_scroll);
-                    debug!("Page down to offset: {} / max: {}", self.scroll_offset, max_scroll);
+                self.session.auto_scroll_enabled = false;
+                self.session.last_manual_scroll = std::time::Instant::now();
+
+                let position = if action == Action::ScrollTop { ScrollPosition::Top } else { ScrollPosition::Bottom };
+                self.session.smooth_scroll.stop();
+                self.session.terminal.scroll_to(position);
+                self.sync_scroll_offset();
+                debug!("Scrolled to {:?}: offset={}", position, self.session.scroll_offset);
+            }
+            Action::PrevPrompt => self.jump_to_prompt(false),
+            Action::NextPrompt => self.jump_to_prompt(true),
+            Action::QuoteNext => self.quote_next = true,
+            Action::ToggleWhitespace => self.show_whitespace = !self.show_whitespace,
+            Action::ToggleAutowrap => {
+                // 프로그램이 바꿨을 수도 있으므로 현재 모드 기준으로 반전
+                let enabled = !self.session.terminal.is_autowrap();
+                let sequence = self.session.terminal.set_autowrap(enabled);
+                debug!("자동 줄 바꿈 {}: {:?}", if enabled { "켜기" } else { "끄기" }, String::from_utf8_lossy(sequence));
+            }
+            Action::SetMark => self.pending_mark = Some(MarkCommand::Set),
+            Action::JumpToMark => self.pending_mark = Some(MarkCommand::Jump),
+            Action::ReloadConfig => self.reload_config(),
+            Action::Quit => {
+                info!("종료 동작 - 앱 종료");
+                self.should_quit = true;
+            }
+            Action::Detach => {
+                info!("세션 분리 - 셸은 데몬에서 계속 실행");
+                self.detaching = true;
+                self.should_quit = true;
+            }
+            Action::ToggleCursorBlink => {
+                self.config.cursor.blink = !self.config.cursor.blink;
+                self.apply_hardware_cursor_blink();
+                debug!("커서 깜빡임: {}", self.config.cursor.blink);
+            }
+            Action::ToggleStats => {
+                self.stats = match self.stats {
+                    Some(_) => None,
+                    None => {
+                        stats::take_lock_wait(); // 꺼져 있던 동안의 값은 버림
+                        Some(RenderStats::new(std::time::Instant::now(), stats::pty_bytes()))
+                    }
+                };
+                stats::set_enabled(self.stats.is_some());
+            }
+            Action::ToggleOutputLog => self.toggle_output_log(),
+            Action::ToggleRecording => self.toggle_recording(),
+            Action::ToggleBroadcast => {
+                let broadcast = self.panes.toggle_broadcast();
+                info!("입력 동기화: {}", broadcast);
+            }
+            Action::ExportScrollback(format) => self.export_scrollback(format),
+            Action::CaptureSnapshot => self.capture_snapshot(),
+            Action::CycleSnapshotDiff => self.cycle_snapshot_diff(),
+            Action::ClearScrollback => self.clear_scrollback(),
+            Action::UndoClearScrollback => self.undo_clear_scrollback(),
+            Action::CloseSession => {
+                // 다른 pane/탭이 남아 있으면 바로 닫고, 마지막 세션이면 설정에 따라 확인 후 종료
+                let open_sessions = self.tabs.len() + self.panes.len() - 1;
+                match self.quit_confirm.request_close(open_sessions, self.config.session.confirm_close_last) {
+                    CloseOutcome::NeedsConfirm => debug!("Close requested - showing quit confirmation"),
+                    CloseOutcome::Closed | CloseOutcome::Quit => self.close_focused_pane(),
                 }
             }
-            KeyCode::Home if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                // Ctrl+Home - 맨 위로
-                // 수동 스크롤 감지 - 자동 추적 임시 비활성화
-                self.auto_scroll_enabled = false;
-                self.last_manual_scroll = std::time::Instant::now();
+            Action::SplitRight => self.split_pane(SplitDirection::Horizontal),
+            Action::SplitDown => self.split_pane(SplitDirection::Vertical),
+            Action::FocusPane(direction) => self.focus_pane_toward(direction),
+            Action::ResizePane(direction) => {
+                self.panes.resize_focused(direction);
+            }
+            Action::NewTab => self.open_tab(),
+            Action::NextTab => self.cycle_tab(true),
+            Action::PrevTab => self.cycle_tab(false),
+            Action::SendString(bytes) => {
+                self.commit_preedit();
+                self.send_input(|terminal| {
+                    let _ = terminal.input(&bytes);
+                });
+                // 입력 시 자동 추적 활성화 및 커서 위치로 이동
+                self.session.auto_scroll_enabled = true;
+                self.auto_scroll_to_cursor();
+            }
+            // Keybindings::find가 반환하지 않음
+            Action::Unbind => {}
+        }
+        Ok(())
+    }
 
-                self.scroll_offset = 0;
-                debug!("Scrolled to top");
+    /// 마크 설정/이동
+    /// 마크 위치는 스크롤해서 보고 있으면 화면 맨 윗줄, 맨 아래를 보고 있으면 커서 줄
+    fn run_mark_command(&mut self, command: MarkCommand, name: char) {
+        let lines = self.session.terminal.get_renderable_content().unwrap_or_default();
+        match command {
+            MarkCommand::Set => {
+                let state = self.session.terminal.scroll_state();
+                let row = if state.offset > 0 {
+                    state.first_visible_row()
+                } else {
+                    self.cursor_state.position.1 as usize
+                };
+                if self.session.marks.set(name, row, &lines) {
+                    debug!("마크 '{}' 설정: {}행", name, row);
+                }
             }
-            KeyCode::End if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                // Ctrl+End - 맨 아래로
-                // 수동 스크롤 감지 - 자동 추적 임시 비활성화
-                self.auto_scroll_enabled = false;
-                self.last_manual_scroll = std::time::Instant::now();
-
-                let visible_lines = self.terminal_area.height.saturating_sub(2) as usize;
-                if self.total_lines > visible_lines {
-                    let max_scroll = self.total_lines.saturating_sub(visible_lines) as u16;
-                    self.scroll_offset = max_scroll;
-                    debug!("Scrolled to bottom: offset={}", self.scroll_offset);
+            MarkCommand::Jump => match self.session.marks.resolve(name, &lines) {
+                Some(row) => {
+                    debug!("마크 '{}'로 이동: {}행", name, row);
+                    self.scroll_row_to_top(row);
                 }
+                None => debug!("마크 '{}' 없음", name),
+            },
+        }
+    }
+
+    /// 이전/다음 명령의 프롬프트를 화면 맨 위로 스크롤하고 그 명령의 출력 범위 강조
+    /// 고른 명령이 없으면 입력 중인 현재 프롬프트 기준이며, 현재 프롬프트까지 내려가면 강조를 끄고 맨 아래로 돌아감
+    fn jump_to_prompt(&mut self, forward: bool) {
+        let marks = self.session.terminal.prompt_marks();
+        let rows = prompts::prompt_rows(&marks);
+        let cursor_row = self.cursor_state.position.1 as usize;
+        // 커서가 있는(입력 중인) 프롬프트
+        let live = prompts::previous_prompt(&rows, cursor_row + 1);
+        let current = self.session.command_block.map(|block| block.prompt).or(live).unwrap_or(cursor_row);
+        let target = if forward {
+            prompts::next_prompt(&rows, current).filter(|&row| live.is_none_or(|live| row < live))
+        } else {
+            prompts::previous_prompt(&rows, current)
+        };
+
+        match target {
+            Some(row) => {
+                debug!("프롬프트로 이동: {}행", row);
+                let last_row = live.unwrap_or(cursor_row + 1).saturating_sub(1);
+                self.session.command_block = prompts::command_block(&marks, row, last_row);
+                self.scroll_row_to_top(row);
             }
-            _ => {}
+            None if forward => self.auto_scroll_to_cursor(),
+            None => debug!("이전 프롬프트 없음 (셸 통합 OSC 133 필요)"),
+        }
+    }
+
+    /// 전체 버퍼 행이 화면 맨 위에 오도록 스크롤
+    fn scroll_row_to_top(&mut self, row: usize) {
+        self.session.auto_scroll_enabled = false;
+        self.session.last_manual_scroll = std::time::Instant::now();
+        self.session.smooth_scroll.stop();
+        let state = self.session.terminal.scroll_state();
+        self.session.terminal.scroll_to(ScrollPosition::Offset(state.max_offset().saturating_sub(row)));
+        self.sync_scroll_offset();
+    }
+
+    /// 클립보드 텍스트를 터미널에 붙여넣기
+    fn paste_clipboard(&mut self, trailing_newline: PasteNewline) {
+        self.paste_from(trailing_newline, false);
+    }
+
+    /// 시스템 클립보드 또는 선택 클립보드(`primary`)의 텍스트를 터미널에 붙여넣기
+    fn paste_from(&mut self, trailing_newline: PasteNewline, primary: bool) {
+        let text = if primary { self.clipboard.get_primary() } else { self.clipboard.get_text() };
+        match text {
+            Ok(text) => {
+                self.commit_preedit();
+                let (text, run) = trailing_newline.apply(&text);
+                self.send_input(|terminal| {
+                    let _ = terminal.paste(text);
+                    if run {
+                        let _ = terminal.input(b"\r");
+                    }
+                });
+                self.session.auto_scroll_enabled = true;
+                self.auto_scroll_to_cursor();
+            }
+            Err(e) => warn!("{}", e),
         }
-        Ok(())
     }
 
     /// 문자 입력 처리 (한글 조합 포함)
     fn handle_char_input(&mut self, c: char) -> Result<()> {
         debug!("Character input: '{}' (U+{:04X})", c, c as u32);
 
-        // UTF-8 바이트로 인코딩하여 터미널에 전송
-        let mut buffer = [0; 4];
-        let utf8_str = c.encode_utf8(&mut buffer);
-
-        debug!("Sending UTF-8 bytes: {:?}", utf8_str.as_bytes());
-        let _ = self.terminal.input(utf8_str.as_bytes());
+        // 입력기를 거쳐 확정된 텍스트만 UTF-8 바이트로 터미널에 전송
+        let committed = self.input_method.input_char(c);
+        if !committed.is_empty() {
+            debug!("Sending UTF-8 bytes: {:?}", committed.as_bytes());
+            self.send_input(|terminal| {
+                let _ = terminal.input(committed.as_bytes());
+            });
+        }
 
         // 입력 시 자동 추적 활성화 및 커서 위치로 이동
-        self.auto_scroll_enabled = true;
+        self.session.auto_scroll_enabled = true;
         self.auto_scroll_to_cursor();
 
         Ok(())
     }
 
+    /// 키를 바이트로 변환하여 터미널에 전송
+    fn send_key(&mut self, key: &KeyEvent) {
+        // pane마다 키보드 모드(애플리케이션 커서 키 등)가 다를 수 있으므로 각각 변환
+        self.send_input(|terminal| {
+            if let Some(bytes) = keys::encode_key(key, terminal.keyboard_mode()) {
+                let _ = terminal.input(&bytes);
+            }
+        });
+        // 입력 시 자동 추적 활성화 및 커서 위치로 이동
+        self.session.auto_scroll_enabled = true;
+        self.auto_scroll_to_cursor();
+    }
+
+    /// 조합 중인 글자를 확정하여 터미널에 전송
+    fn commit_preedit(&mut self) {
+        if let Some(ch) = self.input_method.flush() {
+            let mut buffer = [0; 4];
+            let bytes = ch.encode_utf8(&mut buffer).as_bytes();
+            self.send_input(|terminal| {
+                let _ = terminal.input(bytes);
+            });
+        }
+    }
+
+    /// 키보드 입력을 포커스 pane에 보내고, 입력 동기화가 켜져 있으면 같은 탭의 다른 pane에도 보냄
+    /// (셸이 종료되어 열어 둔 pane은 제외, 마우스 보고와 프로그램 응답은 포커스 pane에만)
+    fn send_input(&mut self, mut send: impl FnMut(&mut Terminal)) {
+        send(&mut self.session.terminal);
+        if self.panes.broadcast() {
+            for (_, session) in self.panes.others_mut().filter(|(_, session)| !session.held) {
+                send(&mut session.terminal);
+            }
+        }
+    }
+
     /// 마우스 이벤트 처리
     fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
         debug!("Mouse event: {:?} [Terminal Area: {}x{} at ({},{})]",
             mouse, self.terminal_area.width, self.terminal_area.height,
             self.terminal_area.x, self.terminal_area.y);
 
-        // 마우스 이벤트를 터미널로 전달 (xterm mouse protocol)
+        // pane 사이 구분선 끌기
+        if self.handle_divider_mouse(&mouse) {
+            return Ok(());
+        }
+
+        // 다른 pane을 클릭하면 그 pane으로 포커스만 이동
+        if let MouseEventKind::Down(_) = mouse.kind {
+            if let Some(id) = panes::pane_at(&self.pane_areas, mouse.column, mouse.row) {
+                if id != self.panes.focused() {
+                    self.focus_pane(id);
+                    return Ok(());
+                }
+            }
+        }
+
+        // 스크롤바 클릭/드래그
+        if self.handle_scrollbar_mouse(&mouse) {
+            return Ok(());
+        }
+
+        // 프로그램이 마우스 보고를 켰으면 선택/스크롤 대신 프로그램에 전달
+        if self.report_mouse(&mouse) {
+            return Ok(());
+        }
+
         match mouse.kind {
             MouseEventKind::Down(MouseButton::Left) => {
-                debug!("Mouse left click at ({}, {})", mouse.column, mouse.row);
+                let click_count = self.click_tracker.register(
+                    mouse.column, mouse.row, std::time::Instant::now(), &self.config.mouse);
+                debug!("Mouse left click at ({}, {}), click_count: {}", mouse.column, mouse.row, click_count);
+
+                // URL 열기 수정 키를 누른 채 URL/파일 경로를 누르면 열기, 그 외에는 링크 위라도 텍스트 선택
+                // 프로그램이 OSC 8로 지정한 링크가 줄 내용에서 찾은 링크보다 우선
+                let action = self.line_at_mouse(mouse.column, mouse.row)
+                    .map(|(row, col, line)| match self.session.terminal.hyperlink_at(row as usize, col) {
+                        Some(uri) if self.config.mouse.url_modifier.is_held(mouse.modifiers) => ClickAction::OpenUrl(uri),
+                        _ => mouse::click_action(&line, columns::column_to_index(&line, col), mouse.modifiers, &self.config.mouse),
+                    });
+                let target = match action {
+                    Some(ClickAction::OpenUrl(url)) => Some(url),
+                    // 파일 경로를 열었으면 끝, 없는 파일이면 선택으로 처리
+                    Some(ClickAction::OpenPath(path)) if self.open_path(&path) => {
+                        self.url_clicked = true;
+                        return Ok(());
+                    }
+                    _ => None,
+                };
+                if let Some(target) = target {
+                    debug!("Opening link: {}", target);
+                    if let Err(e) = mouse::open_url(&target) {
+                        warn!("링크 열기 실패: {}", e);
+                    }
+                    self.url_clicked = true;
+                    return Ok(());
+                }
 
-                // 텍스트 선택 시작
-                self.start_text_selection(mouse.column, mouse.row)?;
+                // 텍스트 선택 시작 (더블 클릭은 단어, 트리플 클릭은 줄 단위, Alt를 누르면 사각형 선택)
+                self.start_text_selection(mouse.column, mouse.row, SelectionUnit::from_click_count(click_count),
+                    mouse.modifiers.contains(KeyModifiers::ALT))?;
             }
             MouseEventKind::Up(MouseButton::Left) => {
                 debug!("Mouse left release at ({}, {}), is_dragging: {}, selection_active: {}",
-                    mouse.column, mouse.row, self.is_dragging, self.text_selection.is_active);
+                    mouse.column, mouse.row, self.is_dragging, self.session.text_selection.is_active);
 
-                if self.is_dragging {
-                    // 드래그 종료 - 텍스트 복사
-                    self.finish_text_selection(mouse.column, mouse.row)?;
-                    debug!("Final selection state: {:?}", self.text_selection);
-                    self.copy_selected_text()?;
-                    debug!("Text selection copied to clipboard");
-                } else if self.text_selection.is_active {
-                    // 단순 클릭으로 선택 완료 - 텍스트 복사
+                if std::mem::take(&mut self.url_clicked) {
+                    // URL을 연 클릭 - 선택/커서 이동 없음
+                } else if self.is_dragging || self.session.text_selection.is_active {
+                    // 드래그 또는 더블/트리플 클릭으로 선택 완료 - 설정에 따라 바로 복사
+                    let dragged = self.is_dragging;
                     self.finish_text_selection(mouse.column, mouse.row)?;
-                    debug!("Single click final selection: {:?}", self.text_selection);
-                    self.copy_selected_text()?;
-                    debug!("Single click selection copied to clipboard");
+                    debug!("Final selection state: {:?}", self.session.text_selection);
+                    if self.config.selection.copy_on_select {
+                        self.copy_selected_text(self.config.selection.clipboard, ExportFormat::Text)?;
+                        debug!("Text selection copied to {:?}", self.config.selection.clipboard);
+                    } else if !dragged && self.session.text_selection.unit == SelectionUnit::Char {
+                        // 끌지 않은 단순 클릭은 선택으로 남기지 않음
+                        self.session.text_selection.is_active = false;
+                    }
                 } else {
                     // 단순 클릭 - 커서 이동
                     if let Some((terminal_col, terminal_row)) = self.mouse_to_terminal_coords(mouse.column, mouse.row) {
                         // 커서 이동 escape sequence 전송
                         let escape_seq = format!("\x1b[{};{}H", terminal_row + 1, terminal_col + 1);
-                        let _ = self.terminal.input(escape_seq.as_bytes());
+                        let _ = self.session.terminal.input(escape_seq.as_bytes());
                         debug!("Cursor moved to ({}, {})", terminal_col, terminal_row);
                     }
                 }
             }
+            MouseEventKind::Down(MouseButton::Middle) => {
+                // 가운데 버튼 - 선택하자마자 복사하는 클립보드에서 붙여넣기
+                let primary = self.config.selection.clipboard == ClipboardTarget::Primary;
+                self.paste_from(self.config.paste.trailing_newline, primary);
+            }
             MouseEventKind::Down(MouseButton::Right) => {
-                debug!("Mouse right click at ({}, {}) - ignored (no terminal forwarding)", mouse.column, mouse.row);
-                // 오른쪽 클릭은 터미널로 전달하지 않음 (이상한 문자 출력 방지)
+                // 스마트 선택 항목 위면 동작 메뉴, 그 외에는 터미널로 전달하지 않음 (이상한 문자 출력 방지)
+                let found: Vec<smart::SmartMatch> = self.line_at_mouse(mouse.column, mouse.row)
+                    .map(|(_, col, line)| {
+                        let index = columns::column_to_index(&line, col);
+                        smart::matches(&self.config.smart, &line).into_iter().filter(|found| found.contains(index)).collect()
+                    })
+                    .unwrap_or_default();
+                debug!("Mouse right click at ({}, {}) - {} smart match(es)", mouse.column, mouse.row, found.len());
+                self.open_smart_menu(&found);
             }
             MouseEventKind::Up(MouseButton::Right) => {
                 debug!("Mouse right release at ({}, {}) - ignored", mouse.column, mouse.row);
@@ -474,52 +1994,53 @@ impl App {
                 self.update_text_selection(mouse.column, mouse.row)?;
             }
             MouseEventKind::Moved => {
+                // URL 열기 수정 키를 누르고 있을 때만 가리킨 URL에 밑줄
+                self.hovered_url = if self.config.mouse.url_modifier.is_held(mouse.modifiers) {
+                    self.line_at_mouse(mouse.column, mouse.row)
+                        .and_then(|(row, col, line)| mouse::url_at(&line, columns::column_to_index(&line, col)).map(|span| (row, span)))
+                } else {
+                    None
+                };
+
                 // 드래그 중일 때만 처리
-                if self.is_dragging && self.text_selection.is_active {
+                if self.is_dragging && self.session.text_selection.is_active {
                     debug!("Mouse moved while dragging to ({}, {})", mouse.column, mouse.row);
                     self.update_text_selection(mouse.column, mouse.row)?;
                 }
             }
             MouseEventKind::ScrollDown => {
                 debug!("Mouse scroll down at ({}, {})", mouse.column, mouse.row);
-                let visible_lines = self.terminal_area.height.saturating_sub(2) as usize; // 테두리 제외
-                debug!("Scroll check: total_lines={}, visible_lines={}, current_offset={}",
-                    self.total_lines, visible_lines, self.scroll_offset);
 
-                // 수동 스크롤 감지 - 자동 추적 임시 비활성화
-                self.auto_scroll_enabled = false;
-                self.last_manual_scroll = std::time::Instant::now();
-
-                if self.total_lines > visible_lines {
-                    let max_scroll = self.total_lines.saturating_sub(visible_lines) as u16;
-                    if self.scroll_offset < max_scroll {
-                        let old_offset = self.scroll_offset;
-                        self.scroll_offset = (self.scroll_offset + 3).min(max_scroll); // 3줄씩 스크롤
-                        debug!("Scrolled down: {} -> {} (max: {})", old_offset, self.scroll_offset, max_scroll);
-                    } else {
-                        debug!("Already at max scroll: offset={}, max={}", self.scroll_offset, max_scroll);
-                    }
-                } else {
-                    debug!("No scrolling possible: total_lines={} <= visible_lines={}", self.total_lines, visible_lines);
+                // 대체 화면이면 프로그램에 화살표 키로 보냄
+                if self.send_alternate_scroll(-(self.config.scrollback.scroll_lines as i32)) {
+                    return Ok(());
                 }
+
+                // 수동 스크롤 감지 - 자동 추적 임시 비활성화
+                self.session.auto_scroll_enabled = false;
+                self.session.last_manual_scroll = std::time::Instant::now();
+
+                let old_offset = self.session.scroll_offset;
+                self.scroll_wheel(-(self.config.scrollback.scroll_lines as i32));
+                debug!("Scrolled down: {} -> {}", old_offset, self.session.scroll_offset);
+                self.apply_wheel_selection_policy(mouse.column, mouse.row)?;
             }
             MouseEventKind::ScrollUp => {
                 debug!("Mouse scroll up at ({}, {})", mouse.column, mouse.row);
-                let visible_lines = self.terminal_area.height.saturating_sub(2) as usize;
-                debug!("Scroll up check: total_lines={}, visible_lines={}, current_offset={}",
-                    self.total_lines, visible_lines, self.scroll_offset);
+
+                // 대체 화면이면 프로그램에 화살표 키로 보냄
+                if self.send_alternate_scroll(self.config.scrollback.scroll_lines as i32) {
+                    return Ok(());
+                }
 
                 // 수동 스크롤 감지 - 자동 추적 임시 비활성화
-                self.auto_scroll_enabled = false;
-                self.last_manual_scroll = std::time::Instant::now();
+                self.session.auto_scroll_enabled = false;
+                self.session.last_manual_scroll = std::time::Instant::now();
 
-                if self.scroll_offset > 0 {
-                    let old_offset = self.scroll_offset;
-                    self.scroll_offset = self.scroll_offset.saturating_sub(3); // 3줄씩 스크롤
-                    debug!("Scrolled up: {} -> {}", old_offset, self.scroll_offset);
-                } else {
-                    debug!("Already at top: offset=0");
-                }
+                let old_offset = self.session.scroll_offset;
+                self.scroll_wheel(self.config.scrollback.scroll_lines as i32);
+                debug!("Scrolled up: {} -> {}", old_offset, self.session.scroll_offset);
+                self.apply_wheel_selection_policy(mouse.column, mouse.row)?;
             }
             _ => {
                 debug!("Other mouse event: {:?}", mouse.kind);
@@ -528,33 +2049,44 @@ impl App {
         Ok(())
     }
 
-    /// 마우스 이벤트를 xterm mouse protocol로 터미널에 전달
-    fn send_mouse_event(&mut self, button: u8, col: u16, row: u16) -> Result<()> {
-        // xterm mouse reporting: ESC[M<button><col+32><row+32>
-        let button_char = (button + 32) as char;
-        let col_char = (col.saturating_add(32).min(255)) as u8 as char;
-        let row_char = (row.saturating_add(32).min(255)) as u8 as char;
-
-        let mouse_sequence = format!("\x1b[M{}{}{}", button_char, col_char, row_char);
-        debug!("Sending mouse sequence: {:?}", mouse_sequence.as_bytes());
+    /// 마우스 보고 모드를 켠 프로그램에 마우스 이벤트 전달 (전달했거나 무시할 이벤트면 true)
+    /// Shift를 누르거나 스크롤백을 보고 있으면 앱에서 선택/스크롤 처리
+    fn report_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let mode = self.session.terminal.mouse_report_mode();
+        if !mode.is_enabled()
+            || mouse.modifiers.contains(KeyModifiers::SHIFT)
+            || self.session.terminal.display_offset() > 0
+        {
+            return false;
+        }
+        let Some((col, row)) = self.mouse_to_terminal_coords(mouse.column, mouse.row) else {
+            return false;
+        };
+        let row = row.saturating_sub(self.session.scroll_offset);
 
-        let _ = self.terminal.input(mouse_sequence.as_bytes());
-        Ok(())
+        match mouse::mouse_report(mouse.kind, mouse.modifiers, col, row, mode) {
+            Some(bytes) => {
+                debug!("Mouse report: {:?}", String::from_utf8_lossy(&bytes));
+                let _ = self.session.terminal.input(&bytes);
+                true
+            }
+            // 보고하지 않는 이동은 URL 강조 등 앱 처리, 그 외(보고 모드가 아닌 드래그 등)는 무시
+            None => !matches!(mouse.kind, MouseEventKind::Moved | MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight),
+        }
     }
 
     /// 텍스트 선택 시작 (Zed 방식 좌표 변환 사용)
-    fn start_text_selection(&mut self, col: u16, row: u16) -> Result<()> {
+    fn start_text_selection(&mut self, col: u16, row: u16, unit: SelectionUnit, block: bool) -> Result<()> {
         if let Some((terminal_col, terminal_row)) = self.mouse_to_terminal_coords(col, row) {
-            debug!("Starting text selection at terminal coords: ({}, {})", terminal_col, terminal_row);
-            self.text_selection = TextSelection {
-                start_row: terminal_row,
-                start_col: terminal_col,
-                end_row: terminal_row,
-                end_col: terminal_col,
-                is_active: true,
+            debug!("Starting {:?} selection at terminal coords: ({}, {}), block: {}", unit, terminal_col, terminal_row, block);
+            self.session.text_selection = if block {
+                TextSelection::start_block(terminal_row, terminal_col)
+            } else {
+                let lines = self.session.terminal.get_renderable_content().unwrap_or_default();
+                TextSelection::start(&lines, terminal_row, terminal_col, unit)
             };
             self.is_dragging = false; // 드래그는 실제 드래그 이벤트에서 시작
-            debug!("Text selection state: {:?}", self.text_selection);
+            debug!("Text selection state: {:?}", self.session.text_selection);
         } else {
             debug!("Failed to convert mouse coords ({}, {}) to terminal coords", col, row);
         }
@@ -563,12 +2095,16 @@ impl App {
 
     /// 텍스트 선택 영역 업데이트 (Zed 방식 좌표 변환 사용)
     fn update_text_selection(&mut self, col: u16, row: u16) -> Result<()> {
-        if self.text_selection.is_active {
+        if self.session.text_selection.is_active {
             if let Some((terminal_col, terminal_row)) = self.mouse_to_terminal_coords(col, row) {
                 debug!("Updating selection to: ({}, {})", terminal_col, terminal_row);
-                self.text_selection.end_row = terminal_row;
-                self.text_selection.end_col = terminal_col;
-                debug!("Updated text selection state: {:?}", self.text_selection);
+                // 글자 단위 선택은 줄 내용이 필요 없음 (드래그 중 매번 전체 버퍼를 읽지 않도록)
+                let lines = match self.session.text_selection.unit {
+                    SelectionUnit::Char => Vec::new(),
+                    _ => self.session.terminal.get_renderable_content().unwrap_or_default(),
+                };
+                self.session.text_selection.extend(&lines, terminal_row, terminal_col);
+                debug!("Updated text selection state: {:?}", self.session.text_selection);
             } else {
                 debug!("Failed to convert mouse coords ({}, {}) during update", col, row);
             }
@@ -582,198 +2118,367 @@ impl App {
     fn finish_text_selection(&mut self, col: u16, row: u16) -> Result<()> {
         if self.is_dragging {
             if let Some((terminal_col, terminal_row)) = self.mouse_to_terminal_coords(col, row) {
-                self.text_selection.end_row = terminal_row;
-                self.text_selection.end_col = terminal_col;
+                let lines = self.session.terminal.get_renderable_content().unwrap_or_default();
+                self.session.text_selection.extend(&lines, terminal_row, terminal_col);
             }
             self.is_dragging = false;
         }
         Ok(())
     }
 
-    /// 선택된 텍스트를 클립보드에 복사
-    fn copy_selected_text(&mut self) -> Result<()> {
-        if !self.text_selection.is_active {
+    /// 선택된 텍스트를 `target` 클립보드에 복사
+    /// 선택 영역 복사 - ANSI/HTML 형식이면 색과 글자 속성을 함께 (HTML은 일반 텍스트도 같이 올림)
+    fn copy_selected_text(&mut self, target: ClipboardTarget, format: ExportFormat) -> Result<()> {
+        if !self.session.text_selection.is_active {
             return Ok(());
         }
 
         // 터미널 내용 가져오기
-        let lines = match self.terminal.get_renderable_content() {
+        let lines = match self.session.terminal.get_renderable_content() {
             Ok(lines) => lines,
             Err(_) => return Ok(()),
         };
 
-        let mut selected_text = String::new();
-        let (start_row, start_col, end_row, end_col) = self.normalize_selection();
-
-        for row in start_row..=end_row {
-            if let Some(line) = lines.get(row as usize) {
-                let line_chars: Vec<char> = line.chars().collect();
-
-                let start_pos = if row == start_row { start_col as usize } else { 0 };
-                let end_pos = if row == end_row {
-                    std::cmp::min(end_col as usize + 1, line_chars.len())
-                } else {
-                    line_chars.len()
-                };
-
-                if start_pos < line_chars.len() {
-                    let selected_part: String = line_chars[start_pos..end_pos].iter().collect();
-                    selected_text.push_str(&selected_part);
-                }
-
-                // 줄 바꿈 추가 (마지막 줄 제외)
-                if row < end_row {
-                    selected_text.push('\n');
-                }
-            }
+        let line_ending = self.config.selection.copy_line_ending;
+        let mut selected_text = self.session.text_selection.text(&lines, line_ending);
+        if self.config.selection.trim_trailing_whitespace {
+            selected_text = selection::trim_line_ends(&selected_text, line_ending);
         }
 
         // 클립보드에 복사
         if !selected_text.trim().is_empty() {
-            if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                let _ = clipboard.set_text(selected_text);
+            let result = match format {
+                ExportFormat::Text => self.clipboard.set_text_to(target, &selected_text),
+                ExportFormat::Ansi | ExportFormat::Html => {
+                    let block = self.session.text_selection.mode == SelectionMode::Block;
+                    let rows = export::selection_rows(&self.session.terminal.history_rows(), self.session.text_selection.normalized(), block);
+                    let formatted = export::render_selection(&rows, format, &self.config.colors.theme, line_ending.separator());
+                    if format == ExportFormat::Html {
+                        self.clipboard.set_html_to(target, &formatted, &selected_text)
+                    } else {
+                        self.clipboard.set_text_to(target, &formatted)
+                    }
+                }
+            };
+            if let Err(e) = result {
+                warn!("{}", e);
             }
         }
 
         // 선택 해제
-        self.text_selection.is_active = false;
+        self.session.text_selection.is_active = false;
         Ok(())
     }
 
-    /// 선택 영역 정규화 (시작점이 끝점보다 뒤에 있을 경우 교환)
-    fn normalize_selection(&self) -> (u16, u16, u16, u16) {
-        let mut start_row = self.text_selection.start_row;
-        let mut start_col = self.text_selection.start_col;
-        let mut end_row = self.text_selection.end_row;
-        let mut end_col = self.text_selection.end_col;
+    /// 휠 스크롤 후 선택 영역 처리 - 설정에 따라 해제하거나 버퍼 좌표에 고정하여 유지
+    /// 드래그 중이면 스크롤된 화면의 마우스 위치까지 선택 확장
+    fn apply_wheel_selection_policy(&mut self, mouse_col: u16, mouse_row: u16) -> Result<()> {
+        self.session.text_selection.on_wheel_scroll(self.config.selection.on_wheel_scroll, self.is_dragging);
+        if self.is_dragging && self.session.text_selection.is_active {
+            self.update_text_selection(mouse_col, mouse_row)?;
+        }
+        Ok(())
+    }
+
+    /// 대체 화면 전환을 확인하고, 전환이 끝날 때까지 다시 그리기를 미뤄야 하는지 반환
+    fn defer_draw_for_screen_switch(&mut self) -> bool {
+        let now = std::time::Instant::now();
+        if self.session.terminal.sync_alt_screen() {
+            // 전환된 화면은 맨 아래부터 표시하고, 이전 화면 기준의 선택 영역은 해제
+            self.sync_scroll_offset();
+            self.session.auto_scroll_enabled = true;
+            self.session.text_selection.is_active = false;
+            if self.config.scrollback.alt_screen_settle {
+                self.session.screen_switch = Some(ScreenSwitchSettle::new(now, self.session.terminal.content_version()));
+            }
+        }
+
+        let Some(settle) = self.session.screen_switch.as_mut() else {
+            return false;
+        };
+        if settle.should_wait(now, self.session.terminal.content_version()) {
+            return true;
+        }
+        self.session.screen_switch = None;
+        false
+    }
+
+    /// PTY 출력으로 내용이 바뀌었으면 선택 영역 해제 (드래그 중에는 유지)
+    fn clear_stale_selection(&mut self) {
+        let version = self.session.terminal.content_version();
+        if version == self.session.seen_content_version {
+            return;
+        }
+        self.session.seen_content_version = version;
+
+        let alternate_screen = self.session.terminal.is_alternate_screen();
+        let scrolled_back = self.session.terminal.display_offset() > 0;
+        if self.session.text_selection.on_output(self.config.selection.clear_on_output, self.is_dragging, alternate_screen, scrolled_back) {
+            debug!("새 출력으로 선택 영역 해제");
+        }
+    }
+
+    /// 포커스 없는 pane 그리기 - 터미널 크기를 영역에 맞추고 현재 화면 내용만 표시
+    /// 입력 동기화 중이면 입력을 받는 pane임을 알 수 있게 테두리를 강조
+    fn render_unfocused_pane(f: &mut ratatui::Frame, session: &mut Session, area: Rect, broadcast: bool, config: &Config) {
+        let border = if broadcast && !session.held { Color::Yellow } else { Color::DarkGray };
+        let block = Block::default()
+            .title(format!(" {} ", session.tab_title()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border));
+        let inner = block.inner(area);
+        if let Err(e) = session.terminal.resize(render::pane_terminal_bounds(area, false)) {
+            warn!("pane 터미널 크기 변경 실패: {}", e);
+        }
 
-        // 시작점이 끝점보다 뒤에 있으면 교환
-        if start_row > end_row || (start_row == end_row && start_col > end_col) {
-            std::mem::swap(&mut start_row, &mut end_row);
-            std::mem::swap(&mut start_col, &mut end_col);
+        session.line_cache.refresh(&session.terminal, &config.colors, &config.smart);
+        let lines: Vec<Line> = session.line_cache.rows()
+            .iter()
+            .take(inner.height as usize)
+            .map(|row| render::borrowed_line(&row.line))
+            .collect();
+        render::fill_background(f, area, config.colors.background());
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        if session.held {
+            render::draw_exit_notice(f, inner, &session.exit_notice());
         }
+    }
 
-        (start_row, start_col, end_row, end_col)
+    /// 선택 영역 정규화 (시작점이 끝점보다 뒤에 있을 경우 교환)
+    fn normalize_selection(&self) -> (u16, u16, u16, u16) {
+        self.session.text_selection.normalized()
     }
 
     /// 커서 상태 업데이트 (Zed 방식 - 깜빡임 처리)
     fn update_cursor_state(&mut self) {
+        // 깜빡임을 끈 경우 항상 보이는 상태 유지 (녹화/스크린샷용 고정 프레임)
+        if !self.config.cursor.blink {
+            self.cursor_state.blink_state = true;
+            return;
+        }
+
         let now = std::time::Instant::now();
 
-        // 500ms마다 깜빡임
-        if now.duration_since(self.cursor_state.last_blink).as_millis() > 500 {
+        // 설정한 간격마다 깜빡임
+        if now.duration_since(self.cursor_state.last_blink).as_millis() > u128::from(self.config.cursor.blink_interval_ms) {
             self.cursor_state.blink_state = !self.cursor_state.blink_state;
             self.cursor_state.last_blink = now;
         }
     }
 
+    /// 호스트 터미널 커서의 모양과 깜빡임 설정 적용 (하드웨어 커서 사용 시)
+    fn apply_hardware_cursor_blink(&self) {
+        let style = match (self.config.cursor.shape, self.config.cursor.blink) {
+            (CursorShape::Block | CursorShape::Hollow, true) => SetCursorStyle::BlinkingBlock,
+            (CursorShape::Block | CursorShape::Hollow, false) => SetCursorStyle::SteadyBlock,
+            (CursorShape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+            (CursorShape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+            (CursorShape::Beam, true) => SetCursorStyle::BlinkingBar,
+            (CursorShape::Beam, false) => SetCursorStyle::SteadyBar,
+        };
+        let _ = execute!(io::stdout(), style);
+    }
+
     /// 스크롤바 렌더링
     fn render_scrollbar(&self, f: &mut ratatui::Frame, scrollbar_area: Rect) {
         if scrollbar_area.height < 3 {
             return; // 너무 작으면 스크롤바를 그리지 않음
         }
 
-        let visible_lines = self.terminal_area.height.saturating_sub(2) as usize;
+        let state = self.session.terminal.scroll_state();
 
         // 스크롤 가능한 경우에만 스크롤바 표시
-        if self.total_lines > visible_lines {
-            let scrollbar_height = scrollbar_area.height as usize;
-            let max_scroll = self.total_lines.saturating_sub(visible_lines) as f32;
-
-            // 스크롤바 썸(thumb) 크기 계산 - 보이는 영역 비율에 따라
-            let thumb_size = ((visible_lines as f32 / self.total_lines as f32) * scrollbar_height as f32).max(1.0) as usize;
-
-            // 스크롤바 썸 위치 계산
-            let scroll_ratio = if max_scroll > 0.0 {
-                self.scroll_offset as f32 / max_scroll
-            } else {
-                0.0
-            };
-            let thumb_position = (scroll_ratio * (scrollbar_height - thumb_size) as f32) as usize;
-
-            // 스크롤바 그리기
+        let scrollbar_height = scrollbar_area.height as usize;
+        if let Some((thumb_position, thumb_size)) = render::scrollbar_thumb(&state, scrollbar_height) {
+            // 스크롤바 그리기 (드래그 중인 썸은 밝게)
             for y in 0..scrollbar_height {
                 let is_thumb = y >= thumb_position && y < thumb_position + thumb_size;
                 let char = if is_thumb { '█' } else { '│' };
                 let style = if is_thumb {
-                    Style::default().fg(Color::White).bg(Color::Blue)
+                    let bg = if self.scrollbar_drag.is_some() { Color::LightBlue } else { Color::Blue };
+                    Style::default().fg(Color::White).bg(bg)
                 } else {
                     Style::default().fg(Color::DarkGray)
                 };
 
-                if scrollbar_area.y + (y as u16) < scrollbar_area.y + scrollbar_area.height {
-                    let span = Span::styled(char.to_string(), style);
-                    let line = Line::from(span);
-                    let para = Paragraph::new(line);
+                let span = Span::styled(char.to_string(), style);
+                let cell_area = Rect {
+                    x: scrollbar_area.x,
+                    y: scrollbar_area.y + y as u16,
+                    width: 1,
+                    height: 1,
+                };
+                f.render_widget(Paragraph::new(Line::from(span)), cell_area);
+            }
+        }
+    }
 
-                    let cell_area = Rect {
-                        x: scrollbar_area.x,
-                        y: scrollbar_area.y + y as u16,
-                        width: 1,
-                        height: 1,
-                    };
-                    f.render_widget(para, cell_area);
+    /// pane 구분선 드래그 처리 (구분선에서 시작한 조작이면 true)
+    /// 바뀐 크기는 다음 프레임에 배치하면서 각 pane 터미널에 적용됨
+    fn handle_divider_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.divider_drag = self.panes.divider_at(mouse.column, mouse.row);
+                self.divider_drag.is_some()
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let Some(drag) = &self.divider_drag else {
+                    return false;
+                };
+                self.panes.drag_divider(drag, mouse.column, mouse.row);
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.divider_drag.take().is_some(),
+            _ => false,
+        }
+    }
+
+    /// 스크롤바 클릭/드래그 처리 (스크롤바에서 시작한 조작이면 true)
+    /// 썸을 누르면 잡은 위치를 유지하며 끌고, 트랙을 누르면 썸 가운데를 그 위치로 옮긴 뒤 계속 끌 수 있음
+    fn handle_scrollbar_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let area = self.scrollbar_area;
+        let height = area.height as usize;
+        let y = mouse.row as i32 - area.y as i32;
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let on_scrollbar = area.height >= 3
+                    && area.contains(ratatui::layout::Position { x: mouse.column, y: mouse.row });
+                if !on_scrollbar {
+                    return false;
+                }
+                let state = self.session.terminal.scroll_state();
+                let Some((thumb_top, thumb_size)) = render::scrollbar_thumb(&state, height) else {
+                    return true;
+                };
+                let on_thumb = (thumb_top..thumb_top + thumb_size).contains(&(y as usize));
+                let grab = if on_thumb { y - thumb_top as i32 } else { thumb_size as i32 / 2 };
+                self.scrollbar_drag = Some(grab);
+                if !on_thumb {
+                    self.scroll_row_to_top(render::scrollbar_row(&state, height, y - grab));
                 }
+                true
             }
+            MouseEventKind::Drag(MouseButton::Left) | MouseEventKind::Moved => {
+                let Some(grab) = self.scrollbar_drag else {
+                    return false;
+                };
+                let state = self.session.terminal.scroll_state();
+                self.scroll_row_to_top(render::scrollbar_row(&state, height, y - grab));
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.scrollbar_drag.take().is_some(),
+            _ => false,
         }
     }
 
-    /// 실제 터미널 커서 위치 설정 (스크롤 오프셋 고려)
-    fn set_terminal_cursor_position(&self, f: &mut ratatui::Frame) {
+    /// 커서의 화면 좌표 (스크롤 오프셋 고려, 보이지 않으면 None)
+    fn cursor_screen_position(&self) -> Option<(u16, u16)> {
         let (cursor_col, cursor_row) = self.cursor_state.position;
 
         // 커서가 현재 보이는 영역에 있는지 확인
-        if cursor_row >= self.scroll_offset {
-            let relative_cursor_row = cursor_row - self.scroll_offset;
-            let visible_height = self.terminal_area.height.saturating_sub(2);
-
-            // 커서가 보이는 영역 내에 있으면 표시
-            if relative_cursor_row < visible_height {
-                let cursor_x = self.terminal_area.x + 1 + cursor_col;
-                let cursor_y = self.terminal_area.y + 1 + relative_cursor_row;
-                f.set_cursor_position((cursor_x, cursor_y));
-                return;
-            }
+        if cursor_row < self.session.scroll_offset {
+            return None;
+        }
+        let relative_cursor_row = cursor_row - self.session.scroll_offset;
+        let visible_height = self.terminal_area.height.saturating_sub(2);
+        let visible_width = self.terminal_area.width.saturating_sub(2);
+        if relative_cursor_row >= visible_height || cursor_col >= visible_width {
+            return None;
         }
 
-        // 커서가 보이지 않는 영역에 있으면 숨김
-        f.set_cursor_position((0, 0));
+        Some((
+            self.terminal_area.x + 1 + cursor_col,
+            self.terminal_area.y + 1 + relative_cursor_row,
+        ))
     }
 
-    /// Zed 방식 커서 렌더링 (사용하지 않음)
-    fn _render_cursor(&self, f: &mut ratatui::Frame, terminal_area: Rect) {
-        if !self.cursor_state.visible || !self.cursor_state.blink_state {
+    /// 실제 터미널 커서 위치 설정 (스크롤 오프셋 고려)
+    fn set_terminal_cursor_position(&self, f: &mut ratatui::Frame) {
+        match self.cursor_screen_position() {
+            Some(position) => f.set_cursor_position(position),
+            // 커서가 보이지 않는 영역에 있으면 숨김
+            None => f.set_cursor_position((0, 0)),
+        }
+    }
+
+    /// 성능 통계를 터미널 영역 오른쪽 위 구석에 표시
+    fn render_stats_overlay(&self, f: &mut ratatui::Frame) {
+        let Some(stats) = &self.stats else {
             return;
+        };
+
+        let lines: Vec<Line> = stats.lines().into_iter().map(Line::from).collect();
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 2;
+        let height = lines.len() as u16 + 2;
+        if self.terminal_area.width <= width + 2 || self.terminal_area.height <= height + 2 {
+            return; // 공간이 부족하면 표시하지 않음
         }
 
-        let (cursor_col, cursor_row) = self.cursor_state.position;
+        let area = Rect {
+            x: self.terminal_area.x + self.terminal_area.width - width - 1,
+            y: self.terminal_area.y + 1,
+            width,
+            height,
+        };
+        let overlay = Paragraph::new(lines)
+            .block(Block::default().title("stats").borders(Borders::ALL))
+            .style(Style::default().fg(Color::Green).bg(Color::Black));
+        f.render_widget(ratatui::widgets::Clear, area);
+        f.render_widget(overlay, area);
+    }
 
-        // 터미널 영역 내부 좌표 계산 (테두리 제외)
-        let inner_area = Rect {
-            x: terminal_area.x + 1,
-            y: terminal_area.y + 1,
-            width: terminal_area.width.saturating_sub(2),
-            height: terminal_area.height.saturating_sub(2),
+    /// 조합 중인 글자(preedit)를 커서 위치에 밑줄로 표시
+    fn render_preedit(&self, f: &mut ratatui::Frame) {
+        let Some(preedit) = self.input_method.preedit() else {
+            return;
+        };
+        let Some((x, y)) = self.cursor_screen_position() else {
+            return;
         };
 
-        // 커서 위치가 터미널 영역을 벗어나지 않는지 확인
-        if cursor_row >= inner_area.height || cursor_col >= inner_area.width {
+        // 한글은 2칸 너비
+        let available = self.terminal_area.x + self.terminal_area.width.saturating_sub(1) - x;
+        let preedit_width = unicode_width::UnicodeWidthStr::width(preedit.as_str()) as u16;
+
+        // 삽입 모드면 커서 뒤 내용을 조합 중인 글자 뒤로 밀어서 표시 (확정 후 실제 출력과 같은 위치)
+        let row = (y - self.terminal_area.y - 1) as usize;
+        let tail = self.session.terminal.get_visible_content().get(row)
+            .map(|line| render::local_echo_tail(
+                line, self.cursor_state.position.0 as usize, &preedit,
+                self.session.terminal.is_insert_mode(), available as usize))
+            .unwrap_or_default();
+        let width = (preedit_width + unicode_width::UnicodeWidthStr::width(tail.as_str()) as u16).min(available);
+
+        let preedit_span = Span::styled(
+            preedit,
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::UNDERLINED),
+        );
+        let line = Line::from(vec![preedit_span, Span::styled(tail, Style::default().fg(Color::White))]);
+        f.render_widget(Paragraph::new(line), Rect { x, y, width, height: 1 });
+    }
+
+    /// Zed 방식 소프트웨어 커서 렌더링 (하드웨어 커서 위치 지정을 신뢰할 수 없을 때 사용)
+    fn render_software_cursor(&self, f: &mut ratatui::Frame) {
+        if !self.cursor_state.visible || !self.cursor_state.blink_state {
             return;
         }
 
-        // 커서 렌더링 위치 계산
-        let cursor_x = inner_area.x + cursor_col;
-        let cursor_y = inner_area.y + cursor_row;
+        let Some((cursor_x, cursor_y)) = self.cursor_screen_position() else {
+            return;
+        };
 
-        // Zed 방식 커서 모양에 따른 렌더링
+        // Zed 방식 커서 모양에 따른 렌더링 (넓은 글자 위에서는 두 칸)
         let cursor_area = Rect {
             x: cursor_x,
             y: cursor_y,
-            width: 1,
+            width: columns::char_width(self.cursor_state.character).clamp(1, 2) as u16,
             height: 1,
         };
 
-        match self.cursor_state.shape {
+        match self.config.cursor.shape {
             CursorShape::Block => {
                 // 블록 커서 - 문자 반전
                 let cursor_char = if self.cursor_state.character == ' ' || self.cursor_state.character == '\0' {
@@ -785,8 +2490,8 @@ impl App {
                 let cursor_span = Span::styled(
                     cursor_char.to_string(),
                     Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::White)
+                        .fg(self.config.colors.background())
+                        .bg(self.config.colors.theme.cursor)
                         .add_modifier(Modifier::BOLD)
                 );
 
@@ -798,7 +2503,7 @@ impl App {
                 let cursor_span = Span::styled(
                     "_",
                     Style::default()
-                        .fg(Color::White)
+                        .fg(self.config.colors.theme.cursor)
                         .add_modifier(Modifier::BOLD)
                 );
 
@@ -810,7 +2515,7 @@ impl App {
                 let cursor_span = Span::styled(
                     "|",
                     Style::default()
-                        .fg(Color::White)
+                        .fg(self.config.colors.theme.cursor)
                         .add_modifier(Modifier::BOLD)
                 );
 
@@ -822,7 +2527,7 @@ impl App {
                 let cursor_span = Span::styled(
                     "□",
                     Style::default()
-                        .fg(Color::White)
+                        .fg(self.config.colors.theme.cursor)
                         .add_modifier(Modifier::BOLD)
                 );
 
@@ -832,48 +2537,79 @@ impl App {
         }
     }
 
-    /// 선택 영역이 있는 줄을 하이라이트하여 렌더링
-    fn render_line_with_selection(&self, line: String, row_idx: u16) -> Line<'_> {
-        if !self.text_selection.is_active {
-            return Line::from(Span::styled(line, Style::default().fg(Color::White)));
+    /// 선택 영역과 검색 일치 항목을 하이라이트하여 렌더링
+    /// `styles`는 줄의 글자별 셀 스타일 (선택/검색 등 강조가 없는 글자에 사용)
+    fn render_line_with_selection<'a>(&self, row: &'a render::CachedRow, row_idx: u16) -> Line<'a> {
+        let (line, styles) = (&row.text, &row.styles);
+        let default_style = Style::default().fg(self.config.colors.foreground());
+        // 화면의 URL/파일 경로 (밑줄 표시)
+        let links = if self.config.mouse.underline_links { mouse::links(line) } else { Vec::new() };
+        // 스마트 선택 일치 항목 (밑줄 표시, 줄 캐시와 함께 바뀐 줄에서만 다시 찾음)
+        let smart_matches = &row.smart;
+        if !self.session.text_selection.is_active && self.session.search.match_count() == 0 && self.changed_cells.is_empty() && !self.show_whitespace
+            && self.hovered_url.is_none() && links.is_empty() && smart_matches.is_empty() {
+            // 덧칠할 것이 없으면 캐시한 줄을 그대로 빌려 씀
+            return render::borrowed_line(&row.line);
         }
 
         let (start_row, start_col, end_row, end_col) = self.normalize_selection();
 
         // 디버그용 로깅 (첫 번째와 마지막 줄만 로그)
-        if row_idx == 0 || (row_idx < 5 && self.text_selection.is_active) {
+        if row_idx == 0 || (row_idx < 5 && self.session.text_selection.is_active) {
             debug!("Rendering line {} with selection: start=({},{}), end=({},{}), active={}",
-                row_idx, start_row, start_col, end_row, end_col, self.text_selection.is_active);
+                row_idx, start_row, start_col, end_row, end_col, self.session.text_selection.is_active);
         }
 
         // 현재 줄이 선택 영역에 포함되는지 확인
-        if row_idx < start_row || row_idx > end_row {
-            return Line::from(Span::styled(line, Style::default().fg(Color::White)));
-        }
-
-        let line_chars: Vec<char> = line.chars().collect();
-        let mut spans = Vec::new();
-
-        for (col_idx, &ch) in line_chars.iter().enumerate() {
-            let is_selected = if row_idx == start_row && row_idx == end_row {
-                // 단일 줄 선택
-                col_idx >= start_col as usize && col_idx <= end_col as usize
-            } else if row_idx == start_row {
-                // 시작 줄
-                col_idx >= start_col as usize
-            } else if row_idx == end_row {
-                // 끝 줄
-                col_idx <= end_col as usize
-            } else {
-                // 중간 줄 (전체 선택)
-                true
-            };
+        let row_in_selection = self.session.text_selection.is_active && row_idx >= start_row && row_idx <= end_row;
+
+        // 공백 표시 모드에서는 공백/탭을 마커로 그림 (복사에는 원래 줄 사용)
+        let hovered_url = self.hovered_url.as_ref()
+            .and_then(|(row, span)| (*row == row_idx).then_some(span));
+
+        // 그리기는 그리드 칸에 맞춘 글자로 (글자 위치는 `line`과 같음)
+        let display = &row.display;
+        let line_chars: Vec<(char, bool)> = if self.show_whitespace {
+            render::whitespace_markers(display)
+        } else {
+            display.chars().map(|ch| (ch, false)).collect()
+        };
+        let mut spans: Vec<Span> = Vec::new();
+        let mut cell_idx = 0; // `styles` 위치 (폭 0 글자는 앞 셀에 포함)
+
+        // 선택/스냅샷 비교는 화면 열, 검색/링크는 글자 위치 기준
+        for (col_idx, ((column, _), &(ch, is_marker))) in columns::char_columns(display).zip(&line_chars).enumerate() {
+            let width = columns::char_width(ch);
+            if width == 0 {
+                // 결합 문자는 앞 글자와 같은 칸에 그려지도록 앞 Span에 붙임
+                if let Some(last) = spans.last_mut() {
+                    last.content.to_mut().push(ch);
+                    continue;
+                }
+            }
+            let style_idx = cell_idx;
+            cell_idx += 1;
+
+            // 일반 선택은 시작/끝 줄 사이 전체, 사각형 선택은 열 범위 안만 (넓은 글자는 한 칸만 걸쳐도 선택)
+            let is_selected = row_in_selection && (column..column + width.max(1))
+                .any(|col| self.session.text_selection.contains(row_idx, col as u16));
 
             let style = if is_selected {
-                Style::default().fg(Color::Black).bg(Color::White) // 선택된 텍스트는 반전
+                let theme = &self.config.colors.theme;
+                Style::default().fg(theme.selection_foreground).bg(theme.selection_background) // 선택된 텍스트
+            } else if let Some(style) = self.session.search.style_at(row_idx as usize, col_idx, &self.config.search) {
+                style // 검색 일치 항목 (현재 항목은 별도 스타일)
+            } else if self.changed_cells.contains(&(row_idx as usize, column)) {
+                Style::default().fg(Color::Black).bg(Color::Magenta) // 스냅샷과 달라진 셀
+            } else if is_marker {
+                render::whitespace_marker_style()
             } else {
-                Style::default().fg(Color::White)
+                styles.get(style_idx).copied().unwrap_or(default_style)
             };
+            let in_link = hovered_url.is_some_and(|span| span.contains(col_idx))
+                || links.iter().any(|span| span.contains(col_idx))
+                || smart_matches.iter().any(|found| found.contains(col_idx));
+            let style = if in_link { style.add_modifier(Modifier::UNDERLINED) } else { style };
 
             spans.push(Span::styled(ch.to_string(), style));
         }
@@ -881,6 +2617,13 @@ impl App {
         Line::from(spans)
     }
 
+    /// 마우스 위치의 전체 버퍼 행, 화면 열과 그 줄 내용 (줄 안의 글자 위치는 `columns::column_to_index`로 변환)
+    fn line_at_mouse(&self, mouse_col: u16, mouse_row: u16) -> Option<(u16, usize, String)> {
+        let (col, row) = self.mouse_to_terminal_coords(mouse_col, mouse_row)?;
+        let line = self.session.terminal.get_renderable_content().ok()?.into_iter().nth(row as usize)?;
+        Some((row, col as usize, line))
+    }
+
     /// 마우스 좌표를 터미널 좌표로 변환 (Zed 방식)
     fn mouse_to_terminal_coords(&self, mouse_col: u16, mouse_row: u16) -> Option<(u16, u16)> {
         // 터미널 영역의 경계 계산 (테두리 포함)
@@ -902,7 +2645,7 @@ impl App {
         let relative_terminal_row = mouse_row.saturating_sub(area_top + 1);
 
         // 스크롤 오프셋을 고려하여 전체 버퍼에서의 절대 위치 계산
-        let terminal_row = relative_terminal_row + self.scroll_offset;
+        let terminal_row = relative_terminal_row + self.session.scroll_offset;
 
         // 터미널 영역 내부 크기 확인
         let inner_width = self.terminal_area.width.saturating_sub(2);
@@ -919,18 +2662,295 @@ impl App {
         }
     }
 
+    /// 현재 세션의 PTY 출력 기록 켜기/끄기
+    fn toggle_output_log(&mut self) {
+        if let Some(path) = self.session.terminal.stop_output_log() {
+            info!("출력 기록 중지: {}", path.display());
+            return;
+        }
+        let Some(directory) = self.config.output_log.directory() else {
+            warn!("출력 기록 디렉토리를 알 수 없음");
+            return;
+        };
+        match self.session.terminal.start_output_log(&directory, self.config.output_log.max_bytes()) {
+            Ok(path) => info!("출력 기록 시작: {}", path.display()),
+            Err(e) => warn!("출력 기록 시작 실패 ({}): {}", directory.display(), e),
+        }
+    }
+
+    /// 현재 세션의 asciicast 녹화 켜기/끄기
+    fn toggle_recording(&mut self) {
+        if let Some(path) = self.session.terminal.stop_recording() {
+            info!("녹화 중지: {}", path.display());
+            return;
+        }
+        let Some(directory) = self.config.recording.directory() else {
+            warn!("녹화 디렉토리를 알 수 없음");
+            return;
+        };
+        let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        let path = directory.join(format!("recording-{}.cast", output_log::timestamp(secs)));
+        match self.session.terminal.start_recording(&path, Some(self.session.tab_title())) {
+            Ok(()) => info!("녹화 시작: {}", path.display()),
+            Err(e) => warn!("녹화 시작 실패 ({}): {}", path.display(), e),
+        }
+    }
+
+    /// 현재 세션의 스크롤백 전체를 내보내기 디렉토리에 파일로 저장
+    fn export_scrollback(&mut self, format: ExportFormat) {
+        let Some(directory) = self.config.export.directory() else {
+            warn!("내보내기 디렉토리를 알 수 없음");
+            return;
+        };
+        let rows = self.session.terminal.history_rows();
+        match export::save(&directory, &rows, format, &self.config.colors.theme, &self.session.tab_title()) {
+            Ok(path) => info!("스크롤백 내보내기: {}", path.display()),
+            Err(e) => warn!("스크롤백 내보내기 실패 ({}): {}", directory.display(), e),
+        }
+    }
+
+    /// 현재 화면을 이름 붙은 스냅샷으로 저장
+    fn capture_snapshot(&mut self) {
+        let name = format!("snap-{}", self.snapshots.len() + 1);
+        debug!("화면 스냅샷 저장: {}", name);
+        self.snapshots.push(FrameSnapshot::new(name, self.session.terminal.get_renderable_cells()));
+    }
+
+    /// 비교할 스냅샷 순환 (끄기 -> 최신 -> 이전 ... -> 가장 오래된 것 -> 끄기)
+    fn cycle_snapshot_diff(&mut self) {
+        self.diff_snapshot = match self.diff_snapshot {
+            None => self.snapshots.len().checked_sub(1),
+            Some(idx) => idx.checked_sub(1),
+        };
+        if self.diff_snapshot.is_none() {
+            self.changed_cells.clear();
+        }
+    }
+
+    /// 비교 중인 스냅샷과 현재 화면의 바뀐 셀 계산 (first_row: 화면 첫 줄의 전체 버퍼 기준 행)
+    fn update_snapshot_diff(&mut self, first_row: usize) {
+        let Some(snapshot) = self.diff_snapshot.map(|idx| &self.snapshots[idx]) else {
+            return;
+        };
+        self.changed_cells = snapshot.changed_cells(&self.session.terminal.get_renderable_cells())
+            .into_iter()
+            .map(|(row, col)| (first_row + row, col))
+            .collect();
+    }
+
+    /// 검색 모드 키 처리
+    /// 문자 입력/Backspace: 검색어 편집, Enter/Up: 이전 일치 항목, Shift+Enter/Down: 다음 일치 항목, Esc: 검색 종료
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(query) = self.search_prompt.as_mut() else {
+            return;
+        };
+
+        // 검색어 편집 중: 글자 입력, Enter로 확정 / 확정 후: n(이전), N(다음)으로 이동, `/`로 다시 편집
+        let typing = !key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.search_prompt = None;
+                self.search_browsing = false;
+                self.session.search.clear();
+                return;
+            }
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.session.search.focus_newer();
+            }
+            KeyCode::Enter if !self.search_browsing => {
+                self.search_browsing = true;
+            }
+            KeyCode::Down => {
+                self.session.search.focus_newer();
+            }
+            KeyCode::Enter | KeyCode::Up => {
+                self.session.search.focus_older();
+            }
+            KeyCode::Char('n') if typing && self.search_browsing => {
+                self.session.search.focus_older();
+            }
+            KeyCode::Char('N') if typing && self.search_browsing => {
+                self.session.search.focus_newer();
+            }
+            KeyCode::Char('/') if self.search_browsing => {
+                self.search_browsing = false;
+                return;
+            }
+            KeyCode::Backspace => {
+                self.search_browsing = false;
+                query.pop();
+                self.run_search();
+            }
+            KeyCode::Char(c) if typing && !self.search_browsing => {
+                query.push(c);
+                self.run_search();
+            }
+            _ => return,
+        }
+
+        self.scroll_to_search_match();
+    }
+
+    /// 현재 검색어로 전체 버퍼 검색
+    fn run_search(&mut self) {
+        let query = self.search_prompt.clone().unwrap_or_default();
+        let lines = self.session.terminal.get_renderable_content().unwrap_or_default();
+        self.session.search.search(&query, &lines);
+        debug!("검색: {:?} - {}개 일치", query, self.session.search.match_count());
+    }
+
+    /// 현재 일치 항목이 보이도록 스크롤
+    fn scroll_to_search_match(&mut self) {
+        let Some(row) = self.session.search.current_match().map(|m| m.row) else {
+            return;
+        };
+
+        let visible_height = self.terminal_area.height.saturating_sub(2) as usize;
+        let first_row = self.session.scroll_offset as usize;
+        if row < first_row || row >= first_row + visible_height {
+            // 일치 항목이 있는 줄을 화면 맨 위에 표시
+            self.scroll_row_to_top(row);
+        } else {
+            self.sync_scroll_offset();
+        }
+    }
+
+    /// 스크롤백 지우기 - 되돌리기를 위해 설정된 줄 수까지 저장
+    fn clear_scrollback(&mut self) {
+        let config = &self.config.scrollback;
+        let snapshot = self.session.terminal.clear_scrollback(config.clear_undo_max_lines);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(config.clear_undo_timeout_secs);
+
+        debug!("스크롤백 지우기 (저장: {}줄)", snapshot.as_ref().map_or(0, |s| s.len()));
+        self.session.cleared_scrollback = snapshot.map(|snapshot| (snapshot, deadline));
+        self.session.terminal.scroll_to(ScrollPosition::Bottom);
+        self.sync_scroll_offset();
+        self.session.auto_scroll_enabled = true;
+    }
+
+    /// 기한 내라면 지운 스크롤백 복원
+    fn undo_clear_scrollback(&mut self) {
+        match self.session.cleared_scrollback.take() {
+            Some((snapshot, deadline)) if std::time::Instant::now() <= deadline => {
+                let restored = self.session.terminal.restore_scrollback(snapshot);
+                debug!("스크롤백 복원: {}", if restored { "성공" } else { "열 수가 바뀌어 실패" });
+            }
+            _ => debug!("복원할 스크롤백 없음"),
+        }
+    }
+
+    /// 되돌리기 기한이 지난 스크롤백 스냅샷 해제
+    fn expire_scrollback_undo(&mut self) {
+        if let Some((_, deadline)) = &self.session.cleared_scrollback {
+            if std::time::Instant::now() > *deadline {
+                self.session.cleared_scrollback = None;
+            }
+        }
+    }
+
     /// 자동 스크롤 상태 업데이트 (3초 타이머 관리)
     fn update_auto_scroll(&mut self) {
         let now = std::time::Instant::now();
 
         // 수동 스크롤 후 3초가 지나면 자동 추적만 재활성화 (위치 이동은 하지 않음)
-        if !self.auto_scroll_enabled
-            && now.duration_since(self.last_manual_scroll).as_secs() >= 3 {
-            self.auto_scroll_enabled = true;
+        if !self.session.auto_scroll_enabled
+            && now.duration_since(self.session.last_manual_scroll).as_secs() >= 3 {
+            self.session.auto_scroll_enabled = true;
             debug!("자동 추적 재활성화됨 (3초 타임아웃) - 입력 시에만 커서 위치로 이동");
         }
     }
 
+    /// 상태 표시줄 구역별 내용 (포커스 세션 기준, 벨/활동은 백그라운드 탭까지)
+    fn status_values(&mut self, scroll_state: &ScrollState, broadcast: bool) -> status::StatusValues {
+        let directory = self.session.terminal.new_session_directory();
+        let terminal = &self.session.terminal;
+        self.session.status.refresh(std::time::Instant::now(), directory.as_deref(), || {
+            terminal.foreground_process_id().and_then(status::process_name)
+        });
+
+        // 벨이 울렸거나 출력을 다시 시작했거나 멈춘 백그라운드 탭 번호
+        let (left, right) = self.tabs.background();
+        let tab_numbers = |flag: fn(&Session) -> bool| {
+            let numbers: Vec<String> = left.iter().enumerate()
+                .chain(right.iter().enumerate().map(|(idx, tab)| (left.len() + 1 + idx, tab)))
+                .filter(|(_, tab)| tab.sessions().any(flag))
+                .map(|(idx, _)| (idx + 1).to_string())
+                .collect();
+            (!numbers.is_empty()).then(|| numbers.join(","))
+        };
+
+        let mut indicators = Vec::new();
+        if self.session.bell || self.panes.others().any(|(_, session)| session.bell) {
+            indicators.push("🔔".to_string());
+        }
+        if let Some(tabs) = tab_numbers(|session| session.bell) {
+            indicators.push(format!("🔔 탭 {}", tabs));
+        }
+        if let Some(tabs) = tab_numbers(|session| session.activity) {
+            indicators.push(format!("활동 탭 {}", tabs));
+        }
+        if let Some(tabs) = tab_numbers(|session| session.silence) {
+            indicators.push(format!("멈춘 탭 {}", tabs));
+        }
+        // 셸이 OSC 133으로 보고한 마지막 명령이 실패했으면 종료 코드 표시
+        if let Some(code) = self.session.terminal.last_exit_code().filter(|&code| code != 0) {
+            indicators.push(format!("종료 코드 {}", code));
+        }
+        let flags = [
+            (!self.session.terminal.is_autowrap(), "줄 바꿈 꺼짐"),
+            (broadcast, "입력 동기화"),
+            (self.session.terminal.recording_path().is_some(), "녹화 중"),
+            (self.session.terminal.output_log_path().is_some(), "출력 기록 중"),
+        ];
+        indicators.extend(flags.into_iter().filter(|(on, _)| *on).map(|(_, text)| text.to_string()));
+
+        status::StatusValues {
+            mode: self.mode_message(),
+            process: self.session.status.process.clone(),
+            directory: directory.map(|path| Self::abbreviate_path(&path)),
+            git: self.session.status.git.clone(),
+            scroll: Some(status::scroll_position(scroll_state)),
+            indicators,
+        }
+    }
+
+    /// 상태 표시줄 모드 구역 - 입력을 기다리는 모드나 잠깐 보여 줄 안내 (평소에는 None)
+    fn mode_message(&self) -> Option<String> {
+        let message = if self.quit_confirm.is_pending() {
+            "한 번 더 누르면 마지막 세션을 닫고 종료됩니다".to_string()
+        } else if let Some(error) = &self.config_error {
+            format!("설정 오류: {}", error)
+        } else if let Some(command) = self.pending_mark {
+            match command {
+                MarkCommand::Set => "마크 설정: 이름(a-z) 입력".to_string(),
+                MarkCommand::Jump => "마크로 이동: 이름(a-z) 입력".to_string(),
+            }
+        } else if let Some(hints) = &self.hints {
+            format!("힌트: 라벨 입력 (대문자로 입력하면 복사, Esc: 취소) {}", hints.typed())
+        } else if self.quote_next {
+            "다음 키를 그대로 전송".to_string()
+        } else if self.detach_prefix {
+            "D: 세션 분리, Ctrl+B: Ctrl+B 전송".to_string()
+        } else if let Some(snapshot) = self.diff_snapshot.map(|idx| &self.snapshots[idx]) {
+            // 스냅샷 비교 중
+            format!("비교: {} - {}셀 변경", snapshot.name(), self.changed_cells.len())
+        } else if let Some(query) = &self.search_prompt {
+            // 검색 모드: 검색어와 현재 일치 항목 위치 표시
+            let position = self.session.search.current_index().map_or(0, |idx| idx + 1);
+            let hint = if self.search_browsing { " n/N: 이동, /: 편집" } else { "" };
+            format!("검색: {} ({}/{}){}", query, position, self.session.search.match_count(), hint)
+        } else if let Some((_, deadline)) = &self.session.cleared_scrollback {
+            // 스크롤백 지우기 되돌리기 가능 표시
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now()).as_secs() + 1;
+            format!("Ctrl+Shift+Z: 스크롤백 복원 {}초", remaining)
+        } else if self.session.text_selection.is_active {
+            if self.is_dragging { "선택 중" } else { "선택됨" }.to_string()
+        } else {
+            return None;
+        };
+        Some(message)
+    }
+
     /// 경로를 축약하여 상위\상위\마지막폴더 형태로 변환
     fn abbreviate_path(path: &std::path::Path) -> String {
         let components: Vec<_> = path.components()
@@ -955,35 +2975,77 @@ impl App {
 
     /// 커서 위치로 자동 스크롤
     fn auto_scroll_to_cursor(&mut self) {
-        let (_, cursor_row, _) = self.terminal.get_renderable_cursor();
-        let visible_lines = self.terminal_area.height.saturating_sub(2) as u16;
-
-        // 현재 보이는 영역의 범위 계산
-        let view_start = self.scroll_offset;
-        let view_end = self.scroll_offset + visible_lines;
-
-        // 커서가 화면을 벗어났는지 확인하고 조정
-        if cursor_row < view_start {
-            // 커서가 화면 위에 있으면 위로 스크롤
-            self.scroll_offset = cursor_row;
-            debug!("자동 스크롤 위로: 커서={}행, offset={}", cursor_row, self.scroll_offset);
-        } else if cursor_row >= view_end {
-            // 커서가 화면 아래에 있으면 아래로 스크롤
-            self.scroll_offset = cursor_row.saturating_sub(visible_lines - 1);
-            debug!("자동 스크롤 아래로: 커서={}행, offset={}", cursor_row, self.scroll_offset);
-        }
-
-        // 스크롤 범위 제한
-        if self.total_lines > visible_lines as usize {
-            let max_scroll = self.total_lines.saturating_sub(visible_lines as usize) as u16;
-            self.scroll_offset = self.scroll_offset.min(max_scroll);
+        self.session.command_block = None;
+        self.session.smooth_scroll.stop();
+        // 커서는 항상 맨 아래 화면에 있으므로 히스토리를 보고 있으면 맨 아래로 이동
+        if self.session.terminal.scroll_state().offset > 0 {
+            self.session.terminal.scroll_to(ScrollPosition::Bottom);
+            debug!("자동 스크롤: 커서 위치(맨 아래)로 이동");
+        }
+        self.sync_scroll_offset();
+    }
+
+    /// 대체 화면(vim, less 등)이면 휠 스크롤을 화살표 키로 바꿔 프로그램에 보내고 true
+    fn send_alternate_scroll(&mut self, lines: i32) -> bool {
+        let Some(app_cursor) = self.session.terminal.alternate_scroll_mode() else {
+            return false;
+        };
+        debug!("Alternate screen wheel: {} lines as arrow keys", lines);
+        self.session.smooth_scroll.stop();
+        let _ = self.session.terminal.input(&mouse::alternate_scroll_bytes(lines, app_cursor));
+        true
+    }
+
+    /// 휠 스크롤 (양수: 위로)
+    /// 부드러운 스크롤이 켜져 있으면 바로 움직이지 않고 다음 프레임부터 나눠 적용
+    fn scroll_wheel(&mut self, lines: i32) {
+        if self.config.scrollback.smooth {
+            self.session.smooth_scroll.impulse(lines, std::time::Instant::now());
         } else {
-            self.scroll_offset = 0;
+            self.session.terminal.scroll_by(lines);
+        }
+        self.sync_scroll_offset();
+    }
+
+    /// 부드러운 스크롤을 현재 시각까지 진행
+    fn advance_smooth_scroll(&mut self) {
+        let lines = self.session.smooth_scroll.step(std::time::Instant::now());
+        if lines != 0 {
+            self.session.terminal.scroll_by(lines);
+            self.sync_scroll_offset();
         }
     }
 
+    /// Terminal의 스크롤 상태를 화면 표시용 값(scroll_offset, total_lines)에 반영
+    fn sync_scroll_offset(&mut self) -> ScrollState {
+        let state = self.session.terminal.scroll_state();
+        let visible_height = self.terminal_area.height.saturating_sub(2) as usize;
+
+        // UI 영역이 터미널 화면보다 작으면 커서가 있는 아래쪽을 보여줌
+        let hidden_rows = state.viewport.saturating_sub(visible_height);
+        self.session.total_lines = state.total;
+        self.session.scroll_offset = (state.first_visible_row() + hidden_rows) as u16;
+        state
+    }
+
     /// 정상 종료 처리
     fn cleanup(&mut self) -> Result<()> {
+        // 다음 실행 시 --restore(또는 session.restore 설정)로 복원할 수 있도록 레이아웃 저장
+        // -e 명령이나 시리얼/연결 실행은 복원하지 않으므로 저장해 둔 레이아웃을 덮어쓰지 않음
+        if let Some(path) = SavedLayout::default_path().filter(|_| !self.cli.is_one_off()) {
+            match self.current_layout().save(&path) {
+                Ok(()) => info!("레이아웃 저장: {}", path.display()),
+                Err(e) => error!("레이아웃 저장 실패: {:?}", e),
+            }
+        }
+
+        // 분리가 아니라 종료했으면 데몬의 셸도 끝냄 (신호로 종료된 경우는 다시 붙을 수 있게 남김)
+        if !self.detaching && !self.shutdown_signal.load(Ordering::Relaxed) {
+            for session in self.all_sessions_mut() {
+                session.kill_daemon_session();
+            }
+        }
+
         // 터미널은 자동으로 정리됩니다 (Drop trait 구현)
         // 현재 alacritty_terminal은 kill 메서드가 없으므로
         // 자동 정리에 맡깁니다
@@ -1004,6 +3066,7 @@ fn setup_signal_handlers() -> Result<Arc<AtomicBool>> {
         std::thread::spawn(move || {
             for _ in signals.forever() {
                 shutdown_clone.store(true, Ordering::Relaxed);
+                wakeup::wake();
                 break;
             }
         });
@@ -1034,38 +3097,104 @@ fn main() -> Result<()> {
 
     info!("터미널 앱 시작");
 
+    // 명령줄 인자 (잘못된 인자는 화면을 바꾸기 전에 사용법과 함께 보고)
+    let cli = match cli::CliArgs::parse(std::env::args().skip(1)) {
+        Ok(cli) if cli.help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // --daemon: 화면 없이 세션 데몬으로 실행 (데몬 모드 클라이언트가 자동으로 띄움)
+    if cli.daemon {
+        #[cfg(unix)]
+        return daemon::run();
+        #[cfg(not(unix))]
+        anyhow::bail!("이 플랫폼에서는 세션 데몬을 지원하지 않음");
+    }
+
+    // --play <파일>: 셸 대신 asciicast 녹화 재생 (파일 오류는 화면을 바꾸기 전에 보고)
+    let cast = match &cli.play {
+        Some(path) => Some(asciicast::Cast::load(path)?),
+        None => None,
+    };
+
+    // 명령줄 값이 설정 파일/환경 변수보다 우선 (없는 프로필은 화면을 바꾸기 전에 보고)
+    let mut config = Config::load();
+    if let Err(e) = cli.apply(&mut config) {
+        eprintln!("{:#}", e);
+        std::process::exit(2);
+    }
+
     // 신호 핸들러 설정
     let shutdown_signal = setup_signal_handlers()?;
 
     // 터미널 설정
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, SetTitle("Rust Terminal App"), EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, SetTitle(title::DEFAULT_TITLE), EnterAlternateScreen, EnableMouseCapture, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut ratatui_terminal = RatatuiTerminal::new(backend)?;
 
+
+    // kitty 키보드 프로토콜을 켰으면 호스트 터미널에도 요청 (Ctrl+I와 Tab 등을 구분하고 반복/뗌 이벤트를 받음)
+    let keyboard_enhanced = cast.is_none() && config.keyboard.kitty
+        && crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if keyboard_enhanced {
+        execute!(ratatui_terminal.backend_mut(), PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES | KeyboardEnhancementFlags::REPORT_EVENT_TYPES))?;
+    }
+
+    // --restore, --attach 또는 session.restore 설정: 마지막으로 저장된 레이아웃 복원 (-e 명령 등 한 번 쓰고 끝내는 실행은 복원하지 않음)
+    let restore_layout = if !cli.is_one_off() && (config.session.restore || cli.restore || cli.attach) {
+        SavedLayout::default_path().and_then(|path| match SavedLayout::load(&path) {
+            Ok(layout) => Some(layout),
+            Err(e) => {
+                error!("레이아웃 복원 실패: {:?}", e);
+                None
+            }
+        })
+    } else {
+        None
+    };
+
     // 애플리케이션 생성 및 실행
-    let app_result = match App::new(shutdown_signal.clone()) {
-        Ok(mut app) => {
-            info!("앱 실행 시작");
-            let result = app.run(&mut ratatui_terminal);
-            info!("앱 실행 완료");
-            app.cleanup().ok(); // 정리 작업 수행
-            result
+    let app_result = if let Some(cast) = cast {
+        info!("녹화 재생: {}x{}, {:.1}초", cast.header.width, cast.header.height, cast.duration());
+        playback::run(&mut ratatui_terminal, &cast, &config)
+    } else {
+        match App::new(config, cli, shutdown_signal.clone(), restore_layout) {
+            Ok(mut app) => {
+                info!("앱 실행 시작");
+                let result = app.run(&mut ratatui_terminal);
+                info!("앱 실행 완료");
+                app.cleanup().ok(); // 정리 작업 수행
+                result
+            }
+            Err(e) => {
+                error!("앱 생성 실패: {:?}", e);
+                Err(e)
+            }
         }
-        Err(e) => {
-            error!("앱 생성 실패: {:?}", e);
-            Err(e)
-        },
     };
 
     // 터미널 복원
     let restore_result = (|| -> Result<()> {
         disable_raw_mode()?;
+        if keyboard_enhanced {
+            execute!(ratatui_terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+        }
         execute!(
             ratatui_terminal.backend_mut(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableFocusChange,
+            SetCursorStyle::DefaultUserShape
         )?;
         ratatui_terminal.show_cursor()?;
         Ok(())
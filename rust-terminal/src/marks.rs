@@ -0,0 +1,109 @@
+//! 사용자 마크(북마크) 모듈
+//! 긴 출력에서 위치를 글자 이름으로 표시해 두고 나중에 그 줄로 돌아감
+//!
+//! 마크는 전체 버퍼 행(히스토리 맨 위 = 0) 기준이지만, 히스토리가 최대 크기에 도달하면
+//! 오래된 줄이 버려지면서 행 번호가 위로 밀리므로 줄 내용도 함께 저장하여 다시 찾음
+
+use std::collections::HashMap;
+
+/// 글자 입력을 기다리는 마크 명령
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkCommand {
+    Set,
+    Jump,
+}
+
+/// 저장된 마크 위치
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Mark {
+    row: usize,   // 마지막으로 확인한 전체 버퍼 행
+    text: String, // 마크한 줄 내용 (행이 밀렸을 때 다시 찾는 용도)
+}
+
+/// 글자별 마크 목록
+#[derive(Debug, Clone, Default)]
+pub struct Marks {
+    marks: HashMap<char, Mark>,
+}
+
+impl Marks {
+    /// 마크 이름으로 쓸 수 있는 글자인지 확인 (영문자)
+    fn is_valid_name(name: char) -> bool {
+        name.is_ascii_alphabetic()
+    }
+
+    /// 전체 버퍼 행에 마크 설정 (같은 이름의 마크는 덮어씀)
+    pub fn set(&mut self, name: char, row: usize, lines: &[String]) -> bool {
+        let Some(line) = lines.get(row) else {
+            return false;
+        };
+        if !Self::is_valid_name(name) {
+            return false;
+        }
+        self.marks.insert(name, Mark { row, text: line.clone() });
+        true
+    }
+
+    /// 마크의 현재 행 찾기
+    /// 저장한 행의 내용이 바뀌었으면 위쪽(히스토리가 밀린 방향)에서 같은 내용의 줄을 찾고,
+    /// 찾지 못하면 히스토리에서 사라진 것으로 보고 마크 삭제
+    pub fn resolve(&mut self, name: char, lines: &[String]) -> Option<usize> {
+        let mark = self.marks.get_mut(&name)?;
+        let start = mark.row.min(lines.len().saturating_sub(1));
+        match (0..=start).rev().find(|&row| lines.get(row) == Some(&mark.text)) {
+            Some(row) => {
+                mark.row = row;
+                Some(row)
+            }
+            None => {
+                self.marks.remove(&name);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_set_and_jump_to_mark() {
+        let buffer = lines(&["$ make", "error: foo", "$ ls", "a b c"]);
+        let mut marks = Marks::default();
+        assert!(marks.set('a', 1, &buffer));
+        assert!(marks.set('b', 3, &buffer));
+        assert!(!marks.set('1', 0, &buffer));
+        assert!(!marks.set('c', 10, &buffer));
+
+        assert_eq!(marks.resolve('a', &buffer), Some(1));
+        assert_eq!(marks.resolve('b', &buffer), Some(3));
+        assert_eq!(marks.resolve('z', &buffer), None);
+
+        // 같은 이름으로 다시 설정하면 덮어씀
+        marks.set('a', 2, &buffer);
+        assert_eq!(marks.resolve('a', &buffer), Some(2));
+    }
+
+    #[test]
+    fn test_mark_follows_shifted_history_and_expires() {
+        let mut buffer = lines(&["one", "two", "three", "four"]);
+        let mut marks = Marks::default();
+        marks.set('m', 1, &buffer);
+
+        // 히스토리가 가득 차 맨 위 줄이 버려지면 행 번호가 위로 밀림
+        buffer.remove(0);
+        buffer.push("five".to_string());
+        assert_eq!(marks.resolve('m', &buffer), Some(0));
+
+        // 마크한 줄까지 버려지면 마크가 무효화됨
+        buffer.remove(0);
+        buffer.push("six".to_string());
+        assert_eq!(marks.resolve('m', &buffer), None);
+        assert_eq!(marks.resolve('m', &buffer), None);
+    }
+}
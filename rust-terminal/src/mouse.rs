@@ -0,0 +1,421 @@
+//! 마우스 입력 보조 모듈
+//! 연속 클릭(더블/트리플 클릭) 판정, 줄에서 URL/파일 경로 찾기,
+//! 마우스 보고 모드를 켠 프로그램(vim, htop 등)에 보낼 마우스 이벤트 인코딩
+
+use crate::config::MouseConfig;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// URL로 인식하는 스킴
+const URL_SCHEMES: &[&str] = &["https://", "http://", "file://", "ftp://"];
+
+/// 파일 경로로 인식하는 시작 부분
+const PATH_PREFIXES: &[&str] = &["/", "~/", "./", "../"];
+
+/// 연속 클릭 횟수 추적기
+#[derive(Debug, Clone, Default)]
+pub struct ClickTracker {
+    last_click: Option<(Instant, u16, u16)>, // (시각, col, row)
+    count: u8,
+}
+
+impl ClickTracker {
+    /// 클릭을 기록하고 현재 연속 클릭 횟수(1~3)를 반환
+    pub fn register(&mut self, col: u16, row: u16, now: Instant, config: &MouseConfig) -> u8 {
+        let is_continuation = match self.last_click {
+            Some((last_time, last_col, last_row)) => {
+                now.saturating_duration_since(last_time) <= config.multi_click_interval()
+                    && col.abs_diff(last_col) <= config.multi_click_col_tolerance
+                    && row.abs_diff(last_row) <= config.multi_click_row_tolerance
+            }
+            None => false,
+        };
+
+        // 트리플 클릭 다음 클릭은 다시 싱글 클릭으로 순환
+        self.count = if is_continuation { self.count % 3 + 1 } else { 1 };
+        self.last_click = Some((now, col, row));
+        self.count
+    }
+}
+
+/// 줄에서 찾은 URL 또는 파일 경로 위치 (문자 단위 열, `end`는 포함하지 않음)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlSpan {
+    pub start: usize,
+    pub end: usize,
+    pub url: String,
+}
+
+impl UrlSpan {
+    pub fn contains(&self, col: usize) -> bool {
+        (self.start..self.end).contains(&col)
+    }
+
+    /// 스킴 없이 찾은 파일 경로인지 확인
+    pub fn is_path(&self) -> bool {
+        !URL_SCHEMES.iter().any(|scheme| self.url.starts_with(scheme))
+    }
+}
+
+/// 줄에 있는 모든 URL과 파일 경로 (왼쪽부터)
+/// 공백/따옴표/꺾쇠로 구분한 단어마다 하나씩 찾음
+pub fn links(line: &str) -> Vec<UrlSpan> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = Vec::new();
+    let mut word_start = 0;
+    for idx in 0..=chars.len() {
+        if chars.get(idx).is_none_or(|&c| is_link_delimiter(c)) {
+            spans.extend(link_in_word(&chars, word_start, idx));
+            word_start = idx + 1;
+        }
+    }
+    spans
+}
+
+/// 줄의 `col` 열에 걸친 URL 또는 파일 경로 찾기
+pub fn url_at(line: &str, col: usize) -> Option<UrlSpan> {
+    links(line).into_iter().find(|span| span.contains(col))
+}
+
+fn is_link_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\'' | '`')
+}
+
+/// 단어(`chars[word_start..word_end]`) 안의 URL 또는 파일 경로
+/// URL은 단어 안의 스킴부터, 경로는 단어 맨 앞(여는 괄호 다음)부터 시작하며,
+/// 끝의 문장 부호와 짝이 없는 닫는 괄호는 제외
+/// 경로 뒤에 줄 번호(`:120`, `:120:5`)가 붙으면 거기까지 (`grep -n` 출력의 뒤따르는 내용 제외),
+/// 접두사 없는 상대 경로(`src/main.rs:120:5`)는 줄 번호가 붙었을 때만 경로로 봄
+fn link_in_word(chars: &[char], word_start: usize, word_end: usize) -> Option<UrlSpan> {
+    let word: String = chars[word_start..word_end].iter().collect();
+
+    // 단어 안의 스킴 위치 (예: `(https://...)`의 여는 괄호 제외)
+    let url = URL_SCHEMES.iter()
+        .filter_map(|scheme| word.find(scheme).map(|offset| (offset, scheme.len())))
+        .min_by_key(|(offset, _)| *offset);
+    let (offset, prefix_len, location_len) = match url {
+        Some((offset, prefix_len)) => (offset, prefix_len, None),
+        None => {
+            let offset = word.len() - word.trim_start_matches(['(', '[']).len();
+            let path = &word[offset..];
+            // `//`로 시작하거나 경로 구분자와 점만 있으면 경로로 보지 않음
+            if path.starts_with("//") {
+                return None;
+            }
+            if PATH_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+                let prefix_len = path.len() - path.trim_start_matches(['/', '.', '~']).len();
+                let location_len = path.find(':').and_then(|colon| line_number_end(path, colon));
+                (offset, prefix_len, location_len)
+            } else {
+                (offset, 0, Some(relative_location_len(path)?))
+            }
+        }
+    };
+    let start = word_start + word[..offset].chars().count();
+
+    let mut end = location_len.map_or(word_end, |len| start + word[offset..offset + len].chars().count());
+    while end > start {
+        let link = &chars[start..end];
+        let unmatched_paren = link[link.len() - 1] == ')'
+            && link.iter().filter(|&&c| c == '(').count() < link.iter().filter(|&&c| c == ')').count();
+        if !unmatched_paren && !matches!(link[link.len() - 1], '.' | ',' | ';' | ':' | '!' | '?') {
+            break;
+        }
+        end -= 1;
+    }
+
+    // 스킴이나 경로 시작 부분만 있으면 링크가 아님
+    if end - start <= prefix_len {
+        return None;
+    }
+    Some(UrlSpan { start, end, url: chars[start..end].iter().collect() })
+}
+
+/// `text[colon..]`이 `:줄` 또는 `:줄:열`로 시작하면 그 끝 위치 (바이트)
+fn line_number_end(text: &str, colon: usize) -> Option<usize> {
+    let digits = |from: usize| text[from..].bytes().take_while(u8::is_ascii_digit).count();
+    let line = digits(colon + 1);
+    if line == 0 {
+        return None;
+    }
+    let end = colon + 1 + line;
+    match text[end..].starts_with(':') {
+        true if digits(end + 1) > 0 => Some(end + 1 + digits(end + 1)),
+        _ => Some(end),
+    }
+}
+
+/// 접두사 없는 상대 경로 뒤에 줄 번호가 붙은 위치(`src/main.rs:120:5`)의 길이 (바이트)
+/// 경로 부분에 `/`나 확장자가 있어야 함 (`12:30` 같은 시각이나 `localhost:8080` 제외)
+fn relative_location_len(text: &str) -> Option<usize> {
+    let colon = text.find(':')?;
+    let path = &text[..colon];
+    let path_chars = path.chars().all(|c| c.is_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '+' | '@'));
+    let has_extension = path.rsplit('/').next()
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(stem, extension)| !stem.is_empty() && extension.starts_with(|c: char| c.is_ascii_alphabetic()));
+    if !path_chars || !(path.contains('/') || has_extension) {
+        return None;
+    }
+    line_number_end(text, colon)
+}
+
+/// 찾은 파일 경로를 실제 경로로 변환 (`~`는 홈 디렉토리, 상대 경로는 `base` 기준)
+pub fn resolve_path(path: &str, base: Option<&Path>) -> PathBuf {
+    let path = crate::config::expand_home(path);
+    match base {
+        Some(base) if path.is_relative() => base.join(path),
+        _ => path,
+    }
+}
+
+/// 왼쪽 버튼을 눌렀을 때 할 일
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClickAction {
+    OpenUrl(String),
+    OpenPath(String), // 줄에 나온 그대로의 경로 (존재 여부는 여는 쪽에서 확인)
+    Select,
+}
+
+/// URL/파일 경로 열기와 텍스트 선택 중 하나 결정
+/// URL 열기 수정 키를 누른 채 링크 위를 눌렀을 때만 열고, 그 외(수정 키 없는 클릭/드래그)는 항상 선택
+pub fn click_action(line: &str, col: usize, modifiers: KeyModifiers, config: &MouseConfig) -> ClickAction {
+    if !config.url_modifier.is_held(modifiers) {
+        return ClickAction::Select;
+    }
+    match url_at(line, col) {
+        Some(span) if span.is_path() => ClickAction::OpenPath(span.url),
+        Some(span) => ClickAction::OpenUrl(span.url),
+        None => ClickAction::Select,
+    }
+}
+
+/// 시스템 기본 프로그램으로 URL 또는 파일 열기 (셸을 거치지 않고 인자로 전달)
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let program = "xdg-open";
+
+    let mut child = std::process::Command::new(program)
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    // 끝나면 거둬서 좀비 프로세스로 남지 않게
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// 프로그램이 켠 마우스 보고 모드
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MouseReportMode {
+    pub clicks: bool, // 버튼 누름/뗌과 휠 (`\e[?1000h`)
+    pub drag: bool,   // 버튼을 누른 채 이동 (`\e[?1002h`)
+    pub motion: bool, // 버튼과 상관없이 모든 이동 (`\e[?1003h`)
+    pub sgr: bool,    // SGR 인코딩 (`\e[?1006h`), 아니면 X10 인코딩
+}
+
+impl MouseReportMode {
+    /// 마우스 보고가 켜져 있는지 확인
+    pub fn is_enabled(&self) -> bool {
+        self.clicks || self.drag || self.motion
+    }
+}
+
+/// 마우스 이벤트를 프로그램에 보낼 바이트로 변환 (보고하지 않는 이벤트면 None)
+/// `col`, `row`는 화면 기준 셀 위치 (0부터)
+pub fn mouse_report(kind: MouseEventKind, modifiers: KeyModifiers, col: u16, row: u16, mode: MouseReportMode) -> Option<Vec<u8>> {
+    if !mode.is_enabled() {
+        return None;
+    }
+
+    let button_code = |button: MouseButton| match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    };
+    // (버튼 코드, 뗌 여부)
+    let (mut code, release) = match kind {
+        MouseEventKind::Down(button) => (button_code(button), false),
+        MouseEventKind::Up(button) => (button_code(button), true),
+        MouseEventKind::Drag(button) if mode.drag || mode.motion => (button_code(button) + 32, false),
+        MouseEventKind::Moved if mode.motion => (3 + 32, false),
+        MouseEventKind::ScrollUp => (64, false),
+        MouseEventKind::ScrollDown => (65, false),
+        _ => return None,
+    };
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        code += 4;
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        code += 8;
+    }
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        code += 16;
+    }
+
+    if mode.sgr {
+        let suffix = if release { 'm' } else { 'M' };
+        return Some(format!("\x1b[<{};{};{}{}", code, col + 1, row + 1, suffix).into_bytes());
+    }
+
+    // X10 인코딩은 뗀 버튼을 구분하지 않고, 223을 넘는 좌표는 표현할 수 없음
+    if release {
+        code = 3 | (code & !3);
+    }
+    let encode = |value: u16| u8::try_from(value + 1 + 32).ok();
+    Some(vec![0x1b, b'[', b'M', 32 + code, encode(col)?, encode(row)?])
+}
+
+/// 대체 화면에서 휠 스크롤을 대신할 화살표 키 입력 (양수: 위로)
+/// 애플리케이션 커서 키 모드(`\e[?1h`)면 `\eOA`/`\eOB`, 아니면 `\e[A`/`\e[B`
+pub fn alternate_scroll_bytes(lines: i32, app_cursor: bool) -> Vec<u8> {
+    let prefix = if app_cursor { b'O' } else { b'[' };
+    let arrow = if lines > 0 { b'A' } else { b'B' };
+    [0x1b, prefix, arrow].repeat(lines.unsigned_abs() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_multi_click_interval_boundary() {
+        let config = MouseConfig::default();
+        let interval = config.multi_click_interval();
+        let start = Instant::now();
+
+        // 간격 경계(정확히 interval)까지는 연속 클릭으로 인정
+        let mut tracker = ClickTracker::default();
+        assert_eq!(tracker.register(5, 3, start, &config), 1);
+        assert_eq!(tracker.register(5, 3, start + interval, &config), 2);
+        assert_eq!(tracker.register(5, 3, start + interval * 2, &config), 3);
+        assert_eq!(tracker.register(5, 3, start + interval * 3, &config), 1);
+
+        // 간격을 1ms 초과하면 새 클릭
+        let mut tracker = ClickTracker::default();
+        tracker.register(5, 3, start, &config);
+        let late = start + interval + Duration::from_millis(1);
+        assert_eq!(tracker.register(5, 3, late, &config), 1);
+    }
+
+    #[test]
+    fn test_multi_click_position_tolerance() {
+        let config = MouseConfig {
+            multi_click_col_tolerance: 1,
+            multi_click_row_tolerance: 0,
+            ..MouseConfig::default()
+        };
+        let start = Instant::now();
+
+        let mut tracker = ClickTracker::default();
+        tracker.register(10, 2, start, &config);
+        // 한 칸 옆은 허용
+        assert_eq!(tracker.register(11, 2, start, &config), 2);
+        // 다른 행은 허용하지 않음
+        assert_eq!(tracker.register(11, 3, start, &config), 1);
+    }
+
+    #[test]
+    fn test_url_at() {
+        let line = "see (https://example.com/a_(b)), or ftp://host. done";
+        let span = url_at(line, 10).unwrap();
+        assert_eq!(span.url, "https://example.com/a_(b)");
+        assert_eq!(span.start, 5);
+        assert_eq!(url_at(line, span.end), None); // 닫는 괄호는 URL 밖
+        assert_eq!(url_at(line, 3), None);
+
+        assert_eq!(url_at(line, 38).unwrap().url, "ftp://host");
+        assert_eq!(url_at("http:// x", 2), None);
+    }
+
+    #[test]
+    fn test_links_find_urls_and_paths() {
+        let line = "error in (./src/main.rs), see ~/notes.txt and https://example.com/x.";
+        let found: Vec<(usize, &str, bool)> = links(line).iter()
+            .map(|span| (span.start, &line[span.start..span.end], span.is_path()))
+            .collect();
+        assert_eq!(found, vec![
+            (10, "./src/main.rs", true),
+            (30, "~/notes.txt", true),
+            (46, "https://example.com/x", false),
+        ]);
+
+        // 구분자만 있거나 경로가 아닌 단어는 제외
+        assert!(links("a / b // c ../ and/or ~user").is_empty());
+        assert_eq!(links("/etc/hosts:").iter().map(|span| span.url.as_str()).collect::<Vec<_>>(), vec!["/etc/hosts"]);
+
+        // 줄 번호가 붙으면 접두사 없는 상대 경로도 찾고, 줄 번호 뒤의 내용은 제외
+        let line = "--> src/main.rs:120:5 and lib.rs:7: note, ./a.txt:3:fn main() at 12:30 on localhost:8080";
+        assert_eq!(links(line).iter().map(|span| span.url.as_str()).collect::<Vec<_>>(),
+            vec!["src/main.rs:120:5", "lib.rs:7", "./a.txt:3"]);
+        assert!(links("README.md and src/ only").is_empty());
+
+        let base = Path::new("/work");
+        assert_eq!(resolve_path("./src/main.rs", Some(base)), PathBuf::from("/work/./src/main.rs"));
+        assert_eq!(resolve_path("/etc/hosts", Some(base)), PathBuf::from("/etc/hosts"));
+    }
+
+    #[test]
+    fn test_plain_drag_over_url_selects() {
+        let config = MouseConfig::default();
+        let lines = vec!["open https://example.com now".to_string()];
+
+        // 수정 키 없이 누르면 URL 위라도 선택 시작, 드래그한 범위가 그대로 복사됨
+        assert_eq!(click_action(&lines[0], 8, KeyModifiers::NONE, &config), ClickAction::Select);
+        let copied = crate::selection::selected_text(&lines, (0, 5, 0, 23), crate::config::LineEnding::Lf);
+        assert_eq!(copied, "https://example.com");
+
+        // 열기 수정 키를 누른 채 URL 위를 누를 때만 열기
+        assert_eq!(click_action(&lines[0], 8, KeyModifiers::CONTROL, &config),
+            ClickAction::OpenUrl("https://example.com".to_string()));
+        assert_eq!(click_action(&lines[0], 1, KeyModifiers::CONTROL, &config), ClickAction::Select);
+        assert_eq!(click_action("cat /tmp/log", 6, KeyModifiers::CONTROL, &config),
+            ClickAction::OpenPath("/tmp/log".to_string()));
+
+        let config = MouseConfig { url_modifier: crate::config::UrlOpenModifier::Alt, ..config };
+        assert_eq!(click_action(&lines[0], 8, KeyModifiers::CONTROL, &config), ClickAction::Select);
+        assert!(matches!(click_action(&lines[0], 8, KeyModifiers::ALT, &config), ClickAction::OpenUrl(_)));
+    }
+
+    #[test]
+    fn test_alternate_scroll_bytes() {
+        assert_eq!(alternate_scroll_bytes(2, false), b"\x1b[A\x1b[A".to_vec());
+        assert_eq!(alternate_scroll_bytes(-1, true), b"\x1bOB".to_vec());
+        assert!(alternate_scroll_bytes(0, false).is_empty());
+    }
+
+    #[test]
+    fn test_mouse_report_encoding() {
+        let none = KeyModifiers::NONE;
+        let clicks = MouseReportMode { clicks: true, ..Default::default() };
+        let sgr = MouseReportMode { sgr: true, ..clicks };
+        let report = |kind, modifiers, mode| mouse_report(kind, modifiers, 4, 9, mode);
+
+        // SGR: 누름 M, 뗌 m, 좌표는 1부터
+        assert_eq!(report(MouseEventKind::Down(MouseButton::Left), none, sgr), Some(b"\x1b[<0;5;10M".to_vec()));
+        assert_eq!(report(MouseEventKind::Up(MouseButton::Right), none, sgr), Some(b"\x1b[<2;5;10m".to_vec()));
+        assert_eq!(report(MouseEventKind::ScrollDown, KeyModifiers::CONTROL, sgr), Some(b"\x1b[<81;5;10M".to_vec()));
+
+        // X10: 32를 더한 바이트, 뗌은 버튼 3
+        assert_eq!(report(MouseEventKind::Down(MouseButton::Middle), none, clicks), Some(vec![0x1b, b'[', b'M', 33, 37, 42]));
+        assert_eq!(report(MouseEventKind::Up(MouseButton::Left), KeyModifiers::ALT, clicks), Some(vec![0x1b, b'[', b'M', 43, 37, 42]));
+        assert_eq!(mouse_report(MouseEventKind::Down(MouseButton::Left), none, 300, 0, clicks), None);
+
+        // 이동은 해당 모드를 켠 경우에만
+        assert_eq!(report(MouseEventKind::Drag(MouseButton::Left), none, sgr), None);
+        let drag = MouseReportMode { drag: true, ..sgr };
+        assert_eq!(report(MouseEventKind::Drag(MouseButton::Left), none, drag), Some(b"\x1b[<32;5;10M".to_vec()));
+        assert_eq!(report(MouseEventKind::Moved, none, drag), None);
+        let motion = MouseReportMode { motion: true, ..sgr };
+        assert_eq!(report(MouseEventKind::Moved, none, motion), Some(b"\x1b[<35;5;10M".to_vec()));
+
+        assert_eq!(report(MouseEventKind::Down(MouseButton::Left), none, MouseReportMode::default()), None);
+    }
+}
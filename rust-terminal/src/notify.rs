@@ -0,0 +1,103 @@
+//! 데스크톱 알림 모듈
+//! 호스트 터미널에 알림 이스케이프 시퀀스(OSC 777/OSC 9)를 보내 호스트가 알림을 띄우게 하거나,
+//! 알림 명령(Linux `notify-send`, macOS `osascript`)을 직접 실행
+//!
+//! 시퀀스 방식은 SSH 너머에서도 동작하지만 호스트 터미널이 지원해야 하고(foot, WezTerm, iTerm2 등),
+//! 명령 방식은 이 프로그램이 실행 중인 컴퓨터의 데스크톱에 알림
+
+use std::io::{self, Write};
+
+/// 알림 보내는 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotifyMethod {
+    /// `ESC ] 777 ; notify ; 제목 ; 본문 BEL` (rxvt 확장, foot/WezTerm/VTE 기반 터미널)
+    #[default]
+    Osc777,
+    /// `ESC ] 9 ; 본문 BEL` (iTerm2, Windows Terminal)
+    Osc9,
+    /// 알림 명령 실행
+    Command,
+}
+
+impl NotifyMethod {
+    /// `osc777`/`osc9`/`command`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "osc777" | "osc" => Some(Self::Osc777),
+            "osc9" => Some(Self::Osc9),
+            "command" => Some(Self::Command),
+            _ => None,
+        }
+    }
+}
+
+/// 시퀀스에 넣을 수 있게 정리한 글자 (제어 문자 제거, OSC 777의 구분자 `;`는 `,`로)
+fn sequence_text(text: &str) -> String {
+    crate::title::sanitize_title(text).replace(';', ",")
+}
+
+/// 호스트 터미널에 보낼 알림 시퀀스 (명령 방식이면 None)
+pub fn sequence(method: NotifyMethod, title: &str, body: &str) -> Option<String> {
+    match method {
+        NotifyMethod::Osc777 => Some(format!("\x1b]777;notify;{};{}\x07", sequence_text(title), sequence_text(body))),
+        NotifyMethod::Osc9 => Some(format!("\x1b]9;{}: {}\x07", sequence_text(title), sequence_text(body))),
+        NotifyMethod::Command => None,
+    }
+}
+
+/// 알림 보내기 (실패해도 로그만 남김)
+pub fn send(method: NotifyMethod, title: &str, body: &str) {
+    if let Some(sequence) = sequence(method, title, body) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(sequence.as_bytes());
+        let _ = stdout.flush();
+        return;
+    }
+    if let Err(e) = run_command(title, body) {
+        log::warn!("notification command failed: {}", e);
+    }
+}
+
+/// 플랫폼 알림 명령 실행 (끝날 때까지 기다리지 않고, 종료 상태는 별도 스레드에서 회수)
+fn run_command(title: &str, body: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            body.replace(['"', '\\'], ""),
+            title.replace(['"', '\\'], ""),
+        );
+        let mut command = std::process::Command::new("osascript");
+        command.arg("-e").arg(script);
+        command
+    } else if cfg!(windows) {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "no notification command on this platform"));
+    } else {
+        let mut command = std::process::Command::new("notify-send");
+        command.arg("--").arg(title).arg(body);
+        command
+    };
+    let mut child = command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_sequences() {
+        assert_eq!(
+            sequence(NotifyMethod::Osc777, "rust-terminal", "build; done\x07"),
+            Some("\x1b]777;notify;rust-terminal;build, done\x07".to_string()),
+        );
+        assert_eq!(sequence(NotifyMethod::Osc9, "tab 2", "bell"), Some("\x1b]9;tab 2: bell\x07".to_string()));
+        assert_eq!(sequence(NotifyMethod::Command, "a", "b"), None);
+        assert_eq!(NotifyMethod::parse("OSC9"), Some(NotifyMethod::Osc9));
+        assert_eq!(NotifyMethod::parse("dbus"), None);
+    }
+}
@@ -0,0 +1,419 @@
+//! 셸 통합 OSC 시퀀스 처리 모듈
+//! alacritty는 OSC 7(현재 작업 디렉토리 보고)과 OSC 133(프롬프트/명령 경계)을 처리하지 않으므로,
+//! PTY에서 읽은 출력을 그리드에 반영하기 전에 직접 훑어서 OSC 시퀀스를 찾아 셸 상태를 갱신
+//!
+//! OSC 133 표시의 위치는 출력 스트림에서 그때까지 나온 줄 바꿈(LF) 수로 기록하고,
+//! 그리드 행으로는 필요할 때 커서 위치에서 거슬러 올라가 계산 (`Terminal::prompt_marks`)
+
+use crate::output_log::{self, SharedOutputSinks};
+use alacritty_terminal::event::{OnResize, WindowSize};
+use alacritty_terminal::tty::{ChildEvent, EventedPty, EventedReadWrite};
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// OSC 본문 최대 길이 (이보다 긴 시퀀스는 무시)
+const MAX_OSC_LEN: usize = 4096;
+
+/// 보관하는 OSC 133 표시 최대 개수 (오래된 것부터 버림)
+const MAX_PROMPT_MARKS: usize = 4096;
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+/// 바이트 스트림에서 OSC 시퀀스(`ESC ] ... BEL` 또는 `ESC ] ... ESC \`)를 찾는 스캐너
+/// 시퀀스가 여러 번의 read에 나뉘어 들어와도 이어서 처리
+#[derive(Debug, Default)]
+pub struct OscScanner {
+    state: ScanState,
+    payload: Vec<u8>,
+    line_feeds: u64, // 지금까지 OSC 밖에서 나온 줄 바꿈 수
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,    // ESC 다음
+    Osc,       // OSC 본문 수집 중
+    OscEscape, // OSC 본문 안에서 ESC 다음 (ST 확인)
+    Overflow,  // 너무 긴 OSC - 끝날 때까지 버림
+}
+
+impl OscScanner {
+    /// 지금까지 OSC 밖에서 나온 줄 바꿈 수
+    pub fn line_feeds(&self) -> u64 {
+        self.line_feeds
+    }
+
+    /// 바이트를 훑고 완성된 OSC 본문마다 (본문, 그때까지의 줄 바꿈 수)로 콜백 호출
+    pub fn scan(&mut self, bytes: &[u8], mut on_osc: impl FnMut(&[u8], u64)) {
+        for &byte in bytes {
+            if byte == b'\n' && matches!(self.state, ScanState::Ground | ScanState::Escape) {
+                self.line_feeds += 1;
+            }
+            self.state = match (self.state, byte) {
+                (ScanState::Ground, ESC) => ScanState::Escape,
+                (ScanState::Ground, _) => ScanState::Ground,
+                (ScanState::Escape, b']') => {
+                    self.payload.clear();
+                    ScanState::Osc
+                }
+                (ScanState::Escape, ESC) => ScanState::Escape,
+                (ScanState::Escape, _) => ScanState::Ground,
+                (ScanState::Osc | ScanState::Overflow, BEL) => {
+                    if self.state == ScanState::Osc {
+                        on_osc(&self.payload, self.line_feeds);
+                    }
+                    ScanState::Ground
+                }
+                (ScanState::Osc, ESC) => ScanState::OscEscape,
+                (ScanState::Osc, _) if self.payload.len() >= MAX_OSC_LEN => ScanState::Overflow,
+                (ScanState::Osc, _) => {
+                    self.payload.push(byte);
+                    ScanState::Osc
+                }
+                (ScanState::OscEscape, b'\\') => {
+                    on_osc(&self.payload, self.line_feeds);
+                    ScanState::Ground
+                }
+                // ST가 아닌 ESC는 OSC를 중단하고 새 이스케이프 시작
+                (ScanState::OscEscape, b']') => {
+                    self.payload.clear();
+                    ScanState::Osc
+                }
+                (ScanState::OscEscape, ESC) => ScanState::Escape,
+                (ScanState::OscEscape, _) => ScanState::Ground,
+                (ScanState::Overflow, ESC) => ScanState::Escape,
+                (ScanState::Overflow, _) => ScanState::Overflow,
+            };
+        }
+    }
+}
+
+/// OSC 133 셸 통합 표시 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptMarkKind {
+    /// 프롬프트 시작 (`133;A`)
+    Prompt,
+    /// 명령 입력 시작, 프롬프트 끝 (`133;B`)
+    Input,
+    /// 명령 출력 시작 (`133;C`)
+    Output,
+    /// 명령 종료와 종료 코드 (`133;D;코드`)
+    Finished(Option<i32>),
+}
+
+impl PromptMarkKind {
+    /// `133;` 뒤의 본문 해석 (`A`, `B`, `C`, `D;0` 등, `;` 뒤의 추가 옵션은 무시)
+    fn parse(body: &[u8]) -> Option<Self> {
+        let body = std::str::from_utf8(body).ok()?;
+        let mut parts = body.split(';');
+        let kind = match parts.next()? {
+            "A" => Self::Prompt,
+            "B" => Self::Input,
+            "C" => Self::Output,
+            "D" => Self::Finished(parts.next().and_then(|code| code.trim().parse().ok())),
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// 출력 스트림 위치가 기록된 OSC 133 표시
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptMark {
+    pub kind: PromptMarkKind,
+    /// 표시가 나오기 전까지의 줄 바꿈 수
+    pub line_feeds: u64,
+}
+
+/// OSC로 보고된 셸 상태
+#[derive(Debug, Clone)]
+pub struct ShellState {
+    current_directory: Option<PathBuf>,
+    local_host: String,
+    prompt_marks: VecDeque<PromptMark>, // OSC 133 표시 (오래된 것부터)
+    line_feeds: u64,                    // 그리드에 반영된 출력의 줄 바꿈 수
+}
+
+impl Default for ShellState {
+    fn default() -> Self {
+        Self::new(local_hostname())
+    }
+}
+
+impl ShellState {
+    pub fn new(local_host: String) -> Self {
+        Self {
+            current_directory: None,
+            local_host,
+            prompt_marks: VecDeque::new(),
+            line_feeds: 0,
+        }
+    }
+
+    /// 셸이 OSC 7로 보고한 현재 작업 디렉토리
+    pub fn current_directory(&self) -> Option<&PathBuf> {
+        self.current_directory.as_ref()
+    }
+
+    /// 셸이 OSC 133으로 보고한 프롬프트/명령 경계 (오래된 것부터)
+    pub fn prompt_marks(&self) -> &VecDeque<PromptMark> {
+        &self.prompt_marks
+    }
+
+    /// 출력 스트림에서 지금까지 나온 줄 바꿈 수
+    pub fn line_feeds(&self) -> u64 {
+        self.line_feeds
+    }
+
+    /// 읽은 출력을 모두 훑은 뒤 스캐너의 줄 바꿈 수 반영
+    pub fn set_line_feeds(&mut self, line_feeds: u64) {
+        self.line_feeds = line_feeds;
+    }
+
+    /// 마지막으로 끝난 명령의 종료 코드 (OSC 133;D, 코드가 없었으면 None)
+    pub fn last_exit_code(&self) -> Option<i32> {
+        self.prompt_marks.iter().rev().find_map(|mark| match mark.kind {
+            PromptMarkKind::Finished(code) => code,
+            _ => None,
+        })
+    }
+
+    /// OSC 본문 처리 (`7;file://host/path`, `133;A` 등)
+    /// `line_feeds`는 시퀀스가 나오기 전까지의 줄 바꿈 수
+    pub fn handle_osc(&mut self, payload: &[u8], line_feeds: u64) {
+        if let Some(body) = payload.strip_prefix(b"133;") {
+            match PromptMarkKind::parse(body) {
+                Some(kind) => {
+                    if self.prompt_marks.len() >= MAX_PROMPT_MARKS {
+                        self.prompt_marks.pop_front();
+                    }
+                    self.prompt_marks.push_back(PromptMark { kind, line_feeds });
+                }
+                None => log::debug!("OSC 133 무시: {}", String::from_utf8_lossy(body)),
+            }
+        } else if let Some(url) = payload.strip_prefix(b"7;") {
+            match parse_osc7(url, &self.local_host) {
+                Some(path) => {
+                    log::debug!("OSC 7 작업 디렉토리: {}", path.display());
+                    self.current_directory = Some(path);
+                }
+                None => log::debug!("OSC 7 무시: {}", String::from_utf8_lossy(url)),
+            }
+        }
+    }
+}
+
+/// OSC 7 `file://host/path` URL에서 경로 추출
+/// 다른 호스트(ssh 접속 등)에서 보낸 보고는 로컬 경로가 아니므로 None
+fn parse_osc7(url: &[u8], local_host: &str) -> Option<PathBuf> {
+    let url = std::str::from_utf8(url).ok()?;
+    let rest = url.strip_prefix("file://")?;
+    let (host, path) = rest.split_at(rest.find('/')?);
+
+    let is_local = host.is_empty()
+        || host.eq_ignore_ascii_case("localhost")
+        || host.eq_ignore_ascii_case(local_host);
+    if !is_local {
+        return None;
+    }
+
+    let path = percent_decode(path)?;
+    // Windows: /C:/Users -> C:/Users
+    let path = match path.strip_prefix('/') {
+        Some(rest) if cfg!(windows) && rest.as_bytes().get(1) == Some(&b':') => rest.to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// URL 퍼센트 인코딩 해제 (`%20` -> 공백)
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(idx + 1..idx + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            idx += 3;
+        } else {
+            decoded.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// 로컬 호스트 이름
+fn local_hostname() -> String {
+    #[cfg(unix)]
+    {
+        let mut buf = [0u8; 256];
+        // SAFETY: buf는 길이만큼 쓰기 가능한 버퍼
+        let result = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+        if result == 0 {
+            let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+            return String::from_utf8_lossy(&buf[..len]).into_owned();
+        }
+        String::new()
+    }
+    #[cfg(not(unix))]
+    {
+        std::env::var("COMPUTERNAME").unwrap_or_default()
+    }
+}
+
+/// PTY 출력을 읽을 때 OSC 시퀀스를 훑어 셸 상태를 갱신하는 PTY 래퍼
+/// 출력 기록이나 녹화가 켜져 있으면 읽은 바이트를 그 파일에도 씀 (읽은 바이트는 그대로 alacritty 이벤트 루프에 전달)
+pub struct OscTrackingPty<P> {
+    pty: P,
+    scanner: OscScanner,
+    shell_state: Arc<Mutex<ShellState>>,
+    output_sinks: SharedOutputSinks,
+}
+
+impl<P> OscTrackingPty<P> {
+    pub fn new(pty: P, shell_state: Arc<Mutex<ShellState>>, output_sinks: SharedOutputSinks) -> Self {
+        Self {
+            pty,
+            scanner: OscScanner::default(),
+            shell_state,
+            output_sinks,
+        }
+    }
+}
+
+impl<P: EventedReadWrite> Read for OscTrackingPty<P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.pty.reader().read(buf)?;
+        output_log::record(&self.output_sinks, &buf[..read]);
+        crate::stats::record_pty_read(read);
+        let shell_state = &self.shell_state;
+        self.scanner.scan(&buf[..read], |payload, line_feeds| {
+            if let Ok(mut state) = shell_state.lock() {
+                state.handle_osc(payload, line_feeds);
+            }
+        });
+        if let Ok(mut state) = shell_state.lock() {
+            state.set_line_feeds(self.scanner.line_feeds());
+        }
+        Ok(read)
+    }
+}
+
+impl<P: EventedReadWrite> EventedReadWrite for OscTrackingPty<P> {
+    type Reader = Self;
+    type Writer = P::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poller: &Arc<polling::Poller>,
+        interest: polling::Event,
+        mode: polling::PollMode,
+    ) -> io::Result<()> {
+        // SAFETY: 내부 PTY는 이 래퍼가 소유하므로 등록 기간 동안 유지됨
+        unsafe { self.pty.register(poller, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poller: &Arc<polling::Poller>,
+        interest: polling::Event,
+        mode: polling::PollMode,
+    ) -> io::Result<()> {
+        self.pty.reregister(poller, interest, mode)
+    }
+
+    fn deregister(&mut self, poller: &Arc<polling::Poller>) -> io::Result<()> {
+        self.pty.deregister(poller)
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        self
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self.pty.writer()
+    }
+}
+
+impl<P: EventedPty> EventedPty for OscTrackingPty<P> {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.pty.next_child_event()
+    }
+}
+
+impl<P: OnResize> OnResize for OscTrackingPty<P> {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.pty.on_resize(window_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_all(scanner: &mut OscScanner, chunks: &[&[u8]]) -> Vec<String> {
+        let mut found = Vec::new();
+        for chunk in chunks {
+            scanner.scan(chunk, |payload, _| found.push(String::from_utf8_lossy(payload).into_owned()));
+        }
+        found
+    }
+
+    #[test]
+    fn test_scanner_finds_osc_split_across_reads() {
+        let mut scanner = OscScanner::default();
+        let found = scan_all(&mut scanner, &[
+            b"ls\r\n\x1b]0;title\x07prompt \x1b]7;file://",
+            b"host/tmp\x1b\\$ \x1b[31mred",
+        ]);
+        assert_eq!(found, vec!["0;title", "7;file://host/tmp"]);
+    }
+
+    #[test]
+    fn test_osc7_sets_current_directory() {
+        let mut state = ShellState::new("myhost".to_string());
+        state.handle_osc(b"7;file://myhost/home/user/My%20Projects", 0);
+        assert_eq!(state.current_directory(), Some(&PathBuf::from("/home/user/My Projects")));
+
+        state.handle_osc(b"7;file:///var/log", 0);
+        assert_eq!(state.current_directory(), Some(&PathBuf::from("/var/log")));
+    }
+
+    #[test]
+    fn test_osc133_prompt_marks() {
+        let mut scanner = OscScanner::default();
+        let mut state = ShellState::new("myhost".to_string());
+        let output: &[u8] = b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x07a\r\nb\r\n\x1b]133;D;2\x1b\\\x1b]133;A;cl=m\x07$ ";
+        scanner.scan(output, |payload, line_feeds| state.handle_osc(payload, line_feeds));
+        state.set_line_feeds(scanner.line_feeds());
+
+        let marks: Vec<(PromptMarkKind, u64)> = state.prompt_marks().iter().map(|mark| (mark.kind, mark.line_feeds)).collect();
+        assert_eq!(marks, vec![
+            (PromptMarkKind::Prompt, 0),
+            (PromptMarkKind::Input, 0),
+            (PromptMarkKind::Output, 1),
+            (PromptMarkKind::Finished(Some(2)), 3),
+            (PromptMarkKind::Prompt, 3),
+        ]);
+        assert_eq!(state.line_feeds(), 3);
+        assert_eq!(state.last_exit_code(), Some(2));
+
+        state.handle_osc(b"133;Z", 3);
+        assert_eq!(state.prompt_marks().len(), 5);
+    }
+
+    #[test]
+    fn test_osc7_from_other_host_is_ignored() {
+        let mut state = ShellState::new("myhost".to_string());
+        state.handle_osc(b"7;file://localhost/srv", 0);
+        state.handle_osc(b"7;file://remote-server/etc", 0);
+        state.handle_osc(b"7;file://myhost/bad%zz", 0);
+        assert_eq!(state.current_directory(), Some(&PathBuf::from("/srv")));
+    }
+}
@@ -0,0 +1,154 @@
+//! 출력 기록(typescript) 모듈
+//! script(1)처럼 PTY에서 받은 바이트를 가공하지 않고 세션별 파일에 이어 씀
+//! 파일 이름에 시작 시각(UTC)을 붙이고, 크기 제한을 넘으면 새 시각의 파일로 교체(rotation)
+//! 같은 읽기 경로에서 asciicast 녹화(`asciicast::Recorder`)에도 출력을 넘김
+
+use crate::asciicast::Recorder;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// PTY 읽기 경로에서 받은 출력을 넘겨받는 기록기들 (None이면 기록하지 않음)
+#[derive(Debug, Default)]
+pub struct OutputSinks {
+    pub log: Option<OutputLog>,
+    pub cast: Option<Recorder>, // asciicast 녹화
+}
+
+/// PTY 읽기 경로와 앱이 함께 쓰는 기록기
+pub type SharedOutputSinks = Arc<Mutex<OutputSinks>>;
+
+/// 이 프로세스에서 기록을 시작한 세션 수 (파일 이름 구분용)
+static SESSION_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// 세션 하나의 출력 기록 파일
+#[derive(Debug)]
+pub struct OutputLog {
+    directory: PathBuf,
+    prefix: String,  // 세션 구분 이름 (`session<번호>-<pid>`)
+    max_bytes: u64,  // 파일 하나의 최대 크기 (0이면 교체하지 않음)
+    file: File,
+    path: PathBuf,
+    written: u64,    // 현재 파일에 쓴 바이트 수
+}
+
+impl OutputLog {
+    /// 새 세션의 기록 파일을 `directory`에 만들어 열기
+    pub fn start(directory: &Path, max_bytes: u64) -> io::Result<Self> {
+        let number = SESSION_COUNTER.fetch_add(1, Ordering::Relaxed) + 1;
+        let prefix = format!("session{}-{}", number, std::process::id());
+        std::fs::create_dir_all(directory)?;
+        let (file, path) = open_new(directory, &prefix, "log")?;
+        Ok(Self { directory: directory.to_path_buf(), prefix, max_bytes, file, path, written: 0 })
+    }
+
+    /// 지금 쓰고 있는 파일 경로
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 받은 출력 이어 쓰기 (크기 제한을 넘으면 먼저 새 파일로 교체)
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.max_bytes > 0 && self.written > 0 && self.written + bytes.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// 현재 시각 이름의 새 파일로 교체
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let (file, path) = open_new(&self.directory, &self.prefix, "log")?;
+        log::debug!("output log rotated: {} -> {}", self.path.display(), path.display());
+        self.file = file;
+        self.path = path;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+/// 켜져 있는 기록기에 출력 쓰기 - 실패한 기록기는 멈추고 경고 (PTY 읽기는 계속)
+pub fn record(sinks: &SharedOutputSinks, bytes: &[u8]) {
+    let Ok(mut sinks) = sinks.lock() else {
+        return;
+    };
+    if let Some(output_log) = sinks.log.as_mut() {
+        if let Err(e) = output_log.write(bytes) {
+            log::warn!("출력 기록 중지 ({}): {}", output_log.path().display(), e);
+            sinks.log = None;
+        }
+    }
+    if let Some(recorder) = sinks.cast.as_mut() {
+        if let Err(e) = recorder.output(bytes) {
+            log::warn!("녹화 중지 ({}): {}", recorder.path().display(), e);
+            sinks.cast = None;
+        }
+    }
+}
+
+/// `<prefix>-<UTC 시각>.<extension>` 파일 만들기 (같은 초에 이미 있으면 번호를 붙임)
+pub fn open_new(directory: &Path, prefix: &str, extension: &str) -> io::Result<(File, PathBuf)> {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let stem = format!("{}-{}", prefix, timestamp(secs));
+    let mut path = directory.join(format!("{}.{}", stem, extension));
+    let mut n = 1;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                n += 1;
+                path = directory.join(format!("{}-{}.{}", stem, n, extension));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 유닉스 시각(초)을 `YYYYMMDD-HHMMSS` (UTC)로
+pub fn timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rest = secs % 86_400;
+    // 1970-01-01부터의 일 수를 그레고리력 날짜로 (3월 시작 연도 기준)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}{:02}{:02}-{:02}{:02}{:02}", year, month, day, rest / 3600, rest % 3600 / 60, rest % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(0), "19700101-000000");
+        assert_eq!(timestamp(951_782_400), "20000229-000000");
+        assert_eq!(timestamp(1_700_000_000), "20231114-221320");
+    }
+
+    #[test]
+    fn test_output_log_rotates() {
+        let directory = std::env::temp_dir().join(format!("rust-terminal-log-test-{}", std::process::id()));
+        let mut log = OutputLog::start(&directory, 8).unwrap();
+        let first = log.path().to_path_buf();
+        log.write(b"hello").unwrap();
+        log.write(b"!!").unwrap();
+        // 제한(8바이트)을 넘으면 새 파일
+        log.write(b"world").unwrap();
+        assert_ne!(log.path(), first);
+        assert_eq!(std::fs::read(&first).unwrap(), b"hello!!");
+        assert_eq!(std::fs::read(log.path()).unwrap(), b"world");
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
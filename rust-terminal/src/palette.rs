@@ -0,0 +1,246 @@
+//! 명령 팔레트 모듈
+//! 키 바인딩과 같은 동작 목록(`bindings::ACTIONS`), 테마 목록, 프로필과 SSH 호스트 목록(새 탭)을 이름으로 퍼지 검색하여 실행
+//! 스마트 선택 메뉴처럼 정해진 항목만 고르는 메뉴로도 사용
+//! 화면 가운데 팝업으로 그리며, 입력한 글자가 순서대로 들어 있는 항목만 점수 순으로 보여 줌
+
+use crate::bindings::{Action, Keybindings, ACTIONS};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// 한 번에 보여 주는 최대 항목 수
+const MAX_VISIBLE: usize = 12;
+
+/// 팔레트 항목 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub title: String,       // 표시 이름 (설명)
+    pub name: String,        // 동작 이름 (검색 대상에 포함)
+    pub key: Option<String>, // 연결된 단축키
+    pub action: Action,
+}
+
+/// 키 입력 처리 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteOutcome {
+    Continue,
+    Close,
+    Run(Action),
+}
+
+/// 열려 있는 명령 팔레트
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    title: String, // 팝업 제목
+    entries: Vec<PaletteEntry>,
+    query: String,
+    matches: Vec<usize>, // 검색어에 맞는 항목 색인 (점수 순)
+    selected: usize,     // `matches` 안에서 고른 위치
+}
+
+impl CommandPalette {
+    /// 동작 목록, 테마 목록, 프로필과 SSH 호스트 목록으로 팔레트 열기 (단축키는 현재 키 바인딩에서 찾음)
+    pub fn new(bindings: &Keybindings, themes: &[String], profiles: &[String], ssh_hosts: &[String]) -> Self {
+        let actions = ACTIONS.iter()
+            .filter(|(_, action, _)| *action != Action::CommandPalette)
+            .map(|(name, action, description)| PaletteEntry {
+                title: description.to_string(),
+                name: name.to_string(),
+                key: bindings.key_for(action),
+                action: action.clone(),
+            });
+        let themes = themes.iter().map(|theme| {
+            let action = Action::SetTheme(theme.clone());
+            PaletteEntry {
+                title: format!("테마: {}", theme),
+                name: format!("set-theme {}", theme),
+                key: bindings.key_for(&action),
+                action,
+            }
+        });
+        let profiles = profiles.iter().map(|profile| {
+            let action = Action::NewTabWithProfile(profile.clone());
+            PaletteEntry {
+                title: format!("새 탭: 프로필 {}", profile),
+                name: format!("new-tab-profile {}", profile),
+                key: bindings.key_for(&action),
+                action,
+            }
+        });
+        let ssh_hosts = ssh_hosts.iter().map(|host| {
+            let action = Action::ConnectSsh(host.clone());
+            PaletteEntry {
+                title: format!("SSH: {}", host),
+                name: format!("ssh {}", host),
+                key: bindings.key_for(&action),
+                action,
+            }
+        });
+        Self::with_entries(" 명령 팔레트 ", actions.chain(themes).chain(profiles).chain(ssh_hosts).collect())
+    }
+
+    /// 주어진 항목만 고르는 메뉴 (제목은 팝업 테두리에 표시)
+    pub fn menu(title: &str, entries: Vec<PaletteEntry>) -> Self {
+        Self::with_entries(&format!(" {} ", title), entries)
+    }
+
+    fn with_entries(title: &str, entries: Vec<PaletteEntry>) -> Self {
+        let mut palette = Self { title: title.to_string(), entries, query: String::new(), matches: Vec::new(), selected: 0 };
+        palette.update_matches();
+        palette
+    }
+
+    /// 검색어에 맞는 항목 (점수 순)
+    pub fn matches(&self) -> impl Iterator<Item = &PaletteEntry> {
+        self.matches.iter().map(|&index| &self.entries[index])
+    }
+
+    /// 키 입력 처리 (글자: 검색어 편집, ↑/↓: 항목 고르기, Enter: 실행, Esc: 닫기)
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteOutcome {
+        match key.code {
+            KeyCode::Esc => return PaletteOutcome::Close,
+            KeyCode::Enter => {
+                return match self.matches.get(self.selected) {
+                    Some(&index) => PaletteOutcome::Run(self.entries[index].action.clone()),
+                    None => PaletteOutcome::Close,
+                };
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1));
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.update_matches();
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.query.push(c);
+                self.update_matches();
+            }
+            _ => {}
+        }
+        PaletteOutcome::Continue
+    }
+
+    /// 검색어로 항목을 다시 걸러 점수 순으로 정렬 (같은 점수면 원래 순서)
+    fn update_matches(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self.entries.iter().enumerate()
+            .filter_map(|(index, entry)| {
+                let score = fuzzy_score(&self.query, &entry.title)
+                    .into_iter()
+                    .chain(fuzzy_score(&self.query, &entry.name))
+                    .max()?;
+                Some((score, index))
+            })
+            .collect();
+        scored.sort_by_key(|&(score, index)| (std::cmp::Reverse(score), index));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+    }
+
+    /// `area` 가운데에 팝업으로 그리기
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = area.width.saturating_sub(4).min(70);
+        let visible = self.matches.len().clamp(1, MAX_VISIBLE);
+        let height = (visible as u16 + 3).min(area.height);
+        let popup = Rect::new(area.x + (area.width - width) / 2, area.y + area.height.saturating_sub(height) / 3, width, height);
+
+        // 고른 항목이 보이도록 목록을 밂
+        let first = self.selected.saturating_sub(MAX_VISIBLE - 1);
+        let inner_width = width.saturating_sub(2) as usize;
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Cyan)),
+            Span::raw(self.query.clone()),
+        ])];
+        for (position, entry) in self.matches().enumerate().skip(first).take(MAX_VISIBLE) {
+            let key = entry.key.clone().unwrap_or_default();
+            let title_width = inner_width.saturating_sub(key.chars().count() + 1);
+            let title: String = entry.title.chars().take(title_width).collect();
+            let padding = inner_width.saturating_sub(title.chars().count() + key.chars().count());
+            let style = if position == self.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{}", title, " ".repeat(padding)), style),
+                Span::styled(key, style.add_modifier(Modifier::DIM)),
+            ]));
+        }
+        if self.matches.is_empty() {
+            lines.push(Line::styled("일치하는 명령 없음", Style::default().fg(Color::DarkGray)));
+        }
+
+        f.render_widget(Clear, popup);
+        let block = Block::default().title(self.title.as_str()).borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan));
+        f.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
+
+/// 검색어 글자가 `text`에 순서대로 들어 있으면 점수 (대소문자 무시, 없으면 None)
+/// 연속으로 맞거나 단어 첫 글자에 맞으면 점수가 높고, 건너뛴 글자가 많으면 낮음
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    let chars: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut position = 0;
+    for wanted in query.chars().flat_map(char::to_lowercase).filter(|c| !c.is_whitespace()) {
+        let found = position + chars[position..].iter().position(|&c| c == wanted)?;
+        let word_start = found == 0 || !chars[found - 1].is_alphanumeric();
+        score += match previous {
+            Some(last) if last + 1 == found => 8,
+            _ if word_start => 6,
+            _ => 1,
+        };
+        score -= (found - position).min(5) as i32;
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "new-tab"), Some(0));
+        assert!(fuzzy_score("nt", "new-tab").is_some());
+        assert!(fuzzy_score("tn", "new-tab").is_none());
+        // 연속으로 맞는 쪽이 더 높음
+        assert!(fuzzy_score("tab", "new-tab") > fuzzy_score("tab", "toggle-autowrap-b"));
+        assert!(fuzzy_score("분할", "좌우로 분할").is_some());
+    }
+
+    #[test]
+    fn test_palette_filters_and_runs() {
+        let mut bindings = Keybindings::default();
+        bindings.push(crate::bindings::KeyBinding::parse("alt+d set-theme dracula").unwrap());
+        let mut palette = CommandPalette::new(&bindings, &["default".to_string(), "dracula".to_string()], &["work".to_string()], &["prod".to_string()]);
+        assert!(palette.matches().any(|entry| entry.action == Action::ConnectSsh("prod".into())));
+        assert!(palette.matches().any(|entry| entry.action == Action::NewTabWithProfile("work".into())));
+        assert!(palette.matches().all(|entry| entry.action != Action::CommandPalette));
+        assert_eq!(palette.matches().find(|entry| entry.action == Action::NewTab).unwrap().key.as_deref(), Some("Ctrl+T"));
+
+        for c in "dracula".chars() {
+            assert_eq!(palette.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)), PaletteOutcome::Continue);
+        }
+        let first = palette.matches().next().unwrap();
+        assert_eq!((first.title.as_str(), first.key.as_deref()), ("테마: dracula", Some("Alt+D")));
+        assert_eq!(palette.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            PaletteOutcome::Run(Action::SetTheme("dracula".into())));
+
+        for c in "zzzz".chars() {
+            palette.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(palette.matches().count(), 0);
+        assert_eq!(palette.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)), PaletteOutcome::Close);
+        assert_eq!(palette.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)), PaletteOutcome::Close);
+    }
+}
@@ -0,0 +1,551 @@
+//! 분할 pane 트리 모듈
+//! 탭 하나를 가로/세로로 나눈 pane 배치를 트리로 관리
+//! 포커스 pane의 세션은 App이 직접 보유하고 (탭과 같은 방식), 여기서는 나머지 pane 세션을 id로 보관
+//! 분할 방향은 ratatui `Direction` 기준 (Horizontal = 좌우 배치, Vertical = 위아래 배치)
+
+use crate::layout::{LayoutNode, PaneLayout, SplitDirection};
+use crate::split::{SplitSizes, MIN_PANE_SIZE};
+use ratatui::layout::{Direction, Layout, Rect};
+use std::collections::HashMap;
+
+/// pane 식별자 (탭 안에서만 유일)
+pub type PaneId = usize;
+
+/// 키보드 크기 조절 한 번에 옮기는 칸 수
+const RESIZE_STEP: i32 = 2;
+
+/// pane 포커스 이동/크기 조절 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl PaneDirection {
+    /// 설정 값 파싱 (`left`, `right`, `up`, `down`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    /// 이 방향으로 움직이는 분할 방향
+    fn axis(self) -> SplitDirection {
+        match self {
+            Self::Left | Self::Right => SplitDirection::Horizontal,
+            Self::Up | Self::Down => SplitDirection::Vertical,
+        }
+    }
+}
+
+/// 분할 트리 노드
+#[derive(Debug)]
+enum PaneNode {
+    Leaf(PaneId),
+    Split {
+        direction: SplitDirection,
+        children: Vec<PaneNode>,
+        sizes: Option<SplitSizes>, // 처음 배치할 때 균등 분할로 채움
+    },
+}
+
+impl PaneNode {
+    /// 트리의 pane id (왼쪽/위쪽부터)
+    fn leaves(&self, out: &mut Vec<PaneId>) {
+        match self {
+            PaneNode::Leaf(id) => out.push(*id),
+            PaneNode::Split { children, .. } => children.iter().for_each(|child| child.leaves(out)),
+        }
+    }
+
+    fn contains(&self, id: PaneId) -> bool {
+        match self {
+            PaneNode::Leaf(leaf) => *leaf == id,
+            PaneNode::Split { children, .. } => children.iter().any(|child| child.contains(id)),
+        }
+    }
+
+    /// `id` pane을 새 pane과 함께 분할 (같은 방향의 분할 안이면 바로 옆에 추가)
+    fn split(&mut self, id: PaneId, new_id: PaneId, direction: SplitDirection) -> bool {
+        match self {
+            PaneNode::Leaf(leaf) if *leaf == id => {
+                *self = PaneNode::Split {
+                    direction,
+                    children: vec![PaneNode::Leaf(id), PaneNode::Leaf(new_id)],
+                    sizes: None,
+                };
+                true
+            }
+            PaneNode::Leaf(_) => false,
+            PaneNode::Split { direction: split_direction, children, sizes } => {
+                if *split_direction == direction {
+                    if let Some(idx) = children.iter().position(|child| matches!(child, PaneNode::Leaf(leaf) if *leaf == id)) {
+                        children.insert(idx + 1, PaneNode::Leaf(new_id));
+                        *sizes = None;
+                        return true;
+                    }
+                }
+                children.iter_mut().any(|child| child.split(id, new_id, direction))
+            }
+        }
+    }
+
+    /// `id` pane 제거 (자식이 하나만 남은 분할은 그 자식으로 합침)
+    fn remove(&mut self, id: PaneId) -> bool {
+        let PaneNode::Split { children, sizes, .. } = self else {
+            return false;
+        };
+        let removed = match children.iter().position(|child| matches!(child, PaneNode::Leaf(leaf) if *leaf == id)) {
+            Some(idx) => {
+                children.remove(idx);
+                *sizes = None;
+                true
+            }
+            None => children.iter_mut().any(|child| child.remove(id)),
+        };
+        if children.len() == 1 {
+            *self = children.pop().expect("자식이 하나 있음");
+        }
+        removed
+    }
+
+    /// 영역을 나눠 pane별 영역 계산 (분할 크기는 영역에 맞춰 갱신)
+    fn layout(&mut self, area: Rect, out: &mut Vec<(PaneId, Rect)>) {
+        match self {
+            PaneNode::Leaf(id) => out.push((*id, area)),
+            PaneNode::Split { direction, children, sizes } => {
+                let total = split_extent(*direction, area).1;
+                let sizes = sizes.get_or_insert_with(|| SplitSizes::even(total, children.len()));
+                if sizes.total() != total {
+                    sizes.resize_total(total, MIN_PANE_SIZE);
+                }
+                for (child, child_area) in children.iter_mut().zip(child_areas(*direction, sizes, area).iter()) {
+                    child.layout(*child_area, out);
+                }
+            }
+        }
+    }
+
+    /// 화면 위치에 있는 구분선 - 분할까지의 자식 경로를 `path`에 채우고 구분선 번호 반환
+    /// `area`는 마지막 `layout`에 쓴 영역 (분할 크기가 그 영역에 맞춰져 있어야 함)
+    fn divider_at(&self, area: Rect, col: u16, row: u16, path: &mut Vec<usize>) -> Option<usize> {
+        let PaneNode::Split { direction, children, sizes: Some(sizes) } = self else {
+            return None;
+        };
+        let position = ratatui::layout::Position { x: col, y: row };
+        if !area.contains(position) {
+            return None;
+        }
+        let (start, _) = split_extent(*direction, area);
+        let offset = match direction {
+            SplitDirection::Horizontal => col,
+            SplitDirection::Vertical => row,
+        } - start;
+        if let Some(divider) = sizes.divider_at(offset) {
+            return Some(divider);
+        }
+        let areas = child_areas(*direction, sizes, area);
+        let idx = areas.iter().position(|child_area| child_area.contains(position))?;
+        path.push(idx);
+        children[idx].divider_at(areas[idx], col, row, path)
+    }
+
+    /// `path` 끝의 분할에서 구분선을 화면 위치로 끌어 옮김 (크기가 바뀌었으면 true)
+    fn drag_divider(&mut self, area: Rect, path: &[usize], divider: usize, col: u16, row: u16) -> bool {
+        let PaneNode::Split { direction, children, sizes: Some(sizes) } = self else {
+            return false;
+        };
+        match path.split_first() {
+            Some((&idx, rest)) => {
+                let Some(&child_area) = child_areas(*direction, sizes, area).get(idx) else {
+                    return false;
+                };
+                children.get_mut(idx).is_some_and(|child| child.drag_divider(child_area, rest, divider, col, row))
+            }
+            None => {
+                let (start, _) = split_extent(*direction, area);
+                let position = match direction {
+                    SplitDirection::Horizontal => col,
+                    SplitDirection::Vertical => row,
+                }.saturating_sub(start);
+                sizes.drag_divider_to(divider, position, MIN_PANE_SIZE)
+            }
+        }
+    }
+
+    /// `id` pane을 포함하는 가장 안쪽의 `axis` 방향 분할에서 구분선 이동
+    /// 해당 분할이 없으면 None, 있으면 크기가 실제로 바뀌었는지 반환
+    fn resize(&mut self, id: PaneId, axis: SplitDirection, delta: i32) -> Option<bool> {
+        let PaneNode::Split { direction, children, sizes } = self else {
+            return None;
+        };
+        let idx = children.iter().position(|child| child.contains(id))?;
+        // 안쪽 분할이 먼저 처리
+        if let Some(done) = children[idx].resize(id, axis, delta) {
+            return Some(done);
+        }
+        if *direction != axis {
+            return None;
+        }
+        let sizes = sizes.as_mut()?;
+        // 이동 방향 쪽 구분선을 옮김 (마지막 pane이면 반대쪽 구분선)
+        let divider = if idx + 1 < children.len() { idx } else { idx - 1 };
+        Some(sizes.move_divider(divider, delta, MIN_PANE_SIZE))
+    }
+
+    /// 저장용 레이아웃 트리
+    fn to_layout(&self, pane: &impl Fn(PaneId) -> PaneLayout) -> LayoutNode {
+        match self {
+            PaneNode::Leaf(id) => LayoutNode::Pane(pane(*id)),
+            PaneNode::Split { direction, children, .. } => LayoutNode::Split {
+                direction: *direction,
+                children: children.iter().map(|child| child.to_layout(pane)).collect(),
+            },
+        }
+    }
+}
+
+/// 분할 영역의 분할 방향 시작 위치와 크기
+fn split_extent(direction: SplitDirection, area: Rect) -> (u16, u16) {
+    match direction {
+        SplitDirection::Horizontal => (area.x, area.width),
+        SplitDirection::Vertical => (area.y, area.height),
+    }
+}
+
+/// 분할 크기대로 나눈 자식 영역
+fn child_areas(direction: SplitDirection, sizes: &SplitSizes, area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(match direction {
+            SplitDirection::Horizontal => Direction::Horizontal,
+            SplitDirection::Vertical => Direction::Vertical,
+        })
+        .constraints(sizes.constraints())
+        .split(area)
+}
+
+/// 마우스로 끌고 있는 구분선 (분할까지의 자식 경로와 그 분할 안의 구분선 번호)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DividerDrag {
+    path: Vec<usize>,
+    divider: usize,
+}
+
+/// 탭 하나의 pane 배치와 포커스 없는 pane 세션
+#[derive(Debug)]
+pub struct Panes<T> {
+    root: PaneNode,
+    focused: PaneId,
+    others: HashMap<PaneId, T>,
+    next_id: PaneId,
+    broadcast: bool, // 입력 동기화 (키 입력을 탭의 모든 pane에 보냄)
+    area: Rect,      // 마지막으로 배치한 영역 (구분선 판정용)
+}
+
+impl<T> Default for Panes<T> {
+    fn default() -> Self {
+        Self {
+            root: PaneNode::Leaf(0),
+            focused: 0,
+            others: HashMap::new(),
+            next_id: 1,
+            broadcast: false,
+            area: Rect::default(),
+        }
+    }
+}
+
+impl<T> Panes<T> {
+    /// 저장된 레이아웃 트리로 pane 구성 (첫 번째 pane에 포커스)
+    /// 포커스 pane 세션과 pane 배치를 반환
+    pub fn from_layout<E>(node: &LayoutNode, spawn: &mut impl FnMut(&PaneLayout) -> Result<T, E>) -> Result<(T, Self), E> {
+        let mut panes = Self { root: PaneNode::Leaf(0), focused: 0, others: HashMap::new(), next_id: 0, broadcast: false, area: Rect::default() };
+        panes.root = panes.build(node, spawn)?;
+        let focused = panes.others.remove(&0).expect("레이아웃에 pane이 하나 이상 있음");
+        Ok((focused, panes))
+    }
+
+    fn build<E>(&mut self, node: &LayoutNode, spawn: &mut impl FnMut(&PaneLayout) -> Result<T, E>) -> Result<PaneNode, E> {
+        match node {
+            LayoutNode::Pane(pane) => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.others.insert(id, spawn(pane)?);
+                Ok(PaneNode::Leaf(id))
+            }
+            LayoutNode::Split { direction, children, .. } => {
+                let children = children.iter()
+                    .map(|child| self.build(child, spawn))
+                    .collect::<Result<Vec<_>, E>>()?;
+                Ok(PaneNode::Split { direction: *direction, children, sizes: None })
+            }
+        }
+    }
+
+    /// pane 수 (포커스 pane 포함)
+    #[allow(clippy::len_without_is_empty)] // 포커스 pane이 항상 있어 비어 있을 수 없음
+    pub fn len(&self) -> usize {
+        self.others.len() + 1
+    }
+
+    /// 입력 동기화가 켜져 있는지
+    pub fn broadcast(&self) -> bool {
+        self.broadcast
+    }
+
+    /// 입력 동기화 켜기/끄기 - 바뀐 상태 반환
+    pub fn toggle_broadcast(&mut self) -> bool {
+        self.broadcast = !self.broadcast;
+        self.broadcast
+    }
+
+    /// 포커스 pane id
+    pub fn focused(&self) -> PaneId {
+        self.focused
+    }
+
+    /// 포커스 pane을 나눠 새 pane을 만들고 포커스 이동
+    pub fn split(&mut self, direction: SplitDirection, focused: &mut T, pane: T) -> PaneId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.root.split(self.focused, id, direction);
+        self.others.insert(self.focused, std::mem::replace(focused, pane));
+        self.focused = id;
+        id
+    }
+
+    /// 포커스 pane을 닫고 다음(없으면 이전) pane에 포커스
+    /// 닫은 pane 세션을 반환하며, 마지막 pane이면 닫지 않고 None
+    pub fn close(&mut self, focused: &mut T) -> Option<T> {
+        let order = self.order();
+        let idx = order.iter().position(|&id| id == self.focused)?;
+        let next = order.get(idx + 1).or_else(|| idx.checked_sub(1).and_then(|prev| order.get(prev)))?;
+        let next_session = self.others.remove(next)?;
+        self.root.remove(self.focused);
+        self.focused = *next;
+        Some(std::mem::replace(focused, next_session))
+    }
+
+    /// 포커스 없는 pane 제거 (셸이 종료된 pane 정리용)
+    pub fn remove(&mut self, id: PaneId) -> Option<T> {
+        let session = self.others.remove(&id)?;
+        self.root.remove(id);
+        Some(session)
+    }
+
+    /// 다른 pane으로 포커스 이동 (`focused`와 보관 중인 세션을 맞바꿈)
+    pub fn focus(&mut self, id: PaneId, focused: &mut T) -> bool {
+        let Some(mut session) = self.others.remove(&id) else {
+            return false;
+        };
+        std::mem::swap(focused, &mut session);
+        self.others.insert(self.focused, session);
+        self.focused = id;
+        true
+    }
+
+    /// pane id 순서 (왼쪽/위쪽부터)
+    pub fn order(&self) -> Vec<PaneId> {
+        let mut out = Vec::new();
+        self.root.leaves(&mut out);
+        out
+    }
+
+    /// 영역 안의 pane별 영역 계산
+    pub fn layout(&mut self, area: Rect) -> Vec<(PaneId, Rect)> {
+        let mut out = Vec::new();
+        self.root.layout(area, &mut out);
+        self.area = area;
+        out
+    }
+
+    /// 화면 위치에 있는 구분선 (경계 양쪽 테두리 칸을 잡을 수 있음, 마지막으로 그린 배치 기준)
+    pub fn divider_at(&self, col: u16, row: u16) -> Option<DividerDrag> {
+        let mut path = Vec::new();
+        let divider = self.root.divider_at(self.area, col, row, &mut path)?;
+        Some(DividerDrag { path, divider })
+    }
+
+    /// 끌고 있는 구분선을 화면 위치로 옮김 - 양쪽 pane은 최소 크기 유지, 크기가 바뀌었으면 true
+    pub fn drag_divider(&mut self, drag: &DividerDrag, col: u16, row: u16) -> bool {
+        self.root.drag_divider(self.area, &drag.path, drag.divider, col, row)
+    }
+
+    /// 포커스 pane에서 `direction` 쪽으로 가장 가까운 pane (`areas`는 마지막으로 그린 배치)
+    pub fn neighbor(&self, areas: &[(PaneId, Rect)], direction: PaneDirection) -> Option<PaneId> {
+        let (_, from) = areas.iter().find(|(id, _)| *id == self.focused)?;
+        areas.iter()
+            .filter(|(id, _)| *id != self.focused)
+            .filter_map(|(id, rect)| {
+                // 이동 방향 쪽에 있고, 수직 방향으로 겹치는 pane만 후보
+                let (distance, overlaps) = match direction {
+                    PaneDirection::Left => (from.left().checked_sub(rect.right())?, spans_overlap(from.top(), from.bottom(), rect.top(), rect.bottom())),
+                    PaneDirection::Right => (rect.left().checked_sub(from.right())?, spans_overlap(from.top(), from.bottom(), rect.top(), rect.bottom())),
+                    PaneDirection::Up => (from.top().checked_sub(rect.bottom())?, spans_overlap(from.left(), from.right(), rect.left(), rect.right())),
+                    PaneDirection::Down => (rect.top().checked_sub(from.bottom())?, spans_overlap(from.left(), from.right(), rect.left(), rect.right())),
+                };
+                overlaps.then_some((distance, *id))
+            })
+            .min()
+            .map(|(_, id)| id)
+    }
+
+    /// 포커스 pane 경계를 `direction` 쪽으로 이동 (tmux `resize-pane`과 같은 방식)
+    pub fn resize_focused(&mut self, direction: PaneDirection) -> bool {
+        let delta = match direction {
+            PaneDirection::Left | PaneDirection::Up => -RESIZE_STEP,
+            PaneDirection::Right | PaneDirection::Down => RESIZE_STEP,
+        };
+        self.root.resize(self.focused, direction.axis(), delta).unwrap_or(false)
+    }
+
+    /// 포커스 없는 pane 세션 (id와 함께)
+    pub fn others(&self) -> impl Iterator<Item = (PaneId, &T)> {
+        self.others.iter().map(|(id, session)| (*id, session))
+    }
+
+    /// 포커스 없는 pane 세션 (id와 함께)
+    pub fn others_mut(&mut self) -> impl Iterator<Item = (PaneId, &mut T)> {
+        self.others.iter_mut().map(|(id, session)| (*id, session))
+    }
+
+    /// 저장용 레이아웃 트리 (`pane`으로 세션을 pane 정보로 변환)
+    pub fn to_layout(&self, focused: &T, pane: impl Fn(&T) -> PaneLayout) -> LayoutNode {
+        self.root.to_layout(&|id| match self.others.get(&id) {
+            Some(session) => pane(session),
+            None => pane(focused),
+        })
+    }
+}
+
+/// 화면 위치에 있는 pane
+pub fn pane_at(areas: &[(PaneId, Rect)], col: u16, row: u16) -> Option<PaneId> {
+    areas.iter()
+        .find(|(_, rect)| rect.contains(ratatui::layout::Position { x: col, y: row }))
+        .map(|(id, _)| *id)
+}
+
+/// 두 구간 [a_start, a_end), [b_start, b_end)이 겹치는지
+fn spans_overlap(a_start: u16, a_end: u16, b_start: u16, b_end: u16) -> bool {
+    a_start < b_end && b_start < a_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::Shell;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_split_focus_and_close() {
+        let mut panes = Panes::default();
+        let mut focused = "a";
+
+        // a | b, b를 위아래로 나눠 b / c
+        let b = panes.split(SplitDirection::Horizontal, &mut focused, "b");
+        let c = panes.split(SplitDirection::Vertical, &mut focused, "c");
+        assert_eq!((focused, panes.len()), ("c", 3));
+        assert_eq!(panes.order(), vec![0, b, c]);
+
+        let areas = panes.layout(Rect::new(0, 0, 80, 20));
+        assert_eq!(areas, vec![
+            (0, Rect::new(0, 0, 40, 20)),
+            (b, Rect::new(40, 0, 40, 10)),
+            (c, Rect::new(40, 10, 40, 10)),
+        ]);
+
+        // 방향으로 이웃 pane 찾기
+        assert_eq!(panes.neighbor(&areas, PaneDirection::Up), Some(b));
+        assert_eq!(panes.neighbor(&areas, PaneDirection::Left), Some(0));
+        assert_eq!(panes.neighbor(&areas, PaneDirection::Right), None);
+        assert_eq!(pane_at(&areas, 10, 5), Some(0));
+
+        assert!(panes.focus(0, &mut focused));
+        assert_eq!((focused, panes.focused()), ("a", 0));
+
+        // 입력 동기화는 탭(pane 배치) 단위로 포커스와 관계없이 유지
+        assert!(panes.toggle_broadcast());
+        assert!(panes.focus(b, &mut focused) && panes.broadcast());
+        assert!(panes.focus(0, &mut focused));
+
+        // 닫으면 다음 pane에 포커스, 분할이 하나만 남으면 합쳐짐
+        assert_eq!(panes.close(&mut focused), Some("a"));
+        assert_eq!((focused, panes.focused()), ("b", b));
+        assert_eq!(panes.layout(Rect::new(0, 0, 80, 20)), vec![
+            (b, Rect::new(0, 0, 80, 10)),
+            (c, Rect::new(0, 10, 80, 10)),
+        ]);
+        assert_eq!(panes.remove(c), Some("c"));
+        assert_eq!(panes.close(&mut focused), None);
+        assert_eq!(panes.len(), 1);
+    }
+
+    #[test]
+    fn test_resize_moves_focused_border() {
+        let mut panes = Panes::default();
+        let mut focused = 'a';
+        panes.split(SplitDirection::Horizontal, &mut focused, 'b');
+        let area = Rect::new(0, 0, 20, 10);
+        panes.layout(area);
+
+        // 마지막 pane에서 오른쪽으로 이동하면 왼쪽 경계가 이동 (pane이 작아짐)
+        assert!(panes.resize_focused(PaneDirection::Right));
+        assert_eq!(panes.layout(area)[1].1.width, 8);
+        // 같은 방향 분할이 없으면 무시
+        assert!(!panes.resize_focused(PaneDirection::Up));
+
+        panes.focus(0, &mut focused);
+        assert!(panes.resize_focused(PaneDirection::Left));
+        assert_eq!(panes.layout(area)[0].1.width, 10);
+    }
+
+    #[test]
+    fn test_drag_dividers_with_mouse() {
+        let mut panes = Panes::default();
+        let mut focused = "a";
+        let b = panes.split(SplitDirection::Horizontal, &mut focused, "b");
+        let c = panes.split(SplitDirection::Vertical, &mut focused, "c");
+        let area = Rect::new(2, 1, 80, 20);
+        panes.layout(area);
+
+        // 좌우 경계(x = 42) 양쪽 테두리 칸이 grip, pane 안쪽은 아님
+        let outer = panes.divider_at(41, 5).unwrap();
+        assert_eq!(panes.divider_at(42, 5), Some(outer.clone()));
+        assert_eq!(panes.divider_at(20, 5), None);
+        assert!(panes.drag_divider(&outer, 52, 5));
+        assert_eq!(panes.layout(area)[0].1.width, 50);
+
+        // 안쪽 위아래 분할의 구분선
+        let inner = panes.divider_at(60, 10).unwrap();
+        assert_ne!(inner, outer);
+        assert!(panes.drag_divider(&inner, 60, 15));
+        let areas = panes.layout(area);
+        assert_eq!((areas[1], areas[2]), ((b, Rect::new(52, 1, 30, 14)), (c, Rect::new(52, 15, 30, 6))));
+
+        // 끝까지 끌어도 최소 크기 유지
+        assert!(panes.drag_divider(&outer, 200, 5));
+        assert_eq!(panes.layout(area)[1].1.width, MIN_PANE_SIZE);
+    }
+
+    #[test]
+    fn test_layout_round_trip() {
+        let pane = |dir: &str| PaneLayout { working_directory: PathBuf::from(dir), shell: Shell::System, scrollback: Vec::new() };
+        let node = LayoutNode::Split {
+            direction: SplitDirection::Vertical,
+            children: vec![LayoutNode::Pane(pane("/a")), LayoutNode::Pane(pane("/b"))],
+        };
+
+        let (focused, panes) = Panes::from_layout(&node, &mut |layout: &PaneLayout| {
+            Ok::<_, ()>(layout.working_directory.clone())
+        }).unwrap();
+        assert_eq!(focused, PathBuf::from("/a"));
+        assert_eq!(panes.len(), 2);
+        assert_eq!(panes.to_layout(&focused, |dir| PaneLayout { working_directory: dir.clone(), shell: Shell::System, scrollback: Vec::new() }), node);
+    }
+}
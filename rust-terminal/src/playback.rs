@@ -0,0 +1,170 @@
+//! 녹화 재생 모듈 (`--play 파일.cast`)
+//! 셸 없이 asciicast 녹화를 ratatui 화면에 재생하고, 일시 정지/이동/재생 속도 키를 처리
+//!
+//! 키: Space 일시 정지, ←/→ 5초 이동, ↑/↓ 30초 이동, +/- 재생 속도 2배/절반, Home 처음으로, End 끝으로, q/Esc 종료
+
+use crate::asciicast::{Cast, CastEvent, Playback};
+use crate::config::Config;
+use crate::render;
+use crate::terminal::{Terminal, TerminalBounds};
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+    Terminal as RatatuiTerminal,
+};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// ←/→, ↑/↓로 이동하는 시간 (초)
+const SHORT_SEEK: f64 = 5.0;
+const LONG_SEEK: f64 = 30.0;
+
+/// 녹화를 끝까지(또는 q를 누를 때까지) 재생
+pub fn run<B: Backend>(ratatui_terminal: &mut RatatuiTerminal<B>, cast: &Cast, config: &Config) -> Result<()> {
+    let new_terminal = || Terminal::detached(cast.header.height, cast.header.width, config.scrollback.history_lines);
+    let mut terminal = new_terminal();
+    let mut playback = Playback::default();
+    let title = cast.header.title.clone().unwrap_or_else(|| "녹화 재생".to_string());
+    let mut last_frame = Instant::now();
+    // 화면 줄 캐시 - 지난 프레임 뒤 바뀐 줄만 다시 만듦 (`seen_version`이 0이면 전체)
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut seen_version = 0;
+
+    loop {
+        let now = Instant::now();
+        let range = playback.advance(cast, now.duration_since(last_frame).as_secs_f64());
+        last_frame = now;
+        if apply_events(&mut terminal, cast, range) {
+            seen_version = 0;
+        }
+
+        let version = terminal.content_version();
+        if seen_version == 0 {
+            lines.clear();
+        }
+        for diff in terminal.diff_since(seen_version) {
+            if diff.row >= lines.len() {
+                lines.resize(diff.row + 1, Line::default());
+            }
+            let styles = render::line_styles(&diff.cells, &config.colors);
+            let text = render::grid_aligned_text(&diff.text, &diff.cells);
+            lines[diff.row] = render::styled_line(&text, &styles, Style::default().fg(config.colors.foreground()));
+        }
+        seen_version = version;
+
+        ratatui_terminal.draw(|f| {
+            let [content_area, status_area] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(1), Constraint::Length(1)])
+                .areas(f.area());
+
+            render::fill_background(f, content_area, config.colors.background());
+            let block = Block::default().title(format!(" {} ", title)).borders(Borders::ALL);
+            let visible: Vec<Line> = lines.iter().map(render::borrowed_line).collect();
+            f.render_widget(Paragraph::new(visible).block(block), content_area);
+
+            let status = Paragraph::new(status_line(&playback, cast))
+                .style(Style::default().bg(Color::DarkGray).fg(Color::White));
+            f.render_widget(status, status_area);
+        })?;
+
+        // 재생 중이면 다음 프레임까지만 기다림
+        let poll = if playback.is_paused() || playback.is_finished(cast) { 200 } else { 16 };
+        if !event::poll(Duration::from_millis(poll))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let seek_to = match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char(' ') => {
+                playback.toggle_pause();
+                None
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                playback.scale_speed(2.0);
+                None
+            }
+            KeyCode::Char('-') => {
+                playback.scale_speed(0.5);
+                None
+            }
+            KeyCode::Left => Some(playback.position() - SHORT_SEEK),
+            KeyCode::Right => Some(playback.position() + SHORT_SEEK),
+            KeyCode::Down => Some(playback.position() - LONG_SEEK),
+            KeyCode::Up => Some(playback.position() + LONG_SEEK),
+            KeyCode::Home => Some(0.0),
+            KeyCode::End => Some(cast.duration()),
+            _ => None,
+        };
+        if let Some(target) = seek_to {
+            let (restart, range) = playback.seek(cast, target);
+            if restart {
+                terminal = new_terminal();
+            }
+            if apply_events(&mut terminal, cast, range) || restart {
+                seen_version = 0;
+            }
+        }
+    }
+}
+
+/// 녹화 이벤트를 터미널에 적용 - 터미널 크기가 바뀌었으면 true (화면 전체를 다시 그려야 함)
+fn apply_events(terminal: &mut Terminal, cast: &Cast, range: Range<usize>) -> bool {
+    let mut resized = false;
+    for (_, event) in &cast.events[range] {
+        match event {
+            CastEvent::Output(text) => terminal.write_output(text.as_bytes()),
+            CastEvent::Resize { cols, rows } => {
+                resized |= terminal.resize(TerminalBounds { num_lines: *rows, num_cols: *cols }).unwrap_or(false);
+            }
+        }
+    }
+    resized
+}
+
+/// 아래 상태 줄 (재생 상태, 위치/길이, 속도, 키 안내)
+fn status_line(playback: &Playback, cast: &Cast) -> String {
+    let state = if playback.is_finished(cast) {
+        "■"
+    } else if playback.is_paused() {
+        "❚❚"
+    } else {
+        "▶"
+    };
+    format!(" {} {} / {}  {}x  Space: 일시 정지  ←/→: 5초  ↑/↓: 30초  +/-: 속도  Home/End: 처음/끝  q: 종료",
+        state, format_time(playback.position()), format_time(cast.duration()), playback.speed())
+}
+
+/// 초를 `분:초`로
+fn format_time(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_events_and_format_time() {
+        let cast = Cast::parse("{\"version\": 2, \"width\": 20, \"height\": 3}\n[0.1, \"o\", \"hello\\r\\n\\u001b[31mre\"]\n[0.2, \"o\", \"d\"]\n[0.3, \"r\", \"30x4\"]").unwrap();
+        let mut terminal = Terminal::detached(3, 20, 100);
+        apply_events(&mut terminal, &cast, 0..3);
+        let lines = terminal.get_visible_content();
+        assert_eq!(lines.len(), 4);
+        assert_eq!((lines[0].trim_end(), lines[1].trim_end()), ("hello", "red"));
+
+        assert_eq!(format_time(0.4), "00:00");
+        assert_eq!(format_time(754.9), "12:34");
+    }
+}
@@ -0,0 +1,131 @@
+//! 프로필 모듈
+//! config.toml의 `[profiles.이름]`으로 정의한 셸/명령 묶음 (셸과 인자, 환경 변수, 작업 디렉토리, 테마, 제목)
+//! 시작할 때 `--profile 이름`으로 고르거나, 명령 팔레트의 `new-tab-profile 이름`으로 새 탭에서 실행
+//!
+//! ```toml
+//! [profiles.work]
+//! program = "/bin/zsh"
+//! args = ["-l"]
+//! working_directory = "~/work"
+//! theme = "dracula"
+//! title = "work"
+//!
+//! [profiles.work.env]
+//! AWS_PROFILE = "work"
+//! ```
+
+use crate::config::expand_home;
+use crate::terminal::Shell;
+use crate::toml;
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+
+/// 프로필 하나 (지정하지 않은 항목은 기본 설정을 따름)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub program: Option<String>,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>, // 셸에 추가로 넘기는 환경 변수 (파일에 나온 순서)
+    pub working_directory: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub title: Option<String>, // 탭 이름과 기본 창 제목
+}
+
+impl Profile {
+    /// 빈 프로필
+    pub fn named(name: &str) -> Self {
+        Self { name: name.to_string(), ..Self::default() }
+    }
+
+    /// `[profiles.이름]`의 `키 = 값` 하나 적용
+    pub fn set(&mut self, key: &str, value: &toml::Value) -> Result<()> {
+        let text = || value.as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("profiles.{}.{}는 문자열이어야 함", self.name, key));
+        match key {
+            "program" => self.program = Some(text()?),
+            "working_directory" => self.working_directory = Some(expand_home(&text()?)),
+            "theme" => self.theme = Some(text()?),
+            "title" => self.title = Some(text()?),
+            "args" => {
+                let toml::Value::Array(items) = value else {
+                    bail!("profiles.{}.args는 문자열 배열이어야 함", self.name);
+                };
+                self.args = items.iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| anyhow!("profiles.{}.args는 문자열 배열이어야 함", self.name))?;
+            }
+            _ => bail!("알 수 없는 프로필 설정: profiles.{}.{}", self.name, key),
+        }
+        Ok(())
+    }
+
+    /// `[profiles.이름.env]`의 환경 변수 하나 (같은 이름이면 나중 값)
+    pub fn set_env(&mut self, key: &str, value: &toml::Value) -> Result<()> {
+        let value = match value {
+            toml::Value::String(value) => value.clone(),
+            toml::Value::Integer(value) => value.to_string(),
+            toml::Value::Boolean(value) => value.to_string(),
+            toml::Value::Array(_) => bail!("profiles.{}.env.{}에는 배열을 쓸 수 없음", self.name, key),
+        };
+        self.env.retain(|(name, _)| name != key);
+        self.env.push((key.to_string(), value));
+        Ok(())
+    }
+
+    /// 실행할 셸 (프로그램을 지정하지 않았으면 None)
+    pub fn shell(&self) -> Option<Shell> {
+        let program = self.program.clone()?;
+        Some(if self.args.is_empty() {
+            Shell::Program(program)
+        } else {
+            Shell::WithArguments { program, args: self.args.clone() }
+        })
+    }
+}
+
+/// `[profiles.이름]` 또는 `[profiles.이름.env]` 테이블의 값을 해당 프로필에 적용 (없으면 새로 만듦)
+pub fn set_entry(profiles: &mut Vec<Profile>, table: &str, entry: &toml::Entry) -> Result<()> {
+    let (name, env) = match table.strip_suffix(".env") {
+        Some(name) => (name, true),
+        None => (table, false),
+    };
+    let index = match profiles.iter().position(|profile| profile.name == name) {
+        Some(index) => index,
+        None => {
+            profiles.push(Profile::named(name));
+            profiles.len() - 1
+        }
+    };
+    if env {
+        profiles[index].set_env(&entry.key, &entry.value)
+    } else {
+        profiles[index].set(&entry.key, &entry.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_entries() {
+        let text = "[profiles.work]\nprogram = \"zsh\"\nargs = [\"-l\"]\ntitle = \"work\"\n\n[profiles.work.env]\nAWS_PROFILE = \"work\"\nDEBUG = 1\n\n[profiles.plain]\ntheme = \"nord\"\n";
+        let mut profiles = Vec::new();
+        for entry in toml::parse(text).unwrap() {
+            let table = entry.table.strip_prefix("profiles.").unwrap().to_string();
+            set_entry(&mut profiles, &table, &entry).unwrap();
+        }
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].shell(), Some(Shell::WithArguments { program: "zsh".into(), args: vec!["-l".into()] }));
+        assert_eq!(profiles[0].env, vec![("AWS_PROFILE".to_string(), "work".to_string()), ("DEBUG".to_string(), "1".to_string())]);
+        assert_eq!(profiles[0].title.as_deref(), Some("work"));
+        assert_eq!((profiles[1].shell(), profiles[1].theme.as_deref()), (None, Some("nord")));
+
+        let mut profile = Profile::named("x");
+        assert!(profile.set("size", &toml::Value::Integer(1)).is_err());
+        assert!(profile.set("args", &toml::Value::String("-l".into())).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! 프롬프트 이동 모듈
+//! 셸 통합(OSC 133) 표시로 이전/다음 명령의 프롬프트를 찾고, 명령 하나의 출력 범위(블록)를 계산
+//!
+//! 행은 모두 전체 버퍼 행(히스토리 맨 위 = 0) 기준 (`Terminal::prompt_marks`와 동일)
+
+use crate::osc::PromptMarkKind;
+use std::ops::RangeInclusive;
+
+/// 명령 하나의 프롬프트와 출력 범위
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandBlock {
+    /// 프롬프트 시작 행
+    pub prompt: usize,
+    /// 출력 시작 행 (OSC 133;C가 없으면 프롬프트 다음 행)
+    pub output: usize,
+    /// 블록 마지막 행 (다음 프롬프트 바로 위)
+    pub end: usize,
+}
+
+impl CommandBlock {
+    /// 출력 행 범위 (출력이 없는 명령이면 None)
+    pub fn output_rows(&self) -> Option<RangeInclusive<usize>> {
+        (self.output <= self.end).then_some(self.output..=self.end)
+    }
+}
+
+/// 프롬프트 시작 행 목록 (위쪽부터, 중복 없음)
+pub fn prompt_rows(marks: &[(usize, PromptMarkKind)]) -> Vec<usize> {
+    let mut rows: Vec<usize> = marks.iter()
+        .filter(|(_, kind)| *kind == PromptMarkKind::Prompt)
+        .map(|(row, _)| *row)
+        .collect();
+    rows.dedup();
+    rows
+}
+
+/// `row`보다 위에 있는 가장 가까운 프롬프트
+pub fn previous_prompt(rows: &[usize], row: usize) -> Option<usize> {
+    rows.iter().rev().find(|&&prompt| prompt < row).copied()
+}
+
+/// `row`보다 아래에 있는 가장 가까운 프롬프트
+pub fn next_prompt(rows: &[usize], row: usize) -> Option<usize> {
+    rows.iter().find(|&&prompt| prompt > row).copied()
+}
+
+/// `prompt` 행에서 시작하는 명령의 블록
+/// 다음 프롬프트가 없으면(마지막 명령) `last_row`까지
+pub fn command_block(marks: &[(usize, PromptMarkKind)], prompt: usize, last_row: usize) -> Option<CommandBlock> {
+    let start = marks.iter().position(|&(row, kind)| row == prompt && kind == PromptMarkKind::Prompt)?;
+    let rest = &marks[start + 1..];
+    let next = rest.iter().position(|(row, kind)| *kind == PromptMarkKind::Prompt && *row > prompt);
+    let within = &rest[..next.unwrap_or(rest.len())];
+
+    let end = match next {
+        Some(idx) => rest[idx].0.saturating_sub(1),
+        None => last_row,
+    };
+    let output = within.iter()
+        .find(|(_, kind)| *kind == PromptMarkKind::Output)
+        .map_or(prompt + 1, |(row, _)| *row);
+    Some(CommandBlock { prompt, output, end: end.max(prompt) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marks() -> Vec<(usize, PromptMarkKind)> {
+        vec![
+            (0, PromptMarkKind::Prompt),
+            (0, PromptMarkKind::Input),
+            (1, PromptMarkKind::Output),
+            (4, PromptMarkKind::Finished(Some(0))),
+            (4, PromptMarkKind::Prompt),
+            (4, PromptMarkKind::Input),
+            (5, PromptMarkKind::Finished(Some(0))),
+            (5, PromptMarkKind::Prompt),
+        ]
+    }
+
+    #[test]
+    fn test_previous_and_next_prompt() {
+        let rows = prompt_rows(&marks());
+        assert_eq!(rows, vec![0, 4, 5]);
+        assert_eq!(previous_prompt(&rows, 5), Some(4));
+        assert_eq!(previous_prompt(&rows, 3), Some(0));
+        assert_eq!(previous_prompt(&rows, 0), None);
+        assert_eq!(next_prompt(&rows, 0), Some(4));
+        assert_eq!(next_prompt(&rows, 5), None);
+    }
+
+    #[test]
+    fn test_command_block() {
+        let marks = marks();
+        let block = command_block(&marks, 0, 9).unwrap();
+        assert_eq!(block, CommandBlock { prompt: 0, output: 1, end: 3 });
+        assert_eq!(block.output_rows(), Some(1..=3));
+
+        // 출력 없이 끝난 명령 (133;C 없음)
+        let block = command_block(&marks, 4, 9).unwrap();
+        assert_eq!(block.output_rows(), None);
+
+        // 마지막 명령은 `last_row`까지
+        assert_eq!(command_block(&marks, 5, 9).unwrap().end, 9);
+        assert_eq!(command_block(&marks, 2, 9), None);
+    }
+}
@@ -0,0 +1,64 @@
+//! 세션 닫기/종료 확인 모듈
+//! 마지막 세션을 닫으면 앱이 종료되므로, 실수로 모든 세션을 잃지 않도록 한 번 더 눌러야 닫히게 함
+
+/// 닫기 요청 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseOutcome {
+    /// 다른 세션이 남아 있어 이 세션만 닫음
+    Closed,
+    /// 마지막 세션이라 확인 대기 (한 번 더 누르면 종료)
+    NeedsConfirm,
+    /// 마지막 세션을 닫고 앱 종료
+    Quit,
+}
+
+/// 마지막 세션 닫기 확인 상태
+#[derive(Debug, Clone, Default)]
+pub struct QuitConfirm {
+    pending: bool,
+}
+
+impl QuitConfirm {
+    /// 세션 닫기 요청 (`open_sessions`는 닫기 전 열린 세션 수)
+    /// 마지막 세션이 아니면 확인 없이 닫고, 마지막 세션이면 `confirm_last`일 때 두 번째 요청에서 종료
+    pub fn request_close(&mut self, open_sessions: usize, confirm_last: bool) -> CloseOutcome {
+        if open_sessions > 1 {
+            self.pending = false;
+            return CloseOutcome::Closed;
+        }
+        if confirm_last && !self.pending {
+            self.pending = true;
+            return CloseOutcome::NeedsConfirm;
+        }
+        self.pending = false;
+        CloseOutcome::Quit
+    }
+
+    /// 확인 대기 중인지 (상단에 안내 표시)
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_session_close_requires_confirmation() {
+        let mut confirm = QuitConfirm::default();
+
+        // 다른 세션이 남아 있으면 바로 닫음
+        assert_eq!(confirm.request_close(2, true), CloseOutcome::Closed);
+        assert!(!confirm.is_pending());
+
+        // 마지막 세션은 한 번 더 눌러야 종료
+        assert_eq!(confirm.request_close(1, true), CloseOutcome::NeedsConfirm);
+        assert!(confirm.is_pending());
+        assert_eq!(confirm.request_close(1, true), CloseOutcome::Quit);
+
+        // 확인을 끄면 바로 종료
+        let mut confirm = QuitConfirm::default();
+        assert_eq!(confirm.request_close(1, false), CloseOutcome::Quit);
+    }
+}
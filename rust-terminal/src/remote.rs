@@ -0,0 +1,248 @@
+//! 원격(SSH) 세션 모듈
+//! config.toml의 `[ssh.이름]` 호스트나 `사용자@호스트:포트`로 시스템 `ssh`를 PTY에서 실행
+//! 로컬 셸과 같은 `Terminal`/PTY 경로를 쓰므로 스크롤백, 검색, 녹화 등은 로컬 세션과 똑같이 동작
+//!
+//! 같은 호스트로 여는 탭/pane은 ControlMaster 연결 하나를 함께 써서 인증 없이 바로 열리고,
+//! 연결이 끊겨 `ssh`가 255로 끝나면 (`reconnect`를 켠 경우) 잠시 뒤 다시 연결
+//!
+//! ```toml
+//! [ssh.prod]
+//! host = "prod.example.com"
+//! user = "deploy"
+//! port = 2222
+//! identity_file = "~/.ssh/prod"
+//! forward_agent = true
+//! options = ["StrictHostKeyChecking=accept-new"]
+//! ```
+
+use crate::config::expand_home;
+use crate::toml;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 연결 오류로 끝난 `ssh`의 종료 코드 (원격 명령의 종료 코드와 구분)
+pub const CONNECTION_LOST_CODE: i32 = 255;
+
+/// 연결이 끊긴 뒤 다시 연결하기까지 기다리는 시간 (네트워크가 없을 때 계속 재시도하지 않도록)
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// 공유 연결이 마지막 세션을 닫은 뒤에도 유지되는 시간 (`ControlPersist`)
+const CONTROL_PERSIST: &str = "10m";
+
+/// SSH 호스트 하나
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SshHost {
+    pub name: String,
+    pub host: String,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<PathBuf>,
+    pub forward_agent: bool,
+    pub reconnect: bool,      // 연결이 끊기면 다시 연결
+    pub options: Vec<String>, // `ssh -o`로 넘기는 `키=값`
+}
+
+impl SshHost {
+    /// 호스트 이름만 정한 설정 (다시 연결은 기본으로 켬)
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            host: name.to_string(),
+            user: None,
+            port: None,
+            identity_file: None,
+            forward_agent: false,
+            reconnect: true,
+            options: Vec::new(),
+        }
+    }
+
+    /// `[사용자@]호스트[:포트]` 형식의 즉석 대상
+    pub fn parse_target(target: &str) -> Result<Self> {
+        let target = target.trim();
+        let mut host = Self::named(target);
+        let (user, address) = match target.rsplit_once('@') {
+            Some((user, address)) => (Some(user), address),
+            None => (None, target),
+        };
+        let address = match address.rsplit_once(':') {
+            Some((address, port)) => {
+                host.port = Some(port.parse().map_err(|_| anyhow!("잘못된 SSH 포트: {}", port))?);
+                address
+            }
+            None => address,
+        };
+        if address.is_empty() || user.is_some_and(str::is_empty) {
+            bail!("잘못된 SSH 대상: {}", target);
+        }
+        host.host = address.to_string();
+        host.user = user.map(str::to_string);
+        Ok(host)
+    }
+
+    /// `[ssh.이름]`의 `키 = 값` 하나 적용
+    pub fn set(&mut self, key: &str, value: &toml::Value) -> Result<()> {
+        let invalid = |kind: &str| anyhow!("ssh.{}.{}는 {}이어야 함", self.name, key, kind);
+        let text = || value.as_str().map(str::to_string).ok_or_else(|| invalid("문자열"));
+        let flag = || match value {
+            toml::Value::Boolean(value) => Ok(*value),
+            _ => Err(invalid("true/false")),
+        };
+        match key {
+            "host" => self.host = text()?,
+            "user" => self.user = Some(text()?),
+            "identity_file" => self.identity_file = Some(expand_home(&text()?)),
+            "forward_agent" => self.forward_agent = flag()?,
+            "reconnect" => self.reconnect = flag()?,
+            "port" => {
+                let toml::Value::Integer(port) = value else {
+                    return Err(invalid("포트 번호"));
+                };
+                self.port = Some(u16::try_from(*port).map_err(|_| invalid("포트 번호"))?);
+            }
+            "options" => {
+                let toml::Value::Array(items) = value else {
+                    return Err(invalid("문자열 배열"));
+                };
+                self.options = items.iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| invalid("문자열 배열"))?;
+            }
+            _ => bail!("알 수 없는 SSH 설정: ssh.{}.{}", self.name, key),
+        }
+        Ok(())
+    }
+
+    /// `ssh`에 넘기는 접속 대상 (`사용자@호스트`)
+    pub fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// 탭 이름 등에 표시할 이름
+    pub fn label(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}:{}", self.destination(), port),
+            None => self.destination(),
+        }
+    }
+
+    /// `ssh` 인자 - `control_dir`가 있으면 그 디렉토리의 소켓으로 연결을 함께 씀
+    pub fn ssh_args(&self, control_dir: Option<&Path>) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut option = |value: String| {
+            args.push("-o".to_string());
+            args.push(value);
+        };
+        if let Some(control_dir) = control_dir {
+            option("ControlMaster=auto".to_string());
+            option(format!("ControlPath={}", control_dir.join("%C").display()));
+            option(format!("ControlPersist={}", CONTROL_PERSIST));
+        }
+        // 끊긴 연결을 빨리 알아채야 다시 연결할 수 있음
+        option("ServerAliveInterval=15".to_string());
+        option("ServerAliveCountMax=3".to_string());
+        for value in &self.options {
+            option(value.clone());
+        }
+        if let Some(port) = self.port {
+            args.extend(["-p".to_string(), port.to_string()]);
+        }
+        if let Some(identity_file) = &self.identity_file {
+            args.extend(["-i".to_string(), identity_file.display().to_string()]);
+        }
+        args.push(if self.forward_agent { "-A" } else { "-a" }.to_string());
+        args.push(self.destination());
+        args
+    }
+}
+
+/// `[ssh.이름]` 테이블의 값을 해당 호스트에 적용 (없으면 새로 만듦)
+pub fn set_entry(hosts: &mut Vec<SshHost>, name: &str, entry: &toml::Entry) -> Result<()> {
+    let index = match hosts.iter().position(|host| host.name == name) {
+        Some(index) => index,
+        None => {
+            hosts.push(SshHost::named(name));
+            hosts.len() - 1
+        }
+    };
+    hosts[index].set(&entry.key, &entry.value)
+}
+
+/// 공유 연결 소켓 디렉토리를 준비 (`$XDG_RUNTIME_DIR/rust-terminal-ssh`, 없으면 임시 디렉토리의 사용자별 디렉토리)
+/// 나만 쓰는 디렉토리가 아니면 None - 다른 사용자가 소켓을 바꿔치기해 인증된 연결을 가로채지 못하게 연결을 함께 쓰지 않음
+#[cfg(unix)]
+pub fn control_dir() -> Option<PathBuf> {
+    let dir = match dirs::runtime_dir() {
+        Some(dir) => dir.join("rust-terminal-ssh"),
+        // SAFETY: getuid는 항상 성공
+        None => std::env::temp_dir().join(format!("rust-terminal-ssh-{}", unsafe { libc::getuid() })),
+    };
+    match crate::daemon::private_dir(&dir) {
+        Ok(()) => Some(dir),
+        Err(e) => {
+            log::warn!("ssh control directory {}: {}", dir.display(), e);
+            None
+        }
+    }
+}
+
+/// Unix 소켓이 없으면 연결을 함께 쓰지 않음
+#[cfg(not(unix))]
+pub fn control_dir() -> Option<PathBuf> {
+    None
+}
+
+/// 다시 연결을 기다리는 pane의 안내 문구
+pub fn reconnect_notice(remaining: Duration) -> String {
+    format!(" 연결 끊김 — {}초 뒤 다시 연결 (아무 키나 누르면 닫기) ", remaining.as_secs() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        let host = SshHost::parse_target("deploy@db.internal:2222").unwrap();
+        assert_eq!((host.user.as_deref(), host.host.as_str(), host.port), (Some("deploy"), "db.internal", Some(2222)));
+        assert_eq!(host.label(), "deploy@db.internal:2222");
+        assert!(host.reconnect);
+
+        let host = SshHost::parse_target("example.com").unwrap();
+        assert_eq!((host.destination(), host.port), ("example.com".to_string(), None));
+        assert!(SshHost::parse_target("user@").is_err());
+        assert!(SshHost::parse_target("host:ssh").is_err());
+    }
+
+    #[test]
+    fn test_host_entries_and_args() {
+        let text = "[ssh.prod]\nhost = \"prod.example.com\"\nuser = \"deploy\"\nport = 2222\nforward_agent = true\noptions = [\"StrictHostKeyChecking=accept-new\"]\n";
+        let mut hosts = Vec::new();
+        for entry in toml::parse(text).unwrap() {
+            let name = entry.table.strip_prefix("ssh.").unwrap().to_string();
+            set_entry(&mut hosts, &name, &entry).unwrap();
+        }
+        assert_eq!(hosts.len(), 1);
+        let args = hosts[0].ssh_args(Some(Path::new("/run/ssh")));
+        assert_eq!(&args[..2], ["-o", "ControlMaster=auto"]);
+        assert!(args.contains(&"ControlPath=/run/ssh/%C".to_string()));
+        assert!(args.contains(&"StrictHostKeyChecking=accept-new".to_string()));
+        assert_eq!(&args[args.len() - 4..], ["-p", "2222", "-A", "deploy@prod.example.com"]);
+
+        // 공유 연결 없이, 에이전트 전달 끔
+        let args = SshHost::named("box").ssh_args(None);
+        assert!(!args.iter().any(|arg| arg.starts_with("Control")));
+        assert_eq!(&args[args.len() - 2..], ["-a", "box"]);
+
+        let mut host = SshHost::named("x");
+        assert!(host.set("port", &toml::Value::Integer(70000)).is_err());
+        assert!(host.set("forward_agent", &toml::Value::String("yes".into())).is_err());
+        assert!(host.set("password", &toml::Value::String("no".into())).is_err());
+    }
+}
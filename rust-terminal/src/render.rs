@@ -0,0 +1,786 @@
+//! 화면 그리기 보조 모듈
+
+use crate::columns;
+use crate::config::{ColorsConfig, WidthConfig};
+use crate::smart::{self, SmartMatch, SmartRule};
+use crate::terminal::{RenderableCell, ScrollState, Terminal, TerminalBounds};
+use crate::theme::Theme;
+use alacritty_terminal::{
+    term::cell::Flags,
+    vte::ansi::{Color as AnsiColor, NamedColor},
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Block,
+    Frame,
+};
+use std::time::{Duration, Instant};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 공백 표시 모드 마커
+const SPACE_MARKER: char = '·';
+const TAB_MARKER: char = '→';
+const WRAP_MARKER: char = '↵';
+
+/// 스크롤 가장자리 표시 (화면 위/아래에 더 볼 내용이 있음)
+const MORE_ABOVE_MARKER: &str = "▲";
+const MORE_BELOW_MARKER: &str = "▼";
+
+/// 기본 탭 간격
+const TAB_WIDTH: usize = 8;
+
+/// 대체 화면 전환 후 다시 그리기를 미루는 최대 시간
+const SCREEN_SWITCH_MAX_DEFER: Duration = Duration::from_millis(50);
+/// 이 시간 동안 새 출력이 없으면 전환이 끝난 것으로 봄
+const SCREEN_SWITCH_QUIET: Duration = Duration::from_millis(8);
+
+/// 영역 전체를 기본 배경색으로 채움
+/// 내용보다 화면이 길 때 아래쪽 빈 줄과 짧은 줄 오른쪽의 빈 칸까지 같은 배경이 되도록
+/// 내용을 그리기 전에 호출
+pub fn fill_background(f: &mut Frame, area: Rect, background: Color) {
+    f.render_widget(Block::default().style(Style::default().bg(background)), area);
+}
+
+/// 공백 표시 모드 마커 스타일 (내용과 구분되도록 흐리게)
+pub fn whitespace_marker_style() -> Style {
+    Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM)
+}
+
+/// 프롬프트 이동으로 고른 명령의 출력 강조 (글자에 배경색이 없는 칸에만 적용)
+pub fn command_block_style() -> Style {
+    Style::default().bg(Color::Indexed(236))
+}
+
+/// 공백 표시 모드에서 줄의 각 칸에 그릴 문자와 마커 여부
+/// 한 칸을 한 글자로만 바꾸므로 커서/마우스 열 계산에 영향 없음
+/// 줄 끝의 빈 칸은 그리드가 채운 칸과 구분할 수 없으므로 표시하지 않고,
+/// 탭이 다음 탭 위치(8칸 단위)까지 건너뛴 칸도 공백 마커 없이 둠
+pub fn whitespace_markers(line: &str) -> Vec<(char, bool)> {
+    let content_len = line.trim_end_matches(' ').chars().count();
+    let mut in_tab = false;
+    line.chars()
+        .enumerate()
+        .map(|(col, ch)| {
+            in_tab &= ch == ' ' && col % TAB_WIDTH != 0;
+            match ch {
+                '\t' => {
+                    in_tab = true;
+                    (TAB_MARKER, true)
+                }
+                ' ' if col < content_len && !in_tab => (SPACE_MARKER, true),
+                ch => (ch, false),
+            }
+        })
+        .collect()
+}
+
+/// 시각 벨: `area`(테두리 안쪽 내용 영역)의 글자색과 배경색을 뒤바꿔 그림
+pub fn flash_visual_bell(f: &mut Frame, area: Rect) {
+    f.buffer_mut().set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+}
+
+/// 자동 줄 바꿈된 줄의 오른쪽 테두리에 줄 바꿈 마커 표시
+/// `area`는 테두리 안쪽 내용 영역, `wraps`는 화면 줄별 줄 바꿈 여부
+pub fn mark_wrapped_rows(f: &mut Frame, area: Rect, wraps: &[bool]) {
+    let x = area.right();
+    let buffer = f.buffer_mut();
+    if x >= buffer.area.right() {
+        return;
+    }
+    for (row, _) in wraps.iter().enumerate().take(area.height as usize).filter(|(_, &wrapped)| wrapped) {
+        buffer[(x, area.y + row as u16)]
+            .set_char(WRAP_MARKER)
+            .set_style(whitespace_marker_style());
+    }
+}
+
+/// 셸이 종료되어 열어 둔 pane의 안내 문구
+pub fn exit_notice(code: Option<i32>) -> String {
+    match code {
+        Some(code) => format!(" 프로세스 종료 (코드 {}) — 아무 키나 누르면 닫기 ", code),
+        None => " 프로세스 종료 — 아무 키나 누르면 닫기 ".to_string(),
+    }
+}
+
+/// 테두리 안쪽 내용 영역(`area`)의 마지막 줄에 종료 안내 표시
+pub fn draw_exit_notice(f: &mut Frame, area: Rect, notice: &str) {
+    if area.height == 0 {
+        return;
+    }
+    let row = Rect { y: area.bottom() - 1, height: 1, ..area };
+    let style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    f.render_widget(ratatui::widgets::Paragraph::new(notice).style(style), row);
+}
+
+/// 화면 위/아래에 더 볼 내용이 있으면 테두리 오른쪽에 ▲/▼를 그리고 그쪽 가장자리 줄을 흐리게 처리
+/// `area`는 테두리를 포함한 터미널 영역
+pub fn draw_scroll_edges(f: &mut Frame, area: Rect, more_above: bool, more_below: bool, style: Style) {
+    if area.width < 4 || area.height < 3 {
+        return;
+    }
+    let buffer = f.buffer_mut();
+    let marker_x = area.right() - 2;
+    let edges = [
+        (more_above, area.y, area.y + 1, MORE_ABOVE_MARKER),
+        (more_below, area.bottom() - 1, area.bottom() - 2, MORE_BELOW_MARKER),
+    ];
+    for (_, border_y, edge_y, marker) in edges.into_iter().filter(|(visible, ..)| *visible) {
+        buffer[(marker_x, border_y)].set_symbol(marker).set_style(style);
+        for x in area.x + 1..area.right() - 1 {
+            buffer[(x, edge_y)].set_style(Style::default().add_modifier(Modifier::DIM));
+        }
+    }
+}
+
+/// 로컬 에코(조합 중인 글자)와 함께 그릴 커서 뒤 내용
+/// 삽입 모드(IRM, `\e[4h`)에서는 프로그램이 글자를 받으면 커서 뒤 내용이 오른쪽으로 밀리므로 밀린 위치에 다시 그리고,
+/// 치환 모드에서는 글자가 덮어쓰므로 빈 문자열. `cursor_col`은 커서의 화면 열, `available`은 커서부터 줄 끝까지의 칸 수
+pub fn local_echo_tail(line: &str, cursor_col: usize, echo: &str, insert_mode: bool, available: usize) -> String {
+    if !insert_mode {
+        return String::new();
+    }
+    let mut remaining = available.saturating_sub(echo.width());
+    let start = columns::column_to_index(line, cursor_col);
+    line.chars()
+        .skip(start)
+        .take_while(|ch| {
+            let width = ch.width().unwrap_or(0);
+            let fits = width <= remaining;
+            remaining = remaining.saturating_sub(width);
+            fits
+        })
+        .collect()
+}
+
+/// 대체 화면 전환 직후 다시 그리기 보류 상태
+/// 전체 화면 앱은 `\e[?1049h` 뒤에 화면 지우기와 내용을 여러 번에 나눠 출력하므로,
+/// 출력이 잠잠해지거나 최대 시간이 지날 때까지 중간 프레임(빈 화면 등)을 그리지 않음
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenSwitchSettle {
+    started: Instant,
+    last_change: Instant,
+    last_version: u64,
+}
+
+impl ScreenSwitchSettle {
+    pub fn new(now: Instant, version: u64) -> Self {
+        Self { started: now, last_change: now, last_version: version }
+    }
+
+    /// 아직 그리기를 미뤄야 하는지 확인 (내용이 바뀌면 대기 시간 연장)
+    pub fn should_wait(&mut self, now: Instant, version: u64) -> bool {
+        if version != self.last_version {
+            self.last_version = version;
+            self.last_change = now;
+        }
+        now.duration_since(self.started) < SCREEN_SWITCH_MAX_DEFER
+            && now.duration_since(self.last_change) < SCREEN_SWITCH_QUIET
+    }
+}
+
+/// 셀의 색상과 속성(굵게, 밑줄 등)을 ratatui 스타일로 변환
+/// 기본 16색은 테마 팔레트로 바꾸고, 기본 전경색은 설정/테마 글자색, 기본 배경색은 지정하지 않아 영역 배경색이 보이게 함
+pub fn cell_style(cell: &RenderableCell, colors: &ColorsConfig) -> Style {
+    let mut style = Style::default().fg(ansi_color(cell.fg, &colors.theme).unwrap_or(colors.foreground()));
+    if let Some(bg) = ansi_color(cell.bg, &colors.theme) {
+        style = style.bg(bg);
+    }
+
+    let modifiers = [
+        (Flags::BOLD, Modifier::BOLD),
+        (Flags::DIM, Modifier::DIM),
+        (Flags::ITALIC, Modifier::ITALIC),
+        (Flags::ALL_UNDERLINES, Modifier::UNDERLINED),
+        (Flags::INVERSE, Modifier::REVERSED),
+        (Flags::HIDDEN, Modifier::HIDDEN),
+        (Flags::STRIKEOUT, Modifier::CROSSED_OUT),
+    ];
+    for (flag, modifier) in modifiers {
+        if cell.flags.intersects(flag) {
+            style = style.add_modifier(modifier);
+        }
+    }
+    // OSC 8 하이퍼링크는 밑줄로 표시
+    if cell.hyperlink.is_some() {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    style
+}
+
+/// 화면 한 줄의 셀을 `get_visible_content` 줄의 글자 순서에 맞춘 스타일 목록으로 변환
+/// (wide char 뒤의 spacer 셀은 문자열에 없으므로 제외)
+pub fn line_styles(cells: &[RenderableCell], colors: &ColorsConfig) -> Vec<Style> {
+    cells.iter()
+        .filter(|cell| !cell.flags.contains(Flags::WIDE_CHAR_SPACER))
+        .map(|cell| cell_style(cell, colors))
+        .collect()
+}
+
+/// 박스 그리기 / 블록 요소 / powerline 기호인지 확인
+/// 이 글자들은 이어진 선과 테두리를 만들기 때문에 항상 한 칸으로 그려야 함
+fn is_box_or_powerline(c: char) -> bool {
+    matches!(c,
+        '\u{2500}'..='\u{259F}'   // 박스 그리기, 블록 요소
+        | '\u{E0A0}'..='\u{E0D7}' // powerline 기호 (확장 포함)
+    )
+}
+
+/// 렌더러가 그리드와 같은 칸 수로 그리도록 글자를 보정
+/// 렌더러(ratatui)의 unicode-width 판정이 alacritty 그리드와 다르면 이후 글자가 밀려 줄 정렬이 깨지므로,
+/// 그리드에서 한 칸인데 두 칸으로 판정되는 글자는 한 칸짜리 대체 문자로 표시
+fn grid_aligned_char(c: char, wide: bool) -> char {
+    if wide || is_box_or_powerline(c) {
+        return c;
+    }
+    match c.width() {
+        Some(2) => '\u{FFFD}',
+        _ => c,
+    }
+}
+
+/// 화면에 그릴 줄 내용 (글자 수는 그대로, `grid_aligned_char`로 보정)
+/// 줄 내용 자체는 복사/검색/내보내기에 쓰이므로 원래 글자를 유지하고, 그릴 때만 보정함
+pub fn grid_aligned_text(line: &str, cells: &[RenderableCell]) -> String {
+    let mut cells = cells.iter().filter(|cell| !cell.flags.contains(Flags::WIDE_CHAR_SPACER));
+    let mut text = String::with_capacity(line.len());
+    for ch in line.chars() {
+        if columns::char_width(ch) == 0 && !text.is_empty() {
+            text.push(ch);
+            continue;
+        }
+        let wide = cells.next().is_some_and(|cell| cell.flags.contains(Flags::WIDE_CHAR));
+        text.push(grid_aligned_char(ch, wide));
+    }
+    text
+}
+
+/// 글자별 스타일로 한 줄 구성 (같은 스타일이 이어지는 글자는 하나의 Span으로 묶음)
+/// 스타일은 셀마다 하나이므로 폭 0 글자(결합 문자)는 앞 글자의 스타일을 따름
+/// 스타일이 모자란 글자는 `fallback` 사용
+pub fn styled_line(line: &str, styles: &[Style], fallback: Style) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut current = String::new();
+    let mut current_style = fallback;
+    let mut cell = 0;
+    for ch in line.chars() {
+        if columns::char_width(ch) == 0 && !current.is_empty() {
+            current.push(ch);
+            continue;
+        }
+        let style = styles.get(cell).copied().unwrap_or(fallback);
+        cell += 1;
+        if style != current_style && !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut current), current_style));
+        }
+        current_style = style;
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, current_style));
+    }
+    Line::from(spans)
+}
+
+/// 화면에 보이는 줄 하나 - 내용, 그릴 글자, 셀 스타일과 그것들로 만든 줄 (선택/검색 등 덧칠 없는 기본 모습)
+#[derive(Debug, Clone, Default)]
+pub struct CachedRow {
+    pub text: String,
+    pub display: String, // `text`를 그리드 칸에 맞춘 글자 (`grid_aligned_text`, 글자 수 같음)
+    pub styles: Vec<Style>,
+    pub line: Line<'static>,
+    pub smart: Vec<SmartMatch>, // 밑줄 표시할 스마트 선택 일치 항목 (`highlight` 규칙만)
+}
+
+/// 보이는 줄 캐시 (damage 기반 부분 렌더링)
+/// alacritty가 바뀌었다고 알린 줄만 그리드에서 다시 읽어 스타일과 Span을 만들고, 나머지 줄은 이전 프레임 것을 씀
+/// 스마트 선택 정규식도 다시 만드는 줄에서만 돌림
+/// 보기 위치가 바뀌거나 `invalidate`하면(색 테마나 스마트 선택 규칙 변경 등) 다음 `refresh`에서 모든 줄을 다시 만듦
+#[derive(Debug, Default)]
+pub struct LineCache {
+    rows: Vec<CachedRow>,
+    view: Option<(usize, usize)>, // 마지막으로 만든 보기 위치 (None이면 전체 다시 만들기)
+}
+
+impl LineCache {
+    /// 다음 `refresh`에서 모든 줄을 다시 만들도록 함
+    pub fn invalidate(&mut self) {
+        self.view = None;
+    }
+
+    /// 바뀐 줄만 다시 만들고, 다시 만든 줄 수를 반환
+    pub fn refresh(&mut self, terminal: &Terminal, colors: &ColorsConfig, rules: &[SmartRule]) -> usize {
+        let damaged = terminal.take_damaged_rows(self.view);
+        self.view = Some(damaged.view);
+        self.rows.resize_with(damaged.screen_lines, CachedRow::default);
+
+        let fallback = Style::default().fg(colors.foreground());
+        let rebuilt = damaged.rows.len();
+        for (row, text, cells) in damaged.rows {
+            let styles = line_styles(&cells, colors);
+            let display = grid_aligned_text(&text, &cells);
+            let line = styled_line(&display, &styles, fallback);
+            let smart = smart::matches(rules, &text).into_iter().filter(|found| rules[found.rule].highlight).collect();
+            self.rows[row] = CachedRow { text, display, styles, line, smart };
+        }
+        rebuilt
+    }
+
+    /// 보이는 줄 (위에서부터)
+    pub fn rows(&self) -> &[CachedRow] {
+        &self.rows
+    }
+}
+
+/// 캐시한 줄을 글자를 복사하지 않고 빌려 쓰는 줄로 (Span 목록만 새로 만듦)
+pub fn borrowed_line<'a>(line: &'a Line<'_>) -> Line<'a> {
+    Line {
+        spans: line.spans.iter().map(|span| Span::styled(span.content.as_ref(), span.style)).collect(),
+        style: line.style,
+        alignment: line.alignment,
+    }
+}
+
+/// alacritty 색상을 ratatui 색상으로 변환 (기본 전경/배경색이면 None)
+/// 기본 16색은 테마 팔레트, 256색 팔레트의 나머지(16~255)는 같은 번호의 색, RGB 색은 그대로 24비트 색
+pub fn ansi_color(color: AnsiColor, theme: &Theme) -> Option<Color> {
+    match color {
+        AnsiColor::Named(named) => named_index(named).map(|index| theme.palette[index as usize]),
+        AnsiColor::Indexed(index) if index < 16 => Some(theme.palette[index as usize]),
+        AnsiColor::Indexed(index) => Some(Color::Indexed(index)),
+        AnsiColor::Spec(rgb) => Some(Color::Rgb(rgb.r, rgb.g, rgb.b)),
+    }
+}
+
+/// 이름 있는 색상의 기본 16색 번호 (흐린 색은 기본 색으로, 기본 전경/배경/커서 색은 None)
+pub fn named_index(named: NamedColor) -> Option<u8> {
+    Some(match named {
+        NamedColor::Black | NamedColor::DimBlack => 0,
+        NamedColor::Red | NamedColor::DimRed => 1,
+        NamedColor::Green | NamedColor::DimGreen => 2,
+        NamedColor::Yellow | NamedColor::DimYellow => 3,
+        NamedColor::Blue | NamedColor::DimBlue => 4,
+        NamedColor::Magenta | NamedColor::DimMagenta => 5,
+        NamedColor::Cyan | NamedColor::DimCyan => 6,
+        NamedColor::White | NamedColor::DimWhite => 7,
+        NamedColor::BrightBlack => 8,
+        NamedColor::BrightRed => 9,
+        NamedColor::BrightGreen => 10,
+        NamedColor::BrightYellow => 11,
+        NamedColor::BrightBlue => 12,
+        NamedColor::BrightMagenta => 13,
+        NamedColor::BrightCyan => 14,
+        NamedColor::BrightWhite => 15,
+        NamedColor::Foreground | NamedColor::BrightForeground | NamedColor::DimForeground
+        | NamedColor::Background | NamedColor::Cursor => return None,
+    })
+}
+
+/// 창 안의 영역 구성
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenLayout {
+    pub banner: Rect,
+    pub info_panel: Rect,
+    pub tab_bar: Rect,
+    pub main: Rect, // pane들이 들어가는 영역
+    pub status_bar: Rect, // 하단 상태 표시줄 (끄면 높이 0)
+}
+
+/// 창 영역을 배너, 정보 패널, 탭 바, 메인 영역, 상태 표시줄로 분할
+/// 최대 열 수를 설정했으면 남는 너비는 여백 (테두리 2칸 + 스크롤바 1칸 제외)
+pub fn screen_layout(area: Rect, banner_height: u16, status_bar: bool, width: &WidthConfig) -> ScreenLayout {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(banner_height), // 배너 (설정하지 않으면 0줄)
+            Constraint::Length(1),             // 정보 패널 1줄
+            Constraint::Length(1),             // 탭 바 1줄
+            Constraint::Min(10),               // 터미널 영역
+            Constraint::Length(u16::from(status_bar)), // 상태 표시줄 1줄 (끄면 0줄)
+        ])
+        .split(area);
+    ScreenLayout {
+        banner: chunks[0],
+        info_panel: chunks[1],
+        tab_bar: chunks[2],
+        main: width.cap(chunks[3], 3),
+        status_bar: chunks[4],
+    }
+}
+
+/// 포커스 pane 영역을 터미널 영역과 스크롤바 1칸으로 분할
+pub fn split_scrollbar(area: Rect) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(10), Constraint::Length(1)])
+        .split(area);
+    (chunks[0], chunks[1])
+}
+
+/// 높이 `height`칸 스크롤바의 썸(thumb) 시작 칸과 크기 (스크롤할 내용이 없으면 None)
+/// 썸 크기는 화면에 보이는 비율, 위치는 화면 첫 줄이 스크롤 범위에서 차지하는 비율
+pub fn scrollbar_thumb(state: &ScrollState, height: usize) -> Option<(usize, usize)> {
+    if state.max_scroll() == 0 || height == 0 {
+        return None;
+    }
+    let thumb_size = ((state.viewport as f32 / state.total as f32) * height as f32)
+        .clamp(1.0, height as f32) as usize;
+    let scroll_ratio = state.first_visible_row() as f32 / state.max_scroll() as f32;
+    Some(((scroll_ratio * (height - thumb_size) as f32) as usize, thumb_size))
+}
+
+/// 썸 맨 위를 `thumb_top` 칸으로 옮겼을 때 화면 첫 줄이 될 전체 버퍼 행 (스크롤바 클릭/드래그용)
+/// 맨 아래 빈 여백까지는 내려가지 않음
+pub fn scrollbar_row(state: &ScrollState, height: usize, thumb_top: i32) -> usize {
+    let Some((_, thumb_size)) = scrollbar_thumb(state, height) else {
+        return 0;
+    };
+    let track = height - thumb_size;
+    if track == 0 {
+        return 0;
+    }
+    let ratio = thumb_top.clamp(0, track as i32) as f32 / track as f32;
+    ((ratio * state.max_scroll() as f32).round() as usize).min(state.max_offset())
+}
+
+/// pane 영역에 들어가는 터미널 크기 (테두리 제외, `scrollbar`이면 스크롤바 칸도 제외)
+pub fn pane_terminal_bounds(area: Rect, scrollbar: bool) -> TerminalBounds {
+    let area = if scrollbar { split_scrollbar(area).0 } else { area };
+    TerminalBounds {
+        num_lines: area.height.saturating_sub(2) as usize,
+        num_cols: area.width.saturating_sub(2) as usize,
+    }
+}
+
+/// 탭 바 한 줄 (`번호: 이름` 목록, 활성 탭은 반전 표시)
+pub fn tab_bar_line(titles: &[String], active: usize) -> Line<'static> {
+    let spans = titles.iter().enumerate().map(|(idx, title)| {
+        let style = if idx == active {
+            Style::default().fg(Color::Black).bg(Color::White).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray).bg(Color::DarkGray)
+        };
+        Span::styled(format!(" {}: {} ", idx + 1, title), style)
+    });
+    Line::from(spans.collect::<Vec<_>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use ratatui::{backend::TestBackend, text::Line, widgets::Paragraph, Terminal};
+
+    #[test]
+    fn test_background_fills_empty_rows_and_columns() {
+        let background = Color::Rgb(0x28, 0x2c, 0x34);
+        let mut terminal = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        terminal.draw(|f| {
+            let area = f.area();
+            fill_background(f, area, background);
+            f.render_widget(Paragraph::new(vec![Line::from("ab"), Line::from("c")]), area);
+        }).unwrap();
+
+        // 내용이 있는 칸, 짧은 줄 오른쪽 칸, 내용 아래 빈 줄 모두 설정한 배경색
+        let buffer = terminal.backend().buffer();
+        for (x, y) in [(0, 0), (9, 0), (5, 1), (0, 3), (9, 3)] {
+            assert_eq!(buffer[(x, y)].bg, background, "({}, {})", x, y);
+        }
+        assert_eq!(buffer[(0, 0)].symbol(), "a");
+    }
+
+    #[test]
+    fn test_reloaded_config_updates_background() {
+        fn draw(terminal: &mut Terminal<TestBackend>, config: &Config) -> Color {
+            terminal.draw(|f| {
+                let area = f.area();
+                fill_background(f, area, config.colors.background());
+            }).unwrap();
+            terminal.backend().buffer()[(3, 1)].bg
+        }
+
+        let mut config = Config::default();
+        let mut terminal = Terminal::new(TestBackend::new(4, 2)).unwrap();
+        assert_eq!(draw(&mut terminal, &config), Color::Black);
+
+        // 다시 읽은 설정으로 교체하면 다음 프레임부터 새 배경색
+        config = Config::from_sources(Some("RUST_TERMINAL_BACKGROUND=#1e1e2e"), |_| None).unwrap();
+        assert_eq!(draw(&mut terminal, &config), Color::Rgb(0x1e, 0x1e, 0x2e));
+    }
+
+    #[test]
+    fn test_whitespace_markers_only_in_rendered_text() {
+        let mut terminal = crate::terminal::Terminal::for_test(3, 12);
+        terminal.feed(b"a b\tc d");
+        let lines = terminal.get_renderable_content().unwrap();
+
+        // 화면에는 공백/탭 마커, 줄 끝 빈 칸은 그대로
+        let rendered: String = whitespace_markers(&lines[0]).iter().map(|(ch, _)| ch).collect();
+        assert_eq!(rendered.trim_end(), "a·b→    c·d");
+        assert_eq!(rendered.chars().count(), lines[0].chars().count());
+
+        // 복사한 텍스트에는 마커 없음
+        let copied = crate::selection::selected_text(&lines, (0, 0, 0, 11), crate::config::LineEnding::Lf);
+        assert!(!copied.contains([SPACE_MARKER, TAB_MARKER, WRAP_MARKER]));
+        assert!(copied.starts_with("a b\t"));
+    }
+
+    #[test]
+    fn test_wrap_marker_drawn_on_border() {
+        let mut terminal = Terminal::new(TestBackend::new(6, 3)).unwrap();
+        terminal.draw(|f| {
+            let inner = Rect::new(1, 0, 4, 3);
+            mark_wrapped_rows(f, inner, &[true, false, true]);
+        }).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(5, 0)].symbol(), "↵");
+        assert_eq!(buffer[(5, 1)].symbol(), " ");
+        assert_eq!(buffer[(5, 2)].symbol(), "↵");
+    }
+
+    #[test]
+    fn test_screen_switch_defers_until_output_settles() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut settle = ScreenSwitchSettle::new(start, 1);
+
+        // 출력이 이어지는 동안은 기다리고, 잠잠해지면 그림
+        assert!(settle.should_wait(ms(5), 2));
+        assert!(settle.should_wait(ms(10), 3));
+        assert!(!settle.should_wait(ms(18), 3));
+
+        // 출력이 계속되어도 최대 시간이 지나면 그림
+        let mut settle = ScreenSwitchSettle::new(start, 1);
+        for n in 1..50 {
+            assert!(settle.should_wait(ms(n), n));
+        }
+        assert!(!settle.should_wait(ms(50), 50));
+    }
+
+    #[test]
+    fn test_local_echo_respects_insert_mode() {
+        let mut terminal = crate::terminal::Terminal::for_test(3, 10);
+        terminal.feed(b"abcdef\x1b[1;3H");
+
+        // 삽입 모드: 에코 뒤로 커서 뒤 내용이 밀려 그려지고, 실제 출력 결과와 같은 위치
+        terminal.feed(b"\x1b[4h");
+        assert!(terminal.is_insert_mode());
+        let line = terminal.get_visible_content()[0].clone();
+        let tail = local_echo_tail(&line, 2, "한", true, 8);
+        assert_eq!(tail.trim_end(), "cdef");
+        assert_eq!(tail.chars().count(), 6); // 2칸 글자 뒤로 남은 6칸만
+        terminal.feed("한".as_bytes());
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), format!("ab한{}", tail.trim_end()));
+
+        // 치환 모드: 에코가 덮어쓰므로 커서 뒤 내용을 다시 그리지 않음
+        terminal.feed(b"\x1b[4l\x1b[2;1Habcdef\x1b[2;3H");
+        assert!(!terminal.is_insert_mode());
+        assert_eq!(local_echo_tail(&terminal.get_visible_content()[1], 2, "x", false, 8), "");
+        terminal.feed(b"x");
+        assert_eq!(terminal.get_visible_content()[1].trim_end(), "abxdef");
+
+        // 커서 열은 화면 열이므로 앞의 넓은 글자는 두 칸으로 셈
+        assert_eq!(local_echo_tail("한글xy", 4, "a", true, 8), "xy");
+    }
+
+    #[test]
+    fn test_scroll_edges_only_where_content_exists() {
+        let mut terminal = crate::terminal::Terminal::for_test(5, 20);
+        for i in 0..20 {
+            terminal.feed(format!("line {}\r\n", i).as_bytes());
+        }
+
+        let mut screen = Terminal::new(TestBackend::new(10, 7)).unwrap();
+        let mut edges = |terminal: &crate::terminal::Terminal| {
+            let state = terminal.scroll_state();
+            screen.draw(|f| {
+                let area = f.area();
+                draw_scroll_edges(f, area, state.has_content_above(), state.has_content_below(), Style::default());
+            }).unwrap();
+            let buffer = screen.backend().buffer();
+            (buffer[(8, 0)].symbol() == MORE_ABOVE_MARKER, buffer[(8, 6)].symbol() == MORE_BELOW_MARKER)
+        };
+
+        // 맨 아래: 위쪽에만 내용
+        assert_eq!(edges(&terminal), (true, false));
+        // 중간: 양쪽
+        terminal.scroll_by(5);
+        assert_eq!(edges(&terminal), (true, true));
+        // 맨 위: 아래쪽에만
+        terminal.scroll_to(crate::terminal::ScrollPosition::Top);
+        assert_eq!(edges(&terminal), (false, true));
+
+        // 히스토리가 없으면 표시 없음
+        assert_eq!(edges(&crate::terminal::Terminal::for_test(5, 20)), (false, false));
+    }
+
+    #[test]
+    fn test_tab_bar_highlights_active_tab() {
+        let titles = vec!["src".to_string(), "홈".to_string()];
+        let line = tab_bar_line(&titles, 1);
+        let texts: Vec<&str> = line.spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(texts, vec![" 1: src ", " 2: 홈 "]);
+        assert!(line.spans[1].style.add_modifier.contains(Modifier::BOLD));
+        assert!(!line.spans[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_terminal_bounds_follow_window_size() {
+        let width = WidthConfig::default();
+        let layout = screen_layout(Rect::new(0, 0, 120, 40), 0, false, &width);
+        // 여백 1칸, 정보 패널과 탭 바 2줄 제외
+        assert_eq!(layout.main, Rect::new(1, 3, 118, 36));
+        assert_eq!(pane_terminal_bounds(layout.main, true), TerminalBounds { num_lines: 34, num_cols: 115 });
+        assert_eq!(pane_terminal_bounds(layout.main, false), TerminalBounds { num_lines: 34, num_cols: 116 });
+
+        // 창이 커지면 터미널 크기도 커짐
+        let layout = screen_layout(Rect::new(0, 0, 200, 60), 0, false, &width);
+        assert_eq!(pane_terminal_bounds(layout.main, true), TerminalBounds { num_lines: 54, num_cols: 195 });
+
+        // 상태 표시줄은 메인 영역 아래 한 줄
+        let layout = screen_layout(Rect::new(0, 0, 120, 40), 0, true, &width);
+        assert_eq!(layout.main, Rect::new(1, 3, 118, 35));
+        assert_eq!(layout.status_bar, Rect::new(1, 38, 118, 1));
+    }
+
+    #[test]
+    fn test_scrollbar_thumb_and_row() {
+        // 100줄 중 10줄이 보이고 맨 아래를 보는 중
+        let state = ScrollState { offset: 0, past_end: 0, past_end_margin: 0, total: 100, viewport: 10 };
+        assert_eq!(scrollbar_thumb(&state, 20), Some((18, 2)));
+        assert_eq!(scrollbar_thumb(&ScrollState { total: 10, ..state }, 20), None);
+
+        // 썸을 맨 위/가운데/트랙 밖으로
+        assert_eq!(scrollbar_row(&state, 20, 0), 0);
+        assert_eq!(scrollbar_row(&state, 20, 9), 45);
+        assert_eq!(scrollbar_row(&state, 20, 30), 90);
+        assert_eq!(scrollbar_row(&state, 20, -5), 0);
+    }
+
+    #[test]
+    fn test_exit_notice() {
+        assert_eq!(exit_notice(Some(127)), " 프로세스 종료 (코드 127) — 아무 키나 누르면 닫기 ");
+        assert!(!exit_notice(None).contains("코드"));
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 5)).unwrap();
+        terminal.draw(|f| draw_exit_notice(f, Rect::new(1, 1, 38, 3), &exit_notice(Some(1)))).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(2, 3)].symbol(), "프");
+        assert_eq!(buffer[(2, 3)].bg, Color::Yellow);
+        assert_eq!(buffer[(2, 2)].symbol(), " ");
+    }
+
+    #[test]
+    fn test_grid_aligned_char() {
+        // 박스 그리기/powerline 기호와 그리드에서 두 칸인 글자는 그대로
+        assert_eq!(grid_aligned_char('─', false), '─');
+        assert_eq!(grid_aligned_char('\u{e0b2}', false), '\u{e0b2}');
+        assert_eq!(grid_aligned_char('가', true), '가');
+        // 그리드는 한 칸인데 렌더러가 두 칸으로 보는 글자는 대체 문자로
+        assert_eq!(grid_aligned_char('가', false), '\u{FFFD}');
+
+        // 줄 단위 보정은 그릴 글자만 바꾸고 글자 수는 그대로 (spacer는 건너뜀)
+        let cell = |c: char, flags: Flags| RenderableCell { c, fg: AnsiColor::Named(NamedColor::Foreground), bg: AnsiColor::Named(NamedColor::Background), flags, hyperlink: None };
+        let cells = [cell('가', Flags::WIDE_CHAR), cell(' ', Flags::WIDE_CHAR_SPACER), cell('나', Flags::empty()), cell('e', Flags::empty())];
+        assert_eq!(grid_aligned_text("가나e\u{301}", &cells), "가\u{FFFD}e\u{301}");
+    }
+
+    #[test]
+    fn test_256_and_true_colors() {
+        let mut terminal = crate::terminal::Terminal::for_test(3, 20);
+        terminal.feed(b"\x1b[38;5;9ma\x1b[38;5;208;48;5;236mb\x1b[38;2;255;128;0;48;2;1;2;3mc\x1b[0m");
+
+        let cells = terminal.get_renderable_cells();
+        let styles = line_styles(&cells[0], &ColorsConfig::default());
+        // 256색의 0~15는 테마의 기본 16색, 나머지는 번호 그대로, RGB는 24비트 색
+        assert_eq!(styles[0].fg, Some(Color::LightRed));
+        assert_eq!((styles[1].fg, styles[1].bg), (Some(Color::Indexed(208)), Some(Color::Indexed(236))));
+        assert_eq!((styles[2].fg, styles[2].bg), (Some(Color::Rgb(255, 128, 0)), Some(Color::Rgb(1, 2, 3))));
+    }
+
+    #[test]
+    fn test_cell_styles_keep_colors_and_attributes() {
+        let mut terminal = crate::terminal::Terminal::for_test(3, 20);
+        terminal.feed("\x1b[1;31mERR\x1b[0m ok \x1b[4;44m한\x1b[0m!".as_bytes());
+
+        let cells = terminal.get_renderable_cells();
+        let styles = line_styles(&cells[0], &ColorsConfig::default());
+        let line = &terminal.get_visible_content()[0];
+        assert_eq!(line.chars().count(), styles.len());
+
+        // 굵은 빨간색, 기본 색, 밑줄 + 파란 배경 (wide char spacer 제외로 '!'와 위치가 맞음)
+        assert_eq!(styles[0], Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        assert_eq!(styles[4], Style::default().fg(Color::White));
+        assert_eq!(styles[7], Style::default().fg(Color::White).bg(Color::Blue).add_modifier(Modifier::UNDERLINED));
+        assert_eq!(line.chars().nth(8), Some('!'));
+        assert_eq!(styles[8], Style::default().fg(Color::White));
+
+        // 같은 스타일이 이어지는 글자는 하나로 묶음
+        let rendered = styled_line(line.trim_end(), &styles, Style::default());
+        let texts: Vec<&str> = rendered.spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(texts, vec!["ERR", " ok ", "한", "!"]);
+
+        // 결합 문자는 앞 글자와 같은 셀이므로 뒤 글자의 스타일 위치를 밀지 않음
+        let rendered = styled_line("e\u{301}!", &[styles[0], styles[4]], Style::default());
+        let texts: Vec<&str> = rendered.spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(texts, vec!["e\u{301}", "!"]);
+
+        // 색은 테마 팔레트를 거쳐 변환, 기본 글자색은 테마 글자색
+        let colors = ColorsConfig { theme: Theme::builtin("dracula").unwrap(), ..ColorsConfig::default() };
+        let styles = line_styles(&cells[0], &colors);
+        assert_eq!(styles[0].fg, Some(Color::Rgb(0xff, 0x55, 0x55)));
+        assert_eq!(styles[4].fg, Some(Color::Rgb(0xf8, 0xf8, 0xf2)));
+        assert_eq!(styles[7].bg, Some(Color::Rgb(0xbd, 0x93, 0xf9)));
+    }
+
+    #[test]
+    fn test_line_cache_rebuilds_only_damaged_rows() {
+        let mut terminal = crate::terminal::Terminal::for_test(3, 10);
+        let mut cache = LineCache::default();
+        terminal.feed(b"\x1b[31mred\x1b[0m\r\nplain");
+        assert_eq!(cache.refresh(&terminal, &ColorsConfig::default(), &[]), 3);
+        assert_eq!(cache.rows()[0].line.spans[0].content, "red");
+        assert_eq!(cache.rows()[0].styles[0].fg, Some(Color::Red));
+
+        // 커서 줄만 다시 만들고 나머지는 그대로
+        terminal.feed(b"!");
+        assert_eq!(cache.refresh(&terminal, &ColorsConfig::default(), &[]), 1);
+        assert_eq!(cache.rows()[1].text.trim_end(), "plain!");
+        assert_eq!(cache.rows().len(), 3);
+
+        // 테마를 바꾸면 전체
+        cache.invalidate();
+        assert_eq!(cache.refresh(&terminal, &ColorsConfig::default(), &[]), 3);
+        let borrowed = borrowed_line(&cache.rows()[0].line);
+        assert_eq!(borrowed, cache.rows()[0].line);
+    }
+
+    #[test]
+    fn test_line_cache_keeps_smart_matches() {
+        let mut rule = SmartRule::named("jira");
+        rule.set("pattern", &crate::toml::Value::String(r"\bPROJ-[0-9]+\b".into())).unwrap();
+        let rules = vec![rule];
+        let mut terminal = crate::terminal::Terminal::for_test(3, 20);
+        let mut cache = LineCache::default();
+        terminal.feed(b"see PROJ-42\r\nplain");
+        assert_eq!(cache.refresh(&terminal, &ColorsConfig::default(), &rules), 3);
+        assert_eq!((cache.rows()[0].smart[0].start, cache.rows()[0].smart[0].end), (4, 11));
+        assert!(cache.rows()[1].smart.is_empty());
+
+        // 바뀐 줄만 다시 찾고, 나머지 줄의 일치 항목은 그대로
+        terminal.feed(b" PROJ-7");
+        assert_eq!(cache.refresh(&terminal, &ColorsConfig::default(), &rules), 1);
+        assert_eq!(cache.rows()[0].smart.len(), 1);
+        assert_eq!(cache.rows()[1].smart[0].captures[0], "PROJ-7");
+
+        // 밑줄을 끈 규칙은 캐시하지 않음
+        let mut rules = rules;
+        rules[0].highlight = false;
+        cache.invalidate();
+        cache.refresh(&terminal, &ColorsConfig::default(), &rules);
+        assert!(cache.rows().iter().all(|row| row.smart.is_empty()));
+    }
+}
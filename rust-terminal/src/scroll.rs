@@ -0,0 +1,117 @@
+//! 부드러운 스크롤 모듈
+//! 휠 입력을 바로 적용하지 않고 남은 이동량으로 쌓아 두었다가 프레임마다 일부씩 적용
+//! 휠을 연달아 굴리면 남은 이동량이 커져 빨라지고(관성), 지수 감속으로 멈춤
+//!
+//! 화면은 줄 단위로만 움직일 수 있으므로 소수 이동량은 다음 프레임으로 넘김
+
+use std::time::{Duration, Instant};
+
+/// 남은 이동량이 줄어드는 시간 상수 (이 시간 동안 약 63%를 이동)
+const TIME_CONSTANT: Duration = Duration::from_millis(60);
+
+/// 남은 이동량이 이보다 작으면 나머지를 한 번에 적용하고 멈춤
+const STOP_THRESHOLD: f32 = 0.5;
+
+/// 애니메이션 중 다시 그리는 간격
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// 부드러운 스크롤 상태 (양수: 위로, `Terminal::scroll_by`와 같은 방향)
+#[derive(Debug, Default, Clone)]
+pub struct SmoothScroll {
+    /// 아직 적용하지 않은 이동량 (줄)
+    remaining: f32,
+    /// 이동했지만 한 줄이 되지 않아 화면에 반영하지 못한 양
+    fraction: f32,
+    /// 마지막으로 진행한 시각 (멈춰 있으면 None)
+    last_step: Option<Instant>,
+}
+
+impl SmoothScroll {
+    /// 휠 한 번의 이동량 추가 (반대 방향이면 남은 이동을 버리고 새로 시작)
+    pub fn impulse(&mut self, lines: i32, now: Instant) {
+        let lines = lines as f32;
+        if self.remaining * lines < 0.0 {
+            self.stop();
+        }
+        self.remaining += lines;
+        self.last_step.get_or_insert(now);
+    }
+
+    /// 애니메이션 진행 중인지 (다음 프레임을 곧 그려야 하는지)
+    pub fn is_active(&self) -> bool {
+        self.last_step.is_some()
+    }
+
+    /// 남은 이동 취소 (다른 방법으로 스크롤했을 때)
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+
+    /// `now`까지 진행하고 이번에 스크롤할 줄 수 반환
+    pub fn step(&mut self, now: Instant) -> i32 {
+        let Some(last) = self.last_step else {
+            return 0;
+        };
+        let elapsed = now.saturating_duration_since(last).as_secs_f32();
+        let portion = self.remaining * (1.0 - (-elapsed / TIME_CONSTANT.as_secs_f32()).exp());
+        self.remaining -= portion;
+        self.fraction += portion;
+        self.last_step = Some(now);
+
+        if self.remaining.abs() < STOP_THRESHOLD {
+            // 입력은 정수 줄이므로 남은 양을 합치면 정수 (오차만 반올림)
+            let lines = (self.fraction + self.remaining).round() as i32;
+            self.stop();
+            return lines;
+        }
+        let lines = self.fraction.trunc();
+        self.fraction -= lines;
+        lines as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 멈출 때까지 프레임 간격으로 진행한 줄 수 목록
+    fn run(scroll: &mut SmoothScroll, start: Instant) -> Vec<i32> {
+        let mut steps = Vec::new();
+        let mut now = start;
+        while scroll.is_active() {
+            now += FRAME_INTERVAL;
+            steps.push(scroll.step(now));
+            assert!(steps.len() < 1000, "애니메이션이 끝나지 않음");
+        }
+        steps
+    }
+
+    #[test]
+    fn test_smooth_scroll_reaches_target() {
+        let start = Instant::now();
+        let mut scroll = SmoothScroll::default();
+        assert_eq!(scroll.step(start), 0);
+
+        scroll.impulse(3, start);
+        scroll.impulse(3, start);
+        let steps = run(&mut scroll, start);
+        assert_eq!(steps.iter().sum::<i32>(), 6);
+        assert!(steps.len() > 1, "한 프레임에 전부 이동하면 부드럽지 않음");
+        // 감속: 처음 이동량이 가장 큼
+        assert!(steps[0] >= *steps.last().unwrap());
+
+        scroll.impulse(-5, start);
+        assert_eq!(run(&mut scroll, start).iter().sum::<i32>(), -5);
+    }
+
+    #[test]
+    fn test_smooth_scroll_reverse_cancels() {
+        let start = Instant::now();
+        let mut scroll = SmoothScroll::default();
+        scroll.impulse(10, start);
+        scroll.step(start + FRAME_INTERVAL);
+        // 반대 방향 휠은 남은 위쪽 이동을 버림
+        scroll.impulse(-3, start + FRAME_INTERVAL);
+        assert_eq!(run(&mut scroll, start + FRAME_INTERVAL).iter().sum::<i32>(), -3);
+    }
+}
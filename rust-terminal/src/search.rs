@@ -0,0 +1,162 @@
+//! 스크롤백 검색 모듈
+//! 검색어와 일치하는 위치를 찾고, 현재 초점이 맞춰진 일치 항목을
+//! 다른 일치 항목과 구분되는 스타일로 표시
+
+use crate::config::SearchConfig;
+use ratatui::style::Style;
+
+/// 검색 일치 위치 (전체 버퍼 기준 행, 문자 단위 열 범위 [start_col, end_col))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub row: usize,
+    pub start_col: usize,
+    pub end_col: usize,
+}
+
+impl SearchMatch {
+    fn contains(&self, row: usize, col: usize) -> bool {
+        self.row == row && col >= self.start_col && col < self.end_col
+    }
+}
+
+/// 검색 상태
+#[derive(Debug, Clone, Default)]
+pub struct SearchState {
+    query: String,
+    matches: Vec<SearchMatch>,
+    current: Option<usize>, // 현재 초점이 맞춰진 일치 항목 인덱스
+}
+
+impl SearchState {
+    /// 검색어로 전체 줄을 검색 - 가장 최근(맨 아래) 일치 항목에 초점
+    pub fn search(&mut self, query: &str, lines: &[String]) {
+        self.query = query.to_string();
+        self.matches = find_matches(query, lines);
+        self.current = self.matches.len().checked_sub(1);
+    }
+
+    /// 검색 해제
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 현재 검색어
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// 일치 항목 수
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// 현재 초점 인덱스 (0부터)
+    pub fn current_index(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// 현재 초점이 맞춰진 일치 항목
+    pub fn current_match(&self) -> Option<&SearchMatch> {
+        self.current.map(|idx| &self.matches[idx])
+    }
+
+    /// 이전 출력 방향(위쪽)의 일치 항목으로 초점 이동 (처음에서 끝으로 순환)
+    pub fn focus_older(&mut self) -> Option<&SearchMatch> {
+        let len = self.matches.len();
+        self.current = self.current.map(|idx| (idx + len - 1) % len);
+        self.current_match()
+    }
+
+    /// 최신 출력 방향(아래쪽)의 일치 항목으로 초점 이동 (끝에서 처음으로 순환)
+    pub fn focus_newer(&mut self) -> Option<&SearchMatch> {
+        let len = self.matches.len();
+        self.current = self.current.map(|idx| (idx + 1) % len);
+        self.current_match()
+    }
+
+    /// 셀에 적용할 검색 하이라이트 스타일 (일치 항목이 아니면 None)
+    pub fn style_at(&self, row: usize, col: usize, config: &SearchConfig) -> Option<Style> {
+        if self.current_match().is_some_and(|m| m.contains(row, col)) {
+            return Some(config.current_match_style);
+        }
+        self.matches.iter()
+            .any(|m| m.contains(row, col))
+            .then_some(config.match_style)
+    }
+}
+
+/// 각 줄에서 검색어와 일치하는 위치를 찾음 (겹치지 않게, 대소문자 구분)
+fn find_matches(query: &str, lines: &[String]) -> Vec<SearchMatch> {
+    let needle: Vec<char> = query.chars().collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut col = 0;
+        while col + needle.len() <= chars.len() {
+            if chars[col..col + needle.len()] == needle[..] {
+                matches.push(SearchMatch { row, start_col: col, end_col: col + needle.len() });
+                col += needle.len();
+            } else {
+                col += 1;
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_only_current_match_has_current_style() {
+        let config = SearchConfig::default();
+        let content = lines(&["foo bar foo", "bar", "foo"]);
+        let mut search = SearchState::default();
+        search.search("foo", &content);
+        assert_eq!(search.match_count(), 3);
+
+        let styled: Vec<(usize, usize, Style)> = content.iter().enumerate()
+            .flat_map(|(row, line)| (0..line.chars().count()).map(move |col| (row, col)))
+            .filter_map(|(row, col)| search.style_at(row, col, &config).map(|style| (row, col, style)))
+            .collect();
+
+        // 가장 최근 일치 항목(마지막 줄)만 current 스타일
+        let current: Vec<_> = styled.iter().filter(|(_, _, s)| *s == config.current_match_style).collect();
+        assert_eq!(current.len(), 3);
+        assert!(current.iter().all(|(row, _, _)| *row == 2));
+        let others = styled.iter().filter(|(_, _, s)| *s == config.match_style).count();
+        assert_eq!(others, 6);
+
+        // 초점을 옮기면 current 스타일도 이동
+        search.focus_older();
+        assert_eq!(search.style_at(0, 8, &config), Some(config.current_match_style));
+        assert_eq!(search.style_at(2, 0, &config), Some(config.match_style));
+        assert_eq!(search.style_at(1, 0, &config), None);
+    }
+
+    #[test]
+    fn test_focus_wraps_around() {
+        let mut search = SearchState::default();
+        search.search("a", &lines(&["a a", "a"]));
+        assert_eq!(search.current_index(), Some(2));
+        assert_eq!(search.focus_newer().map(|m| m.row), Some(0));
+        assert_eq!(search.focus_older().map(|m| (m.row, m.start_col)), Some((1, 0)));
+    }
+
+    #[test]
+    fn test_empty_query_has_no_matches() {
+        let mut search = SearchState::default();
+        search.search("", &lines(&["abc"]));
+        assert_eq!(search.match_count(), 0);
+        assert_eq!(search.focus_older(), None);
+    }
+}
@@ -0,0 +1,419 @@
+//! 텍스트 선택 보조 모듈
+//! 선택 영역 상태(글자/단어/줄 단위, 일반/사각형 선택)와 선택 범위에 해당하는 텍스트 추출
+
+use crate::columns;
+use crate::config::{ClearSelectionOnOutput, LineEnding, WheelScrollSelection};
+
+/// 선택 범위 (정규화된 시작/끝, 전체 버퍼 기준 행과 화면 열, 끝 열 포함)
+pub type SelectionRange = (u16, u16, u16, u16);
+
+/// 선택 단위 (클릭 횟수로 결정: 한 번 = 글자, 두 번 = 단어, 세 번 = 줄)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionUnit {
+    #[default]
+    Char,
+    Word,
+    Line,
+}
+
+impl SelectionUnit {
+    /// 연속 클릭 횟수(1~3)에 해당하는 선택 단위
+    pub fn from_click_count(count: u8) -> Self {
+        match count {
+            2 => SelectionUnit::Word,
+            3 => SelectionUnit::Line,
+            _ => SelectionUnit::Char,
+        }
+    }
+}
+
+/// 선택 모양
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// 시작점부터 끝점까지 줄을 따라 이어지는 선택
+    #[default]
+    Linear,
+    /// 시작점과 끝점을 꼭짓점으로 하는 사각형 (`Alt`+드래그)
+    Block,
+}
+
+/// 텍스트 선택 영역
+/// 좌표는 화면이 아닌 전체 버퍼(히스토리 맨 위 = 0행) 기준이므로 스크롤해도 같은 텍스트를 가리킴
+#[derive(Debug, Clone, Default)]
+pub struct TextSelection {
+    pub start_row: u16,
+    pub start_col: u16,
+    pub end_row: u16,
+    pub end_col: u16,
+    pub is_active: bool,
+    pub mode: SelectionMode,
+    pub unit: SelectionUnit,
+    /// 처음 클릭한 단어/줄 범위 (드래그하면 이 범위를 유지한 채 단위로 확장)
+    pub anchor: SelectionRange,
+}
+
+impl TextSelection {
+    /// `row`행 `col`열을 클릭하여 `unit` 단위로 선택 시작
+    /// 단어/줄 단위면 클릭한 단어/줄 전체가 선택됨
+    pub fn start(lines: &[String], row: u16, col: u16, unit: SelectionUnit) -> Self {
+        let anchor = unit_range(lines, row, col, unit);
+        let (start_row, start_col, end_row, end_col) = anchor;
+        Self { start_row, start_col, end_row, end_col, is_active: true, unit, anchor, ..Self::default() }
+    }
+
+    /// `row`행 `col`열에서 사각형 선택 시작
+    pub fn start_block(row: u16, col: u16) -> Self {
+        Self { mode: SelectionMode::Block, ..Self::start(&[], row, col, SelectionUnit::Char) }
+    }
+
+    /// 정규화한 범위 안에 `row`행 `col`열이 포함되는지 확인
+    pub fn contains(&self, row: u16, col: u16) -> bool {
+        if !self.is_active {
+            return false;
+        }
+        let (start_row, start_col, end_row, end_col) = self.normalized();
+        if row < start_row || row > end_row {
+            return false;
+        }
+        match self.mode {
+            SelectionMode::Block => (start_col..=end_col).contains(&col),
+            SelectionMode::Linear => (row > start_row || col >= start_col) && (row < end_row || col <= end_col),
+        }
+    }
+
+    /// 선택한 텍스트 (줄 사이에는 `line_ending`)
+    pub fn text(&self, lines: &[String], line_ending: LineEnding) -> String {
+        match self.mode {
+            SelectionMode::Linear => selected_text(lines, self.normalized(), line_ending),
+            SelectionMode::Block => block_text(lines, self.normalized(), line_ending),
+        }
+    }
+
+    /// 드래그한 위치까지 선택 확장
+    /// 글자 단위는 시작점에서 `(row, col)`까지, 단어/줄 단위는 처음 범위와 `(row, col)`의 단어/줄까지
+    pub fn extend(&mut self, lines: &[String], row: u16, col: u16) {
+        if self.unit == SelectionUnit::Char {
+            self.end_row = row;
+            self.end_col = col;
+            return;
+        }
+
+        // 처음 범위보다 앞이면 그 위치의 단어/줄 시작부터 처음 범위 끝까지, 아니면 처음 범위 시작부터 그 단어/줄 끝까지
+        let (anchor_start_row, anchor_start_col, anchor_end_row, anchor_end_col) = self.anchor;
+        let (start_row, start_col, end_row, end_col) = unit_range(lines, row, col, self.unit);
+        if (row, col) < (anchor_start_row, anchor_start_col) {
+            (self.start_row, self.start_col) = (start_row, start_col);
+            (self.end_row, self.end_col) = (anchor_end_row, anchor_end_col);
+        } else {
+            (self.start_row, self.start_col) = (anchor_start_row, anchor_start_col);
+            (self.end_row, self.end_col) = (end_row, end_col);
+        }
+    }
+
+    /// 시작점이 끝점보다 앞에 오도록 정규화한 범위
+    /// 사각형 선택은 위쪽 행/왼쪽 열과 아래쪽 행/오른쪽 열
+    pub fn normalized(&self) -> SelectionRange {
+        if self.mode == SelectionMode::Block {
+            return (
+                self.start_row.min(self.end_row),
+                self.start_col.min(self.end_col),
+                self.start_row.max(self.end_row),
+                self.start_col.max(self.end_col),
+            );
+        }
+
+        let mut start_row = self.start_row;
+        let mut start_col = self.start_col;
+        let mut end_row = self.end_row;
+        let mut end_col = self.end_col;
+
+        // 시작점이 끝점보다 뒤에 있으면 교환
+        if start_row > end_row || (start_row == end_row && start_col > end_col) {
+            std::mem::swap(&mut start_row, &mut end_row);
+            std::mem::swap(&mut start_col, &mut end_col);
+        }
+
+        (start_row, start_col, end_row, end_col)
+    }
+
+    /// 마우스 휠 스크롤 시 선택 영역 정책 적용
+    /// 드래그 중인 선택은 정책과 관계없이 유지 (스크롤 후 마우스 위치까지 확장)
+    pub fn on_wheel_scroll(&mut self, policy: WheelScrollSelection, dragging: bool) {
+        if self.is_active && !dragging && policy == WheelScrollSelection::Clear {
+            self.is_active = false;
+        }
+    }
+
+    /// PTY 출력으로 내용이 바뀌었을 때 선택 영역 정책 적용 (해제했으면 true)
+    /// 드래그 중인 선택은 정책과 관계없이 유지
+    pub fn on_output(&mut self, policy: ClearSelectionOnOutput, dragging: bool, alternate_screen: bool, scrolled_back: bool) -> bool {
+        if !self.is_active || dragging || !policy.should_clear(alternate_screen, scrolled_back) {
+            return false;
+        }
+        self.is_active = false;
+        true
+    }
+}
+
+/// `row`행 `col`열을 포함하는 단위 범위
+fn unit_range(lines: &[String], row: u16, col: u16, unit: SelectionUnit) -> SelectionRange {
+    let line = lines.get(row as usize).map(String::as_str).unwrap_or("");
+    match unit {
+        SelectionUnit::Char => (row, col, row, col),
+        SelectionUnit::Word => {
+            // 단어 범위는 글자 위치로 찾고 화면 열로 되돌림 (넓은 글자는 오른쪽 칸까지)
+            let (start, end) = word_bounds(line, columns::column_to_index(line, col as usize));
+            (row, columns::index_to_column(line, start) as u16, row, columns::index_end_column(line, end) as u16)
+        }
+        // 줄 끝의 빈칸은 제외
+        SelectionUnit::Line => (row, 0, row, columns::line_width(line.trim_end()).saturating_sub(1) as u16),
+    }
+}
+
+/// 글자 종류 (같은 종류가 이어지면 한 단어)
+#[derive(PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word, // 글자/숫자/`_` (한글, 한자, 가나, 결합 문자 포함)
+    Punctuation,
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_whitespace() || c == '\0' {
+        CharClass::Space
+    } else if c.is_alphanumeric() || c == '_' || columns::char_width(c) == 0 {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// `col` 위치의 단어 범위 (문자 단위, 끝 포함)
+/// 같은 종류의 글자가 이어진 구간을 한 단어로 보며, 줄 밖을 누르면 그 위치 한 칸
+pub fn word_bounds(line: &str, col: usize) -> (usize, usize) {
+    let chars: Vec<char> = line.chars().collect();
+    let Some(class) = chars.get(col).map(|&c| char_class(c)) else {
+        return (col, col);
+    };
+    let start = chars[..col].iter().rposition(|&c| char_class(c) != class).map_or(0, |i| i + 1);
+    let end = chars[col..].iter().position(|&c| char_class(c) != class).map_or(chars.len(), |i| col + i);
+    (start, end - 1)
+}
+
+/// 선택 범위의 텍스트를 추출하고 줄 사이에 설정된 줄 바꿈을 넣음
+/// 범위는 화면 열 기준 (넓은 글자는 한 칸만 걸쳐도 포함)
+pub fn selected_text(lines: &[String], range: SelectionRange, line_ending: LineEnding) -> String {
+    let (start_row, start_col, end_row, end_col) = range;
+    let mut selected_text = String::new();
+
+    for row in start_row..=end_row {
+        if let Some(line) = lines.get(row as usize) {
+            let start = if row == start_row { start_col as usize } else { 0 };
+            let end = if row == end_row { end_col as usize } else { usize::MAX };
+            selected_text.push_str(columns::slice_columns(line, start, end));
+
+            // 줄 바꿈 추가 (마지막 줄 제외)
+            if row < end_row {
+                selected_text.push_str(line_ending.separator());
+            }
+        }
+    }
+
+    selected_text
+}
+
+/// 복사할 텍스트의 줄마다 끝의 공백 제거 (`line_ending`으로 나눈 줄 기준)
+pub fn trim_line_ends(text: &str, line_ending: LineEnding) -> String {
+    let separator = line_ending.separator();
+    text.split(separator)
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 사각형 범위의 텍스트를 추출 (행마다 같은 화면 열 범위, 줄 끝 빈칸은 제외)
+pub fn block_text(lines: &[String], range: SelectionRange, line_ending: LineEnding) -> String {
+    let (start_row, start_col, end_row, end_col) = range;
+    (start_row..=end_row)
+        .map(|row| {
+            let line = lines.get(row as usize).map(String::as_str).unwrap_or("");
+            columns::slice_columns(line, start_col as usize, end_col as usize).trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(line_ending.separator())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::Terminal;
+
+    fn lines() -> Vec<String> {
+        ["first line", "second", "third line"].iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_line_ending_separators() {
+        let range = (0, 6, 2, 4);
+
+        assert_eq!(selected_text(&lines(), range, LineEnding::Lf), "line\nsecond\nthird");
+        assert_eq!(selected_text(&lines(), range, LineEnding::Crlf), "line\r\nsecond\r\nthird");
+
+        let native = if cfg!(windows) { "line\r\nsecond\r\nthird" } else { "line\nsecond\nthird" };
+        assert_eq!(selected_text(&lines(), range, LineEnding::Native), native);
+    }
+
+    #[test]
+    fn test_single_line_has_no_separator() {
+        assert_eq!(selected_text(&lines(), (1, 0, 1, 2), LineEnding::Crlf), "sec");
+    }
+
+    #[test]
+    fn test_selection_stays_anchored_while_wheel_scrolling() {
+        let mut terminal = Terminal::for_test(5, 20);
+        for i in 0..20 {
+            terminal.feed(format!("line {}\r\n", i).as_bytes());
+        }
+
+        // 화면에 보이는 "line 18" 선택 (전체 버퍼 기준 18행)
+        let mut selection = TextSelection { start_row: 18, start_col: 0, end_row: 18, end_col: 6, is_active: true, ..TextSelection::default() };
+        let first_row = terminal.scroll_state().first_visible_row();
+        assert_eq!(first_row, 16);
+
+        // 위로 스크롤해도 선택은 같은 버퍼 행을 가리키고, 화면에서는 스크롤한 만큼 아래로 이동
+        selection.on_wheel_scroll(WheelScrollSelection::Anchor, false);
+        terminal.scroll_by(2);
+        assert!(selection.is_active);
+        assert_eq!(terminal.scroll_state().first_visible_row(), first_row - 2);
+        let content = terminal.get_renderable_content().unwrap();
+        assert_eq!(selected_text(&content, selection.normalized(), LineEnding::Lf), "line 18");
+        assert_eq!(terminal.get_visible_content()[4].trim_end(), "line 18");
+    }
+
+    #[test]
+    fn test_word_bounds() {
+        let line = "cd ~/한글 폴더 && ls-la 漢字";
+        let word = |col| {
+            let (start, end) = word_bounds(line, col);
+            line.chars().skip(start).take(end + 1 - start).collect::<String>()
+        };
+        assert_eq!(word(0), "cd");
+        assert_eq!(word(5), "한글");
+        assert_eq!(word(8), "폴더");
+        assert_eq!(word(11), "&&");
+        assert_eq!(word(2), " ");
+        assert_eq!(word(20), "漢字");
+        assert_eq!(word(16), "-");
+        assert_eq!(word_bounds(line, 40), (40, 40));
+    }
+
+    #[test]
+    fn test_word_and_line_selection_extend_by_unit() {
+        let lines: Vec<String> = ["alpha beta gamma", "second line   "].iter().map(|s| s.to_string()).collect();
+        let copy = |selection: &TextSelection| selected_text(&lines, selection.normalized(), LineEnding::Lf);
+
+        // 더블 클릭은 단어, 드래그하면 단어 단위로 양쪽 확장
+        let mut selection = TextSelection::start(&lines, 0, 7, SelectionUnit::from_click_count(2));
+        assert_eq!(copy(&selection), "beta");
+        selection.extend(&lines, 0, 12);
+        assert_eq!(copy(&selection), "beta gamma");
+        selection.extend(&lines, 0, 1);
+        assert_eq!(copy(&selection), "alpha beta");
+
+        // 트리플 클릭은 줄 (끝 빈칸 제외), 드래그하면 줄 단위 확장
+        let mut selection = TextSelection::start(&lines, 1, 3, SelectionUnit::from_click_count(3));
+        assert_eq!(copy(&selection), "second line");
+        selection.extend(&lines, 0, 8);
+        assert_eq!(copy(&selection), "alpha beta gamma\nsecond line");
+
+        // 한 번 클릭은 글자 단위
+        let mut selection = TextSelection::start(&lines, 0, 2, SelectionUnit::from_click_count(1));
+        selection.extend(&lines, 0, 4);
+        assert_eq!(copy(&selection), "pha");
+    }
+
+    #[test]
+    fn test_wide_char_selection_uses_display_columns() {
+        // 한글은 두 칸: "ab 한글 cd"의 '한'은 3~4열, '글'은 5~6열
+        let lines: Vec<String> = vec!["ab 한글 cd".to_string()];
+        assert_eq!(selected_text(&lines, (0, 4, 0, 8), LineEnding::Lf), "한글 c");
+        assert_eq!(selected_text(&lines, (0, 8, 0, 9), LineEnding::Lf), "cd");
+
+        // 단어의 오른쪽 칸을 더블 클릭해도 단어 전체, 끝 열은 넓은 글자의 오른쪽 칸
+        let selection = TextSelection::start(&lines, 0, 6, SelectionUnit::Word);
+        assert_eq!(selection.normalized(), (0, 3, 0, 6));
+        assert_eq!(selection.text(&lines, LineEnding::Lf), "한글");
+        let selection = TextSelection::start(&lines, 0, 0, SelectionUnit::Line);
+        assert_eq!(selection.normalized(), (0, 0, 0, 9));
+
+        let block = ["가나다", "abcdef"].iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(block_text(&block, (0, 2, 1, 3), LineEnding::Lf), "나\ncd");
+
+        // 그리드의 결합 문자는 앞 글자와 함께 복사
+        let mut terminal = Terminal::for_test(3, 20);
+        terminal.feed("e\u{301}한 x".as_bytes());
+        let content = terminal.get_renderable_content().unwrap();
+        assert_eq!(selected_text(&content, (0, 0, 0, 1), LineEnding::Lf), "e\u{301}한");
+        assert_eq!(selected_text(&content, (0, 4, 0, 4), LineEnding::Lf), "x");
+    }
+
+    #[test]
+    fn test_trim_line_ends() {
+        assert_eq!(trim_line_ends("ls   \nsrc \t\n  end", LineEnding::Lf), "ls\nsrc\n  end");
+        assert_eq!(trim_line_ends("a  \r\nb ", LineEnding::Crlf), "a\r\nb");
+    }
+
+    #[test]
+    fn test_block_selection() {
+        let lines: Vec<String> = ["id  name   size", "1   alpha  10", "22  b      300"].iter().map(|s| s.to_string()).collect();
+
+        // 오른쪽 아래에서 왼쪽 위로 드래그해도 같은 사각형
+        let mut selection = TextSelection::start_block(2, 10);
+        selection.extend(&lines, 0, 4);
+        assert_eq!(selection.normalized(), (0, 4, 2, 10));
+        assert_eq!(selection.text(&lines, LineEnding::Lf), "name\nalpha\nb");
+
+        // 사각형 밖의 열은 선택에 포함되지 않음 (일반 선택은 중간 줄 전체 포함)
+        assert!(selection.contains(1, 4) && selection.contains(2, 10));
+        assert!(!selection.contains(1, 0) && !selection.contains(1, 11));
+        let linear = TextSelection { mode: SelectionMode::Linear, ..selection.clone() };
+        assert!(linear.contains(1, 0) && !linear.contains(0, 0) && !linear.contains(2, 11));
+    }
+
+    #[test]
+    fn test_wheel_scroll_clear_policy() {
+        let mut selection = TextSelection { is_active: true, ..TextSelection::default() };
+        // 드래그 중에는 해제하지 않음
+        selection.on_wheel_scroll(WheelScrollSelection::Clear, true);
+        assert!(selection.is_active);
+        selection.on_wheel_scroll(WheelScrollSelection::Clear, false);
+        assert!(!selection.is_active);
+    }
+
+    #[test]
+    fn test_clear_on_output_keeps_dragged_selection() {
+        let mut selection = TextSelection { is_active: true, ..TextSelection::default() };
+        // 드래그 중에는 `always`라도 유지
+        assert!(!selection.on_output(ClearSelectionOnOutput::Always, true, false, false));
+        assert!(selection.is_active);
+        // 기본 정책은 스크롤해서 보는 중이면 유지, 맨 아래면 해제
+        assert!(!selection.on_output(ClearSelectionOnOutput::default(), false, false, true));
+        assert!(selection.is_active);
+        assert!(selection.on_output(ClearSelectionOnOutput::default(), false, false, false));
+        assert!(!selection.is_active);
+    }
+
+    #[test]
+    fn test_invalid_utf8_output_is_copied_with_replacement_char() {
+        let mut terminal = Terminal::for_test(3, 20);
+        // 잘못된 시작 바이트, 잘린 3바이트 시퀀스, 단독 연속 바이트
+        terminal.feed(b"ok \xff \xe2\x82 \x80 end\r\n\xed\xa0\x80");
+
+        let content = terminal.get_renderable_content().unwrap();
+        let copied = selected_text(&content, (0, 0, 1, 19), LineEnding::Lf);
+        let first_line = copied.lines().next().unwrap().trim_end();
+        assert!(first_line.starts_with("ok \u{FFFD}"), "{:?}", first_line);
+        assert!(first_line.ends_with("end"), "{:?}", first_line);
+        assert!(copied.contains('\u{FFFD}'));
+    }
+}
@@ -0,0 +1,251 @@
+//! 시리얼 포트 모듈
+//! `--serial /dev/ttyUSB0 --baud 115200`처럼 탭을 PTY 대신 시리얼 장치에 연결 (minicom 대용)
+//! 장치를 raw 모드로 열고 속도, 데이터/정지 비트, 패리티, 흐름 제어를 termios로 설정
+//!
+//! 장치를 뽑거나 읽기 오류가 나면 셸이 종료된 것처럼 처리 (`session.on_exit` 설정을 따름)
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::PathBuf;
+
+/// 패리티
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Parity {
+    #[default]
+    None,
+    Even,
+    Odd,
+}
+
+/// 흐름 제어
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlowControl {
+    #[default]
+    None,
+    Hardware, // RTS/CTS
+    Software, // XON/XOFF
+}
+
+/// 시리얼 연결 설정 (장치 경로 포함, 레이아웃에도 그대로 저장)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerialSettings {
+    pub device: PathBuf,
+    pub baud: u32,
+    pub data_bits: u8,
+    pub parity: Parity,
+    pub stop_bits: u8,
+    pub flow_control: FlowControl,
+}
+
+impl Default for SerialSettings {
+    fn default() -> Self {
+        Self {
+            device: PathBuf::new(),
+            baud: 115_200,
+            data_bits: 8,
+            parity: Parity::None,
+            stop_bits: 1,
+            flow_control: FlowControl::None,
+        }
+    }
+}
+
+/// `none`/`even`/`odd`
+pub fn parse_parity(value: &str) -> Option<Parity> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "none" | "n" => Some(Parity::None),
+        "even" | "e" => Some(Parity::Even),
+        "odd" | "o" => Some(Parity::Odd),
+        _ => None,
+    }
+}
+
+/// `none`/`hardware`(`rtscts`)/`software`(`xonxoff`)
+pub fn parse_flow_control(value: &str) -> Option<FlowControl> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "none" => Some(FlowControl::None),
+        "hardware" | "rtscts" => Some(FlowControl::Hardware),
+        "software" | "xonxoff" => Some(FlowControl::Software),
+        _ => None,
+    }
+}
+
+impl SerialSettings {
+    /// 데이터/정지 비트와 속도가 쓸 수 있는 값인지 확인
+    pub fn validate(&self) -> Result<()> {
+        if !(5..=8).contains(&self.data_bits) {
+            bail!("데이터 비트는 5~8이어야 함: {}", self.data_bits);
+        }
+        if !(1..=2).contains(&self.stop_bits) {
+            bail!("정지 비트는 1 또는 2여야 함: {}", self.stop_bits);
+        }
+        #[cfg(target_os = "linux")]
+        if baud_constant(self.baud).is_none() {
+            bail!("지원하지 않는 속도: {}", self.baud);
+        }
+        Ok(())
+    }
+
+    /// 탭 이름 등에 표시할 이름 (`ttyUSB0 115200 8N1`)
+    pub fn label(&self) -> String {
+        let device = self.device.file_name().map_or_else(|| self.device.display().to_string(), |name| name.to_string_lossy().into_owned());
+        let parity = match self.parity {
+            Parity::None => 'N',
+            Parity::Even => 'E',
+            Parity::Odd => 'O',
+        };
+        format!("{} {} {}{}{}", device, self.baud, self.data_bits, parity, self.stop_bits)
+    }
+
+    /// 장치를 non-blocking raw 모드로 열고 설정 적용 (다른 프로그램이 같은 장치를 열지 못하게 독점)
+    #[cfg(unix)]
+    pub fn open(&self) -> Result<File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        self.validate()?;
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY | libc::O_NONBLOCK)
+            .open(&self.device)
+            .map_err(|e| anyhow!("시리얼 장치 열기 실패 ({}): {}", self.device.display(), e))?;
+        let fd = file.as_raw_fd();
+        let os_error = |what: &str| anyhow!("시리얼 장치 {} 실패 ({}): {}", what, self.device.display(), std::io::Error::last_os_error());
+
+        // SAFETY: fd는 위에서 연 파일의 것이고 termios는 tcgetattr가 채움
+        unsafe {
+            let mut tio: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(fd, &mut tio) != 0 {
+                return Err(os_error("설정 읽기"));
+            }
+            libc::cfmakeraw(&mut tio);
+            #[cfg(target_os = "linux")]
+            let speed = baud_constant(self.baud).unwrap_or(libc::B115200);
+            #[cfg(not(target_os = "linux"))]
+            let speed = self.baud as libc::speed_t;
+            if libc::cfsetispeed(&mut tio, speed) != 0 || libc::cfsetospeed(&mut tio, speed) != 0 {
+                return Err(os_error("속도 설정"));
+            }
+            tio.c_cflag |= libc::CLOCAL | libc::CREAD;
+            tio.c_cflag &= !(libc::CSIZE | libc::PARENB | libc::PARODD | libc::CSTOPB | libc::CRTSCTS);
+            tio.c_cflag |= match self.data_bits {
+                5 => libc::CS5,
+                6 => libc::CS6,
+                7 => libc::CS7,
+                _ => libc::CS8,
+            };
+            match self.parity {
+                Parity::None => {}
+                Parity::Even => tio.c_cflag |= libc::PARENB,
+                Parity::Odd => tio.c_cflag |= libc::PARENB | libc::PARODD,
+            }
+            if self.stop_bits == 2 {
+                tio.c_cflag |= libc::CSTOPB;
+            }
+            tio.c_iflag &= !(libc::IXON | libc::IXOFF | libc::IXANY);
+            match self.flow_control {
+                FlowControl::None => {}
+                FlowControl::Hardware => tio.c_cflag |= libc::CRTSCTS,
+                FlowControl::Software => tio.c_iflag |= libc::IXON | libc::IXOFF,
+            }
+            tio.c_cc[libc::VMIN] = 0;
+            tio.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(fd, libc::TCSANOW, &tio) != 0 {
+                return Err(os_error("설정"));
+            }
+            if libc::ioctl(fd, libc::TIOCEXCL) != 0 {
+                log::warn!("serial device {} could not be opened exclusively", self.device.display());
+            }
+        }
+        Ok(file)
+    }
+
+    /// 시리얼 포트는 Unix에서만 지원
+    #[cfg(not(unix))]
+    pub fn open(&self) -> Result<File> {
+        bail!("이 플랫폼에서는 시리얼 포트를 지원하지 않음")
+    }
+}
+
+/// 속도에 해당하는 termios 상수 (Linux는 정해진 값만 허용)
+#[cfg(target_os = "linux")]
+fn baud_constant(baud: u32) -> Option<libc::speed_t> {
+    Some(match baud {
+        300 => libc::B300,
+        1200 => libc::B1200,
+        2400 => libc::B2400,
+        4800 => libc::B4800,
+        9600 => libc::B9600,
+        19_200 => libc::B19200,
+        38_400 => libc::B38400,
+        57_600 => libc::B57600,
+        115_200 => libc::B115200,
+        230_400 => libc::B230400,
+        460_800 => libc::B460800,
+        500_000 => libc::B500000,
+        921_600 => libc::B921600,
+        1_000_000 => libc::B1000000,
+        1_500_000 => libc::B1500000,
+        2_000_000 => libc::B2000000,
+        3_000_000 => libc::B3000000,
+        4_000_000 => libc::B4000000,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_parse_and_validate() {
+        assert_eq!(parse_parity("Even"), Some(Parity::Even));
+        assert_eq!(parse_flow_control("rtscts"), Some(FlowControl::Hardware));
+        assert_eq!(parse_flow_control("xon"), None);
+
+        let settings = SerialSettings { device: PathBuf::from("/dev/ttyUSB0"), parity: Parity::Odd, stop_bits: 2, ..SerialSettings::default() };
+        assert_eq!(settings.label(), "ttyUSB0 115200 8O2");
+        assert!(settings.validate().is_ok());
+        assert!(SerialSettings { data_bits: 9, ..SerialSettings::default() }.validate().is_err());
+        assert!(SerialSettings { stop_bits: 3, ..SerialSettings::default() }.validate().is_err());
+        #[cfg(target_os = "linux")]
+        assert!(SerialSettings { baud: 12_345, ..SerialSettings::default() }.validate().is_err());
+    }
+
+    /// 의사 터미널의 slave 쪽은 termios를 지원하므로 실제 장치 대신 사용
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_open_applies_termios() {
+        use std::os::unix::io::AsRawFd;
+
+        let (mut master, mut slave) = (0, 0);
+        let mut name = [0 as libc::c_char; 128];
+        // SAFETY: 출력 인자는 모두 유효한 지역 변수
+        let opened = unsafe { libc::openpty(&mut master, &mut slave, name.as_mut_ptr(), std::ptr::null(), std::ptr::null()) };
+        assert_eq!(opened, 0);
+        // SAFETY: openpty가 name에 NUL로 끝나는 경로를 씀
+        let path = unsafe { std::ffi::CStr::from_ptr(name.as_ptr()) }.to_string_lossy().into_owned();
+
+        let settings = SerialSettings { device: PathBuf::from(path), baud: 9600, ..SerialSettings::default() };
+        let file = settings.open().unwrap();
+        // SAFETY: 연 파일의 fd와 지역 termios
+        let tio = unsafe {
+            let mut tio: libc::termios = std::mem::zeroed();
+            assert_eq!(libc::tcgetattr(file.as_raw_fd(), &mut tio), 0);
+            tio
+        };
+        // 의사 터미널은 문자 크기/패리티/정지 비트(c_cflag)를 무시하므로 속도와 raw 모드만 확인
+        assert_eq!(unsafe { libc::cfgetospeed(&tio) }, libc::B9600);
+        assert_eq!(tio.c_lflag & (libc::ICANON | libc::ECHO), 0);
+        assert_eq!((tio.c_cc[libc::VMIN], tio.c_cc[libc::VTIME]), (0, 0));
+        // SAFETY: openpty가 연 fd 닫기
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+
+        assert!(SerialSettings { device: PathBuf::from("/nonexistent/tty"), ..SerialSettings::default() }.open().is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! 스마트 선택 모듈
+//! config.toml의 `[smart.이름]` 규칙(정규식과 동작)으로 화면에서 git 커밋 해시, 이슈 번호, IP 주소 같은 항목을 찾아
+//! 밑줄로 강조하고, 오른쪽 클릭 메뉴나 `smart-actions` 동작으로 복사, URL 템플릿 열기, 명령 실행
+//!
+//! 동작의 `{0}`은 일치한 글자 전체, `{1}`부터는 정규식의 괄호 그룹으로 채움
+//! 화면 출력은 믿을 수 없으므로 `run` 명령은 셸을 거치지 않음 - 템플릿을 공백으로 나눈 단어마다 채워 인자로 전달
+//!
+//! ```toml
+//! [smart.jira]
+//! pattern = '\b[A-Z][A-Z0-9]+-[0-9]+\b'
+//! actions = ["open https://jira.example.com/browse/{0}", "copy"]
+//!
+//! [smart.sha]
+//! pattern = '\b[0-9a-f]{7,40}\b'
+//! actions = ["copy", "run git show {0}"]
+//! ```
+
+use crate::bindings::Action;
+use crate::toml;
+use anyhow::{anyhow, bail, Result};
+use regex::Regex;
+
+/// 일치 항목에 할 수 있는 동작
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmartAction {
+    Copy,         // 일치한 글자 복사
+    Open(String), // URL 템플릿을 채워 기본 브라우저로 열기
+    Run(String),  // 명령 템플릿을 채워 새 탭에서 실행 (셸 없이)
+}
+
+impl SmartAction {
+    /// `copy`, `open <URL 템플릿>`, `run <명령 템플릿>`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (name, template) = spec.split_once(char::is_whitespace).unwrap_or((spec, ""));
+        let template = template.trim();
+        match (name.to_ascii_lowercase().as_str(), template.is_empty()) {
+            ("copy", true) => Ok(Self::Copy),
+            ("open", false) => Ok(Self::Open(template.to_string())),
+            ("run", false) => Ok(Self::Run(template.to_string())),
+            _ => bail!("알 수 없는 스마트 선택 동작: {:?} (copy, open <URL>, run <명령>)", spec),
+        }
+    }
+
+    /// 일치 항목으로 채운 앱 동작
+    pub fn action(&self, captures: &[String]) -> Action {
+        match self {
+            SmartAction::Copy => Action::CopyText(captures[0].clone()),
+            SmartAction::Open(template) => Action::OpenUrl(expand(template, captures)),
+            SmartAction::Run(template) => Action::RunProgram(expand_args(template, captures)),
+        }
+    }
+
+    /// 메뉴에 표시할 이름
+    pub fn title(&self, captures: &[String]) -> String {
+        match self {
+            SmartAction::Copy => format!("복사: {}", captures[0]),
+            SmartAction::Open(template) => format!("열기: {}", expand(template, captures)),
+            SmartAction::Run(template) => format!("실행: {}", expand_args(template, captures).join(" ")),
+        }
+    }
+}
+
+/// `{0}`, `{1}`, ... 자리에 일치한 글자와 괄호 그룹 채우기 (없는 그룹은 빈 글자)
+fn expand(template: &str, captures: &[String]) -> String {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        expanded.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}').and_then(|close| Some((close, after[..close].parse::<usize>().ok()?))) {
+            Some((close, index)) => {
+                expanded.push_str(captures.get(index).map_or("", String::as_str));
+                rest = &after[close + 1..];
+            }
+            None => {
+                expanded.push('{');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// 명령 템플릿을 공백으로 나눈 단어마다 채운 인자 목록 (첫 단어가 프로그램)
+/// 채운 글자는 공백, `;`, `$()`, 따옴표가 있어도 인자 하나로만 전달됨
+fn expand_args(template: &str, captures: &[String]) -> Vec<String> {
+    template.split_whitespace().map(|word| expand(word, captures)).collect()
+}
+
+/// 규칙 하나
+#[derive(Debug, Clone)]
+pub struct SmartRule {
+    pub name: String,
+    pub pattern: Option<Regex>,
+    pub actions: Vec<SmartAction>, // 첫 동작이 기본 동작 (지정하지 않으면 복사)
+    pub highlight: bool,           // 화면의 일치 항목에 밑줄
+}
+
+impl SmartRule {
+    pub fn named(name: &str) -> Self {
+        Self { name: name.to_string(), pattern: None, actions: Vec::new(), highlight: true }
+    }
+
+    /// `[smart.이름]`의 `키 = 값` 하나 적용
+    pub fn set(&mut self, key: &str, value: &toml::Value) -> Result<()> {
+        let invalid = |kind: &str| anyhow!("smart.{}.{}는 {}이어야 함", self.name, key, kind);
+        match key {
+            "pattern" => {
+                let pattern = value.as_str().ok_or_else(|| invalid("문자열"))?;
+                let regex = Regex::new(pattern).map_err(|e| anyhow!("smart.{}.pattern: {}", self.name, e))?;
+                self.pattern = Some(regex);
+            }
+            // 동작 하나는 문자열로, 여러 개는 배열로
+            "action" | "actions" => {
+                let specs = match value {
+                    toml::Value::String(spec) => vec![spec.as_str()],
+                    toml::Value::Array(items) => items.iter().map(toml::Value::as_str).collect::<Option<_>>()
+                        .ok_or_else(|| invalid("문자열 배열"))?,
+                    _ => return Err(invalid("문자열 배열")),
+                };
+                self.actions = specs.into_iter().map(SmartAction::parse).collect::<Result<_>>()?;
+            }
+            "highlight" => {
+                let toml::Value::Boolean(highlight) = value else {
+                    return Err(invalid("true/false"));
+                };
+                self.highlight = *highlight;
+            }
+            _ => bail!("알 수 없는 스마트 선택 설정: smart.{}.{}", self.name, key),
+        }
+        Ok(())
+    }
+
+    /// 메뉴에 넣을 동작 (지정하지 않았으면 복사만)
+    pub fn actions(&self) -> &[SmartAction] {
+        if self.actions.is_empty() { &[SmartAction::Copy] } else { &self.actions }
+    }
+}
+
+/// `[smart.이름]` 테이블의 값 하나를 해당 규칙에 적용 (처음 나온 이름이면 새 규칙)
+pub fn set_entry(rules: &mut Vec<SmartRule>, name: &str, entry: &toml::Entry) -> Result<()> {
+    let index = match rules.iter().position(|rule| rule.name == name) {
+        Some(index) => index,
+        None => {
+            rules.push(SmartRule::named(name));
+            rules.len() - 1
+        }
+    };
+    rules[index].set(&entry.key, &entry.value)
+}
+
+/// 줄에서 찾은 일치 항목 (문자 단위 열, `end`는 포함하지 않음)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartMatch {
+    pub start: usize,
+    pub end: usize,
+    pub rule: usize,           // `rules` 안의 규칙 위치
+    pub captures: Vec<String>, // 일치한 글자 전체와 괄호 그룹
+}
+
+impl SmartMatch {
+    pub fn contains(&self, col: usize) -> bool {
+        (self.start..self.end).contains(&col)
+    }
+}
+
+/// 줄에 있는 모든 일치 항목 (왼쪽부터, 겹치면 앞 규칙 우선)
+pub fn matches(rules: &[SmartRule], line: &str) -> Vec<SmartMatch> {
+    let char_index = |byte: usize| line[..byte].chars().count();
+    let mut found: Vec<SmartMatch> = Vec::new();
+    for (rule_index, rule) in rules.iter().enumerate() {
+        let Some(pattern) = &rule.pattern else {
+            continue;
+        };
+        for captures in pattern.captures_iter(line) {
+            let whole = captures.get(0).expect("capture 0");
+            if whole.as_str().is_empty() {
+                continue;
+            }
+            let (start, end) = (char_index(whole.start()), char_index(whole.end()));
+            if found.iter().any(|other| start < other.end && other.start < end) {
+                continue;
+            }
+            found.push(SmartMatch {
+                start,
+                end,
+                rule: rule_index,
+                captures: captures.iter().map(|group| group.map_or(String::new(), |group| group.as_str().to_string())).collect(),
+            });
+        }
+    }
+    found.sort_by_key(|found| found.start);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, pattern: &str, actions: &[&str]) -> SmartRule {
+        let mut rule = SmartRule::named(name);
+        rule.set("pattern", &toml::Value::String(pattern.to_string())).unwrap();
+        let actions = actions.iter().map(|action| toml::Value::String(action.to_string())).collect();
+        rule.set("actions", &toml::Value::Array(actions)).unwrap();
+        rule
+    }
+
+    #[test]
+    fn test_parse_actions() {
+        assert_eq!(SmartAction::parse("copy").unwrap(), SmartAction::Copy);
+        assert_eq!(SmartAction::parse("open https://x/{0}").unwrap(), SmartAction::Open("https://x/{0}".into()));
+        assert_eq!(SmartAction::parse("RUN  git show {0}").unwrap(), SmartAction::Run("git show {0}".into()));
+        assert!(SmartAction::parse("open").is_err());
+        assert!(SmartAction::parse("paste").is_err());
+        assert!(SmartRule::named("x").set("pattern", &toml::Value::String("(".into())).is_err());
+    }
+
+    #[test]
+    fn test_matches_and_expand() {
+        let rules = vec![
+            rule("jira", r"\b([A-Z]+)-([0-9]+)\b", &["open https://jira/browse/{0}?project={1}", "copy"]),
+            rule("sha", r"\b[0-9a-f]{7,40}\b", &[]),
+            rule("word", r"PROJ", &["copy"]),
+        ];
+        let line = "수정 PROJ-42 in 1a2b3c4d, see PROJ-7";
+        let found = matches(&rules, line);
+        let texts: Vec<(usize, &str)> = found.iter().map(|found| (found.start, found.captures[0].as_str())).collect();
+        // 앞 규칙과 겹치는 `PROJ`는 제외, 열은 문자 단위
+        assert_eq!(texts, vec![(3, "PROJ-42"), (14, "1a2b3c4d"), (28, "PROJ-7")]);
+
+        let jira = &rules[found[0].rule];
+        assert_eq!(jira.actions()[0].action(&found[0].captures), Action::OpenUrl("https://jira/browse/PROJ-42?project=PROJ".into()));
+        assert_eq!(jira.actions()[1].title(&found[0].captures), "복사: PROJ-42");
+        // 동작을 지정하지 않으면 복사
+        assert_eq!(rules[found[1].rule].actions(), &[SmartAction::Copy]);
+        assert_eq!(expand("{x} {0} {9}", &["a".to_string()]), "{x} a ");
+    }
+
+    #[test]
+    fn test_run_does_not_use_shell() {
+        let hostile = "x;rm -rf ~ $(id) 'q\"".to_string();
+        let run = SmartAction::parse("run git show --stat {0}").unwrap();
+        assert_eq!(run.action(std::slice::from_ref(&hostile)), Action::RunProgram(vec![
+            "git".to_string(), "show".to_string(), "--stat".to_string(), hostile,
+        ]));
+    }
+}
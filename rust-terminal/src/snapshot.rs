@@ -0,0 +1,74 @@
+//! 화면 스냅샷(freeze frame) 모듈
+//! 현재 화면을 이름 붙은 스냅샷으로 저장해 두고, 이후 화면과 셀 단위로 비교하여
+//! 명령을 다시 실행했을 때 무엇이 바뀌었는지 확인
+
+use crate::terminal::RenderableCell;
+use std::collections::HashSet;
+
+/// 저장된 화면 스냅샷
+#[derive(Debug, Clone)]
+pub struct FrameSnapshot {
+    name: String,
+    rows: Vec<Vec<RenderableCell>>, // 화면 기준 행별 셀
+}
+
+impl FrameSnapshot {
+    pub fn new(name: impl Into<String>, rows: Vec<Vec<RenderableCell>>) -> Self {
+        Self {
+            name: name.into(),
+            rows,
+        }
+    }
+
+    /// 스냅샷 이름
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 현재 화면과 비교하여 바뀐 셀의 (화면 행, 열) 목록 반환
+    /// 화면 크기가 달라 한쪽에만 있는 셀도 바뀐 것으로 봄
+    pub fn changed_cells(&self, live: &[Vec<RenderableCell>]) -> HashSet<(usize, usize)> {
+        let mut changed = HashSet::new();
+        let row_count = self.rows.len().max(live.len());
+
+        for row in 0..row_count {
+            let old = self.rows.get(row).map(Vec::as_slice).unwrap_or_default();
+            let new = live.get(row).map(Vec::as_slice).unwrap_or_default();
+            for col in 0..old.len().max(new.len()) {
+                if old.get(col) != new.get(col) {
+                    changed.insert((row, col));
+                }
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::Terminal;
+
+    #[test]
+    fn test_diff_highlights_exactly_changed_cells() {
+        let mut terminal = Terminal::for_test(3, 10);
+        terminal.feed(b"abc\r\ndef");
+        let snapshot = FrameSnapshot::new("before", terminal.get_renderable_cells());
+
+        // 같은 화면이면 바뀐 셀 없음
+        assert!(snapshot.changed_cells(&terminal.get_renderable_cells()).is_empty());
+
+        // 첫 줄 두 번째 글자를 바꾸고, 둘째 줄 첫 글자에 색상만 적용
+        terminal.feed(b"\x1b[1;2HX\x1b[2;1H\x1b[31md\x1b[0m");
+        let changed = snapshot.changed_cells(&terminal.get_renderable_cells());
+        let expected: HashSet<_> = [(0, 1), (1, 0)].into_iter().collect();
+        assert_eq!(changed, expected);
+    }
+
+    #[test]
+    fn test_resized_screen_marks_extra_cells_changed() {
+        let snapshot = FrameSnapshot::new("small", vec![vec![]]);
+        let terminal = Terminal::for_test(2, 2);
+        assert_eq!(snapshot.changed_cells(&terminal.get_renderable_cells()).len(), 4);
+    }
+}
@@ -0,0 +1,167 @@
+//! 분할 pane 크기 계산 모듈
+//! pane 사이 구분선(divider)을 마우스로 드래그하거나 키보드로 옮길 때
+//! 각 pane의 크기(분할 방향 기준 셀 수)를 다시 계산하고 최소 크기를 보장
+
+use ratatui::layout::Constraint;
+
+/// pane 최소 크기 (테두리 2칸 + 내용 1칸)
+pub const MIN_PANE_SIZE: u16 = 3;
+
+/// 한 분할 영역 안의 pane 크기 목록
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitSizes {
+    sizes: Vec<u16>,
+}
+
+impl SplitSizes {
+    /// 전체 크기를 pane 수만큼 균등 분할 (나머지는 앞쪽 pane부터 1칸씩)
+    pub fn even(total: u16, count: usize) -> Self {
+        let count = count.max(1) as u16;
+        let (base, extra) = (total / count, total % count);
+        Self {
+            sizes: (0..count).map(|i| base + u16::from(i < extra)).collect(),
+        }
+    }
+
+    /// 전체 크기
+    pub fn total(&self) -> u16 {
+        self.sizes.iter().sum()
+    }
+
+    /// ratatui 레이아웃 제약 조건
+    pub fn constraints(&self) -> Vec<Constraint> {
+        self.sizes.iter().map(|&size| Constraint::Length(size)).collect()
+    }
+
+    /// i번째 구분선 위치 (i번째 pane과 i+1번째 pane의 경계, 분할 영역 시작 기준)
+    pub fn divider_position(&self, divider: usize) -> Option<u16> {
+        (divider + 1 < self.sizes.len()).then(|| self.sizes[..=divider].iter().sum())
+    }
+
+    /// 위치에 있는 구분선 찾기 - 경계 양쪽 테두리 칸을 잡을 수 있는 grip으로 봄
+    pub fn divider_at(&self, position: u16) -> Option<usize> {
+        (0..self.sizes.len().saturating_sub(1)).find(|&divider| {
+            let boundary = self.divider_position(divider).unwrap_or_default();
+            position + 1 == boundary || position == boundary
+        })
+    }
+
+    /// 구분선을 delta만큼 이동 (양수: 오른쪽/아래쪽) - 양쪽 pane이 최소 크기 미만이 되지 않도록 제한
+    /// 실제로 크기가 바뀌었으면 true
+    pub fn move_divider(&mut self, divider: usize, delta: i32, min_size: u16) -> bool {
+        if divider + 1 >= self.sizes.len() {
+            return false;
+        }
+
+        let (before, after) = (self.sizes[divider] as i32, self.sizes[divider + 1] as i32);
+        let min = min_size as i32;
+        // 한쪽이 이미 최소 크기보다 작으면 더 줄이지 않음
+        let lower = (min - before).min(0);
+        let upper = (after - min).max(0);
+        let delta = delta.clamp(lower, upper);
+        if delta == 0 {
+            return false;
+        }
+
+        self.sizes[divider] = (before + delta) as u16;
+        self.sizes[divider + 1] = (after - delta) as u16;
+        true
+    }
+
+    /// 구분선을 지정한 위치로 드래그 (분할 영역 시작 기준)
+    pub fn drag_divider_to(&mut self, divider: usize, position: u16, min_size: u16) -> bool {
+        match self.divider_position(divider) {
+            Some(current) => self.move_divider(divider, position as i32 - current as i32, min_size),
+            None => false,
+        }
+    }
+
+    /// 분할 영역 전체 크기가 바뀌면 기존 비율을 유지하며 다시 분배
+    pub fn resize_total(&mut self, new_total: u16, min_size: u16) {
+        let old_total = self.total().max(1) as u32;
+        let mut sizes: Vec<u16> = self.sizes.iter()
+            .map(|&size| ((size as u32 * new_total as u32) / old_total) as u16)
+            .collect();
+
+        // 반올림 오차는 마지막 pane에 반영
+        let assigned: u16 = sizes.iter().sum();
+        if let Some(last) = sizes.last_mut() {
+            *last += new_total.saturating_sub(assigned);
+        }
+        self.sizes = sizes;
+
+        // 최소 크기 미만인 pane은 이웃 pane에서 크기를 가져옴
+        for idx in 0..self.sizes.len() {
+            let shortage = min_size.saturating_sub(self.sizes[idx]);
+            if shortage == 0 {
+                continue;
+            }
+            if idx + 1 < self.sizes.len() {
+                self.move_divider(idx, shortage as i32, min_size);
+            }
+            if idx > 0 {
+                let shortage = min_size.saturating_sub(self.sizes[idx]);
+                self.move_divider(idx - 1, -(shortage as i32), min_size);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_split_distributes_remainder() {
+        let split = SplitSizes::even(80, 3);
+        assert_eq!(split.sizes, [27, 27, 26]);
+        assert_eq!(split.total(), 80);
+        assert_eq!(split.divider_position(0), Some(27));
+        assert_eq!(split.divider_position(2), None);
+    }
+
+    #[test]
+    fn test_move_divider_enforces_min_size() {
+        let mut split = SplitSizes::even(20, 2);
+        assert!(split.move_divider(0, 4, MIN_PANE_SIZE));
+        assert_eq!(split.sizes, [14, 6]);
+
+        // 오른쪽 pane이 최소 크기가 될 때까지만 이동
+        assert!(split.move_divider(0, 100, MIN_PANE_SIZE));
+        assert_eq!(split.sizes, [17, 3]);
+        assert!(!split.move_divider(0, 1, MIN_PANE_SIZE));
+
+        assert!(split.move_divider(0, -100, MIN_PANE_SIZE));
+        assert_eq!(split.sizes, [3, 17]);
+        assert_eq!(split.total(), 20);
+    }
+
+    #[test]
+    fn test_drag_divider_and_hit_test() {
+        let mut split = SplitSizes::even(30, 3);
+        // 경계(10) 양쪽 테두리 칸이 grip
+        assert_eq!(split.divider_at(9), Some(0));
+        assert_eq!(split.divider_at(10), Some(0));
+        assert_eq!(split.divider_at(5), None);
+        assert_eq!(split.divider_at(20), Some(1));
+
+        assert!(split.drag_divider_to(1, 25, MIN_PANE_SIZE));
+        assert_eq!(split.sizes, [10, 15, 5]);
+        assert_eq!(split.constraints(), vec![Constraint::Length(10), Constraint::Length(15), Constraint::Length(5)]);
+    }
+
+    #[test]
+    fn test_resize_total_keeps_ratio_and_min_size() {
+        let mut split = SplitSizes::even(40, 2);
+        split.move_divider(0, 10, MIN_PANE_SIZE);
+        assert_eq!(split.sizes, [30, 10]);
+
+        split.resize_total(80, MIN_PANE_SIZE);
+        assert_eq!(split.sizes, [60, 20]);
+
+        // 너무 작아지면 최소 크기 보장
+        split.resize_total(8, MIN_PANE_SIZE);
+        assert_eq!(split.total(), 8);
+        assert!(split.sizes.iter().all(|&size| size >= MIN_PANE_SIZE));
+    }
+}
@@ -0,0 +1,129 @@
+//! 성능 통계 오버레이 모듈
+//! 오버레이가 켜져 있을 때만 렌더 루프에서 측정값을 수집 (꺼져 있으면 비용 없음)
+//! PTY에서 읽은 바이트 수와 렌더링 중 그리드 잠금 대기 시간은 모든 세션이 함께 쓰는 카운터에 쌓음
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 초당 값을 계산하는 측정 구간 길이
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// 측정 중인지 (꺼져 있으면 카운터를 건드리지 않음)
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// 측정을 켠 뒤 PTY에서 읽은 바이트 수 (모든 세션 합계)
+static PTY_BYTES: AtomicU64 = AtomicU64::new(0);
+/// 마지막으로 가져간 뒤 렌더 경로가 그리드 잠금을 기다린 시간 (나노초)
+static LOCK_WAIT_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// 측정 켜기/끄기
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// PTY 읽기 스레드가 읽은 바이트 수 기록
+pub fn record_pty_read(bytes: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        PTY_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// 지금까지 읽은 PTY 바이트 수
+pub fn pty_bytes() -> u64 {
+    PTY_BYTES.load(Ordering::Relaxed)
+}
+
+/// 그리드 잠금 얻기 - 측정 중이면 기다린 시간을 기록
+pub fn timed_lock<T>(lock: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return lock();
+    }
+    let start = Instant::now();
+    let guard = lock();
+    LOCK_WAIT_NANOS.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    guard
+}
+
+/// 마지막 호출 뒤 쌓인 그리드 잠금 대기 시간을 가져오고 비움
+pub fn take_lock_wait() -> Duration {
+    Duration::from_nanos(LOCK_WAIT_NANOS.swap(0, Ordering::Relaxed))
+}
+
+/// 렌더링 통계
+#[derive(Debug, Clone)]
+pub struct RenderStats {
+    window_start: Instant,
+    window_frames: u32,
+    window_start_bytes: u64, // 구간 시작 시점까지 읽은 PTY 바이트 수
+    redraws_per_sec: f64,
+    pty_bytes_per_sec: f64,
+    lock_wait: Duration,   // 마지막 프레임에서 렌더 경로가 그리드 잠금을 기다린 시간
+    last_render: Duration, // 마지막 프레임 렌더링 시간
+}
+
+impl RenderStats {
+    pub fn new(now: Instant, pty_bytes: u64) -> Self {
+        Self {
+            window_start: now,
+            window_frames: 0,
+            window_start_bytes: pty_bytes,
+            redraws_per_sec: 0.0,
+            pty_bytes_per_sec: 0.0,
+            lock_wait: Duration::ZERO,
+            last_render: Duration::ZERO,
+        }
+    }
+
+    /// 프레임 하나의 측정값 기록 - 구간이 끝나면 초당 값 갱신
+    pub fn record_frame(&mut self, now: Instant, render_time: Duration, lock_wait: Duration, pty_bytes: u64) {
+        self.window_frames += 1;
+        self.last_render = render_time;
+        self.lock_wait = lock_wait;
+
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed >= WINDOW {
+            let secs = elapsed.as_secs_f64();
+            self.redraws_per_sec = self.window_frames as f64 / secs;
+            self.pty_bytes_per_sec = pty_bytes.saturating_sub(self.window_start_bytes) as f64 / secs;
+            self.window_start = now;
+            self.window_frames = 0;
+            self.window_start_bytes = pty_bytes;
+        }
+    }
+
+    /// 오버레이에 표시할 줄
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!("redraw/s  {:>7.1}", self.redraws_per_sec),
+            format!("pty KiB/s {:>7.1}", self.pty_bytes_per_sec / 1024.0),
+            format!("lock wait {:>7.2}ms", self.lock_wait.as_secs_f64() * 1000.0),
+            format!("render    {:>7.2}ms", self.last_render.as_secs_f64() * 1000.0),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_computed_per_window() {
+        let start = Instant::now();
+        let mut stats = RenderStats::new(start, 1024);
+        let frame = Duration::from_millis(2);
+
+        // 1초 구간 안에서는 초당 값이 갱신되지 않음
+        for i in 1..20 {
+            stats.record_frame(start + Duration::from_millis(i * 50), frame, Duration::ZERO, 1024 + i * 100);
+        }
+        assert_eq!(stats.redraws_per_sec, 0.0);
+
+        // 구간이 끝나면 프레임 수와 읽은 바이트 증가량으로 초당 값 계산
+        stats.record_frame(start + WINDOW, frame, Duration::from_micros(500), 1024 + 3 * 1024);
+        assert_eq!(stats.redraws_per_sec, 20.0);
+        assert_eq!(stats.pty_bytes_per_sec, 3072.0);
+        assert!(stats.lines()[1].contains("3.0"));
+        assert_eq!(stats.lock_wait, Duration::from_micros(500));
+        assert_eq!(stats.last_render, frame);
+        assert!(stats.lines()[3].contains("2.00ms"));
+    }
+}
@@ -0,0 +1,291 @@
+//! 하단 상태 표시줄 모듈
+//! 포커스 세션의 모드(검색, 마크 입력 등), 포그라운드 프로세스, 작업 디렉토리, git 브랜치, 스크롤 위치,
+//! 알림 표시(벨, 백그라운드 활동, 녹화 등)를 설정한 순서대로 한 줄에 그림 - `|` 앞의 구역은 왼쪽, 뒤는 오른쪽 정렬
+//!
+//! 프로세스 이름과 git 브랜치는 프로세스 목록과 파일 시스템을 읽으므로 세션마다 캐시하고 일정 간격으로만 다시 확인
+
+use crate::columns;
+use crate::terminal::ScrollState;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// 프로세스 이름과 git 브랜치를 다시 확인하는 간격
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 구역 사이 구분자
+const SEPARATOR: &str = " │ ";
+
+/// 상태 표시줄 구역
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment {
+    Mode,       // 검색, 마크 입력, 종료 확인 같은 입력 모드와 안내
+    Process,    // 포그라운드 프로세스 이름
+    Directory,  // 작업 디렉토리 (축약)
+    Git,        // 작업 디렉토리의 git 브랜치
+    Scroll,     // 스크롤 위치
+    Indicators, // 벨, 백그라운드 활동, 녹화/출력 기록, 입력 동기화 등
+}
+
+impl Segment {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "mode" => Some(Segment::Mode),
+            "process" => Some(Segment::Process),
+            "directory" | "cwd" => Some(Segment::Directory),
+            "git" => Some(Segment::Git),
+            "scroll" => Some(Segment::Scroll),
+            "indicators" => Some(Segment::Indicators),
+            _ => None,
+        }
+    }
+}
+
+/// 구역 배치 (둘 다 비면 상태 표시줄을 그리지 않음)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusLayout {
+    pub left: Vec<Segment>,
+    pub right: Vec<Segment>,
+}
+
+impl Default for StatusLayout {
+    fn default() -> Self {
+        Self {
+            left: vec![Segment::Mode, Segment::Process, Segment::Directory, Segment::Git],
+            right: vec![Segment::Scroll, Segment::Indicators],
+        }
+    }
+}
+
+impl StatusLayout {
+    /// `mode process directory git | scroll indicators` 형식 (쉼표로 나눠도 됨), `off`면 끄기
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if matches!(value.to_ascii_lowercase().as_str(), "off" | "none" | "false") {
+            return Some(Self { left: Vec::new(), right: Vec::new() });
+        }
+        let segments = |part: &str| {
+            part.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|name| !name.is_empty())
+                .map(Segment::parse)
+                .collect::<Option<Vec<_>>>()
+        };
+        let (left, right) = value.split_once('|').unwrap_or((value, ""));
+        if right.contains('|') {
+            return None;
+        }
+        Some(Self { left: segments(left)?, right: segments(right)? })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.left.is_empty() || !self.right.is_empty()
+    }
+}
+
+/// 한 프레임의 구역별 내용 (None이거나 비어 있으면 그 구역은 생략)
+#[derive(Debug, Default)]
+pub struct StatusValues {
+    pub mode: Option<String>,
+    pub process: Option<String>,
+    pub directory: Option<String>,
+    pub git: Option<String>,
+    pub scroll: Option<String>,
+    pub indicators: Vec<String>,
+}
+
+impl StatusValues {
+    fn text(&self, segment: Segment) -> Option<String> {
+        match segment {
+            Segment::Mode => self.mode.clone(),
+            Segment::Process => self.process.clone(),
+            Segment::Directory => self.directory.clone(),
+            Segment::Git => self.git.as_ref().map(|branch| format!("⎇ {}", branch)),
+            Segment::Scroll => self.scroll.clone(),
+            Segment::Indicators => (!self.indicators.is_empty()).then(|| self.indicators.join(" ")),
+        }
+    }
+
+    /// 상태 표시줄을 껐을 때 테두리 제목에 붙일 모드 안내와 알림 표시
+    pub fn title_suffix(&self) -> String {
+        self.mode.iter().chain(&self.indicators).map(|text| format!(" [{}]", text)).collect()
+    }
+}
+
+/// 상태 표시줄 한 줄 (너비 `width`) - 오른쪽 구역은 왼쪽과 겹치지 않을 때만 표시
+pub fn status_line(layout: &StatusLayout, values: &StatusValues, width: u16) -> Line<'static> {
+    let spans = |segments: &[Segment]| {
+        let mut spans = Vec::new();
+        for (segment, text) in segments.iter().filter_map(|&segment| values.text(segment).map(|text| (segment, text))) {
+            if !spans.is_empty() {
+                spans.push(Span::raw(SEPARATOR));
+            }
+            spans.push(match segment {
+                Segment::Mode => Span::styled(format!(" {} ", text), mode_style()),
+                _ => Span::raw(text),
+            });
+        }
+        spans
+    };
+    let mut left = vec![Span::raw(" ")];
+    left.extend(spans(&layout.left));
+    let mut right = spans(&layout.right);
+    right.push(Span::raw(" "));
+
+    let span_width = |spans: &[Span]| spans.iter().map(|span| columns::line_width(&span.content)).sum::<usize>();
+    let gap = (width as usize).checked_sub(span_width(&left) + span_width(&right));
+    match gap {
+        Some(gap) if right.len() > 1 => {
+            left.push(Span::raw(" ".repeat(gap)));
+            left.extend(right);
+        }
+        _ => {}
+    }
+    Line::from(left)
+}
+
+/// 상태 표시줄 스타일
+pub fn bar_style() -> Style {
+    Style::default().bg(Color::DarkGray).fg(Color::White)
+}
+
+fn mode_style() -> Style {
+    Style::default().bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD)
+}
+
+/// 스크롤 위치 (`전체`/`맨 위`/`맨 아래` 또는 백분율과 맨 아래에서 올라간 줄 수)
+pub fn scroll_position(state: &ScrollState) -> String {
+    let max_offset = state.max_offset();
+    if max_offset == 0 {
+        "전체".to_string()
+    } else if state.offset == 0 {
+        "맨 아래".to_string()
+    } else if state.offset >= max_offset {
+        format!("맨 위 (↑{})", state.offset)
+    } else {
+        format!("{}% (↑{})", (max_offset - state.offset) * 100 / max_offset, state.offset)
+    }
+}
+
+/// `directory`가 속한 git 저장소의 현재 브랜치 (브랜치가 아니면 커밋 앞 7자리)
+/// `.git` 파일(워크트리, 서브모듈)의 `gitdir:`도 따라감
+pub fn git_branch(directory: &Path) -> Option<String> {
+    let git_dir = directory.ancestors().find_map(|ancestor| {
+        let dot_git = ancestor.join(".git");
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+        let link = std::fs::read_to_string(&dot_git).ok()?;
+        link.trim().strip_prefix("gitdir:").map(|path| ancestor.join(path.trim()))
+    })?;
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            Some(reference.strip_prefix("refs/heads/").unwrap_or(reference).to_string())
+        }
+        None => head.get(..7).map(str::to_string),
+    }
+}
+
+/// 프로세스 ID의 실행 파일 이름
+pub fn process_name(pid: u32) -> Option<String> {
+    use sysinfo::{Pid, ProcessRefreshKind, System};
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+    system.process(pid).map(|process| process.name().to_string())
+}
+
+/// 세션별로 캐시한 프로세스 이름과 git 브랜치
+#[derive(Debug, Default)]
+pub struct StatusCache {
+    checked: Option<Instant>,
+    directory: Option<PathBuf>,
+    pub process: Option<String>,
+    pub git: Option<String>,
+}
+
+impl StatusCache {
+    /// 확인한 지 `REFRESH_INTERVAL`이 지났거나 작업 디렉토리가 바뀌었으면 다시 확인
+    pub fn refresh(&mut self, now: Instant, directory: Option<&Path>, process: impl FnOnce() -> Option<String>) {
+        let fresh = self.checked.is_some_and(|checked| now.duration_since(checked) < REFRESH_INTERVAL);
+        if fresh && self.directory.as_deref() == directory {
+            return;
+        }
+        self.checked = Some(now);
+        self.directory = directory.map(Path::to_path_buf);
+        self.process = process();
+        self.git = directory.and_then(git_branch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        assert_eq!(StatusLayout::parse("mode process directory git | scroll indicators"), Some(StatusLayout::default()));
+        assert_eq!(
+            StatusLayout::parse("git,cwd"),
+            Some(StatusLayout { left: vec![Segment::Git, Segment::Directory], right: Vec::new() }),
+        );
+        assert!(!StatusLayout::parse("off").unwrap().is_enabled());
+        assert_eq!(StatusLayout::parse("mode | clock"), None);
+        assert_eq!(StatusLayout::parse("mode | git | scroll"), None);
+    }
+
+    #[test]
+    fn test_status_line_aligns_right_segments() {
+        let values = StatusValues {
+            process: Some("vim".to_string()),
+            git: Some("main".to_string()),
+            scroll: Some("맨 아래".to_string()),
+            ..Default::default()
+        };
+        let line = status_line(&StatusLayout::default(), &values, 30);
+        assert_eq!(text(&line), " vim │ ⎇ main         맨 아래 ");
+        assert_eq!(columns::line_width(&text(&line)), 30);
+
+        // 자리가 모자라면 오른쪽 구역 생략
+        assert_eq!(text(&status_line(&StatusLayout::default(), &values, 15)), " vim │ ⎇ main");
+    }
+
+    #[test]
+    fn test_scroll_position() {
+        let state = |offset, total| ScrollState { offset, past_end: 0, past_end_margin: 0, total, viewport: 10 };
+        assert_eq!(scroll_position(&state(0, 10)), "전체");
+        assert_eq!(scroll_position(&state(0, 110)), "맨 아래");
+        assert_eq!(scroll_position(&state(25, 110)), "75% (↑25)");
+        assert_eq!(scroll_position(&state(100, 110)), "맨 위 (↑100)");
+    }
+
+    #[test]
+    fn test_git_branch() {
+        let root = std::env::temp_dir().join(format!("rust-terminal-status-{}", std::process::id()));
+        let nested = root.join("repo").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(root.join("repo").join(".git")).unwrap();
+        std::fs::write(root.join("repo").join(".git").join("HEAD"), "ref: refs/heads/feature/status\n").unwrap();
+        assert_eq!(git_branch(&nested).as_deref(), Some("feature/status"));
+
+        // 워크트리: `.git` 파일이 실제 git 디렉토리를 가리킴
+        let worktree = root.join("worktree");
+        std::fs::create_dir_all(root.join("meta")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(root.join("meta").join("HEAD"), "0123456789abcdef\n").unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: ../meta\n").unwrap();
+        assert_eq!(git_branch(&worktree).as_deref(), Some("0123456"));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
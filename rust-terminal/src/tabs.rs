@@ -0,0 +1,151 @@
+//! 탭 목록 모듈
+//! 활성 탭은 App이 직접 보유하고 (필드 접근을 그대로 유지하기 위해),
+//! 여기서는 활성 탭의 왼쪽/오른쪽에 있는 백그라운드 탭만 순서대로 보관
+//! 탭 전환은 `activate` 콜백으로 App의 활성 탭 상태와 보관 중인 탭을 맞바꾸는 방식
+
+/// 백그라운드 탭 목록 (활성 탭 위치 = 왼쪽 탭 수)
+#[derive(Debug)]
+pub struct Tabs<T> {
+    left: Vec<T>,  // 활성 탭 왼쪽 탭 (앞쪽부터)
+    right: Vec<T>, // 활성 탭 오른쪽 탭 (가까운 것부터)
+}
+
+impl<T> Default for Tabs<T> {
+    fn default() -> Self {
+        Self { left: Vec::new(), right: Vec::new() }
+    }
+}
+
+impl<T> Tabs<T> {
+    /// 활성 탭을 포함한 전체 탭 수
+    #[allow(clippy::len_without_is_empty)] // 활성 탭이 항상 있어 비어 있을 수 없음
+    pub fn len(&self) -> usize {
+        self.left.len() + 1 + self.right.len()
+    }
+
+    /// 활성 탭 위치 (0부터)
+    pub fn active_index(&self) -> usize {
+        self.left.len()
+    }
+
+    /// 새 탭을 활성 탭 오른쪽에 열고 활성화
+    /// `activate`는 넘겨받은 탭과 현재 활성 탭을 맞바꿈 (호출 후 이전 활성 탭이 들어 있음)
+    pub fn open(&mut self, mut tab: T, activate: impl FnOnce(&mut T)) {
+        activate(&mut tab);
+        self.left.push(tab);
+    }
+
+    /// 활성 탭 오른쪽 끝에 백그라운드 탭 추가 (레이아웃 복원용)
+    pub fn push_back(&mut self, tab: T) {
+        self.right.push(tab);
+    }
+
+    /// 활성 탭을 닫고 오른쪽 탭(없으면 왼쪽 탭)을 활성화
+    /// 닫은 탭을 반환하며, 마지막 탭이면 닫지 않고 None
+    pub fn close(&mut self, activate: impl FnOnce(&mut T)) -> Option<T> {
+        let mut next = if self.right.is_empty() {
+            self.left.pop()?
+        } else {
+            self.right.remove(0)
+        };
+        activate(&mut next);
+        Some(next)
+    }
+
+    /// 다음 탭으로 전환 (마지막 탭이면 처음으로)
+    pub fn next(&mut self, activate: impl FnOnce(&mut T)) {
+        if !self.right.is_empty() {
+            let mut next = self.right.remove(0);
+            activate(&mut next);
+            self.left.push(next);
+        } else if !self.left.is_empty() {
+            let mut first = self.left.remove(0);
+            activate(&mut first);
+            self.right = self.left.drain(..).chain(std::iter::once(first)).collect();
+        }
+    }
+
+    /// 이전 탭으로 전환 (첫 탭이면 마지막으로)
+    pub fn prev(&mut self, activate: impl FnOnce(&mut T)) {
+        if let Some(mut previous) = self.left.pop() {
+            activate(&mut previous);
+            self.right.insert(0, previous);
+        } else if let Some(mut last) = self.right.pop() {
+            activate(&mut last);
+            self.left = std::iter::once(last).chain(self.right.drain(..)).collect();
+        }
+    }
+
+    /// 활성 탭 왼쪽과 오른쪽의 백그라운드 탭 (화면 순서)
+    pub fn background(&self) -> (&[T], &[T]) {
+        (&self.left, &self.right)
+    }
+
+    /// 백그라운드 탭 (이벤트 처리용)
+    pub fn background_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.left.iter_mut().chain(self.right.iter_mut())
+    }
+
+    /// 조건에 맞는 백그라운드 탭 제거 (셸이 종료된 탭 정리용)
+    pub fn retain_background(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        self.left.retain(&mut keep);
+        self.right.retain(&mut keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap_with(active: &mut u32) -> impl FnOnce(&mut u32) + '_ {
+        move |tab| std::mem::swap(tab, active)
+    }
+
+    fn order(tabs: &Tabs<u32>, active: u32) -> Vec<u32> {
+        let (left, right) = tabs.background();
+        left.iter().copied().chain(std::iter::once(active)).chain(right.iter().copied()).collect()
+    }
+
+    #[test]
+    fn test_open_cycle_and_close_tabs() {
+        let mut tabs = Tabs::default();
+        let mut active = 1;
+        assert_eq!(tabs.len(), 1);
+
+        tabs.open(2, swap_with(&mut active));
+        tabs.open(3, swap_with(&mut active));
+        assert_eq!((active, tabs.active_index()), (3, 2));
+        assert_eq!(order(&tabs, active), vec![1, 2, 3]);
+
+        // 끝에서 다음 탭은 처음으로, 처음에서 이전 탭은 끝으로
+        tabs.next(swap_with(&mut active));
+        assert_eq!((active, tabs.active_index()), (1, 0));
+        tabs.prev(swap_with(&mut active));
+        assert_eq!((active, tabs.active_index()), (3, 2));
+        tabs.prev(swap_with(&mut active));
+        assert_eq!(active, 2);
+        assert_eq!(order(&tabs, active), vec![1, 2, 3]);
+
+        // 닫으면 오른쪽 탭, 오른쪽이 없으면 왼쪽 탭 활성화
+        assert_eq!(tabs.close(swap_with(&mut active)), Some(2));
+        assert_eq!(active, 3);
+        assert_eq!(tabs.close(swap_with(&mut active)), Some(3));
+        assert_eq!(active, 1);
+        assert_eq!(tabs.close(swap_with(&mut active)), None);
+        assert_eq!(tabs.len(), 1);
+    }
+
+    #[test]
+    fn test_background_tabs() {
+        let mut tabs = Tabs::default();
+        let mut active = 1;
+        tabs.push_back(2);
+        tabs.push_back(3);
+        assert_eq!(order(&tabs, active), vec![1, 2, 3]);
+
+        tabs.next(swap_with(&mut active));
+        tabs.background_mut().for_each(|tab| *tab *= 10);
+        tabs.retain_background(|&tab| tab != 30);
+        assert_eq!(order(&tabs, active), vec![10, 2]);
+    }
+}
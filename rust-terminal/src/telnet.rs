@@ -0,0 +1,382 @@
+//! 네트워크(telnet/raw TCP) 연결 모듈
+//! `--connect 호스트:포트`처럼 탭을 PTY 대신 TCP 소켓에 연결 (네트워크 콘솔, BBS 등)
+//! 소켓은 시리얼 장치와 같은 스트림 연결(`transport::StreamTransport`)로 IO 스레드에 붙음
+//!
+//! telnet이면 옵션 협상을 처리 - 창 크기(NAWS), 터미널 종류(TTYPE), 서버 에코, SGA, BINARY만 받아들이고 나머지는 거절
+//! 대상: `호스트:포트`/`telnet://호스트[:포트]`(telnet, 기본 포트 23), `tcp://호스트:포트`(협상 없이 그대로 주고받음)
+
+use crate::transport::StreamProtocol;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 연결을 기다리는 최대 시간 (연결하는 동안 화면이 멈추므로 짧게)
+#[cfg(unix)]
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// telnet 기본 포트
+const TELNET_PORT: u16 = 23;
+
+/// 서버에 알리는 터미널 종류 (`TERM`과 같은 값)
+const TERMINAL_TYPE: &[u8] = b"XTERM-256COLOR";
+
+/// 하위 협상 내용의 최대 길이 (이보다 긴 내용은 버림)
+const MAX_SUBNEGOTIATION: usize = 256;
+
+// telnet 명령 (RFC 854)
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+// telnet 옵션
+const BINARY: u8 = 0;
+const ECHO: u8 = 1;
+const SGA: u8 = 3;
+const TTYPE: u8 = 24;
+const NAWS: u8 = 31;
+
+const TTYPE_IS: u8 = 0;
+const TTYPE_SEND: u8 = 1;
+
+/// 이쪽에서 켜는 옵션과 서버가 켜도 되는 옵션
+const LOCAL_OPTIONS: &[u8] = &[BINARY, SGA, TTYPE, NAWS];
+const REMOTE_OPTIONS: &[u8] = &[BINARY, ECHO, SGA];
+
+/// 연결 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Protocol {
+    #[default]
+    Telnet,
+    Raw, // 협상 없이 바이트를 그대로 주고받음
+}
+
+/// 접속 대상 (레이아웃에도 그대로 저장)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkTarget {
+    pub host: String,
+    pub port: u16,
+    pub protocol: Protocol,
+}
+
+impl NetworkTarget {
+    /// `[telnet://|tcp://]호스트[:포트]` 파싱 (IPv6 주소는 `[::1]:23`)
+    pub fn parse(target: &str) -> Result<Self> {
+        let target = target.trim();
+        let (protocol, address) = if let Some(address) = target.strip_prefix("telnet://") {
+            (Protocol::Telnet, address)
+        } else if let Some(address) = target.strip_prefix("tcp://").or_else(|| target.strip_prefix("raw://")) {
+            (Protocol::Raw, address)
+        } else {
+            (Protocol::Telnet, target)
+        };
+        let (host, port) = match address.strip_prefix('[') {
+            Some(bracketed) => {
+                let (host, rest) = bracketed.split_once(']').ok_or_else(|| anyhow!("잘못된 접속 대상: {}", target))?;
+                match rest.strip_prefix(':') {
+                    Some(port) => (host, Some(port)),
+                    None if rest.is_empty() => (host, None),
+                    None => bail!("잘못된 접속 대상: {}", target),
+                }
+            }
+            None => match address.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (address, None),
+            },
+        };
+        if host.is_empty() {
+            bail!("잘못된 접속 대상: {}", target);
+        }
+        let port = match (port, protocol) {
+            (Some(port), _) => port.parse().map_err(|_| anyhow!("잘못된 포트: {}", port))?,
+            (None, Protocol::Telnet) => TELNET_PORT,
+            (None, Protocol::Raw) => bail!("tcp 연결에는 포트가 필요함: {}", target),
+        };
+        Ok(Self { host: host.to_string(), port, protocol })
+    }
+
+    /// 탭 이름 등에 표시할 이름 (`host:23`, raw TCP는 `tcp://host:7000`)
+    pub fn label(&self) -> String {
+        let host = if self.host.contains(':') { format!("[{}]", self.host) } else { self.host.clone() };
+        match self.protocol {
+            Protocol::Telnet => format!("{}:{}", host, self.port),
+            Protocol::Raw => format!("tcp://{}:{}", host, self.port),
+        }
+    }
+
+    /// 접속해서 non-blocking 소켓을 파일로 반환 (주소가 여럿이면 차례로 시도)
+    #[cfg(unix)]
+    pub fn connect(&self) -> Result<std::fs::File> {
+        use std::net::{TcpStream, ToSocketAddrs};
+
+        let addresses = (self.host.as_str(), self.port).to_socket_addrs()
+            .map_err(|e| anyhow!("주소를 찾을 수 없음 ({}): {}", self.label(), e))?;
+        let mut last_error = None;
+        for address in addresses {
+            match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+                Ok(stream) => {
+                    stream.set_nodelay(true)?; // 키 입력을 모아 보내지 않음
+                    stream.set_nonblocking(true)?;
+                    return Ok(std::fs::File::from(std::os::fd::OwnedFd::from(stream)));
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        match last_error {
+            Some(e) => bail!("접속 실패 ({}): {}", self.label(), e),
+            None => bail!("주소를 찾을 수 없음: {}", self.label()),
+        }
+    }
+
+    /// 네트워크 연결은 Unix에서만 지원
+    #[cfg(not(unix))]
+    pub fn connect(&self) -> Result<std::fs::File> {
+        bail!("이 플랫폼에서는 네트워크 연결을 지원하지 않음")
+    }
+}
+
+/// 받은 바이트를 해석하는 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Data,
+    Iac,             // IAC 다음
+    Negotiate(u8),   // WILL/WONT/DO/DONT 다음 (옵션 대기)
+    Sub,             // SB 내용
+    SubIac,          // SB 내용 중 IAC 다음
+}
+
+/// telnet 프로토콜 처리 - 받은 데이터에서 명령을 걸러 내고, 보낼 데이터를 이스케이프
+/// 협상 응답은 `reply`로 넘긴 버퍼에 쌓음 (호출한 쪽이 소켓에 씀)
+#[derive(Debug, Clone)]
+pub struct Telnet {
+    state: State,
+    local: Vec<u8>,        // 켜진 이쪽 옵션
+    remote: Vec<u8>,       // 켜진 서버 옵션
+    subnegotiation: Vec<u8>,
+    after_cr: bool,        // 바로 앞에 받은 데이터가 CR (뒤따르는 NUL은 버림)
+    size: Option<(u16, u16)>, // 마지막 창 크기 (열, 줄)
+}
+
+impl Default for Telnet {
+    fn default() -> Self {
+        Self {
+            state: State::Data,
+            local: Vec::new(),
+            remote: Vec::new(),
+            subnegotiation: Vec::new(),
+            after_cr: false,
+            size: None,
+        }
+    }
+}
+
+impl StreamProtocol for Telnet {
+    /// 받은 바이트에서 telnet 명령을 걸러 내고 데이터만 앞쪽에 남김 - 남은 데이터 길이 반환
+    /// 명령이 읽기 경계에서 잘려도 다음 호출에서 이어서 처리
+    fn receive(&mut self, buf: &mut [u8], reply: &mut Vec<u8>) -> usize {
+        let mut len = 0;
+        for index in 0..buf.len() {
+            let byte = buf[index];
+            match self.state {
+                State::Data if byte == IAC => self.state = State::Iac,
+                State::Data => {
+                    // NVT에서 CR 뒤의 NUL은 줄 끝 표시일 뿐 (BINARY가 아닐 때)
+                    let skip = byte == 0 && self.after_cr && !self.remote.contains(&BINARY);
+                    self.after_cr = byte == b'\r';
+                    if !skip {
+                        buf[len] = byte;
+                        len += 1;
+                    }
+                }
+                State::Iac => {
+                    self.state = match byte {
+                        IAC => {
+                            buf[len] = IAC;
+                            len += 1;
+                            self.after_cr = false;
+                            State::Data
+                        }
+                        WILL | WONT | DO | DONT => State::Negotiate(byte),
+                        SB => {
+                            self.subnegotiation.clear();
+                            State::Sub
+                        }
+                        _ => State::Data, // NOP, GA 등은 무시
+                    };
+                }
+                State::Negotiate(command) => {
+                    self.negotiate(command, byte, reply);
+                    self.state = State::Data;
+                }
+                State::Sub if byte == IAC => self.state = State::SubIac,
+                State::Sub => self.push_subnegotiation(byte),
+                State::SubIac => match byte {
+                    SE => {
+                        self.subnegotiate(reply);
+                        self.state = State::Data;
+                    }
+                    IAC => {
+                        self.push_subnegotiation(IAC);
+                        self.state = State::Sub;
+                    }
+                    // 잘못된 순서 - 하위 협상을 버리고 데이터로 돌아감
+                    _ => self.state = State::Data,
+                },
+            }
+        }
+        len
+    }
+
+    /// 보낼 데이터를 telnet 형식으로 (IAC는 두 번, BINARY가 아니면 CR 뒤에 NUL)
+    fn encode(&self, data: &[u8], out: &mut Vec<u8>) {
+        let binary = self.local.contains(&BINARY);
+        for &byte in data {
+            out.push(byte);
+            match byte {
+                IAC => out.push(IAC),
+                b'\r' if !binary => out.push(0),
+                _ => {}
+            }
+        }
+    }
+
+    /// 창 크기 변경 - NAWS가 켜져 있으면 서버에 알림
+    fn resize(&mut self, columns: u16, lines: u16, reply: &mut Vec<u8>) {
+        if self.size == Some((columns, lines)) {
+            return;
+        }
+        self.size = Some((columns, lines));
+        if self.local.contains(&NAWS) {
+            self.send_window_size(reply);
+        }
+    }
+}
+
+impl Telnet {
+    /// WILL/WONT/DO/DONT 처리 - 상태가 바뀔 때만 응답해서 협상이 되풀이되지 않게 함 (RFC 1143)
+    fn negotiate(&mut self, command: u8, option: u8, reply: &mut Vec<u8>) {
+        match command {
+            WILL if REMOTE_OPTIONS.contains(&option) => {
+                if !self.remote.contains(&option) {
+                    self.remote.push(option);
+                    reply.extend([IAC, DO, option]);
+                }
+            }
+            WILL => reply.extend([IAC, DONT, option]),
+            WONT => {
+                if let Some(index) = self.remote.iter().position(|&enabled| enabled == option) {
+                    self.remote.remove(index);
+                    reply.extend([IAC, DONT, option]);
+                }
+            }
+            DO if LOCAL_OPTIONS.contains(&option) => {
+                if !self.local.contains(&option) {
+                    self.local.push(option);
+                    reply.extend([IAC, WILL, option]);
+                    if option == NAWS {
+                        self.send_window_size(reply);
+                    }
+                }
+            }
+            DO => reply.extend([IAC, WONT, option]),
+            _ => {
+                if let Some(index) = self.local.iter().position(|&enabled| enabled == option) {
+                    self.local.remove(index);
+                    reply.extend([IAC, WONT, option]);
+                }
+            }
+        }
+    }
+
+    fn push_subnegotiation(&mut self, byte: u8) {
+        if self.subnegotiation.len() < MAX_SUBNEGOTIATION {
+            self.subnegotiation.push(byte);
+        }
+    }
+
+    /// 하위 협상 처리 (터미널 종류 요청만 응답)
+    fn subnegotiate(&mut self, reply: &mut Vec<u8>) {
+        if self.subnegotiation == [TTYPE, TTYPE_SEND] && self.local.contains(&TTYPE) {
+            reply.extend([IAC, SB, TTYPE, TTYPE_IS]);
+            reply.extend_from_slice(TERMINAL_TYPE);
+            reply.extend([IAC, SE]);
+        }
+    }
+
+    /// NAWS 하위 협상 (크기를 아직 모르면 다음 resize에서 보냄)
+    fn send_window_size(&self, reply: &mut Vec<u8>) {
+        let Some((columns, lines)) = self.size else {
+            return;
+        };
+        reply.extend([IAC, SB, NAWS]);
+        for byte in columns.to_be_bytes().into_iter().chain(lines.to_be_bytes()) {
+            reply.push(byte);
+            if byte == IAC {
+                reply.push(IAC);
+            }
+        }
+        reply.extend([IAC, SE]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        let target = NetworkTarget::parse("bbs.example.org").unwrap();
+        assert_eq!((target.host.as_str(), target.port, target.protocol), ("bbs.example.org", 23, Protocol::Telnet));
+        assert_eq!(NetworkTarget::parse("telnet://10.0.0.5:2323").unwrap().label(), "10.0.0.5:2323");
+        let target = NetworkTarget::parse("tcp://[::1]:7000").unwrap();
+        assert_eq!((target.host.as_str(), target.protocol), ("::1", Protocol::Raw));
+        assert_eq!(target.label(), "tcp://[::1]:7000");
+
+        assert!(NetworkTarget::parse("tcp://console").is_err());
+        assert!(NetworkTarget::parse("host:telnet").is_err());
+        assert!(NetworkTarget::parse(":23").is_err());
+        assert!(NetworkTarget::parse("[::1").is_err());
+    }
+
+    #[test]
+    fn test_negotiation_and_data() {
+        let mut telnet = Telnet::default();
+        let mut reply = Vec::new();
+        telnet.resize(80, 24, &mut reply);
+        assert!(reply.is_empty()); // NAWS를 켜기 전에는 크기를 보내지 않음
+
+        // 서버 에코/SGA는 받아들이고, 모르는 옵션(LINEMODE 34)은 거절
+        let mut buf = [b'h', IAC, WILL, ECHO, b'i', IAC, WILL, SGA, IAC, DO, 34, IAC, IAC, b'\r', 0, b'\n'];
+        let len = telnet.receive(&mut buf, &mut reply);
+        assert_eq!(&buf[..len], [b'h', b'i', IAC, b'\r', b'\n']);
+        assert_eq!(reply, [IAC, DO, ECHO, IAC, DO, SGA, IAC, WONT, 34]);
+        assert_eq!(telnet.remote, [ECHO, SGA]);
+
+        // 같은 요청을 다시 받으면 응답하지 않음 (명령이 읽기 경계에서 잘려도 이어서 처리)
+        reply.clear();
+        let mut buf = [IAC, WILL];
+        assert_eq!(telnet.receive(&mut buf, &mut reply), 0);
+        let mut buf = [ECHO, IAC, DO, NAWS];
+        assert_eq!(telnet.receive(&mut buf, &mut reply), 0);
+        assert_eq!(reply, [IAC, WILL, NAWS, IAC, SB, NAWS, 0, 80, 0, 24, IAC, SE]);
+
+        // 창 크기가 바뀌면 알림 (255는 두 번)
+        reply.clear();
+        telnet.resize(255, 50, &mut reply);
+        assert_eq!(reply, [IAC, SB, NAWS, 0, IAC, IAC, 0, 50, IAC, SE]);
+
+        // 터미널 종류 요청
+        reply.clear();
+        let mut buf = [IAC, DO, TTYPE, IAC, SB, TTYPE, TTYPE_SEND, IAC, SE];
+        assert_eq!(telnet.receive(&mut buf, &mut reply), 0);
+        assert!(reply.starts_with(&[IAC, WILL, TTYPE, IAC, SB, TTYPE, TTYPE_IS]));
+        assert!(reply.ends_with(b"XTERM-256COLOR\xff\xf0"));
+
+        let mut out = Vec::new();
+        telnet.encode(&[b'a', IAC, b'\r'], &mut out);
+        assert_eq!(out, [b'a', IAC, IAC, b'\r', 0]);
+    }
+}
@@ -1,22 +1,38 @@
 use alacritty_terminal::{
     Term,
-    event::{Event as AlacTermEvent, EventListener},
-    event_loop::{EventLoop, Notifier},
-    grid::Dimensions,
-    term::Config,
-    tty::{self, Options as PtyOptions, Shell as AlacShell},
+    event::{Event as AlacTermEvent, EventListener, OnResize, WindowSize},
+    event_loop::{EventLoop, Msg, Notifier},
+    grid::{Dimensions, Row, Scroll},
+    term::{Config, TermDamage, cell::{Cell, Flags, Hyperlink}},
+    vte::ansi::Color as AnsiColor,
+    tty::{self, ChildEvent, EventedPty, Options as PtyOptions, Shell as AlacShell},
 };
 use anyhow::{Result, bail};
 use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
 use std::{
     collections::HashMap,
-    path::PathBuf,
-    sync::Arc,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
 };
 use alacritty_terminal::sync::FairMutex;
+use crate::env_policy::EnvPolicy;
+use crate::keys::{self, KeyboardMode};
+use crate::mouse::MouseReportMode;
+use crate::osc::{OscScanner, OscTrackingPty, PromptMarkKind, ShellState};
+use crate::remote::{self, SshHost};
+use crate::serial::SerialSettings;
+use crate::telnet::{NetworkTarget, Protocol, Telnet};
+use crate::transport::{self, StreamProtocol, Transport};
+use crate::asciicast::Recorder;
+use crate::output_log::{self, OutputLog, SharedOutputSinks};
+use serde::{Deserialize, Serialize};
 
 /// 터미널 크기와 경계 정보
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TerminalBounds {
     pub num_lines: usize,
     pub num_cols: usize,
@@ -45,37 +61,325 @@ impl Dimensions for TerminalBounds {
     }
 }
 
-impl From<TerminalBounds> for alacritty_terminal::event::WindowSize {
-    fn from(bounds: TerminalBounds) -> Self {
-        Self {
+/// 셀 하나의 픽셀 크기 (PTY 크기 보고의 픽셀 값 계산에 사용)
+/// TUI 앱은 실제 글꼴 크기를 알 수 없으므로 설정 값을 사용
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for CellSize {
+    fn default() -> Self {
+        Self { width: 8, height: 16 }
+    }
+}
+
+impl CellSize {
+    /// `8x16` 형식 파싱
+    pub fn parse(value: &str) -> Option<Self> {
+        let (width, height) = value.trim().split_once(['x', 'X'])?;
+        let cell_size = Self {
+            width: width.trim().parse().ok()?,
+            height: height.trim().parse().ok()?,
+        };
+        (cell_size.width > 0 && cell_size.height > 0).then_some(cell_size)
+    }
+
+    /// 터미널 크기와 셀 크기로 PTY에 보고할 창 크기 계산
+    /// (PTY 생성과 크기 변경 모두 이 함수를 거쳐 같은 셀 크기를 사용)
+    pub fn window_size(&self, bounds: TerminalBounds) -> WindowSize {
+        WindowSize {
             num_lines: bounds.num_lines as u16,
             num_cols: bounds.num_cols as u16,
-            cell_width: 8,  // 기본값
-            cell_height: 16, // 기본값
+            cell_width: self.width,
+            cell_height: self.height,
         }
     }
 }
 
 /// Zed의 ZedListener와 동일한 역할
-pub struct TerminalListener(pub UnboundedSender<AlacTermEvent>);
+/// PTY 출력이 그리드에 반영될 때(Wakeup)마다 내용 버전을 증가시키고, 이벤트가 생기면 메인 루프를 깨움
+pub struct TerminalListener {
+    events_tx: UnboundedSender<AlacTermEvent>,
+    content_version: Arc<AtomicU64>,
+}
+
+impl TerminalListener {
+    pub fn new(events_tx: UnboundedSender<AlacTermEvent>, content_version: Arc<AtomicU64>) -> Self {
+        Self { events_tx, content_version }
+    }
+}
 
 impl EventListener for TerminalListener {
     fn send_event(&self, event: AlacTermEvent) {
-        let _ = self.0.unbounded_send(event);
+        if let AlacTermEvent::Wakeup = event {
+            self.content_version.fetch_add(1, Ordering::Relaxed);
+        }
+        let _ = self.events_tx.unbounded_send(event);
+        // 입력을 기다리며 잠든 메인 루프 깨우기
+        crate::wakeup::wake();
+    }
+}
+
+/// PTY로 메시지를 보내는 통로 (테스트에서는 IO 스레드 없는 TestPty 사용)
+enum PtySender {
+    EventLoop(Notifier),
+    /// IO 스레드 없이 호출한 쪽이 `pump_output`으로 출력을 읽는 연결 (`TerminalBuilder::pumped`)
+    Pumped(Box<PumpedPty>),
+    /// PTY 없이 출력만 반영하는 터미널 (녹화 재생용, 보낸 입력은 버림)
+    Detached(Box<OutputBatcher>),
+    #[cfg(test)]
+    Test(Box<TestPty>),
+}
+
+impl PtySender {
+    fn send(&mut self, msg: Msg) -> Result<()> {
+        match self {
+            PtySender::EventLoop(notifier) => notifier.0.send(msg)?,
+            PtySender::Pumped(pumped) => match msg {
+                Msg::Input(data) => {
+                    pumped.pending_input.extend_from_slice(&data);
+                    pumped.flush_input()?;
+                }
+                Msg::Resize(window_size) => pumped.pty.on_resize(window_size),
+                Msg::Shutdown => {}
+            },
+            PtySender::Detached(_) => {}
+            #[cfg(test)]
+            PtySender::Test(pty) => pty.sent.push(msg),
+        }
+        Ok(())
+    }
+}
+
+/// 테스트용 PTY - 보낸 메시지를 기록하고, 자식 프로세스 출력은 버퍼에 쌓아 두었다가
+/// `Terminal::process_pending` 호출 시 호출한 스레드에서 그리드에 반영 (IO 스레드 타이밍에 의존하지 않음)
+#[cfg(test)]
+struct TestPty {
+    sent: Vec<Msg>,
+    pending_output: Vec<u8>,
+    batcher: OutputBatcher, // 출력 조각 사이의 파서/OSC 추적 상태 유지
+}
+
+/// IO 스레드 없이 호출한 쪽이 직접 읽고 쓰는 연결 (PTY, 시리얼 장치, 소켓 모두 non-blocking)
+struct PumpedPty {
+    pty: Transport,
+    batcher: OutputBatcher,
+    listener: TerminalListener, // 셸 종료를 EventLoop와 같은 이벤트로 알림
+    pending_input: Vec<u8>,     // 연결이 아직 받지 못한 입력
+    exited: bool,               // 셸 종료를 이미 알렸음
+}
+
+impl PumpedPty {
+    /// 남은 입력을 쓸 수 있는 만큼 씀 (나머지는 다음 입력이나 `pump_output`에서)
+    fn flush_input(&mut self) -> io::Result<()> {
+        while !self.pending_input.is_empty() {
+            match self.pty.write(&self.pending_input) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(written) => {
+                    self.pending_input.drain(..written);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `write_output`이 한 번에 그리드에 반영하는 최대 바이트 수
+const OUTPUT_BATCH_SIZE: usize = 4096;
+
+/// IO 스레드 없이 출력을 그리드에 반영하는 상태 (출력이 여러 번에 나뉘어 와도 이어서 처리)
+struct OutputBatcher {
+    parser: alacritty_terminal::vte::ansi::Processor, // 배치 사이의 파서 상태 유지
+    scanner: OscScanner,                              // OscTrackingPty와 같은 OSC 추적
+    buffer: Vec<u8>,                                  // `pump` 읽기 버퍼 (배치 크기)
+}
+
+impl OutputBatcher {
+    fn new() -> Self {
+        Self {
+            parser: Default::default(),
+            scanner: OscScanner::default(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// `reader`에서 최대 `batch_size`바이트 읽어 기록하고 그리드에 반영 (읽을 것이 없으면 0)
+    fn pump(
+        &mut self,
+        reader: &mut impl Read,
+        batch_size: usize,
+        term: &FairMutex<Term<TerminalListener>>,
+        shell_state: &Mutex<ShellState>,
+        output_sinks: &SharedOutputSinks,
+    ) -> io::Result<usize> {
+        // 읽기 버퍼를 잠시 꺼내 파서 상태와 따로 빌림
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.resize(batch_size.max(1), 0);
+        let result = match reader.read(&mut buffer) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(0),
+            result => result,
+        };
+        if let Ok(read @ 1..) = result {
+            output_log::record(output_sinks, &buffer[..read]);
+            self.apply(term, shell_state, &buffer[..read]);
+        }
+        self.buffer = buffer;
+        result
+    }
+
+    /// 출력 바이트를 그리드에 반영 (셸 상태 OSC 추적 포함)
+    fn apply(&mut self, term: &FairMutex<Term<TerminalListener>>, shell_state: &Mutex<ShellState>, bytes: &[u8]) {
+        self.scanner.scan(bytes, |payload, line_feeds| {
+            shell_state.lock().unwrap().handle_osc(payload, line_feeds);
+        });
+        shell_state.lock().unwrap().set_line_feeds(self.scanner.line_feeds());
+
+        let mut term = term.lock();
+        let parser = &mut self.parser;
+        for byte in bytes {
+            parser.advance(&mut *term, *byte);
+        }
+    }
+}
+
+/// 렌더링용 셀 (문자 + 색상 + 속성 + OSC 8 하이퍼링크)
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderableCell {
+    pub c: char,
+    pub fg: AnsiColor,
+    pub bg: AnsiColor,
+    pub flags: Flags,
+    pub hyperlink: Option<Hyperlink>, // 프로그램이 OSC 8로 지정한 링크
+}
+
+impl From<&Cell> for RenderableCell {
+    fn from(cell: &Cell) -> Self {
+        Self {
+            c: cell.c,
+            fg: cell.fg,
+            bg: cell.bg,
+            flags: cell.flags,
+            hyperlink: cell.hyperlink(),
+        }
+    }
+}
+
+/// 특정 내용 버전 이후 바뀐 화면 한 줄
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineDiff {
+    pub row: usize,                 // 화면 기준 행 번호
+    pub text: String,               // 새 내용 (`get_visible_content`와 같은 줄 문자열)
+    pub cells: Vec<RenderableCell>, // 새 셀 (색상/속성)
+}
+
+/// 스크롤 이동 목표
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollPosition {
+    /// 히스토리 맨 위
+    Top,
+    /// 최신 출력 (맨 아래)
+    Bottom,
+    /// 맨 아래 기준으로 위로 올라간 줄 수
+    Offset(usize),
+}
+
+/// 스크롤 상태
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollState {
+    pub offset: usize,          // 맨 아래 기준으로 위로 올라간 줄 수 (grid display offset)
+    pub past_end: usize,        // 맨 아래를 지나 더 내려간 빈 줄 수
+    pub past_end_margin: usize, // 맨 아래를 지나 내려갈 수 있는 최대 빈 줄 수
+    pub total: usize,           // 전체 줄 수 (히스토리 + 화면)
+    pub viewport: usize,        // 화면 줄 수
+}
+
+impl ScrollState {
+    /// 최대 스크롤 오프셋 (= 히스토리 줄 수)
+    pub fn max_offset(&self) -> usize {
+        self.total - self.viewport
+    }
+
+    /// 최대 스크롤 범위 (화면 첫 줄 행 번호의 최댓값, 맨 아래 빈 여백 포함)
+    pub fn max_scroll(&self) -> usize {
+        self.max_offset() + self.past_end_margin
+    }
+
+    /// 화면 첫 줄의 전체 버퍼 기준 행 번호 (맨 위 = 0)
+    pub fn first_visible_row(&self) -> usize {
+        self.max_offset() - self.offset + self.past_end
+    }
+
+    /// 화면 위쪽에 스크롤해서 볼 내용이 있는지
+    pub fn has_content_above(&self) -> bool {
+        self.first_visible_row() > 0
+    }
+
+    /// 화면 아래쪽에 스크롤해서 볼 내용이 있는지 (맨 아래 빈 여백은 내용이 아님)
+    pub fn has_content_below(&self) -> bool {
+        self.offset > 0
+    }
+}
+
+/// 스크롤백을 지우기 전에 저장한 히스토리 (지우기 되돌리기용, 오래된 줄부터)
+pub struct ScrollbackSnapshot {
+    rows: Vec<Row<Cell>>,
+}
+
+impl ScrollbackSnapshot {
+    /// 저장된 줄 수
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// 저장된 줄이 없는지
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
     }
 }
 
-/// Shell 타입 정의 (현재는 System만 사용)
-#[derive(Clone, Debug)]
+/// Shell 타입 정의 (설정의 셸 프로그램/인자에 따라 선택)
+/// `Ssh`는 시스템 `ssh`를 같은 PTY 경로로 실행하고, `Serial`/`Network`는 PTY 대신 장치/소켓을 같은 IO 스레드에 연결하므로
+/// 나머지 터미널 동작은 로컬 셸과 같음
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Shell {
     System,
-    #[allow(dead_code)]
     Program(String),
-    #[allow(dead_code)]
     WithArguments {
         program: String,
         args: Vec<String>,
     },
+    Ssh(SshHost),
+    Serial(SerialSettings), // 셸 대신 시리얼 장치에 연결
+    Network(NetworkTarget), // 셸 대신 telnet/TCP 소켓에 연결
+    /// 세션 데몬에서 실행 중인 셸 (`shell`은 데몬 세션이 없을 때 새로 실행할 셸)
+    Attached {
+        session: u64,
+        shell: Box<Shell>,
+    },
+}
+
+impl Shell {
+    /// 데몬 세션을 벗긴 실제 셸 (탭 이름, 다시 실행 등에 사용)
+    pub fn base(&self) -> &Shell {
+        match self {
+            Shell::Attached { shell, .. } => shell.base(),
+            shell => shell,
+        }
+    }
+
+    /// 명령 한 줄을 시스템 셸로 실행 (Unix는 `$SHELL -c`, Windows는 `cmd /C`)
+    pub fn command_line(command: &str) -> Shell {
+        #[cfg(target_os = "windows")]
+        let (program, flag) = ("cmd".to_string(), "/C");
+        #[cfg(not(target_os = "windows"))]
+        let (program, flag) = (std::env::var("SHELL").unwrap_or("/bin/sh".to_string()), "-c");
+        Shell::WithArguments { program, args: vec![flag.to_string(), command.to_string()] }
+    }
 }
 
 /// 터미널 빌더 (Zed TerminalBuilder와 동일 구조)
@@ -84,105 +388,266 @@ pub struct TerminalBuilder {
     events_rx: UnboundedReceiver<AlacTermEvent>,
 }
 
+/// `Terminal::take_damaged_rows` 결과
+#[derive(Debug)]
+pub struct DamagedRows {
+    pub view: (usize, usize), // 보기 위치 (display offset, 맨 아래를 지나 내려간 줄 수)
+    pub screen_lines: usize,
+    pub rows: Vec<(usize, String, Vec<RenderableCell>)>, // 다시 읽은 (보이는 줄 번호, 내용, 셀)
+}
+
 /// 메인 터미널 구조체 (Zed Terminal과 동일 구조)
 pub struct Terminal {
-    pty_tx: Notifier,
+    pty_tx: PtySender,
     term: Arc<FairMutex<Term<TerminalListener>>>,
     #[allow(dead_code)]
     events_rx: Option<UnboundedReceiver<AlacTermEvent>>,
+    working_directory: Option<PathBuf>, // 시작 작업 디렉토리
+    shell: Shell,                       // 실행한 셸 (레이아웃 저장용)
+    bounds: TerminalBounds,             // 마지막으로 적용한 크기
+    cell_size: CellSize,                // PTY에 보고하는 셀 픽셀 크기
+    scroll_past_end: usize,             // 맨 아래를 지나 스크롤할 수 있는 빈 줄 수 (설정)
+    past_end_offset: usize,             // 현재 맨 아래를 지나 내려간 빈 줄 수
+    alt_screen: bool,                   // 마지막으로 확인한 대체 화면 여부 (전환 감지용)
+    history_lines: usize,               // 스크롤백 히스토리 크기 (설정)
+    kitty_keyboard: bool,               // kitty 키보드 프로토콜 허용 (설정)
+    shell_state: Arc<Mutex<ShellState>>, // OSC 7 등으로 셸이 보고한 상태
+    output_sinks: SharedOutputSinks,    // PTY 출력 기록과 asciicast 녹화
+    content_version: Arc<AtomicU64>,    // PTY 출력으로 내용이 바뀔 때마다 증가
+    line_stamps: Vec<(u64, String, Vec<RenderableCell>)>, // diff_since용 화면 줄별 (변경 버전, 내용, 셀)
+    grid_rewritten: AtomicBool,         // alacritty 모르게 그리드를 직접 고침 (다음 손상 확인 때 화면 전체)
+    #[cfg(unix)]
+    pty_master: Option<std::fs::File>,  // PTY master 복제본 (포그라운드 프로세스 확인용, 스트림 연결이면 None)
+}
+
+/// 자동 줄 바꿈(DECAWM) 설정(`CSI ? 7 h`)/해제(`CSI ? 7 l`) 시퀀스
+pub fn autowrap_sequence(enabled: bool) -> &'static [u8] {
+    if enabled { b"\x1b[?7h" } else { b"\x1b[?7l" }
+}
+
+/// 셸에 알리는 터미널 프로그램 이름 (`TERM_PROGRAM`)
+pub const TERM_PROGRAM: &str = "rust-terminal";
+
+/// 셸에 전달할 터미널 환경 변수 설정 (터미널 종류, 프로그램 이름과 버전, 색상 지원 정보)
+/// `colorterm`이 있으면 `COLORTERM`으로 24비트 색 지원을 알리고, 없으면 호스트에서 물려받은 값도 제거
+fn set_terminal_env(env: &mut HashMap<String, String>, colorterm: Option<&str>) {
+    env.entry("LANG".to_string())
+        .or_insert_with(|| "en_US.UTF-8".to_string());
+
+    env.insert("TERM".to_string(), "xterm-256color".to_string());
+    env.insert("TERM_PROGRAM".to_string(), TERM_PROGRAM.to_string());
+    env.insert("TERM_PROGRAM_VERSION".to_string(), env!("CARGO_PKG_VERSION").to_string());
+
+    match colorterm {
+        Some(value) => env.insert("COLORTERM".to_string(), value.to_string()),
+        None => env.remove("COLORTERM"),
+    };
+}
+
+/// 스크롤백 히스토리 크기와 kitty 키보드 프로토콜 허용 여부를 지정한 alacritty 터미널 설정
+fn term_config(history_lines: usize, kitty_keyboard: bool) -> Config {
+    Config { scrolling_history: history_lines, kitty_keyboard, ..Config::default() }
 }
 
 impl TerminalBuilder {
-    /// Zed의 TerminalBuilder::new()와 동일한 시그니처로 구현
+    /// Zed의 TerminalBuilder::new()와 같은 구조로 구현
+    /// 셀 크기, 색상 지원 알림, 스크롤백 크기는 앱 설정에서 가져옴
+    /// 셸 환경은 `parent_env`(보통 호스트 환경)에 `env_policy`를 적용해 만듦 (`child_env` 참고)
     pub fn new(
         working_directory: Option<PathBuf>,
         shell: Shell,
-        mut env: HashMap<String, String>,
+        parent_env: HashMap<String, String>,
+        env_policy: &EnvPolicy,
         window_id: u64,
+        config: &crate::config::Config,
     ) -> Result<TerminalBuilder> {
-        // 1. Zed와 동일한 환경 변수 설정
-        if std::env::var("LANG").is_err() {
-            env.entry("LANG".to_string())
-                .or_insert_with(|| "en_US.UTF-8".to_string());
-        }
+        Self::open(working_directory, shell, parent_env, env_policy, window_id, config, true)
+    }
+
+    /// `new`와 같지만 IO 스레드(alacritty EventLoop)를 띄우지 않는 터미널
+    /// 셸 출력은 호출한 쪽이 `Terminal::pump_output`으로 원하는 주기와 크기로 읽어 그리드에 반영
+    pub fn pumped(
+        working_directory: Option<PathBuf>,
+        shell: Shell,
+        parent_env: HashMap<String, String>,
+        env_policy: &EnvPolicy,
+        window_id: u64,
+        config: &crate::config::Config,
+    ) -> Result<TerminalBuilder> {
+        Self::open(working_directory, shell, parent_env, env_policy, window_id, config, false)
+    }
+
+    /// 셸을 실행하거나 연결을 열어 터미널 생성 (`io_thread`가 false면 `pumped`)
+    fn open(
+        working_directory: Option<PathBuf>,
+        shell: Shell,
+        parent_env: HashMap<String, String>,
+        env_policy: &EnvPolicy,
+        window_id: u64,
+        config: &crate::config::Config,
+        io_thread: bool,
+    ) -> Result<TerminalBuilder> {
+        let cell_size = config.cell_size;
 
-        env.insert("ZED_TERM".to_string(), "true".to_string());
-        env.insert("TERM_PROGRAM".to_string(), "zed".to_string());
-        env.insert("TERM".to_string(), "xterm-256color".to_string());
-        env.insert("TERM_PROGRAM_VERSION".to_string(), "1.0.0".to_string());
+        // 1. 환경 변수 정책과 터미널 변수 적용
+        let env = Self::child_env(parent_env, env_policy, config.colors.colorterm.as_deref());
 
-        // 2. Shell 파라미터 설정 (Zed와 동일한 로직)
-        let shell_program = match shell.clone() {
+        // 데몬 세션에 다시 붙기 (세션이 끝났거나 데몬이 없으면 원래 셸을 새로 실행)
+        #[cfg(unix)]
+        if let Shell::Attached { session, .. } = &shell {
+            match crate::daemon::attach(*session) {
+                Ok(socket) => return Self::with_daemon_session(working_directory, shell.clone(), socket, config, io_thread),
+                Err(e) => log::warn!("daemon session {} could not be attached: {:#}", session, e),
+            }
+        }
+        let shell = shell.base().clone();
+
+        // 2. Shell 파라미터 설정 (Zed와 동일한 로직, 시리얼 장치와 네트워크 연결은 프로그램을 실행하지 않음)
+        let command = match shell.clone() {
             Shell::System => {
                 #[cfg(target_os = "windows")]
-                {
-                    get_windows_system_shell()
-                }
+                let program = get_windows_system_shell();
                 #[cfg(not(target_os = "windows"))]
-                {
-                    std::env::var("SHELL").unwrap_or("/bin/sh".to_string())
-                }
+                let program = std::env::var("SHELL").unwrap_or("/bin/sh".to_string());
+                Some((program, Vec::new()))
             }
-            Shell::Program(program) => program,
-            Shell::WithArguments { program, .. } => program,
-        };
-
-        let shell_args = match shell {
-            Shell::WithArguments { args, .. } => Some(args),
-            _ => None,
+            Shell::Program(program) => Some((program, Vec::new())),
+            Shell::WithArguments { program, args } => Some((program, args)),
+            Shell::Ssh(host) => Some(("ssh".to_string(), host.ssh_args(remote::control_dir().as_deref()))),
+            Shell::Serial(_) | Shell::Network(_) => None,
+            Shell::Attached { .. } => unreachable!("데몬 세션은 위에서 벗겨 냄"),
         };
 
         // 3. PTY 옵션 구성 (Zed와 동일)
-        let alac_shell = AlacShell::new(shell_program, shell_args.unwrap_or_default());
         let working_dir = working_directory
             .or_else(|| dirs::home_dir());
 
-        let pty_options = PtyOptions {
-            shell: Some(alac_shell),
-            working_directory: working_dir,
-            env: env.into_iter().collect(),
-            hold: false,
+        // 데몬 모드면 셸을 데몬에서 실행하고 소켓으로 붙음
+        #[cfg(unix)]
+        if config.session.daemon {
+            if let Some((program, args)) = &command {
+                let window_size = cell_size.window_size(TerminalBounds::default());
+                let (session, socket) = crate::daemon::open(program, args, working_dir.as_deref(), env, window_size)?;
+                let shell = Shell::Attached { session, shell: Box::new(shell) };
+                return Self::with_daemon_session(working_dir, shell, socket, config, io_thread);
+            }
+        }
+
+        // 4. PTY 생성 (Zed와 동일) 또는 시리얼 장치 열기/소켓 연결
+        let pty = match (command, &shell) {
+            (Some((program, args)), _) => {
+                let pty_options = PtyOptions {
+                    shell: Some(AlacShell::new(program, args)),
+                    working_directory: working_dir.clone(),
+                    env: env.into_iter().collect(),
+                    hold: false,
+                };
+                match tty::new(&pty_options, cell_size.window_size(TerminalBounds::default()), window_id) {
+                    Ok(pty) => Transport::Pty(pty),
+                    Err(error) => {
+                        bail!("PTY 생성 실패: {}", error);
+                    }
+                }
+            }
+            (None, Shell::Serial(settings)) => transport::stream(settings.open()?, None)?,
+            (None, Shell::Network(target)) => {
+                let telnet = (target.protocol == Protocol::Telnet).then(|| Box::new(Telnet::default()) as Box<dyn StreamProtocol>);
+                transport::stream(target.connect()?, telnet)?
+            }
+            (None, _) => unreachable!("셸이 아닌 연결은 시리얼 장치와 네트워크뿐"),
         };
 
-        // 4. 이벤트 채널 생성
+        Self::start(working_dir, shell, pty, config, io_thread)
+    }
+
+    /// 세션 데몬에 연결된 소켓으로 터미널 생성 (데몬이 보낸 출력만 터미널로 넘김)
+    #[cfg(unix)]
+    fn with_daemon_session(
+        working_directory: Option<PathBuf>,
+        shell: Shell,
+        socket: std::fs::File,
+        config: &crate::config::Config,
+        io_thread: bool,
+    ) -> Result<TerminalBuilder> {
+        let pty = transport::stream(socket, Some(Box::new(crate::daemon::ClientCodec::default())))?;
+        Self::start(working_directory, shell, pty, config, io_thread)
+    }
+
+    /// 연결(PTY/장치/소켓)에 터미널과 IO 스레드 붙이기 (`io_thread`가 false면 `pump_output`으로 읽음)
+    fn start(working_dir: Option<PathBuf>, shell: Shell, pty: Transport, config: &crate::config::Config, io_thread: bool) -> Result<TerminalBuilder> {
+        let cell_size = config.cell_size;
+
+        // 5. 이벤트 채널 생성
         let (events_tx, events_rx) = unbounded();
 
-        // 5. 터미널 생성 (Zed와 동일)
-        let config = Config::default();
+        // 6. 터미널 생성 (Zed와 동일)
+        let term_config = term_config(config.scrollback.history_lines, config.keyboard.kitty);
         let bounds = TerminalBounds::default();
+        let content_version = Arc::new(AtomicU64::new(1)); // 0은 "아무것도 보지 않음"을 의미
         let term = Term::new(
-            config,
+            term_config,
             &bounds,
-            TerminalListener(events_tx.clone()),
+            TerminalListener::new(events_tx.clone(), content_version.clone()),
         );
 
         let term = Arc::new(FairMutex::new(term));
 
-        // 6. PTY 생성 (Zed와 동일)
-        let pty = match tty::new(&pty_options, bounds.into(), window_id) {
-            Ok(pty) => pty,
-            Err(error) => {
-                bail!("PTY 생성 실패: {}", error);
-            }
+        // PTY 출력에서 OSC 7(작업 디렉토리) 등을 직접 추적
+        // 출력 기록과 녹화도 같은 읽기 경로에서 처리 (나중에 켜고 끌 수 있음)
+        let shell_state = Arc::new(Mutex::new(ShellState::default()));
+        let output_sinks = SharedOutputSinks::default();
+        #[cfg(unix)]
+        let pty_master = match &pty {
+            Transport::Pty(pty) => pty.file().try_clone().ok(),
+            Transport::Stream(_) => None,
         };
+        let pty_tx = if io_thread {
+            let pty = OscTrackingPty::new(pty, shell_state.clone(), output_sinks.clone());
 
-        // 7. EventLoop 연결 (Zed와 동일)
-        let event_loop = EventLoop::new(
-            term.clone(),
-            TerminalListener(events_tx),
-            pty,
-            true, // drain_on_exit
-            false, // hold
-        )?;
+            // 7. EventLoop 연결 (Zed와 동일)
+            let event_loop = EventLoop::new(
+                term.clone(),
+                TerminalListener::new(events_tx, content_version.clone()),
+                pty,
+                true, // drain_on_exit
+                false, // hold
+            )?;
 
-        // 8. IO 스레드 시작 (Zed와 동일)
-        let pty_tx = event_loop.channel();
-        let _io_thread = event_loop.spawn();
+            // 8. IO 스레드 시작 (Zed와 동일)
+            let pty_tx = event_loop.channel();
+            let _io_thread = event_loop.spawn();
+            PtySender::EventLoop(Notifier(pty_tx))
+        } else {
+            // OSC 추적과 출력 기록은 `pump_output`이 읽을 때 처리
+            PtySender::Pumped(Box::new(PumpedPty {
+                pty,
+                batcher: OutputBatcher::new(),
+                listener: TerminalListener::new(events_tx, content_version.clone()),
+                pending_input: Vec::new(),
+                exited: false,
+            }))
+        };
 
         let terminal = Terminal {
-            pty_tx: Notifier(pty_tx),
+            pty_tx,
             term,
             events_rx: None, // events_rx는 따로 관리
+            working_directory: working_dir,
+            shell,
+            bounds,
+            cell_size,
+            scroll_past_end: 0,
+            past_end_offset: 0,
+            alt_screen: false,
+            history_lines: config.scrollback.history_lines,
+            kitty_keyboard: config.keyboard.kitty,
+            shell_state,
+            output_sinks,
+            content_version,
+            line_stamps: Vec::new(),
+            grid_rewritten: AtomicBool::new(false),
+            #[cfg(unix)]
+            pty_master,
         };
 
         Ok(TerminalBuilder {
@@ -191,6 +656,15 @@ impl TerminalBuilder {
         })
     }
 
+    /// 셸에 넘길 환경 - 물려받을 변수만 남기고 터미널 변수를 넣은 뒤 정책의 지우기/넣기 적용
+    /// 호스트 환경을 직접 읽지 않으므로 같은 입력이면 항상 같은 환경
+    pub fn child_env(parent_env: HashMap<String, String>, policy: &EnvPolicy, colorterm: Option<&str>) -> HashMap<String, String> {
+        let mut env = policy.inherited(parent_env);
+        set_terminal_env(&mut env, colorterm);
+        policy.apply(&mut env);
+        env
+    }
+
     /// 터미널 빌더에서 완성된 터미널 반환
     pub fn build(self) -> (Terminal, UnboundedReceiver<AlacTermEvent>) {
         (self.terminal, self.events_rx)
@@ -201,10 +675,134 @@ impl Terminal {
     /// 터미널에 입력 전송
     pub fn input(&mut self, data: &[u8]) -> Result<()> {
         let data_vec = data.to_vec();
-        self.pty_tx.0.send(alacritty_terminal::event_loop::Msg::Input(data_vec.into()))?;
+        self.pty_tx.send(Msg::Input(data_vec.into()))
+    }
+
+    /// 텍스트 붙여넣기 - 프로그램이 bracketed paste 모드를 켰으면 시작/끝 표시로 감쌈
+    /// (붙여넣은 내용 안의 끝 표시는 제거하여 붙여넣기 밖으로 빠져나가지 못하게 함)
+    pub fn paste(&mut self, text: &str) -> Result<()> {
+        let bracketed = self.term.lock().mode().contains(alacritty_terminal::term::TermMode::BRACKETED_PASTE);
+        if bracketed {
+            let text = text.replace("\x1b[201~", "");
+            self.input(format!("\x1b[200~{}\x1b[201~", text).as_bytes())
+        } else {
+            self.input(text.as_bytes())
+        }
+    }
+
+    /// 터미널에서 실행 중인 셸
+    pub fn shell(&self) -> &Shell {
+        &self.shell
+    }
+
+    /// 셸이 OSC 7로 보고한 현재 작업 디렉토리 (보고가 없었으면 None)
+    pub fn current_directory(&self) -> Option<PathBuf> {
+        self.shell_state.lock().ok()?.current_directory().cloned()
+    }
+
+    /// 셸의 작업 디렉토리 - OSC 7로 보고한 디렉토리, 보고가 없거나 사라진 경로면 시작 디렉토리
+    pub fn working_directory(&self) -> Option<PathBuf> {
+        self.current_directory()
+            .filter(|path| path.is_dir())
+            .or_else(|| self.working_directory.clone())
+    }
+
+    /// 이 세션에서 새 탭/세션을 열 때 사용할 작업 디렉토리 (알 수 없으면 앱의 작업 디렉토리)
+    pub fn new_session_directory(&self) -> Option<PathBuf> {
+        self.working_directory()
+            .or_else(|| std::env::current_dir().ok())
+    }
+
+    /// PTY 출력 기록 시작 (`directory`에 새 파일을 만들고, 이미 기록 중이면 그 파일은 닫음)
+    pub fn start_output_log(&self, directory: &Path, max_bytes: u64) -> io::Result<PathBuf> {
+        let log = OutputLog::start(directory, max_bytes)?;
+        let path = log.path().to_path_buf();
+        self.output_sinks.lock().unwrap().log = Some(log);
+        Ok(path)
+    }
+
+    /// PTY 출력 기록 중지 (기록 중이었으면 마지막 파일 경로 반환)
+    pub fn stop_output_log(&self) -> Option<PathBuf> {
+        let log = self.output_sinks.lock().ok()?.log.take()?;
+        Some(log.path().to_path_buf())
+    }
+
+    /// 출력을 기록 중이면 지금 쓰고 있는 파일 경로
+    pub fn output_log_path(&self) -> Option<PathBuf> {
+        Some(self.output_sinks.lock().ok()?.log.as_ref()?.path().to_path_buf())
+    }
+
+    /// 지금 크기로 asciicast 녹화 시작 (이미 녹화 중이면 그 녹화는 끝냄)
+    pub fn start_recording(&self, path: &Path, title: Option<String>) -> io::Result<()> {
+        let recorder = Recorder::start(path, self.bounds.num_cols, self.bounds.num_lines, title)?;
+        self.output_sinks.lock().unwrap().cast = Some(recorder);
         Ok(())
     }
 
+    /// 녹화 중지 (녹화 중이었으면 파일 경로 반환)
+    pub fn stop_recording(&self) -> Option<PathBuf> {
+        let recorder = self.output_sinks.lock().ok()?.cast.take()?;
+        Some(recorder.path().to_path_buf())
+    }
+
+    /// 녹화 중이면 녹화 파일 경로
+    pub fn recording_path(&self) -> Option<PathBuf> {
+        Some(self.output_sinks.lock().ok()?.cast.as_ref()?.path().to_path_buf())
+    }
+
+    /// 셸이 OSC 133;D로 보고한 마지막 명령의 종료 코드
+    pub fn last_exit_code(&self) -> Option<i32> {
+        self.shell_state.lock().ok()?.last_exit_code()
+    }
+
+    /// 셸이 OSC 133으로 보고한 프롬프트/명령 경계의 전체 버퍼 행(히스토리 맨 위 = 0)과 종류 (위쪽부터)
+    /// 표시 뒤에 나온 줄 바꿈 수만큼 커서 위치에서 논리 줄(자동 줄 바꿈으로 이어진 행 묶음) 단위로
+    /// 거슬러 올라가 계산하므로 리사이즈로 줄이 다시 배치되어도 맞음. 히스토리에서 밀려난 표시는 제외
+    pub fn prompt_marks(&self) -> Vec<(usize, PromptMarkKind)> {
+        let (marks, total_line_feeds) = match self.shell_state.lock() {
+            Ok(state) => (state.prompt_marks().clone(), state.line_feeds()),
+            Err(_) => return Vec::new(),
+        };
+        let term = self.term.lock();
+        let grid = term.grid();
+        let history_size = grid.history_size();
+        let last_column = alacritty_terminal::index::Column(grid.columns().saturating_sub(1));
+        let wrapped = |row: usize| {
+            let line = alacritty_terminal::index::Line(row as i32 - history_size as i32);
+            grid[line][last_column].flags.contains(Flags::WRAPLINE)
+        };
+        // 논리 줄의 첫 행
+        let line_start = |mut row: usize| {
+            while row > 0 && wrapped(row - 1) {
+                row -= 1;
+            }
+            row
+        };
+
+        let cursor_row = history_size + term.grid().cursor.point.line.0.max(0) as usize;
+        let mut row = line_start(cursor_row);
+        let mut line_feeds = total_line_feeds;
+        let mut resolved = Vec::new();
+        for mark in marks.iter().rev() {
+            while line_feeds > mark.line_feeds {
+                if row == 0 {
+                    resolved.reverse();
+                    return resolved;
+                }
+                row = line_start(row - 1);
+                line_feeds -= 1;
+            }
+            resolved.push((row, mark.kind));
+        }
+        resolved.reverse();
+        resolved
+    }
+
+    /// PTY 출력으로 내용이 바뀔 때마다 증가하는 버전
+    pub fn content_version(&self) -> u64 {
+        self.content_version.load(Ordering::Relaxed)
+    }
+
     /// 터미널 내용을 렌더링 가능한 형태로 가져오기 (한글 지원 개선 + 스크롤백 히스토리 포함)
     pub fn get_renderable_content(&self) -> Result<Vec<String>> {
         let term = self.term.lock();
@@ -226,27 +824,7 @@ impl Terminal {
                 alacritty_terminal::index::Line((line_index - history_size) as i32)
             };
 
-            let line = &grid[line_idx];
-
-            let mut line_content = String::new();
-            let mut col_index = 0;
-
-            while col_index < line.len() {
-                let cell = &line[alacritty_terminal::index::Column(col_index)];
-                let ch = cell.c;
-
-                // 실제 문자만 추가 (null character와 wide char spacer 제외)
-                if ch != '\0' && ch != ' ' || !cell.flags.contains(alacritty_terminal::term::cell::Flags::WIDE_CHAR_SPACER) {
-                    line_content.push(ch);
-                }
-
-                // wide character인 경우 다음 셀은 spacer이므로 건너뛰기
-                if cell.flags.contains(alacritty_terminal::term::cell::Flags::WIDE_CHAR) {
-                    col_index += 2; // wide char는 2개 셀을 차지
-                } else {
-                    col_index += 1;
-                }
-            }
+            let line_content = row_to_string(&grid[line_idx]);
 
             // 줄 끝의 공백 유지
             lines.push(line_content);
@@ -255,84 +833,629 @@ impl Terminal {
         Ok(lines)
     }
 
-    /// 커서 위치 가져오기 (마우스 커서 위치 - 디버그용)
-    pub fn get_cursor(&self) -> (u16, u16) {
+    /// 히스토리와 화면의 모든 줄 셀과 자동 줄 바꿈 여부 (스크롤백 내보내기용, 오래된 줄부터)
+    pub fn history_rows(&self) -> Vec<(Vec<RenderableCell>, bool)> {
         let term = self.term.lock();
         let grid = term.grid();
-        let cursor_pos = grid.cursor.point;
-        (cursor_pos.column.0 as u16, cursor_pos.line.0 as u16)
+        let history_size = grid.history_size() as i32;
+        let last_column = alacritty_terminal::index::Column(grid.columns().saturating_sub(1));
+
+        (-history_size..grid.screen_lines() as i32)
+            .map(|line| {
+                let row = &grid[alacritty_terminal::index::Line(line)];
+                (row.into_iter().map(RenderableCell::from).collect(), row[last_column].flags.contains(Flags::WRAPLINE))
+            })
+            .collect()
     }
 
-    /// 깜빡이는 터미널 커서 위치 가져오기 (RenderableCursor - 히스토리 포함 전체 좌표계 기준)
-    pub fn get_renderable_cursor(&self) -> (u16, u16, char) {
+    /// PTY의 포그라운드 프로세스 그룹 ID (셸 또는 셸에서 실행 중인 프로그램, 스트림 연결이면 None)
+    #[cfg(unix)]
+    pub fn foreground_process_id(&self) -> Option<u32> {
+        use std::os::unix::io::AsRawFd;
+
+        let master = self.pty_master.as_ref()?;
+        // SAFETY: fd는 이 터미널이 가진 PTY master 복제본
+        let group = unsafe { libc::tcgetpgrp(master.as_raw_fd()) };
+        (group > 0).then_some(group as u32)
+    }
+
+    #[cfg(not(unix))]
+    pub fn foreground_process_id(&self) -> Option<u32> {
+        None
+    }
+
+    /// 현재 스크롤백 보기 위치에서 화면에 보이는 줄들 가져오기
+    pub fn get_visible_content(&self) -> Vec<String> {
         let term = self.term.lock();
-        let content = term.renderable_content();
         let grid = term.grid();
-        let cursor_char = grid[content.cursor.point].c;
+        let display_offset = grid.display_offset() as i32;
+        let past_end = self.past_end_offset as i32;
+        let bottom = grid.screen_lines() as i32 - 1;
 
-        // 히스토리 크기를 고려하여 전체 좌표계에서의 커서 위치 계산
-        let history_size = grid.history_size();
-        let cursor_row_in_full_buffer = history_size as u16 + content.cursor.point.line.0 as u16;
+        // 맨 아래를 지나 스크롤한 만큼 아래쪽은 빈 줄
+        (0..grid.screen_lines() as i32)
+            .map(|line| line - display_offset + past_end)
+            .map(|line| if line > bottom { String::new() } else { row_to_string(&grid[alacritty_terminal::index::Line(line)]) })
+            .collect()
+    }
 
-        (
-            content.cursor.point.column.0 as u16,
-            cursor_row_in_full_buffer,
-            cursor_char
-        )
+    /// 마지막 호출 뒤 바뀐(손상된) 화면 줄만 (보이는 줄 번호, 내용, 셀)로 가져오고 손상 정보를 비움
+    /// 지난번 보기 위치(`since_view`)와 다르거나(None 포함) alacritty가 화면 전체 손상(크기 변경, 화면 전환 등)을 알리면 모든 줄
+    pub fn take_damaged_rows(&self, since_view: Option<(usize, usize)>) -> DamagedRows {
+        // PTY 스레드와의 잠금 경합은 통계 오버레이가 켜져 있을 때 여기서 측정
+        let mut term = crate::stats::timed_lock(|| self.term.lock());
+        let display_offset = term.grid().display_offset();
+        let view = (display_offset, self.past_end_offset);
+        let full = self.grid_rewritten.swap(false, Ordering::Relaxed) || since_view != Some(view);
+        let screen_lines = term.grid().screen_lines();
+        // 보이는 줄은 맨 아래를 지나 내려간 만큼 alacritty 화면 줄보다 위에 있음
+        let rows: Vec<usize> = match term.damage() {
+            TermDamage::Partial(lines) if !full => lines
+                .filter_map(|bounds| bounds.line.checked_sub(self.past_end_offset))
+                .collect(),
+            _ => (0..screen_lines).collect(),
+        };
+        term.reset_damage();
+
+        let grid = term.grid();
+        let bottom = screen_lines as i32 - 1;
+        let rows = rows.into_iter()
+            .map(|row| {
+                let line = row as i32 - display_offset as i32 + self.past_end_offset as i32;
+                if line > bottom {
+                    return (row, String::new(), Vec::new());
+                }
+                let grid_row = &grid[alacritty_terminal::index::Line(line)];
+                (row, row_to_string(grid_row), grid_row.into_iter().map(RenderableCell::from).collect())
+            })
+            .collect();
+        DamagedRows { view, screen_lines, rows }
     }
 
-    /// 터미널이 마우스 모드를 지원하는지 확인
-    pub fn is_mouse_mode_enabled(&self) -> bool {
+    /// 현재 스크롤백 보기 위치에서 화면에 보이는 줄별 자동 줄 바꿈 여부 (다음 줄로 이어지면 true)
+    pub fn get_visible_wraps(&self) -> Vec<bool> {
         let term = self.term.lock();
-        let mode = term.mode();
-        // MOUSE_REPORT_CLICK, MOUSE_DRAG, MOUSE_MOTION 등 마우스 모드 확인
-        mode.intersects(
-            alacritty_terminal::term::TermMode::MOUSE_REPORT_CLICK
-            | alacritty_terminal::term::TermMode::MOUSE_DRAG
-            | alacritty_terminal::term::TermMode::MOUSE_MOTION
-        )
+        let grid = term.grid();
+        let display_offset = grid.display_offset() as i32;
+        let past_end = self.past_end_offset as i32;
+        let bottom = grid.screen_lines() as i32 - 1;
+        let last_column = alacritty_terminal::index::Column(grid.columns().saturating_sub(1));
+
+        (0..grid.screen_lines() as i32)
+            .map(|line| line - display_offset + past_end)
+            .map(|line| line <= bottom && grid[alacritty_terminal::index::Line(line)][last_column].flags.contains(Flags::WRAPLINE))
+            .collect()
     }
 
-    /// 터미널이 대체 화면 모드인지 확인
-    #[allow(dead_code)]
-    pub fn is_alternate_screen(&self) -> bool {
+    /// 현재 스크롤백 보기 위치에서 화면에 보이는 셀들 가져오기 (색상/속성 포함)
+    pub fn get_renderable_cells(&self) -> Vec<Vec<RenderableCell>> {
         let term = self.term.lock();
-        term.mode().contains(alacritty_terminal::term::TermMode::ALT_SCREEN)
+        let grid = term.grid();
+        let display_offset = grid.display_offset() as i32;
+        let past_end = self.past_end_offset as i32;
+        let bottom = grid.screen_lines() as i32 - 1;
+
+        (0..grid.screen_lines() as i32)
+            .map(|line| line - display_offset + past_end)
+            .map(|line| {
+                if line > bottom {
+                    return Vec::new();
+                }
+                grid[alacritty_terminal::index::Line(line)]
+                    .into_iter()
+                    .map(RenderableCell::from)
+                    .collect()
+            })
+            .collect()
     }
-}
 
-/// Windows 시스템 Shell 찾기 (Zed와 동일한 로직)
-#[cfg(target_os = "windows")]
-fn get_windows_system_shell() -> String {
+    /// `version`(content_version 값) 이후 바뀐 화면 줄만 반환 (원격/임베디드 렌더링용).
+    /// 0을 넘기면 화면 전체를 반환
+    /// 호출할 때 지난 호출에서 본 내용과 비교하여 바뀐 줄에 현재 버전을 기록하므로, 한 터미널의 변경은 한 곳에서만 받아 감.
+    /// 크기 변경이나 보기 위치 이동은 버전을 올리지 않으므로 그 뒤에는 0을 넘겨 화면 전체를 받아야 함
+    pub fn diff_since(&mut self, version: u64) -> Vec<LineDiff> {
+        let current_version = self.content_version();
+        let rows = self.get_visible_content().into_iter().zip(self.get_renderable_cells());
 
-    // 1. PowerShell 7+ 찾기
-    if let Some(pwsh) = find_powershell(false, false) {
-        return pwsh.to_string_lossy().to_string();
-    }
+        // 이전에 본 내용과 비교하여 바뀐 줄에 현재 버전 기록
+        let stamps = &mut self.line_stamps;
+        stamps.truncate(rows.len());
+        for (row_idx, (text, cells)) in rows.enumerate() {
+            match stamps.get_mut(row_idx) {
+                Some((_, cached_text, cached)) if *cached == cells && *cached_text == text => {}
+                Some(entry) => *entry = (current_version, text, cells),
+                None => stamps.push((current_version, text, cells)),
+            }
+        }
 
-    // 2. PowerShell 6+ 찾기
-    if let Some(pwsh) = find_powershell(true, false) {
-        return pwsh.to_string_lossy().to_string();
+        stamps.iter()
+            .enumerate()
+            .filter(|(_, (stamp, _, _))| *stamp > version)
+            .map(|(row, (_, text, cells))| LineDiff { row, text: text.clone(), cells: cells.clone() })
+            .collect()
     }
 
-    // 3. Windows PowerShell 찾기
-    if let Some(powershell) = find_windows_powershell() {
-        return powershell.to_string_lossy().to_string();
+    /// 스크롤백 보기 위치 설정 (맨 아래 기준으로 위로 올라간 줄 수, 히스토리 크기로 제한)
+    pub fn set_display_offset(&mut self, lines: usize) {
+        self.set_past_end_offset(0);
+        let mut term = self.term.lock();
+        let target = lines.min(term.grid().history_size());
+        let delta = target as i32 - term.grid().display_offset() as i32;
+        if delta != 0 {
+            term.scroll_display(Scroll::Delta(delta));
+            // 화면에 보이는 내용이 바뀌었으므로 버전 증가
+            self.content_version.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
-    // 4. cmd.exe fallback
-    std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
-}
+    /// 스크롤백 히스토리 지우기 - 되돌리기용으로 최근 `snapshot_limit`줄까지 저장하여 반환
+    pub fn clear_scrollback(&mut self, snapshot_limit: usize) -> Option<ScrollbackSnapshot> {
+        let mut term = self.term.lock();
+        let history_size = term.grid().history_size();
+        let keep = history_size.min(snapshot_limit);
 
-#[cfg(target_os = "windows")]
-fn find_powershell(find_alternate: bool, find_preview: bool) -> Option<PathBuf> {
-    let env_var = if find_alternate {
-        "ProgramFiles(x86)"
-    } else {
-        "ProgramFiles"
-    };
+        let rows: Vec<Row<Cell>> = (1..=keep)
+            .rev()
+            .map(|i| term.grid()[alacritty_terminal::index::Line(-(i as i32))].clone())
+            .collect();
 
-    let install_base_dir = PathBuf::from(std::env::var_os(env_var)?)
+        term.grid_mut().clear_history();
+        drop(term);
+        self.grid_rewritten.store(true, Ordering::Relaxed);
+        self.content_version.fetch_add(1, Ordering::Relaxed);
+
+        (!rows.is_empty()).then_some(ScrollbackSnapshot { rows })
+    }
+
+    /// 지운 스크롤백 복원 (지운 뒤 생긴 히스토리 위쪽에 다시 삽입).
+    /// 그 사이 열 수가 바뀌었으면 복원하지 않고 false 반환
+    pub fn restore_scrollback(&mut self, snapshot: ScrollbackSnapshot) -> bool {
+        let mut term = self.term.lock();
+        let grid = term.grid_mut();
+        let columns = grid.columns();
+        if snapshot.rows.iter().any(|row| row.len() != columns) {
+            return false;
+        }
+
+        // 현재 히스토리 + 화면 내용 보관
+        let screen_lines = grid.screen_lines() as i32;
+        let current_history = grid.history_size() as i32;
+        let current_rows: Vec<Row<Cell>> = (-current_history..screen_lines)
+            .map(|line| grid[alacritty_terminal::index::Line(line)].clone())
+            .collect();
+
+        // 화면을 위로 밀어서 스냅샷 크기만큼 히스토리 공간 확보
+        let region = alacritty_terminal::index::Line(0)..alacritty_terminal::index::Line(screen_lines);
+        let mut remaining = snapshot.rows.len();
+        while remaining > 0 {
+            let positions = remaining.min(screen_lines as usize);
+            grid.scroll_up::<alacritty_terminal::vte::ansi::Color>(&region, positions);
+            remaining -= positions;
+        }
+
+        // 스냅샷 + 기존 내용을 순서대로 다시 기록 (히스토리 최대치를 넘는 오래된 줄은 버림)
+        let history_size = grid.history_size() as i32;
+        let total = (history_size + screen_lines) as usize;
+        let all_rows: Vec<Row<Cell>> = snapshot.rows.into_iter().chain(current_rows).collect();
+        let skip = all_rows.len().saturating_sub(total);
+        for (i, row) in all_rows.into_iter().skip(skip).enumerate() {
+            grid[alacritty_terminal::index::Line(i as i32 - history_size)] = row;
+        }
+
+        drop(term);
+        self.grid_rewritten.store(true, Ordering::Relaxed);
+        self.content_version.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// 세션 저장용 텍스트 - 히스토리와 화면의 마지막 `limit`줄 (끝의 빈 줄 제외)
+    /// 대체 화면(vim 등)이면 기본 화면 내용을 읽을 수 없으므로 빈 목록
+    pub fn scrollback_lines(&self, limit: usize) -> Vec<String> {
+        if limit == 0 || self.is_alternate_screen() {
+            return Vec::new();
+        }
+        let mut lines: Vec<String> = self.get_renderable_content().unwrap_or_default()
+            .into_iter()
+            .map(|line| line.trim_end().to_string())
+            .collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let skip = lines.len().saturating_sub(limit);
+        lines.split_off(skip)
+    }
+
+    /// 저장된 세션의 줄들을 프로그램이 출력한 것처럼 그리드에 기록 (세션 복원용)
+    /// 셸에는 보내지 않으므로 새 셸의 프롬프트는 복원한 내용 아래에 나타남
+    pub fn replay_lines(&mut self, lines: &[String]) {
+        if lines.is_empty() {
+            return;
+        }
+        let mut bytes = Vec::new();
+        for line in lines {
+            // 저장된 텍스트의 제어 문자는 그대로 실행하지 않음
+            bytes.extend(line.chars().filter(|c| !c.is_control()).collect::<String>().bytes());
+            bytes.extend_from_slice(b"\r\n");
+        }
+        let mut term = self.term.lock();
+        let mut parser: alacritty_terminal::vte::ansi::Processor = Default::default();
+        for byte in &bytes {
+            parser.advance(&mut *term, *byte);
+        }
+        drop(term);
+        self.content_version.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 셸 없이 출력만 그리는 터미널 생성 (녹화 재생용)
+    pub fn detached(num_lines: usize, num_cols: usize, history_lines: usize) -> Terminal {
+        let (events_tx, _) = unbounded();
+        let bounds = TerminalBounds { num_lines: num_lines.max(1), num_cols: num_cols.max(1) };
+        let content_version = Arc::new(AtomicU64::new(1));
+        let term = Term::new(
+            term_config(history_lines, false),
+            &bounds,
+            TerminalListener::new(events_tx, content_version.clone()),
+        );
+
+        Terminal {
+            pty_tx: PtySender::Detached(Box::new(OutputBatcher::new())),
+            term: Arc::new(FairMutex::new(term)),
+            events_rx: None,
+            working_directory: None,
+            shell: Shell::System,
+            bounds,
+            cell_size: CellSize::default(),
+            scroll_past_end: 0,
+            past_end_offset: 0,
+            alt_screen: false,
+            history_lines,
+            kitty_keyboard: false,
+            shell_state: Arc::new(Mutex::new(ShellState::default())),
+            output_sinks: SharedOutputSinks::default(),
+            content_version,
+            line_stamps: Vec::new(),
+            grid_rewritten: AtomicBool::new(false),
+            #[cfg(unix)]
+            pty_master: None,
+        }
+    }
+
+    /// 프로그램 출력을 그리드에 바로 반영 (`detached` 터미널 전용, 이스케이프 시퀀스가 나뉘어 와도 이어서 처리)
+    /// 긴 출력도 배치 단위로 나눠 반영하므로 그리드 잠금을 오래 잡지 않음
+    pub fn write_output(&mut self, bytes: &[u8]) {
+        let PtySender::Detached(batcher) = &mut self.pty_tx else {
+            return;
+        };
+        let mut reader = bytes;
+        while let Ok(1..) = batcher.pump(&mut reader, OUTPUT_BATCH_SIZE, &self.term, &self.shell_state, &self.output_sinks) {
+            self.content_version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 터미널 크기 변경 (그리드 리플로우 + PTY 리사이즈)
+    /// 타일링 WM처럼 리사이즈가 연달아 들어와도 마지막으로 적용한 크기와 같으면 아무것도 하지 않음
+    /// 실제로 크기가 바뀌었으면 true
+    pub fn resize(&mut self, bounds: TerminalBounds) -> Result<bool> {
+        if bounds == self.bounds || bounds.num_lines == 0 || bounds.num_cols == 0 {
+            return Ok(false);
+        }
+
+        self.term.lock().resize(bounds);
+        self.pty_tx.send(Msg::Resize(self.cell_size.window_size(bounds)))?;
+        self.bounds = bounds;
+        if let Ok(mut sinks) = self.output_sinks.lock() {
+            if let Some(recorder) = sinks.cast.as_mut() {
+                if let Err(e) = recorder.resize(bounds.num_cols, bounds.num_lines) {
+                    log::warn!("녹화 중지 ({}): {}", recorder.path().display(), e);
+                    sinks.cast = None;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// 셀 픽셀 크기 변경 (설정 다시 읽기, 확대/축소) - 바뀌었으면 PTY에 새 창 크기 보고
+    pub fn set_cell_size(&mut self, cell_size: CellSize) -> Result<()> {
+        if cell_size == self.cell_size {
+            return Ok(());
+        }
+        self.cell_size = cell_size;
+        self.pty_tx.send(Msg::Resize(cell_size.window_size(self.bounds)))
+    }
+
+    /// 스크롤백 히스토리 크기 변경 (줄이면 오래된 줄부터 버림)
+    pub fn set_history_lines(&mut self, history_lines: usize) {
+        let mut term = self.term.lock();
+        if term.grid().history_size() > history_lines {
+            self.content_version.fetch_add(1, Ordering::Relaxed);
+        }
+        self.history_lines = history_lines;
+        term.set_options(term_config(history_lines, self.kitty_keyboard));
+    }
+
+    /// kitty 키보드 프로토콜 허용/금지 (끄면 프로그램이 요청한 플래그도 초기화)
+    pub fn set_kitty_keyboard(&mut self, enabled: bool) {
+        if enabled == self.kitty_keyboard {
+            return;
+        }
+        self.kitty_keyboard = enabled;
+        self.term.lock().set_options(term_config(self.history_lines, enabled));
+    }
+
+    /// 프로그램이 요청한 키 입력 방식 (kitty 키보드 프로토콜 플래그, 애플리케이션 커서 키/키패드 모드)
+    pub fn keyboard_mode(&self) -> KeyboardMode {
+        use alacritty_terminal::term::TermMode;
+        let mode = *self.term.lock().mode();
+        let kitty_flags = [
+            (TermMode::DISAMBIGUATE_ESC_CODES, keys::KITTY_DISAMBIGUATE),
+            (TermMode::REPORT_EVENT_TYPES, keys::KITTY_EVENT_TYPES),
+            (TermMode::REPORT_ALTERNATE_KEYS, keys::KITTY_ALTERNATE_KEYS),
+            (TermMode::REPORT_ALL_KEYS_AS_ESC, keys::KITTY_ALL_KEYS),
+            (TermMode::REPORT_ASSOCIATED_TEXT, keys::KITTY_ASSOCIATED_TEXT),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| mode.contains(*flag))
+        .map(|(_, bit)| bit)
+        .sum();
+        KeyboardMode {
+            kitty_flags,
+            app_cursor: mode.contains(TermMode::APP_CURSOR),
+            app_keypad: mode.contains(TermMode::APP_KEYPAD),
+        }
+    }
+
+    /// 스크롤 (양수: 위쪽 히스토리 방향, 음수: 아래쪽 최신 출력 방향)
+    /// 대체 화면에는 히스토리가 없으므로 움직이지 않음 (맨 아래 너머 여백도 없음)
+    pub fn scroll_by(&mut self, lines: i32) {
+        if self.is_alternate_screen() {
+            return;
+        }
+        // 음수 위치는 맨 아래를 지나 빈 여백으로 내려간 줄 수
+        let current = self.display_offset() as i64 - self.past_end_offset as i64;
+        let target = (current + lines as i64).max(-(self.scroll_past_end as i64));
+        self.set_display_offset(target.max(0) as usize);
+        self.set_past_end_offset((-target).max(0) as usize);
+    }
+
+    /// 맨 아래를 지나 스크롤할 수 있는 빈 줄 수 설정 (0이면 맨 아래에서 멈춤)
+    pub fn set_scroll_past_end(&mut self, lines: usize) {
+        self.scroll_past_end = lines;
+        self.set_past_end_offset(self.past_end_offset.min(lines));
+    }
+
+    fn set_past_end_offset(&mut self, lines: usize) {
+        if self.past_end_offset != lines {
+            self.past_end_offset = lines;
+            // 화면에 보이는 내용이 바뀌었으므로 버전 증가
+            self.content_version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 지정한 위치로 스크롤
+    pub fn scroll_to(&mut self, position: ScrollPosition) {
+        let target = match position {
+            ScrollPosition::Top => usize::MAX,
+            ScrollPosition::Bottom => 0,
+            ScrollPosition::Offset(offset) => offset,
+        };
+        self.set_display_offset(target);
+    }
+
+    /// 현재 스크롤 상태
+    pub fn scroll_state(&self) -> ScrollState {
+        let term = self.term.lock();
+        let grid = term.grid();
+        ScrollState {
+            offset: grid.display_offset(),
+            past_end: self.past_end_offset,
+            past_end_margin: self.scroll_past_end,
+            total: grid.history_size() + grid.screen_lines(),
+            viewport: grid.screen_lines(),
+        }
+    }
+
+    /// 현재 스크롤백 보기 위치 (맨 아래 기준으로 위로 올라간 줄 수)
+    pub fn display_offset(&self) -> usize {
+        self.term.lock().grid().display_offset()
+    }
+
+    /// 전체 버퍼 행(`get_renderable_content` 기준), 열의 셀에 붙은 OSC 8 하이퍼링크 주소
+    pub fn hyperlink_at(&self, row: usize, col: usize) -> Option<String> {
+        let term = self.term.lock();
+        let grid = term.grid();
+        let line = row as i32 - grid.history_size() as i32;
+        if line >= grid.screen_lines() as i32 || col >= grid.columns() {
+            return None;
+        }
+        let cell = &grid[alacritty_terminal::index::Line(line)][alacritty_terminal::index::Column(col)];
+        cell.hyperlink().map(|link| link.uri().to_string())
+    }
+
+    /// 커서 위치 가져오기 (마우스 커서 위치 - 디버그용)
+    pub fn get_cursor(&self) -> (u16, u16) {
+        let term = self.term.lock();
+        let grid = term.grid();
+        let cursor_pos = grid.cursor.point;
+        (cursor_pos.column.0 as u16, cursor_pos.line.0 as u16)
+    }
+
+    /// 깜빡이는 터미널 커서 위치 가져오기 (RenderableCursor - 히스토리 포함 전체 좌표계 기준)
+    pub fn get_renderable_cursor(&self) -> (u16, u16, char) {
+        let term = self.term.lock();
+        let content = term.renderable_content();
+        let grid = term.grid();
+        let cursor_char = grid[content.cursor.point].c;
+
+        // 히스토리 크기를 고려하여 전체 좌표계에서의 커서 위치 계산
+        let history_size = grid.history_size();
+        let cursor_row_in_full_buffer = history_size as u16 + content.cursor.point.line.0 as u16;
+
+        (
+            content.cursor.point.column.0 as u16,
+            cursor_row_in_full_buffer,
+            cursor_char
+        )
+    }
+
+    /// 프로그램이 켠 마우스 보고 모드
+    pub fn mouse_report_mode(&self) -> MouseReportMode {
+        use alacritty_terminal::term::TermMode;
+        let mode = *self.term.lock().mode();
+        MouseReportMode {
+            clicks: mode.contains(TermMode::MOUSE_REPORT_CLICK),
+            drag: mode.contains(TermMode::MOUSE_DRAG),
+            motion: mode.contains(TermMode::MOUSE_MOTION),
+            sgr: mode.contains(TermMode::SGR_MOUSE),
+        }
+    }
+
+    /// 터미널이 대체 화면 모드인지 확인
+    pub fn is_alternate_screen(&self) -> bool {
+        let term = self.term.lock();
+        term.mode().contains(alacritty_terminal::term::TermMode::ALT_SCREEN)
+    }
+
+    /// 휠 스크롤을 화살표 키로 바꿔 프로그램에 보내야 하면 Some(애플리케이션 커서 키 모드 여부)
+    /// 대체 화면이고 대체 스크롤 모드(`\e[?1007h`, 기본 켜짐)일 때 - vim, less 등이 휠로 스크롤됨
+    pub fn alternate_scroll_mode(&self) -> Option<bool> {
+        use alacritty_terminal::term::TermMode;
+        let mode = *self.term.lock().mode();
+        mode.contains(TermMode::ALT_SCREEN | TermMode::ALTERNATE_SCROLL)
+            .then_some(mode.contains(TermMode::APP_CURSOR))
+    }
+
+    /// 셸 출력을 읽을 수 있는 만큼, 최대 `batch_size`바이트 읽어 그리드에 반영하고 반영한 바이트 수 반환
+    /// 읽을 것이 없으면 0 (기다리지 않음). 호출한 쪽이 주기를 정하므로 호출당 지연 시간과 메모리 사용량이 제한됨
+    /// 못 쓴 입력이 남아 있으면 먼저 쓰고, 셸이 끝났으면 EventLoop처럼 `ChildExit`/`Exit` 이벤트를 보냄
+    /// IO 스레드 없이 만든 터미널(`TerminalBuilder::pumped`) 전용 - 아니면 `Unsupported` 오류
+    pub fn pump_output(&mut self, batch_size: usize) -> io::Result<usize> {
+        let PtySender::Pumped(pumped) = &mut self.pty_tx else {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "IO 스레드가 있는 터미널에는 출력을 직접 반영할 수 없음"));
+        };
+        pumped.flush_input()?;
+        let result = pumped.batcher.pump(&mut pumped.pty, batch_size, &self.term, &self.shell_state, &self.output_sinks);
+        let read = match result {
+            // 셸이 끝나 PTY가 닫힘 (Linux는 EOF 대신 EIO)
+            #[cfg(unix)]
+            Err(e) if e.raw_os_error() == Some(libc::EIO) => 0,
+            result => result?,
+        };
+        if read > 0 {
+            // EventLoop의 Wakeup과 동일하게 버전 증가
+            self.content_version.fetch_add(1, Ordering::Relaxed);
+        } else if !pumped.exited {
+            // 남은 출력을 다 읽은 뒤에만 종료 처리
+            if let Some(ChildEvent::Exited(code)) = pumped.pty.next_child_event() {
+                pumped.exited = true;
+                if let Some(code) = code {
+                    pumped.listener.send_event(AlacTermEvent::ChildExit(code));
+                }
+                self.term.lock().exit();
+            }
+        }
+        Ok(read)
+    }
+
+    /// 삽입 모드(IRM)인지 확인 - 출력한 글자가 커서 뒤 내용을 오른쪽으로 밂
+    pub fn is_insert_mode(&self) -> bool {
+        self.term.lock().mode().contains(alacritty_terminal::term::TermMode::INSERT)
+    }
+
+    /// 자동 줄 바꿈(DECAWM)이 켜져 있는지 확인 (프로그램이 직접 바꾼 상태도 반영)
+    pub fn is_autowrap(&self) -> bool {
+        self.term.lock().mode().contains(alacritty_terminal::term::TermMode::LINE_WRAP)
+    }
+
+    /// 자동 줄 바꿈 켜기/끄기
+    /// 셸 입력으로 보내면 셸이 문자로 받으므로, 프로그램이 출력한 것처럼 DECSET/DECRST 시퀀스를 그리드에 적용
+    pub fn set_autowrap(&mut self, enabled: bool) -> &'static [u8] {
+        let sequence = autowrap_sequence(enabled);
+        let mut term = self.term.lock();
+        let mut parser: alacritty_terminal::vte::ansi::Processor = Default::default();
+        for byte in sequence {
+            parser.advance(&mut *term, *byte);
+        }
+        drop(term);
+        self.content_version.fetch_add(1, Ordering::Relaxed);
+        sequence
+    }
+
+    /// 마지막 확인 이후 대체 화면 전환(1049/47/1047)이 있었는지 확인하고, 있었으면 스크롤 위치 초기화
+    /// 대체 화면에는 히스토리가 없고, 기본 화면으로 돌아오면 스크롤백 중간이 아닌 맨 아래를 보여야 하므로
+    /// 전환된 화면을 맨 아래(오프셋 0)로 맞춤
+    pub fn sync_alt_screen(&mut self) -> bool {
+        let alt_screen = self.is_alternate_screen();
+        if alt_screen == self.alt_screen {
+            return false;
+        }
+        self.alt_screen = alt_screen;
+        self.set_display_offset(0);
+        log::debug!("Alternate screen switched: {}", alt_screen);
+        true
+    }
+}
+
+/// 그리드 한 줄을 문자열로 변환 (한글 등 wide char spacer 제외)
+fn row_to_string(row: &Row<Cell>) -> String {
+    let mut line_content = String::new();
+    let mut col_index = 0;
+
+    while col_index < row.len() {
+        let cell = &row[alacritty_terminal::index::Column(col_index)];
+        let ch = cell.c;
+
+        // 실제 문자만 추가 (null character와 wide char spacer 제외)
+        if ch != '\0' && ch != ' ' || !cell.flags.contains(alacritty_terminal::term::cell::Flags::WIDE_CHAR_SPACER) {
+            line_content.push(ch);
+            // 결합 문자 등 같은 셀에 붙은 폭 0 글자 (한 묶음으로 복사/표시)
+            if let Some(zerowidth) = cell.zerowidth() {
+                line_content.extend(zerowidth);
+            }
+        }
+
+        // wide character인 경우 다음 셀은 spacer이므로 건너뛰기
+        if cell.flags.contains(alacritty_terminal::term::cell::Flags::WIDE_CHAR) {
+            col_index += 2; // wide char는 2개 셀을 차지
+        } else {
+            col_index += 1;
+        }
+    }
+
+    line_content
+}
+
+/// Windows 시스템 Shell 찾기 (Zed와 동일한 로직)
+#[cfg(target_os = "windows")]
+fn get_windows_system_shell() -> String {
+
+    // 1. PowerShell 7+ 찾기
+    if let Some(pwsh) = find_powershell(false, false) {
+        return pwsh.to_string_lossy().to_string();
+    }
+
+    // 2. PowerShell 6+ 찾기
+    if let Some(pwsh) = find_powershell(true, false) {
+        return pwsh.to_string_lossy().to_string();
+    }
+
+    // 3. Windows PowerShell 찾기
+    if let Some(powershell) = find_windows_powershell() {
+        return powershell.to_string_lossy().to_string();
+    }
+
+    // 4. cmd.exe fallback
+    std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn find_powershell(find_alternate: bool, find_preview: bool) -> Option<PathBuf> {
+    let env_var = if find_alternate {
+        "ProgramFiles(x86)"
+    } else {
+        "ProgramFiles"
+    };
+
+    let install_base_dir = PathBuf::from(std::env::var_os(env_var)?)
         .join("PowerShell");
 
     let entries = install_base_dir.read_dir().ok()?;
@@ -375,4 +1498,766 @@ fn find_windows_powershell() -> Option<PathBuf> {
     } else {
         None
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+impl Terminal {
+    /// PTY 없이 테스트용 터미널 생성 (PTY로 보낸 메시지는 기록됨)
+    pub(crate) fn for_test(num_lines: usize, num_cols: usize) -> Terminal {
+        let (events_tx, events_rx) = unbounded();
+        let bounds = TerminalBounds { num_lines, num_cols };
+        let content_version = Arc::new(AtomicU64::new(1)); // 0은 "아무것도 보지 않음"을 의미
+        let term = Term::new(
+            Config::default(),
+            &bounds,
+            TerminalListener::new(events_tx, content_version.clone()),
+        );
+
+        Terminal {
+            pty_tx: PtySender::Test(Box::new(TestPty {
+                sent: Vec::new(),
+                pending_output: Vec::new(),
+                batcher: OutputBatcher::new(),
+            })),
+            term: Arc::new(FairMutex::new(term)),
+            events_rx: Some(events_rx),
+            working_directory: None,
+            shell: Shell::System,
+            bounds,
+            cell_size: CellSize::default(),
+            scroll_past_end: 0,
+            past_end_offset: 0,
+            alt_screen: false,
+            history_lines: Config::default().scrolling_history,
+            kitty_keyboard: false,
+            shell_state: Arc::new(Mutex::new(ShellState::new("testhost".to_string()))),
+            output_sinks: SharedOutputSinks::default(),
+            content_version,
+            line_stamps: Vec::new(),
+            grid_rewritten: AtomicBool::new(false),
+            #[cfg(unix)]
+            pty_master: None,
+        }
+    }
+
+    /// PTY 출력을 흉내내어 바이트를 직접 그리드에 반영
+    pub(crate) fn feed(&mut self, bytes: &[u8]) {
+        self.queue_output(bytes);
+        self.process_pending();
+    }
+
+    /// 자식 프로세스가 PTY에 출력한 것처럼 바이트를 버퍼에 쌓음 (아직 그리드에 반영하지 않음)
+    pub(crate) fn queue_output(&mut self, bytes: &[u8]) {
+        if let PtySender::Test(pty) = &mut self.pty_tx {
+            pty.pending_output.extend_from_slice(bytes);
+        }
+    }
+
+    /// 쌓인 PTY 출력을 호출한 스레드에서 동기적으로 그리드에 반영
+    pub(crate) fn process_pending(&mut self) {
+        let PtySender::Test(pty) = &mut self.pty_tx else {
+            return;
+        };
+        if pty.pending_output.is_empty() {
+            return;
+        }
+
+        let output = std::mem::take(&mut pty.pending_output);
+        output_log::record(&self.output_sinks, &output);
+        pty.batcher.apply(&self.term, &self.shell_state, &output);
+        // EventLoop가 출력 처리 후 보내는 Wakeup과 동일하게 버전 증가
+        self.content_version.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 지금까지 발생한 alacritty 이벤트를 꺼냄
+    pub(crate) fn take_events(&mut self) -> Vec<AlacTermEvent> {
+        let Some(events_rx) = self.events_rx.as_mut() else {
+            return Vec::new();
+        };
+        std::iter::from_fn(|| events_rx.try_recv().ok()).collect()
+    }
+
+    /// 지금까지 PTY로 보낸 메시지
+    pub(crate) fn sent_messages(&self) -> &[Msg] {
+        match &self.pty_tx {
+            PtySender::Test(pty) => &pty.sent,
+            PtySender::EventLoop(_) | PtySender::Pumped(_) | PtySender::Detached(_) => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alacritty_terminal::vte::ansi::NamedColor;
+
+    /// 0..count 번호가 붙은 줄을 출력
+    fn feed_numbered_lines(terminal: &mut Terminal, count: usize) {
+        for i in 0..count {
+            terminal.feed(format!("line {}\r\n", i).as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_visible_content_follows_display_offset() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+
+        // 20줄 출력 + 빈 프롬프트 줄 = 21줄, 화면 5줄 -> 히스토리 16줄
+        assert_eq!(terminal.scroll_state().max_offset(), 16);
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "line 16");
+
+        terminal.set_display_offset(3);
+        assert_eq!(terminal.display_offset(), 3);
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "line 13");
+
+        // 히스토리보다 크게 요청하면 맨 위로 제한
+        terminal.set_display_offset(100);
+        assert_eq!(terminal.display_offset(), 16);
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "line 0");
+
+        terminal.set_display_offset(0);
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "line 16");
+    }
+
+    #[test]
+    fn test_box_drawing_and_powerline_align() {
+        use ratatui::{backend::TestBackend, text::Line, widgets::Paragraph};
+
+        let mut terminal = Terminal::for_test(3, 8);
+        terminal.feed("┌────┐\r\n│\u{e0b0}\u{e0a0}ab│\r\n└────┘".as_bytes());
+
+        // 화면 내용을 렌더러로 그린 결과에서 모서리와 변이 같은 열에 있어야 함
+        let lines: Vec<Line> = terminal.get_visible_content().into_iter().map(Line::from).collect();
+        let mut backend = ratatui::Terminal::new(TestBackend::new(8, 3)).unwrap();
+        backend.draw(|f| f.render_widget(Paragraph::new(lines), f.area())).unwrap();
+        let buffer = backend.backend().buffer();
+
+        let symbol = |x: u16, y: u16| buffer[(x, y)].symbol().to_string();
+        assert_eq!((symbol(0, 0), symbol(5, 0)), ("┌".to_string(), "┐".to_string()));
+        assert_eq!((symbol(0, 1), symbol(5, 1)), ("│".to_string(), "│".to_string()));
+        assert_eq!((symbol(0, 2), symbol(5, 2)), ("└".to_string(), "┘".to_string()));
+        assert_eq!(symbol(1, 1), "\u{e0b0}");
+        assert_eq!(symbol(4, 1), "b");
+        for x in 1..5 {
+            assert_eq!(symbol(x, 0), "─");
+            assert_eq!(symbol(x, 2), "─");
+        }
+    }
+
+    #[test]
+    fn test_process_pending_is_deterministic() {
+        let mut terminal = Terminal::for_test(3, 20);
+
+        // 이스케이프 시퀀스 중간에서 나뉜 출력 조각은 process_pending 전까지 화면에 반영되지 않음
+        terminal.queue_output(b"plain \x1b[3");
+        terminal.queue_output(b"1mred\x1b[0m");
+        let version = terminal.content_version();
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "");
+
+        terminal.process_pending();
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "plain red");
+        assert_eq!(terminal.get_renderable_cells()[0][6].fg, AnsiColor::Named(NamedColor::Red));
+        assert!(terminal.content_version() > version);
+
+        // 남은 출력이 없으면 아무것도 바뀌지 않음
+        let version = terminal.content_version();
+        terminal.process_pending();
+        assert_eq!(terminal.content_version(), version);
+
+        // 파서 상태는 process_pending 호출 사이에서도 유지됨
+        terminal.queue_output(b"\r\n\x1b[");
+        terminal.process_pending();
+        terminal.queue_output(b"32mgreen");
+        terminal.process_pending();
+        assert_eq!(terminal.get_visible_content()[1].trim_end(), "green");
+        assert_eq!(terminal.get_renderable_cells()[1][0].fg, AnsiColor::Named(NamedColor::Green));
+    }
+
+    #[test]
+    fn test_resize_to_same_size_is_noop() {
+        let mut terminal = Terminal::for_test(5, 20);
+        let resizes = |terminal: &Terminal| {
+            terminal.sent_messages().iter().filter(|msg| matches!(msg, Msg::Resize(_))).count()
+        };
+
+        // 현재 크기와 같으면 PTY 리사이즈 메시지를 보내지 않음
+        assert!(!terminal.resize(TerminalBounds { num_lines: 5, num_cols: 20 }).unwrap());
+        assert_eq!(resizes(&terminal), 0);
+
+        // 크기가 바뀌면 한 번만 리사이즈
+        assert!(terminal.resize(TerminalBounds { num_lines: 10, num_cols: 40 }).unwrap());
+        assert!(!terminal.resize(TerminalBounds { num_lines: 10, num_cols: 40 }).unwrap());
+        assert_eq!(resizes(&terminal), 1);
+        assert_eq!(terminal.scroll_state().viewport, 10);
+        assert_eq!(terminal.get_renderable_cells()[0].len(), 40);
+    }
+
+    #[test]
+    fn test_current_directory_from_osc7() {
+        let mut terminal = Terminal::for_test(3, 20);
+        assert_eq!(terminal.current_directory(), None);
+
+        terminal.feed(b"\x1b]7;file://testhost/home/user/src%20code\x07$ ");
+        assert_eq!(terminal.current_directory(), Some(PathBuf::from("/home/user/src code")));
+        // OSC 7은 화면에 표시되지 않음
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "$");
+    }
+
+    #[test]
+    fn test_new_session_starts_in_reported_directory() {
+        let mut terminal = Terminal::for_test(3, 20);
+        // OSC 7 보고가 없으면 앱의 작업 디렉토리
+        assert_eq!(terminal.new_session_directory(), std::env::current_dir().ok());
+
+        let reported = std::env::temp_dir();
+        terminal.feed(format!("\x1b]7;file://testhost{}\x1b\\", reported.display()).as_bytes());
+        assert_eq!(terminal.new_session_directory(), Some(reported));
+
+        // 보고된 디렉토리가 없어졌으면 앱의 작업 디렉토리로 대체
+        terminal.feed(b"\x1b]7;file://testhost/nonexistent/rust-terminal/dir\x07");
+        assert_eq!(terminal.new_session_directory(), std::env::current_dir().ok());
+    }
+
+    #[test]
+    fn test_prompt_marks_follow_output() {
+        let mut terminal = Terminal::for_test(4, 10);
+        terminal.feed(b"\x1b]133;A\x07$ ls\r\n\x1b]133;C\x07a\r\n0123456789wrapped\r\n\x1b]133;D;1\x07\x1b]133;A\x07$ ");
+        // 0: $ ls, 1: a, 2-3: 자동 줄 바꿈된 긴 줄, 4: 새 프롬프트
+        assert_eq!(terminal.prompt_marks(), vec![
+            (0, PromptMarkKind::Prompt),
+            (1, PromptMarkKind::Output),
+            (4, PromptMarkKind::Finished(Some(1))),
+            (4, PromptMarkKind::Prompt),
+        ]);
+        assert_eq!(terminal.last_exit_code(), Some(1));
+
+        // 줄이 다시 배치되어도 같은 논리 줄
+        terminal.resize(TerminalBounds { num_lines: 4, num_cols: 20 }).unwrap();
+        let rows: Vec<usize> = terminal.prompt_marks().iter().map(|(row, _)| *row).collect();
+        assert_eq!(rows, vec![0, 1, 3, 3]);
+    }
+
+    #[test]
+    fn test_scroll_by() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+
+        terminal.scroll_by(4);
+        assert_eq!(terminal.scroll_state().offset, 4);
+        terminal.scroll_by(-1);
+        assert_eq!(terminal.scroll_state().offset, 3);
+
+        // 범위를 벗어나면 양 끝으로 제한
+        terminal.scroll_by(-100);
+        assert_eq!(terminal.scroll_state().offset, 0);
+        terminal.scroll_by(100);
+        assert_eq!(terminal.scroll_state().offset, 16);
+    }
+
+    #[test]
+    fn test_history_lines_limit() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+        assert_eq!(terminal.get_renderable_content().unwrap().len(), 21);
+
+        // 히스토리를 줄이면 오래된 줄부터 버리고 스크롤 범위도 줄어듦
+        terminal.set_history_lines(3);
+        let lines = terminal.get_renderable_content().unwrap();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with("line 13"));
+        terminal.scroll_by(100);
+        assert_eq!(terminal.scroll_state().offset, 3);
+
+        // 0이면 스크롤백 없음
+        terminal.set_history_lines(0);
+        assert_eq!(terminal.scroll_state().offset, 0);
+        assert_eq!(terminal.get_renderable_content().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_scroll_to() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+
+        terminal.scroll_to(ScrollPosition::Top);
+        assert_eq!(terminal.scroll_state().first_visible_row(), 0);
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "line 0");
+
+        terminal.scroll_to(ScrollPosition::Offset(2));
+        assert_eq!(terminal.scroll_state().offset, 2);
+
+        terminal.scroll_to(ScrollPosition::Bottom);
+        assert_eq!(terminal.scroll_state().offset, 0);
+    }
+
+    #[test]
+    fn test_scroll_state() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+        terminal.scroll_by(6);
+
+        let state = terminal.scroll_state();
+        assert_eq!(state, ScrollState { offset: 6, past_end: 0, past_end_margin: 0, total: 21, viewport: 5 });
+        assert_eq!(state.max_offset(), 16);
+        assert_eq!(state.first_visible_row(), 10);
+    }
+
+    #[test]
+    fn test_visible_wraps() {
+        let mut terminal = Terminal::for_test(3, 5);
+        terminal.feed(b"abcdefg\r\nhi");
+        assert_eq!(terminal.get_visible_wraps(), vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_scroll_past_end_margin() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+
+        // 기본값(0)에서는 맨 아래보다 더 내려가지 않음
+        terminal.scroll_by(-3);
+        assert_eq!(terminal.scroll_state().first_visible_row(), 16);
+
+        terminal.set_scroll_past_end(3);
+        terminal.scroll_by(-10);
+        let state = terminal.scroll_state();
+        assert_eq!(state.past_end, 3);
+        assert_eq!(state.max_scroll(), state.max_offset() + 3);
+        assert_eq!(state.first_visible_row(), state.max_scroll());
+
+        // 내용은 위로 밀리고 아래쪽에 빈 줄 표시
+        let content = terminal.get_visible_content();
+        assert_eq!(content[0].trim_end(), "line 19");
+        assert!(content[2..].iter().all(|line| line.is_empty()));
+        assert!(terminal.get_renderable_cells()[4].is_empty());
+
+        // 위로 스크롤하면 여백부터 줄어들고, 맨 아래로 이동하면 여백 없이 표시
+        terminal.scroll_by(5);
+        assert_eq!(terminal.scroll_state().first_visible_row(), 14);
+        terminal.scroll_by(-4);
+        terminal.scroll_to(ScrollPosition::Bottom);
+        assert_eq!(terminal.scroll_state().past_end, 0);
+        assert_eq!(terminal.get_visible_content()[3].trim_end(), "line 19");
+    }
+
+    #[test]
+    fn test_alt_screen_switch_resets_scroll_offset() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+        assert!(!terminal.sync_alt_screen());
+
+        // 스크롤백을 보던 중 전체 화면 앱 시작: 대체 화면은 맨 아래(히스토리 없음)부터 표시
+        terminal.scroll_by(5);
+        terminal.feed(b"\x1b[?1049h\x1b[H\x1b[2Jvim");
+        assert!(terminal.sync_alt_screen());
+        assert!(terminal.is_alternate_screen());
+        assert_eq!(terminal.display_offset(), 0);
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "vim");
+
+        // 대체 화면에서는 로컬 스크롤 대신 휠을 화살표 키로 보냄
+        terminal.set_scroll_past_end(3);
+        terminal.scroll_by(-2);
+        assert_eq!(terminal.scroll_state().past_end, 0);
+        assert_eq!(terminal.alternate_scroll_mode(), Some(false));
+        terminal.feed(b"\x1b[?1h");
+        assert_eq!(terminal.alternate_scroll_mode(), Some(true));
+        terminal.feed(b"\x1b[?1007l");
+        assert_eq!(terminal.alternate_scroll_mode(), None);
+        terminal.feed(b"\x1b[?1007h\x1b[?1l");
+
+        // 앱 종료: 기본 화면은 이전 스크롤 위치가 아닌 맨 아래에서 표시
+        terminal.feed(b"\x1b[?1049l");
+        assert!(terminal.sync_alt_screen());
+        assert!(!terminal.sync_alt_screen());
+        assert_eq!(terminal.display_offset(), 0);
+        assert_eq!(terminal.get_visible_content()[3].trim_end(), "line 19");
+        assert_eq!(terminal.alternate_scroll_mode(), None);
+    }
+
+    #[test]
+    fn test_clear_scrollback_and_restore() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+        let before = terminal.get_renderable_content().unwrap();
+
+        let snapshot = terminal.clear_scrollback(usize::MAX).unwrap();
+        assert_eq!(snapshot.len(), 16);
+        assert_eq!(terminal.scroll_state().max_offset(), 0);
+
+        assert!(terminal.restore_scrollback(snapshot));
+        assert_eq!(terminal.scroll_state().max_offset(), 16);
+        assert_eq!(terminal.get_renderable_content().unwrap(), before);
+    }
+
+    #[test]
+    fn test_clear_scrollback_snapshot_limit() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 20);
+
+        // 최근 4줄만 저장
+        let snapshot = terminal.clear_scrollback(4).unwrap();
+        assert_eq!(snapshot.len(), 4);
+        assert!(terminal.restore_scrollback(snapshot));
+        assert_eq!(terminal.get_renderable_content().unwrap()[0].trim_end(), "line 12");
+    }
+
+    #[test]
+    fn test_diff_since_reports_only_changed_lines() {
+        let mut terminal = Terminal::for_test(5, 20);
+        feed_numbered_lines(&mut terminal, 3);
+
+        // 처음에는 화면 전체
+        assert_eq!(terminal.diff_since(0).len(), 5);
+
+        let version = terminal.content_version();
+        assert!(terminal.diff_since(version).is_empty());
+
+        // 두 번째 줄의 한 셀만 변경
+        terminal.feed(b"\x1b[2;1HX");
+        let diff = terminal.diff_since(version);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].row, 1);
+        assert_eq!(diff[0].cells[0].c, 'X');
+        assert!(diff[0].text.starts_with('X'));
+    }
+
+    #[test]
+    fn test_content_version_increases_on_output() {
+        let mut terminal = Terminal::for_test(5, 20);
+        let before = terminal.content_version();
+        terminal.feed(b"hello");
+        assert!(terminal.content_version() > before);
+    }
+
+    #[test]
+    fn test_input_is_sent_to_pty() {
+        let mut terminal = Terminal::for_test(5, 20);
+        terminal.input(b"ls\r").unwrap();
+        assert!(matches!(terminal.sent_messages(), [Msg::Input(data)] if data.as_ref() == b"ls\r"));
+    }
+
+    /// 제목 이벤트만 골라냄
+    fn titles(terminal: &mut Terminal) -> Vec<String> {
+        terminal.take_events().into_iter()
+            .filter_map(|event| match event {
+                AlacTermEvent::Title(title) => Some(title),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_osc_title_with_bel_and_st_terminators() {
+        let mut terminal = Terminal::for_test(5, 20);
+
+        // alacritty가 BEL/ST 종결자를 모두 제거한 제목을 이벤트로 보냄
+        terminal.feed(b"\x1b]0;bel title\x07");
+        terminal.feed(b"\x1b]2;st title\x1b\\");
+        assert_eq!(titles(&mut terminal), vec!["bel title", "st title"]);
+
+        // 종결자가 다른 read로 나뉘어 들어와도 같은 결과
+        terminal.feed(b"\x1b]2;split\x1b");
+        terminal.feed(b"\\$ ");
+        terminal.feed(b"\x1b]0;split bel");
+        terminal.feed(b"\x07");
+        let received = titles(&mut terminal);
+        assert_eq!(received, vec!["split", "split bel"]);
+        assert!(received.iter().all(|title| crate::title::sanitize_title(title) == *title));
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "$");
+    }
+
+    #[test]
+    fn test_osc8_hyperlinks() {
+        let mut terminal = Terminal::for_test(3, 30);
+        terminal.feed(b"see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\ here");
+        assert_eq!(terminal.get_visible_content()[0].trim_end(), "see docs here");
+
+        let cells = &terminal.get_renderable_cells()[0];
+        assert_eq!(cells[4].hyperlink.as_ref().map(|link| link.uri()), Some("https://example.com"));
+        assert!(cells[8].hyperlink.is_none());
+
+        let row = terminal.get_renderable_content().unwrap().len() - 3;
+        assert_eq!(terminal.hyperlink_at(row, 7).as_deref(), Some("https://example.com"));
+        assert_eq!(terminal.hyperlink_at(row, 3), None);
+        assert_eq!(terminal.hyperlink_at(row + 3, 4), None);
+    }
+
+    #[test]
+    fn test_scrollback_lines_round_trip() {
+        let mut terminal = Terminal::for_test(3, 20);
+        terminal.feed(b"one\r\ntwo\r\nthree\r\nfour\r\n");
+        assert_eq!(terminal.scrollback_lines(10), vec!["one", "two", "three", "four"]);
+        assert_eq!(terminal.scrollback_lines(2), vec!["three", "four"]);
+        assert!(terminal.scrollback_lines(0).is_empty());
+
+        let mut restored = Terminal::for_test(3, 20);
+        restored.replay_lines(&["saved\x1b[2J".to_string(), "lines".to_string()]);
+        assert_eq!(restored.scrollback_lines(10), vec!["saved[2J", "lines"]);
+    }
+
+    #[test]
+    fn test_output_log_records_raw_output() {
+        let directory = std::env::temp_dir().join(format!("rust-terminal-output-log-{}", std::process::id()));
+        let mut terminal = Terminal::for_test(3, 20);
+        terminal.feed(b"before\r\n");
+        let path = terminal.start_output_log(&directory, 0).unwrap();
+        assert_eq!(terminal.output_log_path(), Some(path.clone()));
+
+        terminal.feed(b"\x1b[31mred\x1b[0m\r\n");
+        assert_eq!(terminal.stop_output_log(), Some(path.clone()));
+        terminal.feed(b"after\r\n");
+
+        // 켜져 있는 동안 받은 바이트만 가공 없이 기록
+        assert_eq!(std::fs::read(&path).unwrap(), b"\x1b[31mred\x1b[0m\r\n");
+        assert_eq!(terminal.output_log_path(), None);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_mouse_report_mode() {
+        let mut terminal = Terminal::for_test(5, 20);
+        assert!(!terminal.mouse_report_mode().is_enabled());
+
+        terminal.feed(b"\x1b[?1002h\x1b[?1006h");
+        let mode = terminal.mouse_report_mode();
+        assert!(mode.drag && mode.sgr && !mode.motion);
+
+        terminal.feed(b"\x1b[?1002l");
+        assert!(!terminal.mouse_report_mode().is_enabled());
+    }
+
+    #[test]
+    fn test_kitty_keyboard_flags_follow_program_requests() {
+        let mut terminal = Terminal::for_test(5, 20);
+        // 허용하지 않으면 프로그램 요청을 무시
+        terminal.feed(b"\x1b[>1u");
+        assert_eq!(terminal.keyboard_mode(), KeyboardMode::default());
+
+        terminal.set_kitty_keyboard(true);
+        terminal.feed(b"\x1b[>11u");
+        let mode = terminal.keyboard_mode();
+        assert_eq!(mode.kitty_flags, keys::KITTY_DISAMBIGUATE | keys::KITTY_EVENT_TYPES | keys::KITTY_ALL_KEYS);
+        assert!(mode.reports_release() && mode.reports_all_keys());
+
+        // 스택에서 꺼내면 이전(기본) 방식으로, 끄면 요청한 플래그도 초기화
+        terminal.feed(b"\x1b[>1u\x1b[<u");
+        assert_eq!(terminal.keyboard_mode().kitty_flags, 11);
+        terminal.set_kitty_keyboard(false);
+        assert_eq!(terminal.keyboard_mode(), KeyboardMode::default());
+    }
+
+    #[test]
+    fn test_take_damaged_rows_returns_changed_rows_only() {
+        let mut terminal = Terminal::for_test(5, 20);
+        terminal.feed(b"one\r\ntwo\r\nthree");
+        // 처음에는 화면 전체
+        let first = terminal.take_damaged_rows(None);
+        assert_eq!((first.view, first.screen_lines, first.rows.len()), ((0, 0), 5, 5));
+        assert_eq!(first.rows[1].1.trim_end(), "two");
+
+        // 커서 줄만 바뀜
+        terminal.feed(b"!");
+        let damaged = terminal.take_damaged_rows(Some(first.view));
+        let rows: Vec<usize> = damaged.rows.iter().map(|(row, _, _)| *row).collect();
+        assert_eq!(rows, vec![2]);
+        assert_eq!(damaged.rows[0].1.trim_end(), "three!");
+        assert!(terminal.take_damaged_rows(Some(damaged.view)).rows.iter().all(|(row, _, _)| *row == 2));
+
+        // 보기 위치가 다르거나 그리드를 직접 고쳤으면 전체
+        assert_eq!(terminal.take_damaged_rows(Some((1, 0))).rows.len(), 5);
+        terminal.clear_scrollback(10);
+        assert_eq!(terminal.take_damaged_rows(Some(damaged.view)).rows.len(), 5);
+    }
+
+    #[test]
+    fn test_application_cursor_and_keypad_modes() {
+        let mut terminal = Terminal::for_test(5, 20);
+        terminal.feed(b"\x1b[?1h\x1b=");
+        let mode = terminal.keyboard_mode();
+        assert!(mode.app_cursor && mode.app_keypad);
+
+        terminal.feed(b"\x1b[?1l\x1b>");
+        assert_eq!(terminal.keyboard_mode(), KeyboardMode::default());
+    }
+
+    #[test]
+    fn test_paste_uses_bracketed_paste_mode() {
+        let mut terminal = Terminal::for_test(5, 20);
+        terminal.paste("echo hi\n").unwrap();
+
+        // 프로그램이 bracketed paste를 켜면 감싸서 보내고, 내용 안의 끝 표시는 제거
+        terminal.feed(b"\x1b[?2004h");
+        terminal.paste("a\x1b[201~b").unwrap();
+
+        let sent: Vec<&[u8]> = terminal.sent_messages().iter()
+            .filter_map(|msg| match msg {
+                Msg::Input(data) => Some(data.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sent, vec![&b"echo hi\n"[..], &b"\x1b[200~ab\x1b[201~"[..]]);
+    }
+
+    #[test]
+    fn test_window_size_uses_configured_cell_size() {
+        let mut terminal = Terminal::for_test(5, 20);
+        let cell_size = CellSize::parse("10x22").unwrap();
+        terminal.set_cell_size(cell_size).unwrap();
+        terminal.set_cell_size(cell_size).unwrap();
+        terminal.resize(TerminalBounds { num_lines: 10, num_cols: 40 }).unwrap();
+
+        // 셀 크기 변경과 터미널 크기 변경 모두 설정한 셀 크기로 보고
+        let reported: Vec<(u16, u16, u16, u16)> = terminal.sent_messages().iter()
+            .filter_map(|msg| match msg {
+                Msg::Resize(size) => Some((size.num_cols, size.num_lines, size.cell_width, size.cell_height)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reported, vec![(20, 5, 10, 22), (40, 10, 10, 22)]);
+
+        assert_eq!(CellSize::parse(" 7 X 14 "), Some(CellSize { width: 7, height: 14 }));
+        assert_eq!(CellSize::parse("0x16"), None);
+        assert_eq!(CellSize::parse("8"), None);
+    }
+
+    #[test]
+    fn test_resize_to_capped_width() {
+        use crate::config::{ContentAlign, WidthConfig};
+        use ratatui::layout::Rect;
+
+        let mut terminal = Terminal::for_test(5, 20);
+        let width = WidthConfig { max_columns: 120, align: ContentAlign::Center };
+
+        // 메인 영역을 제한한 뒤 스크롤바(1칸)와 테두리(2칸)를 빼고 PTY 크기 계산
+        let main_area = width.cap(Rect::new(1, 3, 300, 42), 3);
+        let terminal_area = Rect { width: main_area.width - 1, ..main_area };
+        terminal.resize(TerminalBounds {
+            num_lines: terminal_area.height as usize - 2,
+            num_cols: terminal_area.width as usize - 2,
+        }).unwrap();
+
+        let reported: Vec<(u16, u16)> = terminal.sent_messages().iter()
+            .filter_map(|msg| match msg {
+                Msg::Resize(size) => Some((size.num_cols, size.num_lines)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reported, vec![(120, 40)]);
+        assert_eq!(terminal.get_visible_content()[0].chars().count(), 120);
+        // 가운데 정렬: 남는 177칸을 좌우 여백으로 나눔
+        assert_eq!(main_area, Rect::new(89, 3, 123, 42));
+    }
+
+    #[test]
+    fn test_spawn_env_reports_truecolor() {
+        let mut env = HashMap::from([("COLORTERM".to_string(), "host".to_string())]);
+        set_terminal_env(&mut env, Some("truecolor"));
+        assert_eq!(env.get("COLORTERM").map(String::as_str), Some("truecolor"));
+        assert_eq!(env.get("TERM").map(String::as_str), Some("xterm-256color"));
+
+        // 끄면 호스트에서 물려받은 값도 전달하지 않음
+        set_terminal_env(&mut env, None);
+        assert!(!env.contains_key("COLORTERM"));
+    }
+
+    #[test]
+    fn test_child_env_applies_policy_and_branding() {
+        let parent = HashMap::from([
+            ("PATH".to_string(), "/bin".to_string()),
+            ("TERM_PROGRAM".to_string(), "zed".to_string()),
+            ("ZED_TERM".to_string(), "true".to_string()),
+            ("LANG".to_string(), "ko_KR.UTF-8".to_string()),
+        ]);
+        let env = TerminalBuilder::child_env(parent.clone(), &EnvPolicy::default(), None);
+        assert_eq!(env.get("TERM_PROGRAM").map(String::as_str), Some(TERM_PROGRAM));
+        assert_eq!(env.get("TERM_PROGRAM_VERSION").map(String::as_str), Some(env!("CARGO_PKG_VERSION")));
+        assert_eq!(env.get("LANG").map(String::as_str), Some("ko_KR.UTF-8"));
+        assert!(!env.contains_key("ZED_TERM"));
+
+        // 아무것도 물려받지 않아도 터미널 변수와 넣을 변수는 있고, `set`이 터미널 변수보다 우선
+        let mut policy = EnvPolicy { remove: vec!["TERM_PROGRAM_VERSION".to_string()], ..EnvPolicy::default() };
+        policy.set_inherit("none");
+        policy.set_var("TERM", "xterm-direct");
+        let env = TerminalBuilder::child_env(parent, &policy, Some("truecolor"));
+        let mut keys: Vec<&str> = env.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["COLORTERM", "LANG", "TERM", "TERM_PROGRAM"]);
+        assert_eq!((env["TERM"].as_str(), env["LANG"].as_str()), ("xterm-direct", "en_US.UTF-8"));
+    }
+
+    #[test]
+    fn test_toggle_autowrap_sequence() {
+        let mut terminal = Terminal::for_test(3, 5);
+        assert!(terminal.is_autowrap());
+
+        // 끄면 DECRST 7, 긴 줄이 다음 줄로 넘어가지 않고 마지막 열에 덮어씀
+        assert_eq!(terminal.set_autowrap(false), b"\x1b[?7l");
+        assert!(!terminal.is_autowrap());
+        terminal.feed(b"abcdefg");
+        assert_eq!(terminal.get_visible_content()[0], "abcdg");
+        assert!(terminal.get_visible_content()[1].trim_end().is_empty());
+
+        // 켜면 DECSET 7
+        assert_eq!(terminal.set_autowrap(true), b"\x1b[?7h");
+        assert!(terminal.is_autowrap());
+
+        // 프로그램이 직접 바꾼 상태도 반영
+        terminal.feed(b"\x1b[?7l");
+        assert!(!terminal.is_autowrap());
+    }
+
+    fn numbered_output(count: usize) -> Vec<u8> {
+        (0..count).flat_map(|i| format!("\x1b[3{}mline {}\x1b[0m\r\n", i % 8, i).into_bytes()).collect()
+    }
+
+    #[test]
+    fn test_write_output_in_batches_matches_feed() {
+        let output = numbered_output(500);
+        let mut expected = Terminal::for_test(5, 20);
+        expected.feed(&output);
+
+        // 시퀀스가 배치 경계에 걸쳐도 같은 결과 (배치마다 버전 증가)
+        let mut terminal = Terminal::detached(5, 20, Config::default().scrolling_history);
+        let version = terminal.content_version();
+        terminal.write_output(&output);
+        assert!(terminal.content_version() > version + 1);
+        assert_eq!(terminal.get_renderable_content().unwrap(), expected.get_renderable_content().unwrap());
+        assert_eq!(terminal.get_renderable_cells(), expected.get_renderable_cells());
+
+        // IO 스레드 없이 만든 터미널만 펌프할 수 있음
+        assert_eq!(terminal.pump_output(7).unwrap_err().kind(), io::ErrorKind::Unsupported);
+        assert_eq!(Terminal::for_test(5, 20).pump_output(7).unwrap_err().kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pump_output_from_shell_without_io_thread() {
+        let shell = Shell::WithArguments {
+            program: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), "read line; printf 'got %s\\n' \"$line\"; exit 3".to_string()],
+        };
+        let config = crate::config::Config::default();
+        let (mut terminal, mut events_rx) =
+            TerminalBuilder::pumped(None, shell, std::env::vars().collect(), &EnvPolicy::default(), 0, &config).unwrap().build();
+        terminal.input(b"ping\r").unwrap();
+
+        // 호출할 때만 읽고, 한 번에 배치 크기까지만 반영
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut exit_code = None;
+        while exit_code.is_none() && std::time::Instant::now() < deadline {
+            match terminal.pump_output(4).unwrap() {
+                0 => std::thread::sleep(std::time::Duration::from_millis(5)),
+                read => assert!(read <= 4),
+            }
+            while let Ok(event) = events_rx.try_recv() {
+                if let AlacTermEvent::ChildExit(code) = event {
+                    exit_code = Some(code);
+                }
+            }
+        }
+        assert_eq!(exit_code, Some(3));
+        assert!(terminal.get_visible_content().iter().any(|line| line.trim_end() == "got ping"));
+        assert_eq!(terminal.pump_output(4).unwrap(), 0);
+    }
+}
@@ -0,0 +1,152 @@
+//! 색 테마 모듈
+//! 기본 16색(ANSI), 기본 글자색/배경색, 커서와 선택 영역 색을 한 묶음으로 정의
+//! 내장 테마(default, solarized, dracula, gruvbox)와 config.toml의 `[themes.이름]` 사용자 테마 지원
+
+use crate::toml;
+use anyhow::{anyhow, bail, Result};
+use ratatui::style::Color;
+
+/// 내장 테마 이름
+pub const BUILTIN_THEMES: &[&str] = &["default", "solarized", "dracula", "gruvbox"];
+
+/// 색 테마
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    /// 기본 16색 (0~7: 기본색, 8~15: 밝은 색)
+    pub palette: [Color; 16],
+    /// 프로그램이 색을 지정하지 않은 글자의 색
+    pub foreground: Color,
+    /// 터미널 영역 배경색
+    pub background: Color,
+    pub cursor: Color,
+    pub selection_foreground: Color,
+    pub selection_background: Color,
+}
+
+impl Default for Theme {
+    /// 호스트 터미널의 기본 16색을 그대로 쓰는 테마
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            palette: [
+                Color::Black, Color::Red, Color::Green, Color::Yellow,
+                Color::Blue, Color::Magenta, Color::Cyan, Color::Gray,
+                Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow,
+                Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White,
+            ],
+            foreground: Color::White,
+            background: Color::Black,
+            cursor: Color::White,
+            selection_foreground: Color::Black,
+            selection_background: Color::White,
+        }
+    }
+}
+
+/// `0xRRGGBB` 값을 색으로
+const fn rgb(hex: u32) -> Color {
+    Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+}
+
+/// 16색과 기본/커서/선택 색으로 테마 구성 (`colors`: 글자, 배경, 커서, 선택 글자, 선택 배경)
+fn palette_theme(name: &str, palette: [u32; 16], colors: [u32; 5]) -> Theme {
+    Theme {
+        name: name.to_string(),
+        palette: palette.map(rgb),
+        foreground: rgb(colors[0]),
+        background: rgb(colors[1]),
+        cursor: rgb(colors[2]),
+        selection_foreground: rgb(colors[3]),
+        selection_background: rgb(colors[4]),
+    }
+}
+
+impl Theme {
+    /// 이름으로 내장 테마 찾기
+    pub fn builtin(name: &str) -> Option<Self> {
+        let theme = match name {
+            "default" => Self::default(),
+            "solarized" => palette_theme("solarized", [
+                0x073642, 0xdc322f, 0x859900, 0xb58900, 0x268bd2, 0xd33682, 0x2aa198, 0xeee8d5,
+                0x002b36, 0xcb4b16, 0x586e75, 0x657b83, 0x839496, 0x6c71c4, 0x93a1a1, 0xfdf6e3,
+            ], [0x839496, 0x002b36, 0x93a1a1, 0x93a1a1, 0x073642]),
+            "dracula" => palette_theme("dracula", [
+                0x21222c, 0xff5555, 0x50fa7b, 0xf1fa8c, 0xbd93f9, 0xff79c6, 0x8be9fd, 0xf8f8f2,
+                0x6272a4, 0xff6e6e, 0x69ff94, 0xffffa5, 0xd6acff, 0xff92df, 0xa4ffff, 0xffffff,
+            ], [0xf8f8f2, 0x282a36, 0xf8f8f2, 0xf8f8f2, 0x44475a]),
+            "gruvbox" => palette_theme("gruvbox", [
+                0x282828, 0xcc241d, 0x98971a, 0xd79921, 0x458588, 0xb16286, 0x689d6a, 0xa89984,
+                0x928374, 0xfb4934, 0xb8bb26, 0xfabd2f, 0x83a598, 0xd3869b, 0x8ec07c, 0xebdbb2,
+            ], [0xebdbb2, 0x282828, 0xebdbb2, 0xebdbb2, 0x504945]),
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// 기본 테마에서 시작하는 사용자 테마
+    pub fn named(name: &str) -> Self {
+        Self { name: name.to_string(), ..Self::default() }
+    }
+
+    /// 사용자 테마 값 하나 설정 (config.toml `[themes.이름]`의 `키 = 값`)
+    pub fn set(&mut self, key: &str, value: &toml::Value) -> Result<()> {
+        let color = |value: &toml::Value| -> Result<Color> {
+            let text = value.as_str().ok_or_else(|| anyhow!("{}: 색은 문자열이어야 함", key))?;
+            text.trim().parse::<Color>().map_err(|_| anyhow!("{}: 알 수 없는 색: {}", key, text))
+        };
+        match key {
+            "foreground" => self.foreground = color(value)?,
+            "background" => self.background = color(value)?,
+            "cursor" => self.cursor = color(value)?,
+            "selection_foreground" => self.selection_foreground = color(value)?,
+            "selection_background" => self.selection_background = color(value)?,
+            "palette" => {
+                let toml::Value::Array(items) = value else {
+                    bail!("palette는 색 16개의 배열이어야 함");
+                };
+                if items.len() != 16 {
+                    bail!("palette는 색 16개의 배열이어야 함 (현재 {}개)", items.len());
+                }
+                for (slot, item) in self.palette.iter_mut().zip(items) {
+                    *slot = color(item)?;
+                }
+            }
+            _ => bail!("알 수 없는 테마 설정: {}", key),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes() {
+        for name in BUILTIN_THEMES {
+            assert_eq!(Theme::builtin(name).unwrap().name, *name);
+        }
+        assert_eq!(Theme::builtin("nord"), None);
+
+        let dracula = Theme::builtin("dracula").unwrap();
+        assert_eq!(dracula.background, Color::Rgb(0x28, 0x2a, 0x36));
+        assert_eq!(dracula.palette[1], Color::Rgb(0xff, 0x55, 0x55));
+    }
+
+    #[test]
+    fn test_user_theme_values() {
+        let mut theme = Theme::named("mine");
+        theme.set("background", &toml::Value::String("#101010".into())).unwrap();
+        let palette = toml::Value::Array((0..16).map(|i| toml::Value::String(format!("#0000{:02x}", i))).collect());
+        theme.set("palette", &palette).unwrap();
+        assert_eq!(theme.background, Color::Rgb(0x10, 0x10, 0x10));
+        assert_eq!(theme.palette[15], Color::Rgb(0, 0, 15));
+        // 지정하지 않은 값은 기본 테마 값
+        assert_eq!(theme.foreground, Theme::default().foreground);
+
+        assert!(theme.set("palette", &toml::Value::Array(vec![])).is_err());
+        assert!(theme.set("cursor", &toml::Value::Integer(1)).is_err());
+        assert!(theme.set("font", &toml::Value::String("x".into())).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! 창 제목 처리 모듈
+//! 셸/프로그램이 OSC 0/2로 보낸 제목은 신뢰할 수 없는 출력이므로
+//! 호스트 터미널에 SetTitle로 전달하기 전에 정리
+
+/// 기본 창 제목
+pub const DEFAULT_TITLE: &str = "Rust Terminal App";
+
+/// 호스트에 전달할 제목 최대 길이 (문자 수)
+const MAX_TITLE_CHARS: usize = 256;
+
+/// 탭 바에 표시할 탭 이름 최대 길이 (문자 수)
+const MAX_TAB_LABEL_CHARS: usize = 24;
+
+/// 제어 문자(ESC, BEL, C1 등)를 제거하고 길이를 제한한 제목 반환
+pub fn sanitize_title(title: &str) -> String {
+    title.chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_TITLE_CHARS)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// 프로그램이 보낸 제목 (정리한 결과가 비어 있으면 None - 초기화와 같이 취급)
+pub fn program_title(title: &str) -> Option<String> {
+    Some(sanitize_title(title)).filter(|title| !title.is_empty())
+}
+
+/// 탭 바에 표시할 이름 (길면 줄이고 `…` 표시)
+pub fn tab_label(title: &str) -> String {
+    if title.chars().count() <= MAX_TAB_LABEL_CHARS {
+        return title.to_string();
+    }
+    let mut label: String = title.chars().take(MAX_TAB_LABEL_CHARS - 1).collect();
+    label.push('…');
+    label
+}
+
+/// 호스트 터미널에 전달할 제목
+/// 프로그램이 빈 제목을 보내거나 제목을 초기화하면 비워 두지 않고 설정한 기본 제목 사용
+pub fn host_title(title: &str, fallback: &str) -> String {
+    let title = sanitize_title(title);
+    if !title.is_empty() {
+        return title;
+    }
+    match sanitize_title(fallback) {
+        fallback if fallback.is_empty() => DEFAULT_TITLE.to_string(),
+        fallback => fallback,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_title_strips_control_chars() {
+        let title = "vim \x1b]0;evil\x07main.rs\x1b[31m\u{9b}\r\n";
+        let sanitized = sanitize_title(title);
+        assert!(!sanitized.chars().any(|c| c.is_control()));
+        assert_eq!(sanitized, "vim ]0;evilmain.rs[31m");
+    }
+
+    #[test]
+    fn test_sanitize_title_limits_length() {
+        let title = "가".repeat(MAX_TITLE_CHARS * 2);
+        assert_eq!(sanitize_title(&title).chars().count(), MAX_TITLE_CHARS);
+    }
+
+    #[test]
+    fn test_empty_title_uses_fallback() {
+        assert_eq!(host_title("vim main.rs", "zsh"), "vim main.rs");
+        // 빈 제목(제어 문자만 있는 경우 포함)은 호스트 제목을 지우지 않고 기본 제목으로 대체
+        assert_eq!(host_title("", "zsh"), "zsh");
+        assert_eq!(host_title(" \x07 ", "zsh"), "zsh");
+        assert_eq!(host_title("", ""), DEFAULT_TITLE);
+    }
+
+    #[test]
+    fn test_program_title_and_tab_label() {
+        assert_eq!(program_title("vim main.rs"), Some("vim main.rs".to_string()));
+        assert_eq!(program_title("  "), None);
+        assert_eq!(tab_label("vim"), "vim");
+        let label = tab_label("user@host: ~/projects/rust-terminal/src");
+        assert_eq!(label.chars().count(), MAX_TAB_LABEL_CHARS);
+        assert_eq!(label, "user@host: ~/projects/r…");
+    }
+}
@@ -0,0 +1,327 @@
+//! 설정 파일(config.toml)용 TOML 파서
+//! 설정에 필요한 부분만 지원: `[테이블]`, `키 = 값`, 점으로 구분한 키,
+//! 문자열(기본/리터럴), 정수, 불리언, 배열(여러 줄 가능), `#` 주석
+
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashSet;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// TOML 값
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// 문자열 값이면 내용 반환
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// `키 = 값` 한 줄 (파일에 나온 순서대로)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub table: String, // 속한 테이블 이름 (최상위면 빈 문자열, 하위 테이블은 점으로 연결)
+    pub key: String,
+    pub value: Value,
+    pub line: usize, // 키가 있는 줄 번호 (1부터, 오류 메시지용)
+}
+
+impl Entry {
+    /// 테이블 이름을 포함한 전체 키 (`cursor.shape` 등)
+    pub fn path(&self) -> String {
+        if self.table.is_empty() {
+            self.key.clone()
+        } else {
+            format!("{}.{}", self.table, self.key)
+        }
+    }
+}
+
+/// TOML 문서를 파싱하여 `키 = 값` 목록 반환
+/// 같은 키가 두 번 나오면 오류
+pub fn parse(text: &str) -> Result<Vec<Entry>> {
+    let mut parser = Parser { chars: text.chars().peekable(), line: 1 };
+    let mut table = String::new();
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+
+    loop {
+        parser.skip_blank();
+        if parser.chars.peek().is_none() {
+            break;
+        }
+        let line = parser.line;
+        match parser.statement(&mut table) {
+            Ok(Some(entry)) => {
+                if !seen.insert(entry.path()) {
+                    bail!("{}번째 줄: 중복된 키: {}", line, entry.path());
+                }
+                entries.push(Entry { line, ..entry });
+            }
+            Ok(None) => {}
+            Err(e) => bail!("{}번째 줄: {}", parser.line, e),
+        }
+    }
+    Ok(entries)
+}
+
+/// 글자 단위 파서 (현재 줄 번호 추적)
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    /// 테이블 머리글 또는 `키 = 값` 하나를 읽고 줄 끝까지 확인
+    /// 테이블 머리글이면 `table`을 바꾸고 None 반환
+    fn statement(&mut self, table: &mut String) -> Result<Option<Entry>> {
+        let entry = if self.chars.peek() == Some(&'[') {
+            self.chars.next();
+            if self.chars.peek() == Some(&'[') {
+                bail!("테이블 배열([[...]])은 지원하지 않음");
+            }
+            self.skip_spaces();
+            *table = self.key_path()?.join(".");
+            self.expect(']')?;
+            None
+        } else {
+            let mut path = self.key_path()?;
+            self.expect('=')?;
+            self.skip_spaces();
+            let value = self.value()?;
+            // 점으로 구분한 키의 앞부분은 하위 테이블
+            let key = path.pop().unwrap_or_default();
+            let table = std::iter::once(table.as_str())
+                .chain(path.iter().map(String::as_str))
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(".");
+            Some(Entry { table, key, value, line: 0 })
+        };
+
+        // 값 뒤에는 주석이나 줄 끝만 올 수 있음
+        self.skip_spaces();
+        match self.chars.peek() {
+            None | Some('\n') | Some('#') => Ok(entry),
+            Some('\r') => Ok(entry),
+            Some(c) => bail!("줄 끝에 예상하지 못한 글자: {:?}", c),
+        }
+    }
+
+    /// 점으로 구분한 키 (각 부분은 bare 키 또는 따옴표 문자열), 뒤의 공백 포함
+    fn key_path(&mut self) -> Result<Vec<String>> {
+        let mut parts = Vec::new();
+        loop {
+            let part = match self.chars.peek() {
+                Some('"') => self.basic_string()?,
+                Some('\'') => self.literal_string()?,
+                _ => {
+                    let word = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                    if word.is_empty() {
+                        bail!("키가 없음");
+                    }
+                    word
+                }
+            };
+            parts.push(part);
+            self.skip_spaces();
+            if self.chars.peek() != Some(&'.') {
+                return Ok(parts);
+            }
+            self.chars.next();
+            self.skip_spaces();
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.chars.peek() {
+            Some('"') => Ok(Value::String(self.basic_string()?)),
+            Some('\'') => Ok(Value::String(self.literal_string()?)),
+            Some('[') => self.array(),
+            Some('{') => bail!("인라인 테이블은 지원하지 않음"),
+            Some(_) => {
+                let word = self.take_while(|c| c.is_ascii_alphanumeric() || "_+-.:".contains(c));
+                match word.as_str() {
+                    "true" => Ok(Value::Boolean(true)),
+                    "false" => Ok(Value::Boolean(false)),
+                    "" => bail!("값이 없음"),
+                    _ => word.replace('_', "").parse().map(Value::Integer)
+                        .map_err(|_| anyhow!("지원하지 않는 값: {}", word)),
+                }
+            }
+            None => bail!("값이 없음"),
+        }
+    }
+
+    /// `[값, 값, ...]` (줄 바꿈, 주석, 끝의 쉼표 허용)
+    fn array(&mut self) -> Result<Value> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.chars.peek() == Some(&']') {
+                self.chars.next();
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank();
+            match self.chars.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(items)),
+                _ => bail!("배열 항목 사이에 쉼표가 없음"),
+            }
+        }
+    }
+
+    /// `"..."` 문자열 (이스케이프 처리)
+    fn basic_string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        if self.chars.peek() == Some(&'"') {
+            self.chars.next();
+            if self.chars.peek() == Some(&'"') {
+                bail!("여러 줄 문자열은 지원하지 않음");
+            }
+            return Ok(String::new());
+        }
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => value.push(self.escape()?),
+                Some('\n') | None => bail!("문자열이 닫히지 않음"),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char> {
+        let c = match self.chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('e') => '\x1b',
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some(kind @ ('u' | 'U')) => {
+                let len = if kind == 'u' { 4 } else { 8 };
+                let hex: String = (0..len).filter_map(|_| self.chars.next()).collect();
+                u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                    .ok_or_else(|| anyhow!("잘못된 유니코드 이스케이프: \\{}{}", kind, hex))?
+            }
+            Some(c) => bail!("알 수 없는 이스케이프: \\{}", c),
+            None => bail!("문자열이 닫히지 않음"),
+        };
+        Ok(c)
+    }
+
+    /// `'...'` 문자열 (이스케이프 없음)
+    fn literal_string(&mut self) -> Result<String> {
+        self.expect('\'')?;
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\'') => return Ok(value),
+                Some('\n') | None => bail!("문자열이 닫히지 않음"),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => bail!("'{}'가 와야 할 자리에 {:?}", expected, c),
+            None => bail!("'{}'가 와야 할 자리에서 파일이 끝남", expected),
+        }
+    }
+
+    fn take_while(&mut self, mut accept: impl FnMut(char) -> bool) -> String {
+        let mut word = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !accept(c) {
+                break;
+            }
+            word.push(c);
+            self.chars.next();
+        }
+        word
+    }
+
+    /// 같은 줄의 공백 건너뛰기
+    fn skip_spaces(&mut self) {
+        while matches!(self.chars.peek(), Some(' ' | '\t')) {
+            self.chars.next();
+        }
+    }
+
+    /// 공백, 줄 바꿈, 주석 건너뛰기
+    fn skip_blank(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                ' ' | '\t' | '\r' => {
+                    self.chars.next();
+                }
+                '\n' => {
+                    self.chars.next();
+                    self.line += 1;
+                }
+                '#' => {
+                    while self.chars.peek().is_some_and(|&c| c != '\n') {
+                        self.chars.next();
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tables_and_values() {
+        let text = "# 주석\ntitle = 'rust-terminal' # 끝 주석\n\n[shell]\nprogram = \"/bin/zsh\"\nargs = [\n  \"-l\", # 로그인 셸\n  \"-i\",\n]\n\n[scrollback]\nlines = 20_000\nedges = false\n\n[keybindings]\n\"ctrl+shift+v\" = \"paste\"\nfont.size = -1\n";
+        let entries = parse(text).unwrap();
+        let paths: Vec<String> = entries.iter().map(Entry::path).collect();
+        assert_eq!(paths, vec![
+            "title", "shell.program", "shell.args", "scrollback.lines", "scrollback.edges",
+            "keybindings.ctrl+shift+v", "keybindings.font.size",
+        ]);
+        assert_eq!(entries[0].value.as_str(), Some("rust-terminal"));
+        assert_eq!(entries[2].value, Value::Array(vec![Value::String("-l".into()), Value::String("-i".into())]));
+        assert_eq!(entries[2].line, 6);
+        assert_eq!(entries[3].value, Value::Integer(20_000));
+        assert_eq!(entries[4].value, Value::Boolean(false));
+        assert_eq!((entries[5].table.as_str(), entries[5].key.as_str()), ("keybindings", "ctrl+shift+v"));
+        assert_eq!(entries[6].value, Value::Integer(-1));
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let entries = parse(r#"text = "탭\t\"따옴표\" \u00e9\\""#).unwrap();
+        assert_eq!(entries[0].value.as_str(), Some("탭\t\"따옴표\" é\\"));
+        assert_eq!(parse("empty = \"\"").unwrap()[0].value.as_str(), Some(""));
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let error = |text: &str| parse(text).unwrap_err().to_string();
+        assert!(error("a = 1\nb = \"닫히지 않음").starts_with("2번째 줄"));
+        assert!(error("a = 1\n\na = 2").starts_with("3번째 줄: 중복된 키"));
+        assert!(error("a = 1 2").contains("줄 끝"));
+        assert!(error("a = yes").contains("지원하지 않는 값"));
+        assert!(error("[[profiles]]").contains("테이블 배열"));
+        assert!(error("= 1").contains("키가 없음"));
+    }
+}
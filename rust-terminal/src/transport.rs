@@ -0,0 +1,342 @@
+//! 터미널 입출력 연결 모듈
+//! alacritty IO 스레드(`EventLoop`)가 읽고 쓰는 대상 - 셸을 실행한 PTY 또는 시리얼 장치, TCP 소켓 같은 스트림
+//! 어느 쪽이든 같은 `Terminal`/OSC 추적/출력 기록 경로를 지나므로 나머지 기능은 연결 종류를 몰라도 됨
+//!
+//! 스트림은 자식 프로세스가 없으므로, 읽다가 끝(EOF)이나 오류를 만나면 알림 소켓에 써서
+//! PTY의 자식 종료와 같은 경로(`ChildEvent::Exited`)로 세션 종료를 알림
+//! telnet이나 데몬 세션처럼 프로토콜이 있는 스트림은 `StreamProtocol`이 읽은 데이터에서 제어 내용을 걸러 내고,
+//! 보낼 입력과 창 크기를 프로토콜 형식으로 바꿔 같은 소켓에 씀
+
+use alacritty_terminal::event::{OnResize, WindowSize};
+use alacritty_terminal::tty::{self, ChildEvent, EventedPty, EventedReadWrite};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+#[cfg(unix)]
+use std::{fs::File, os::unix::net::UnixStream};
+
+/// alacritty `tty::unix`의 IO 스레드 토큰과 같은 값 (crate 밖에 공개되지 않음)
+#[cfg(unix)]
+const PTY_READ_WRITE_TOKEN: usize = 0;
+#[cfg(unix)]
+const PTY_CHILD_EVENT_TOKEN: usize = 1;
+
+/// 스트림이 끊겼을 때의 종료 코드 (장치 제거, 읽기 오류)
+#[cfg(unix)]
+pub const STREAM_ERROR_CODE: i32 = 1;
+
+/// 스트림 위의 프로토콜 (telnet, 데몬 세션)
+/// 응답과 보낼 데이터는 넘겨받은 버퍼에 쌓고, 소켓에 쓰는 것은 `StreamTransport`가 함
+pub trait StreamProtocol: Send {
+    /// 받은 바이트에서 제어 내용을 걸러 내고 터미널 출력만 앞쪽에 남김 - 남은 길이 반환
+    /// 제어 내용이 읽기 경계에서 잘려도 다음 호출에서 이어서 처리
+    fn receive(&mut self, buf: &mut [u8], reply: &mut Vec<u8>) -> usize;
+
+    /// 터미널 입력을 보낼 형식으로
+    fn encode(&self, data: &[u8], out: &mut Vec<u8>);
+
+    /// 창 크기 변경 알림
+    fn resize(&mut self, columns: u16, lines: u16, reply: &mut Vec<u8>);
+
+    /// 스트림이 끝났을 때의 종료 코드 (상대가 알려 준 값, 없으면 0)
+    fn exit_code(&self) -> Option<i32> {
+        None
+    }
+}
+
+/// IO 스레드의 입출력 대상
+pub enum Transport {
+    Pty(tty::Pty),
+    #[cfg(unix)]
+    Stream(StreamTransport),
+}
+
+/// PTY가 아닌 스트림 연결 (끊김을 알리는 소켓 쌍 포함)
+#[cfg(unix)]
+pub struct StreamTransport {
+    io: File,
+    protocol: Option<Box<dyn StreamProtocol>>, // 프로토콜이 있는 스트림이면 그 처리
+    pending: Vec<u8>,       // 아직 쓰지 못한 바이트 (프로토콜 응답과 변환한 입력)
+    closed_tx: UnixStream,  // 끊김 알림 쓰기 쪽
+    closed_rx: UnixStream,  // IO 스레드가 자식 이벤트로 기다리는 쪽
+    exit_code: Option<i32>, // 알린 종료 코드
+}
+
+#[cfg(unix)]
+impl StreamTransport {
+    /// non-blocking으로 연 스트림 연결
+    pub fn new(io: File, protocol: Option<Box<dyn StreamProtocol>>) -> io::Result<Self> {
+        let (closed_tx, closed_rx) = UnixStream::pair()?;
+        closed_tx.set_nonblocking(true)?;
+        closed_rx.set_nonblocking(true)?;
+        Ok(Self { io, protocol, pending: Vec::new(), closed_tx, closed_rx, exit_code: None })
+    }
+
+    /// 끊김 알림 (한 번만)
+    fn close(&mut self, code: i32) {
+        if self.exit_code.is_none() {
+            self.exit_code = Some(code);
+            let _ = self.closed_tx.write(&[0]);
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.exit_code.is_some() {
+                return Ok(0);
+            }
+            let read = match self.io.read(buf) {
+                Ok(0) => {
+                    self.close(self.protocol.as_ref().and_then(|protocol| protocol.exit_code()).unwrap_or(0));
+                    return Ok(0);
+                }
+                Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted) => return Err(e),
+                // 장치 제거, 연결 끊김 등 - IO 스레드를 멈추지 않고 종료 알림으로 바꿈
+                Err(e) => {
+                    log::warn!("stream read failed: {}", e);
+                    self.close(STREAM_ERROR_CODE);
+                    return Ok(0);
+                }
+                Ok(read) => read,
+            };
+            let Some(protocol) = &mut self.protocol else {
+                return Ok(read);
+            };
+            let data = protocol.receive(&mut buf[..read], &mut self.pending);
+            self.send_pending();
+            // 제어 내용만 받았으면 데이터가 올 때까지 계속 읽음 (0은 끝으로 보므로)
+            if data > 0 {
+                return Ok(data);
+            }
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.exit_code.is_some() {
+            return Ok(buf.len());
+        }
+        // 앞서 못 쓴 바이트가 남아 있으면 쓸 수 있을 때까지 새 입력을 받지 않음
+        if !self.send_pending() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let Some(protocol) = &self.protocol else {
+            return match self.io.write(buf) {
+                Err(e) if !matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted) => {
+                    log::warn!("stream write failed: {}", e);
+                    self.close(STREAM_ERROR_CODE);
+                    Ok(buf.len())
+                }
+                result => result,
+            };
+        };
+        protocol.encode(buf, &mut self.pending);
+        self.send_pending();
+        Ok(buf.len())
+    }
+
+    /// 남은 바이트를 쓸 수 있는 만큼 씀 - 모두 썼으면 true
+    fn send_pending(&mut self) -> bool {
+        while !self.pending.is_empty() && self.exit_code.is_none() {
+            match self.io.write(&self.pending) {
+                Ok(written) => {
+                    self.pending.drain(..written);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return false,
+                Err(e) => {
+                    log::warn!("stream write failed: {}", e);
+                    self.close(STREAM_ERROR_CODE);
+                }
+            }
+        }
+        true
+    }
+
+    fn resize(&mut self, window_size: WindowSize) {
+        if let Some(protocol) = &mut self.protocol {
+            protocol.resize(window_size.num_cols, window_size.num_lines, &mut self.pending);
+            self.send_pending();
+        }
+    }
+}
+
+/// 스트림(시리얼 장치, TCP 소켓 등)으로 연결 - `protocol`이 있으면 그 프로토콜로 주고받음
+#[cfg(unix)]
+pub fn stream(io: File, protocol: Option<Box<dyn StreamProtocol>>) -> anyhow::Result<Transport> {
+    Ok(Transport::Stream(StreamTransport::new(io, protocol)?))
+}
+
+/// 스트림 연결은 Unix에서만 지원
+#[cfg(not(unix))]
+pub fn stream(_io: std::fs::File, _protocol: Option<Box<dyn StreamProtocol>>) -> anyhow::Result<Transport> {
+    anyhow::bail!("이 플랫폼에서는 스트림 연결을 지원하지 않음")
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Pty(pty) => pty.reader().read(buf),
+            #[cfg(unix)]
+            Self::Stream(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Pty(pty) => pty.writer().write(buf),
+            #[cfg(unix)]
+            Self::Stream(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Pty(pty) => pty.writer().flush(),
+            #[cfg(unix)]
+            Self::Stream(stream) => stream.io.flush(),
+        }
+    }
+}
+
+impl EventedReadWrite for Transport {
+    type Reader = Self;
+    type Writer = Self;
+
+    unsafe fn register(
+        &mut self,
+        poller: &Arc<polling::Poller>,
+        interest: polling::Event,
+        mode: polling::PollMode,
+    ) -> io::Result<()> {
+        match self {
+            // SAFETY: PTY는 이 Transport가 소유하므로 등록 기간 동안 유지됨
+            Self::Pty(pty) => unsafe { pty.register(poller, interest, mode) },
+            // SAFETY: 스트림과 알림 소켓은 이 Transport가 소유하고, deregister 전에 닫지 않음
+            #[cfg(unix)]
+            Self::Stream(stream) => unsafe {
+                poller.add_with_mode(&stream.io, with_key(interest, PTY_READ_WRITE_TOKEN), mode)?;
+                poller.add_with_mode(&stream.closed_rx, polling::Event::readable(PTY_CHILD_EVENT_TOKEN), polling::PollMode::Level)
+            },
+        }
+    }
+
+    fn reregister(
+        &mut self,
+        poller: &Arc<polling::Poller>,
+        interest: polling::Event,
+        mode: polling::PollMode,
+    ) -> io::Result<()> {
+        match self {
+            Self::Pty(pty) => pty.reregister(poller, interest, mode),
+            #[cfg(unix)]
+            Self::Stream(stream) => {
+                poller.modify_with_mode(&stream.io, with_key(interest, PTY_READ_WRITE_TOKEN), mode)?;
+                poller.modify_with_mode(&stream.closed_rx, polling::Event::readable(PTY_CHILD_EVENT_TOKEN), polling::PollMode::Level)
+            }
+        }
+    }
+
+    fn deregister(&mut self, poller: &Arc<polling::Poller>) -> io::Result<()> {
+        match self {
+            Self::Pty(pty) => pty.deregister(poller),
+            #[cfg(unix)]
+            Self::Stream(stream) => {
+                poller.delete(&stream.io)?;
+                poller.delete(&stream.closed_rx)
+            }
+        }
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        self
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self
+    }
+}
+
+impl EventedPty for Transport {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        match self {
+            Self::Pty(pty) => pty.next_child_event(),
+            #[cfg(unix)]
+            Self::Stream(stream) => {
+                let mut buf = [0u8; 1];
+                match stream.closed_rx.read(&mut buf) {
+                    Ok(1) => Some(ChildEvent::Exited(stream.exit_code)),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl OnResize for Transport {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        match self {
+            Self::Pty(pty) => pty.on_resize(window_size),
+            // 시리얼 장치와 raw TCP는 창 크기를 전달할 방법이 없고, 프로토콜이 있으면 그 방식으로 알림
+            #[cfg(unix)]
+            Self::Stream(stream) => stream.resize(window_size),
+        }
+    }
+}
+
+/// 등록할 이벤트의 토큰 바꾸기
+#[cfg(unix)]
+fn with_key(mut event: polling::Event, key: usize) -> polling::Event {
+    event.key = key;
+    event
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::telnet::Telnet;
+
+    #[test]
+    fn test_stream_end_becomes_child_exit() {
+        let (reader, mut writer) = std::io::pipe().unwrap();
+        let file = File::from(std::os::fd::OwnedFd::from(reader));
+        let mut transport = Transport::Stream(StreamTransport::new(file, None).unwrap());
+
+        writer.write_all(b"boot> ").unwrap();
+        let mut buf = [0u8; 16];
+        assert_eq!(transport.read(&mut buf).unwrap(), 6);
+        assert!(transport.next_child_event().is_none());
+
+        // 쓰는 쪽이 닫히면 EOF -> 종료 알림, 이후 읽기는 계속 0
+        drop(writer);
+        assert_eq!(transport.read(&mut buf).unwrap(), 0);
+        assert!(matches!(transport.next_child_event(), Some(ChildEvent::Exited(Some(0)))));
+        assert_eq!(transport.read(&mut buf).unwrap(), 0);
+        assert!(transport.next_child_event().is_none());
+    }
+
+    #[test]
+    fn test_telnet_stream_negotiates_window_size() {
+        let (client, mut server) = UnixStream::pair().unwrap();
+        client.set_nonblocking(true).unwrap();
+        let file = File::from(std::os::fd::OwnedFd::from(client));
+        let mut transport = Transport::Stream(StreamTransport::new(file, Some(Box::new(Telnet::default()))).unwrap());
+        transport.on_resize(WindowSize { num_lines: 24, num_cols: 80, cell_width: 1, cell_height: 1 });
+
+        // 협상만 받으면 데이터가 올 때까지 기다림 (WouldBlock), 응답은 바로 소켓에 씀
+        server.write_all(&[255, 253, 31]).unwrap();
+        let mut buf = [0u8; 32];
+        assert_eq!(transport.read(&mut buf).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+        let mut reply = [0u8; 12];
+        server.read_exact(&mut reply).unwrap();
+        assert_eq!(reply, [255, 251, 31, 255, 250, 31, 0, 80, 0, 24, 255, 240]);
+
+        server.write_all(b"login: ").unwrap();
+        assert_eq!(transport.read(&mut buf).unwrap(), 7);
+        transport.write_all(b"guest\r").unwrap();
+        let mut sent = [0u8; 7];
+        server.read_exact(&mut sent).unwrap();
+        assert_eq!(&sent, b"guest\r\0");
+    }
+}
@@ -0,0 +1,128 @@
+//! 메인 루프 깨우기 모듈
+//! 입력 스레드가 읽은 crossterm 이벤트와 터미널 출력 알림(alacritty 이벤트)을 한 채널로 모아,
+//! 메인 루프가 고정 간격으로 폴링하지 않고 둘 중 하나가 오거나 다음 타이머 시각이 될 때까지 잠들게 함
+//!
+//! 출력 알림은 메인 루프가 받아 갈 때까지 하나만 쌓이므로, 출력이 몰려도 채널이 불어나지 않음
+
+use crossterm::event::{self, Event};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// 깨울 일이 없어도 이 간격마다 한 번은 깨어남 (종료 안내 카운트다운, 되돌리기 기한 등 시각에 따른 표시)
+pub const IDLE_TICK: Duration = Duration::from_secs(1);
+
+/// 메인 루프를 깨운 이유
+#[derive(Debug)]
+pub enum LoopEvent {
+    Input(Event), // 키보드/마우스/크기 변경 등 crossterm 이벤트
+    Terminal,     // 어떤 세션에서든 alacritty 이벤트(출력, 제목, 종료 등)가 쌓임
+}
+
+/// 터미널 출력 알림을 보내는 쪽 (같은 알림은 받아 갈 때까지 하나로 합침)
+#[derive(Debug, Clone)]
+pub struct Waker {
+    tx: Sender<LoopEvent>,
+    pending: Arc<AtomicBool>,
+}
+
+impl Waker {
+    /// 메인 루프 깨우기 - 이미 받아 가지 않은 알림이 있으면 보내지 않음
+    pub fn wake(&self) {
+        if !self.pending.swap(true, Ordering::AcqRel) {
+            let _ = self.tx.send(LoopEvent::Terminal);
+        }
+    }
+}
+
+/// 메인 루프가 기다리는 채널
+pub struct LoopEvents {
+    rx: Receiver<LoopEvent>,
+    pending: Arc<AtomicBool>,
+}
+
+impl LoopEvents {
+    /// 입력/출력 알림 채널과 알림을 보낼 Waker 만들기
+    fn channel() -> (Self, Waker, Sender<LoopEvent>) {
+        let (tx, rx) = mpsc::channel();
+        let pending = Arc::new(AtomicBool::new(false));
+        let waker = Waker { tx: tx.clone(), pending: pending.clone() };
+        (Self { rx, pending }, waker, tx)
+    }
+
+    /// 이벤트가 오거나 `timeout`이 지날 때까지 기다림 (시간이 지났으면 None)
+    pub fn wait(&self, timeout: Duration) -> Option<LoopEvent> {
+        let event = match self.rx.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return None,
+        };
+        if let LoopEvent::Terminal = event {
+            // 받아 간 뒤에 생긴 출력은 다시 알림
+            self.pending.store(false, Ordering::Release);
+        }
+        Some(event)
+    }
+}
+
+/// 프로세스 전체에서 쓰는 Waker (메인 루프가 시작하기 전에는 없음)
+static WAKER: OnceLock<Waker> = OnceLock::new();
+
+/// 입력 스레드를 띄우고 전역 Waker를 설치 (한 번만 호출)
+/// 입력 스레드는 crossterm 이벤트를 읽는 유일한 곳이 되므로, 이후에는 `event::poll`/`event::read`를 직접 쓰지 않음
+pub fn start() -> LoopEvents {
+    let (events, waker, input_tx) = LoopEvents::channel();
+    if WAKER.set(waker).is_err() {
+        log::warn!("main loop waker already installed");
+    }
+    std::thread::spawn(move || loop {
+        match event::read() {
+            Ok(event) => {
+                if input_tx.send(LoopEvent::Input(event)).is_err() {
+                    break; // 메인 루프가 끝남
+                }
+            }
+            Err(e) => {
+                log::warn!("input read failed: {}", e);
+                break;
+            }
+        }
+    });
+    events
+}
+
+/// 메인 루프 깨우기 (Waker가 설치되지 않았으면 아무것도 하지 않음 - 테스트, 녹화 재생)
+pub fn wake() {
+    if let Some(waker) = WAKER.get() {
+        waker.wake();
+    }
+}
+
+/// 기다릴 시간 - 가장 먼저 오는 타이머까지, 타이머가 없으면 `IDLE_TICK`
+pub fn wait_timeout(timers: impl IntoIterator<Item = Option<Duration>>) -> Duration {
+    timers.into_iter().flatten().fold(IDLE_TICK, Duration::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wakes_coalesce_until_received() {
+        let (events, waker, _input_tx) = LoopEvents::channel();
+        waker.wake();
+        waker.clone().wake();
+        assert!(matches!(events.wait(Duration::ZERO), Some(LoopEvent::Terminal)));
+        assert!(events.wait(Duration::ZERO).is_none());
+        // 받아 간 뒤의 알림은 다시 옴
+        waker.wake();
+        assert!(matches!(events.wait(Duration::ZERO), Some(LoopEvent::Terminal)));
+    }
+
+    #[test]
+    fn test_wait_timeout_picks_earliest_timer() {
+        assert_eq!(wait_timeout([None, None]), IDLE_TICK);
+        assert_eq!(wait_timeout([Some(Duration::from_millis(300)), None, Some(Duration::from_millis(16))]), Duration::from_millis(16));
+        assert_eq!(wait_timeout([Some(Duration::from_secs(5))]), IDLE_TICK);
+    }
+}